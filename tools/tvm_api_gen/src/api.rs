@@ -127,7 +127,7 @@ fn reduce_api(api: &API) -> API {
     }
 }
 
-fn get_api() -> CliResult<API> {
+pub fn get_api() -> CliResult<API> {
     let context = Arc::new(ClientContext::new(Default::default())?);
     let api = tvm_client::client::get_api_reference(context)?.api;
     Ok(reduce_api(&api))
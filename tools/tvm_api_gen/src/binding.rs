@@ -0,0 +1,211 @@
+// Copyright 2018-2021 EverX Labs Ltd.
+//
+// Licensed under the SOFTWARE EVALUATION License (the "License"); you may not
+// use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific EVERX DEV software governing permissions and
+// limitations under the License.
+//
+
+use std::path::PathBuf;
+
+use api_info::API;
+use api_info::Field;
+use api_info::Function;
+use api_info::Type;
+
+use crate::api::get_api;
+use crate::command_line::CommandLine;
+use crate::errors::CliError;
+use crate::errors::CliResult;
+
+/// Parameters of an API function in the form used by the bindings:
+/// `params` struct, callback for `Arc<Request>` events and application
+/// object implemented on the binding side.
+pub struct FunctionInfo<'a> {
+    pub params: Option<&'a Field>,
+    pub has_response_handler: bool,
+    pub app_object: Option<AppObject>,
+}
+
+/// Application object derived from `AppObject<ParamsOfAppX, ResultOfAppX>`
+/// parameter. Every variant of `ParamsOfAppX` is a method of the object.
+pub struct AppObject {
+    pub name: String,
+    pub params_type: String,
+    pub result_type: String,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub functions: Vec<AppObjectFunction>,
+}
+
+pub struct AppObjectFunction {
+    pub name: String,
+    pub variant: String,
+    pub params_type: String,
+    pub result_type: Option<String>,
+    pub summary: Option<String>,
+}
+
+pub fn type_name(ref_name: &str) -> &str {
+    ref_name.rsplit('.').next().unwrap_or(ref_name)
+}
+
+pub fn variant_type_name(enum_name: &str, variant: &str) -> String {
+    format!("{}{}Variant", enum_name, variant)
+}
+
+pub fn find_type<'a>(api: &'a API, ref_name: &str) -> Option<&'a Field> {
+    let name = type_name(ref_name);
+    api.modules.iter().flat_map(|module| module.types.iter()).find(|ty| ty.name == name)
+}
+
+pub fn result_type(function: &Function) -> &Type {
+    match &function.result {
+        Type::Generic { name, args } if name == "ClientResult" && !args.is_empty() => &args[0],
+        result => result,
+    }
+}
+
+pub fn function_info<'a>(function: &'a Function, api: &API) -> CliResult<FunctionInfo<'a>> {
+    let mut info = FunctionInfo { params: None, has_response_handler: false, app_object: None };
+    for param in &function.params {
+        match &param.value {
+            Type::Generic { name, args } if name == "Arc" => {
+                if let Some(Type::Ref { name }) = args.first() {
+                    if name == "Request" {
+                        info.has_response_handler = true;
+                    }
+                }
+            }
+            Type::Generic { name, args } if name == "AppObject" => {
+                info.app_object = Some(app_object(args, api)?);
+            }
+            _ if param.name == "params" => info.params = Some(param),
+            _ => {}
+        }
+    }
+    Ok(info)
+}
+
+fn enum_variants<'a>(ty: Option<&Type>, api: &'a API) -> CliResult<(&'a str, &'a [Field])> {
+    if let Some(Type::Ref { name }) = ty {
+        if let Some(field) = find_type(api, name) {
+            if let Type::EnumOfTypes { types } = &field.value {
+                return Ok((&field.name, types));
+            }
+        }
+    }
+    Err(CliError::with_message("AppObject params and result must refer to enums of types"))
+}
+
+fn app_object(args: &[Type], api: &API) -> CliResult<AppObject> {
+    let (params_type, params) = enum_variants(args.first(), api)?;
+    let (result_type, results) = enum_variants(args.get(1), api)?;
+    let params_field = find_type(api, params_type);
+    Ok(AppObject {
+        name: params_type.trim_start_matches("ParamsOf").to_string(),
+        params_type: params_type.to_string(),
+        result_type: result_type.to_string(),
+        summary: params_field.and_then(|field| field.summary.clone()),
+        description: params_field.and_then(|field| field.description.clone()),
+        functions: params
+            .iter()
+            .map(|variant| AppObjectFunction {
+                name: pascal_to_snake(&variant.name),
+                variant: variant.name.clone(),
+                params_type: variant_type_name(params_type, &variant.name),
+                result_type: results
+                    .iter()
+                    .find(|result| result.name == variant.name)
+                    .map(|result| variant_type_name(result_type, &result.name)),
+                summary: variant.summary.clone(),
+            })
+            .collect(),
+    })
+}
+
+pub fn pascal_to_snake(name: &str) -> String {
+    let mut snake = String::new();
+    for c in name.chars() {
+        if c.is_ascii_uppercase() && !snake.is_empty() {
+            snake.push('_');
+        }
+        snake.push(c.to_ascii_lowercase());
+    }
+    snake
+}
+
+/// Converts `snake_case` or arbitrary text like `ABI version` into
+/// `PascalCase` identifier.
+pub fn to_pascal(name: &str) -> String {
+    let mut pascal = String::new();
+    let mut upper = true;
+    for c in name.chars() {
+        if !c.is_ascii_alphanumeric() {
+            upper = true;
+        } else if upper {
+            pascal.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            pascal.push(c);
+        }
+    }
+    if pascal.starts_with(|c: char| c.is_ascii_digit()) {
+        pascal.insert(0, 'F');
+    }
+    pascal
+}
+
+/// Joins summary and description into lines of a doc comment.
+pub fn doc_lines(summary: &Option<String>, description: &Option<String>) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(summary) = summary {
+        lines.extend(summary.lines().map(str::to_string));
+    }
+    if let Some(description) = description {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.extend(description.lines().map(str::to_string));
+    }
+    lines
+}
+
+fn read_api(command_line: &CommandLine) -> CliResult<API> {
+    match command_line.get_opt("i|api") {
+        Some(path) => Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?),
+        None => get_api(),
+    }
+}
+
+pub fn command(args: &[String]) -> Result<(), CliError> {
+    let command_line = CommandLine::parse(args)?;
+    let api = read_api(&command_line)?;
+    let (file_name, code) = match command_line.get_opt("l|language") {
+        Some("python") => ("tvm_client.py", crate::binding_python::generate(&api)?),
+        Some("go") => {
+            let package = command_line.get_opt("p|package").unwrap_or("tvmclient");
+            ("tvm_client.go", crate::binding_go::generate(&api, package)?)
+        }
+        Some(language) => {
+            return Err(CliError::with_message(format!("Unsupported language: {}", language)));
+        }
+        None => return Err(CliError::with_message("Binding language is required")),
+    };
+    if let Some(out_dir) = command_line.get_opt("o|out-dir") {
+        let out_dir: PathBuf = if let Some(stripped) = out_dir.strip_prefix("~/") {
+            dirs::home_dir().ok_or(CliError::with_message("Home dir not found"))?.join(stripped)
+        } else {
+            out_dir.into()
+        };
+        std::fs::create_dir_all(&out_dir)?;
+        std::fs::write(out_dir.join(file_name), code)?;
+    } else {
+        print!("{}", code);
+    }
+    Ok(())
+}
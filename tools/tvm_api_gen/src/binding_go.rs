@@ -0,0 +1,642 @@
+// Copyright 2018-2021 EverX Labs Ltd.
+//
+// Licensed under the SOFTWARE EVALUATION License (the "License"); you may not
+// use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific EVERX DEV software governing permissions and
+// limitations under the License.
+//
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use api_info::API;
+use api_info::ConstValue;
+use api_info::Field;
+use api_info::Module;
+use api_info::NumberType;
+use api_info::Type;
+
+use crate::binding::AppObject;
+use crate::binding::doc_lines;
+use crate::binding::find_type;
+use crate::binding::function_info;
+use crate::binding::result_type;
+use crate::binding::to_pascal;
+use crate::binding::variant_type_name;
+use crate::errors::CliResult;
+
+const RUNTIME: &str = r#"
+/*
+#cgo LDFLAGS: -ltvm_client
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct {
+    const char* content;
+    uint32_t len;
+} tc_string_data_t;
+
+typedef struct tc_string_handle_t tc_string_handle_t;
+
+typedef void (*tc_response_handler_t)(
+    uint32_t request_id,
+    tc_string_data_t params_json,
+    uint32_t response_type,
+    bool finished);
+
+tc_string_handle_t* tc_create_context(tc_string_data_t config);
+void tc_destroy_context(uint32_t context);
+void tc_request(
+    uint32_t context,
+    tc_string_data_t function_name,
+    tc_string_data_t function_params_json,
+    uint32_t request_id,
+    tc_response_handler_t response_handler);
+tc_string_data_t tc_read_string(const tc_string_handle_t* handle);
+void tc_destroy_string(const tc_string_handle_t* handle);
+
+extern void tvmResponseHandler(uint32_t, tc_string_data_t, uint32_t, bool);
+*/
+import "C"
+
+import (
+	"encoding/json"
+	"fmt"
+	"sync"
+	"unsafe"
+)
+
+const (
+	responseSuccess    = 0
+	responseError      = 1
+	responseNop        = 2
+	responseAppRequest = 3
+	responseAppNotify  = 4
+	responseCustom     = 100
+)
+
+// Error is returned by the client library.
+type Error struct {
+	Code    uint32          `json:"code"`
+	Message string          `json:"message"`
+	Data    json.RawMessage `json:"data,omitempty"`
+}
+
+func (e *Error) Error() string {
+	return fmt.Sprintf("%s (code %d)", e.Message, e.Code)
+}
+
+// EventCallback receives events of functions like `processing.process_message`
+// and `net.subscribe_collection`.
+type EventCallback func(event json.RawMessage, responseType uint32)
+
+type appObjectHandler func(params json.RawMessage) (interface{}, error)
+
+type enumVariant interface {
+	variantType() string
+}
+
+func marshalEnumOfTypes(value interface{}) ([]byte, error) {
+	variant, ok := value.(enumVariant)
+	if !ok {
+		return nil, fmt.Errorf("unsupported enum variant %T", value)
+	}
+	data, err := json.Marshal(value)
+	if err != nil {
+		return nil, err
+	}
+	fields := map[string]json.RawMessage{}
+	if err := json.Unmarshal(data, &fields); err != nil {
+		return nil, err
+	}
+	fields["type"], _ = json.Marshal(variant.variantType())
+	return json.Marshal(fields)
+}
+
+func enumType(data []byte) (string, error) {
+	var tag struct {
+		Type string `json:"type"`
+	}
+	err := json.Unmarshal(data, &tag)
+	return tag.Type, err
+}
+
+type response struct {
+	data []byte
+	err  error
+}
+
+type request struct {
+	client    *Client
+	result    chan response
+	callback  EventCallback
+	appObject appObjectHandler
+}
+
+var (
+	requestsLock  sync.Mutex
+	requests      = map[uint32]*request{}
+	lastRequestID uint32
+)
+
+//export tvmResponseHandler
+func tvmResponseHandler(requestID C.uint32_t, paramsJSON C.tc_string_data_t, responseType C.uint32_t, finished C.bool) {
+	requestsLock.Lock()
+	req := requests[uint32(requestID)]
+	if bool(finished) {
+		delete(requests, uint32(requestID))
+	}
+	requestsLock.Unlock()
+	if req == nil {
+		return
+	}
+	data := C.GoBytes(unsafe.Pointer(paramsJSON.content), C.int(paramsJSON.len))
+	switch uint32(responseType) {
+	case responseSuccess:
+		req.result <- response{data: data}
+	case responseError:
+		clientErr := &Error{}
+		if err := json.Unmarshal(data, clientErr); err != nil {
+			req.result <- response{err: err}
+		} else {
+			req.result <- response{err: clientErr}
+		}
+	case responseNop:
+	case responseAppRequest, responseAppNotify:
+		if req.appObject != nil {
+			go req.client.dispatchAppRequest(req.appObject, data, uint32(responseType))
+		}
+	default:
+		if uint32(responseType) >= responseCustom && req.callback != nil {
+			req.callback(data, uint32(responseType))
+		}
+	}
+}
+
+func stringData(value string) (C.tc_string_data_t, func()) {
+	content := C.CString(value)
+	return C.tc_string_data_t{content: content, len: C.uint32_t(len(value))},
+		func() { C.free(unsafe.Pointer(content)) }
+}
+
+// Client is a context of the client library with API functions as methods.
+type Client struct {
+	context C.uint32_t
+}
+
+// NewClient creates client context with the `ClientConfig` given as any
+// value serializable to JSON. Nil config means default configuration.
+func NewClient(config interface{}) (*Client, error) {
+	configJSON := []byte("{}")
+	if config != nil {
+		var err error
+		if configJSON, err = json.Marshal(config); err != nil {
+			return nil, err
+		}
+	}
+	configData, free := stringData(string(configJSON))
+	defer free()
+	handle := C.tc_create_context(configData)
+	data := C.tc_read_string(handle)
+	text := C.GoBytes(unsafe.Pointer(data.content), C.int(data.len))
+	C.tc_destroy_string(handle)
+
+	var result struct {
+		Result *uint32 `json:"result"`
+		Error  *Error  `json:"error"`
+	}
+	if err := json.Unmarshal(text, &result); err != nil {
+		return nil, err
+	}
+	if result.Error != nil {
+		return nil, result.Error
+	}
+	if result.Result == nil {
+		return nil, fmt.Errorf("unexpected response: %s", text)
+	}
+	return &Client{context: C.uint32_t(*result.Result)}, nil
+}
+
+// Close destroys client context.
+func (c *Client) Close() {
+	C.tc_destroy_context(c.context)
+}
+
+func (c *Client) call(function string, params interface{}, result interface{}, callback EventCallback, appObject appObjectHandler) error {
+	paramsJSON := []byte{}
+	if params != nil {
+		var err error
+		if paramsJSON, err = json.Marshal(params); err != nil {
+			return err
+		}
+	}
+	req := &request{client: c, result: make(chan response, 1), callback: callback, appObject: appObject}
+	requestsLock.Lock()
+	lastRequestID++
+	requestID := lastRequestID
+	requests[requestID] = req
+	requestsLock.Unlock()
+
+	functionData, freeFunction := stringData(function)
+	defer freeFunction()
+	paramsData, freeParams := stringData(string(paramsJSON))
+	defer freeParams()
+	C.tc_request(c.context, functionData, paramsData, C.uint32_t(requestID),
+		C.tc_response_handler_t(C.tvmResponseHandler))
+
+	resp := <-req.result
+	if resp.err != nil {
+		return resp.err
+	}
+	if result != nil {
+		return json.Unmarshal(resp.data, result)
+	}
+	return nil
+}
+
+func (c *Client) dispatchAppRequest(handler appObjectHandler, data []byte, responseType uint32) {
+	var appRequest struct {
+		AppRequestID uint32          `json:"app_request_id"`
+		RequestData  json.RawMessage `json:"request_data"`
+	}
+	if responseType == responseAppNotify {
+		appRequest.RequestData = data
+	} else if err := json.Unmarshal(data, &appRequest); err != nil {
+		return
+	}
+	result, err := handler(appRequest.RequestData)
+	if responseType == responseAppNotify {
+		return
+	}
+	var appResult map[string]interface{}
+	if err != nil {
+		appResult = map[string]interface{}{"type": "Error", "text": err.Error()}
+	} else if result == nil {
+		variant, _ := enumType(appRequest.RequestData)
+		appResult = map[string]interface{}{"type": "Ok", "result": map[string]string{"type": variant}}
+	} else {
+		appResult = map[string]interface{}{"type": "Ok", "result": result}
+	}
+	_ = c.call("client.resolve_app_request", map[string]interface{}{
+		"app_request_id": appRequest.AppRequestID,
+		"result":         appResult,
+	}, nil, nil, nil)
+}
+"#;
+
+fn comment(code: &mut String, indent: &str, lines: &[String]) {
+    for line in lines {
+        if line.is_empty() {
+            writeln!(code, "{}//", indent).unwrap();
+        } else {
+            writeln!(code, "{}// {}", indent, line).unwrap();
+        }
+    }
+}
+
+fn string_literal(value: &str) -> String {
+    serde_json::Value::String(value.to_string()).to_string()
+}
+
+struct Generator<'a> {
+    api: &'a API,
+    code: String,
+}
+
+impl<'a> Generator<'a> {
+    fn go_type(&self, ty: &Type) -> String {
+        match ty {
+            Type::None | Type::Any | Type::Struct { .. } | Type::EnumOfTypes { .. } => {
+                "json.RawMessage".to_string()
+            }
+            Type::Boolean => "bool".to_string(),
+            Type::String | Type::EnumOfConsts { .. } => "string".to_string(),
+            Type::Number { number_type: NumberType::Float, number_size } => {
+                if *number_size == 32 { "float32" } else { "float64" }.to_string()
+            }
+            Type::Number { number_type, number_size }
+            | Type::BigInt { number_type, number_size } => {
+                let prefix = if matches!(number_type, NumberType::Int) { "int" } else { "uint" };
+                match number_size {
+                    8 | 16 | 32 | 64 => format!("{}{}", prefix, number_size),
+                    _ if matches!(ty, Type::Number { .. }) => prefix.to_string(),
+                    _ => "json.Number".to_string(),
+                }
+            }
+            Type::Ref { name } => match find_type(self.api, name) {
+                Some(field) => field.name.clone(),
+                None => "json.RawMessage".to_string(),
+            },
+            Type::Optional { inner } => {
+                let inner = self.go_type(inner);
+                if inner.starts_with("[]") || inner == "json.RawMessage" {
+                    inner
+                } else {
+                    format!("*{}", inner)
+                }
+            }
+            Type::Array { item } => format!("[]{}", self.go_type(item)),
+            Type::Generic { name, args } if name == "Box" && !args.is_empty() => {
+                self.go_type(&args[0])
+            }
+            Type::Generic { .. } => "json.RawMessage".to_string(),
+        }
+    }
+
+    fn struct_fields(&mut self, fields: &[Field]) {
+        for field in fields {
+            comment(&mut self.code, "\t", &doc_lines(&field.summary, &field.description));
+            let omit = if matches!(field.value, Type::Optional { .. }) { ",omitempty" } else { "" };
+            writeln!(
+                self.code,
+                "\t{} {} `json:\"{}{}\"`",
+                to_pascal(&field.name),
+                self.go_type(&field.value),
+                field.name,
+                omit
+            )
+            .unwrap();
+        }
+    }
+
+    fn type_def(&mut self, ty: &Field) {
+        writeln!(self.code).unwrap();
+        comment(&mut self.code, "", &doc_lines(&ty.summary, &ty.description));
+        match &ty.value {
+            Type::Struct { fields } => {
+                writeln!(self.code, "type {} struct {{", ty.name).unwrap();
+                self.struct_fields(fields);
+                writeln!(self.code, "}}").unwrap();
+            }
+            Type::EnumOfConsts { consts } => {
+                let base = match consts.first().map(|c| &c.value) {
+                    Some(ConstValue::Number(_)) => "int",
+                    Some(ConstValue::Bool(_)) => "bool",
+                    _ => "string",
+                };
+                writeln!(self.code, "type {} {}\n\nconst (", ty.name, base).unwrap();
+                for c in consts {
+                    comment(&mut self.code, "\t", &doc_lines(&c.summary, &c.description));
+                    let value = match &c.value {
+                        ConstValue::None => string_literal(&c.name),
+                        ConstValue::String(value) => string_literal(value),
+                        ConstValue::Bool(value) | ConstValue::Number(value) => value.clone(),
+                    };
+                    writeln!(
+                        self.code,
+                        "\t{}{} {} = {}",
+                        ty.name,
+                        to_pascal(&c.name),
+                        ty.name,
+                        value
+                    )
+                    .unwrap();
+                }
+                writeln!(self.code, ")").unwrap();
+            }
+            Type::EnumOfTypes { types } => self.enum_of_types(&ty.name, types),
+            Type::Number { .. } | Type::BigInt { .. } => {
+                writeln!(self.code, "type {} {}", ty.name, self.go_type(&ty.value)).unwrap();
+            }
+            value => {
+                writeln!(self.code, "type {} = {}", ty.name, self.go_type(value)).unwrap();
+            }
+        }
+    }
+
+    fn enum_of_types(&mut self, name: &str, types: &[Field]) {
+        let variants =
+            types.iter().map(|variant| variant_type_name(name, &variant.name)).collect::<Vec<_>>();
+        writeln!(self.code, "type {} struct {{", name).unwrap();
+        writeln!(self.code, "\t// One of {}.", variants.join(", ")).unwrap();
+        writeln!(self.code, "\tEnumTypeValue interface{{}}\n}}").unwrap();
+
+        writeln!(
+            self.code,
+            "\nfunc (e {}) MarshalJSON() ([]byte, error) {{\n\
+             \treturn marshalEnumOfTypes(e.EnumTypeValue)\n}}",
+            name
+        )
+        .unwrap();
+
+        writeln!(
+            self.code,
+            "\nfunc (e *{}) UnmarshalJSON(data []byte) error {{\n\
+             \tvariant, err := enumType(data)\n\
+             \tif err != nil {{\n\t\treturn err\n\t}}\n\
+             \tswitch variant {{",
+            name
+        )
+        .unwrap();
+        for (variant, type_name) in types.iter().zip(&variants) {
+            writeln!(
+                self.code,
+                "\tcase {}:\n\
+                 \t\tvar value {}\n\
+                 \t\tif err := json.Unmarshal(data, &value); err != nil {{\n\t\t\treturn err\n\t\t}}\n\
+                 \t\te.EnumTypeValue = value",
+                string_literal(&variant.name),
+                type_name
+            )
+            .unwrap();
+        }
+        writeln!(
+            self.code,
+            "\tdefault:\n\t\treturn fmt.Errorf(\"unknown {} type: %s\", variant)\n\t}}\n\treturn nil\n}}",
+            name
+        )
+        .unwrap();
+
+        for (variant, type_name) in types.iter().zip(&variants) {
+            writeln!(self.code).unwrap();
+            comment(&mut self.code, "", &doc_lines(&variant.summary, &variant.description));
+            match &variant.value {
+                Type::Struct { fields } => {
+                    writeln!(self.code, "type {} struct {{", type_name).unwrap();
+                    self.struct_fields(fields);
+                    writeln!(self.code, "}}").unwrap();
+                }
+                value => {
+                    writeln!(
+                        self.code,
+                        "type {} struct {{\n\t{}\n}}",
+                        type_name,
+                        self.go_type(value)
+                    )
+                    .unwrap();
+                }
+            }
+            writeln!(
+                self.code,
+                "\nfunc ({}) variantType() string {{\n\treturn {}\n}}",
+                type_name,
+                string_literal(&variant.name)
+            )
+            .unwrap();
+        }
+    }
+
+    fn app_object(&mut self, obj: &AppObject) {
+        writeln!(self.code).unwrap();
+        let mut doc = doc_lines(&obj.summary, &obj.description);
+        if !doc.is_empty() {
+            doc.push(String::new());
+        }
+        doc.push(format!("{} is implemented by the application.", obj.name));
+        comment(&mut self.code, "", &doc);
+        writeln!(self.code, "type {} interface {{", obj.name).unwrap();
+        for function in &obj.functions {
+            comment(&mut self.code, "\t", &doc_lines(&function.summary, &None));
+            let result = match &function.result_type {
+                Some(result) => format!("({}, error)", result),
+                None => "error".to_string(),
+            };
+            writeln!(
+                self.code,
+                "\t{}(params {}) {}",
+                function.variant, function.params_type, result
+            )
+            .unwrap();
+        }
+        writeln!(self.code, "}}").unwrap();
+
+        writeln!(
+            self.code,
+            "\nfunc dispatch{}(obj {}) appObjectHandler {{\n\
+             \treturn func(data json.RawMessage) (interface{{}}, error) {{\n\
+             \t\tvar params {}\n\
+             \t\tif err := json.Unmarshal(data, &params); err != nil {{\n\t\t\treturn nil, err\n\t\t}}\n\
+             \t\tswitch p := params.EnumTypeValue.(type) {{",
+            obj.name, obj.name, obj.params_type
+        )
+        .unwrap();
+        for function in &obj.functions {
+            writeln!(self.code, "\t\tcase {}:", function.params_type).unwrap();
+            if function.result_type.is_some() {
+                writeln!(
+                    self.code,
+                    "\t\t\tresult, err := obj.{}(p)\n\
+                     \t\t\tif err != nil {{\n\t\t\t\treturn nil, err\n\t\t\t}}\n\
+                     \t\t\treturn {}{{EnumTypeValue: result}}, nil",
+                    function.variant, obj.result_type
+                )
+                .unwrap();
+            } else {
+                writeln!(self.code, "\t\t\treturn nil, obj.{}(p)", function.variant).unwrap();
+            }
+        }
+        writeln!(
+            self.code,
+            "\t\t}}\n\t\treturn nil, fmt.Errorf(\"unsupported {} request %T\", params.EnumTypeValue)\n\t}}\n}}",
+            obj.name
+        )
+        .unwrap();
+    }
+
+    fn module(&mut self, module: &Module) -> CliResult<()> {
+        writeln!(self.code, "\n// {} module\n//", module.name).unwrap();
+        comment(&mut self.code, "", &doc_lines(&module.summary, &module.description));
+        for function in &module.functions {
+            let info = function_info(function, self.api)?;
+            let method = format!("{}{}", to_pascal(&module.name), to_pascal(&function.name));
+            let mut params = Vec::new();
+            let mut args = vec![string_literal(&format!("{}.{}", module.name, function.name))];
+            if let Some(field) = info.params {
+                let ty = match &field.value {
+                    Type::Ref { .. } => format!("*{}", self.go_type(&field.value)),
+                    value => self.go_type(value),
+                };
+                params.push(format!("params {}", ty));
+                args.push("params".to_string());
+            } else {
+                args.push("nil".to_string());
+            }
+            let result = result_type(function);
+            args.push(if matches!(result, Type::None) { "nil" } else { "result" }.to_string());
+            if info.has_response_handler {
+                params.push("callback EventCallback".to_string());
+                args.push("callback".to_string());
+            } else {
+                args.push("nil".to_string());
+            }
+            if let Some(obj) = &info.app_object {
+                params.push(format!("obj {}", obj.name));
+                args.push(format!("dispatch{}(obj)", obj.name));
+            } else {
+                args.push("nil".to_string());
+            }
+
+            writeln!(self.code).unwrap();
+            let mut doc = doc_lines(&function.summary, &function.description);
+            if let Some(first) = doc.first_mut() {
+                first.insert_str(0, &format!("{} ", method));
+            }
+            comment(&mut self.code, "", &doc);
+            let call = format!("c.call({})", args.join(", "));
+            if matches!(result, Type::None) {
+                writeln!(
+                    self.code,
+                    "func (c *Client) {}({}) error {{\n\treturn {}\n}}",
+                    method,
+                    params.join(", "),
+                    call
+                )
+                .unwrap();
+            } else {
+                let result = self.go_type(result);
+                writeln!(
+                    self.code,
+                    "func (c *Client) {}({}) (*{}, error) {{\n\
+                     \tresult := new({})\n\
+                     \tif err := {}; err != nil {{\n\t\treturn nil, err\n\t}}\n\
+                     \treturn result, nil\n}}",
+                    method,
+                    params.join(", "),
+                    result,
+                    result,
+                    call
+                )
+                .unwrap();
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Generates Go package working with the client library through `cgo`.
+pub fn generate(api: &API, package: &str) -> CliResult<String> {
+    let mut generator = Generator { api, code: String::new() };
+    writeln!(
+        generator.code,
+        "// Code generated by tvm_api_gen from TVM SDK API {}. DO NOT EDIT.\n\n\
+         // Package {} is a Go binding of TVM SDK client library.\n\
+         package {}",
+        api.version, package, package
+    )
+    .unwrap();
+    generator.code.push_str(RUNTIME);
+
+    let mut app_objects = HashSet::new();
+    for module in &api.modules {
+        writeln!(generator.code, "\n// {} module types", module.name).unwrap();
+        for ty in &module.types {
+            generator.type_def(ty);
+        }
+        for function in &module.functions {
+            if let Some(obj) = function_info(function, api)?.app_object {
+                if app_objects.insert(obj.name.clone()) {
+                    generator.app_object(&obj);
+                }
+            }
+        }
+    }
+    for module in &api.modules {
+        generator.module(module)?;
+    }
+    Ok(generator.code)
+}
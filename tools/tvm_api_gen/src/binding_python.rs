@@ -0,0 +1,635 @@
+// Copyright 2018-2021 EverX Labs Ltd.
+//
+// Licensed under the SOFTWARE EVALUATION License (the "License"); you may not
+// use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific EVERX DEV software governing permissions and
+// limitations under the License.
+//
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use api_info::API;
+use api_info::ConstValue;
+use api_info::Field;
+use api_info::Module;
+use api_info::NumberType;
+use api_info::Type;
+
+use crate::binding::AppObject;
+use crate::binding::doc_lines;
+use crate::binding::find_type;
+use crate::binding::function_info;
+use crate::binding::pascal_to_snake;
+use crate::binding::result_type;
+use crate::binding::variant_type_name;
+use crate::errors::CliResult;
+
+const INDENT: &str = "    ";
+
+const KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+const RUNTIME: &str = r#"
+from __future__ import annotations
+
+import ctypes
+import json
+import sys
+import threading
+from dataclasses import dataclass
+from enum import Enum
+from typing import Any, Callable, Dict, List, Optional
+
+# `kw_only` dataclasses let optional fields precede required ones
+if sys.version_info < (3, 10):
+    raise ImportError("tvm_client module requires Python 3.10 or newer")
+
+
+class TvmClientError(Exception):
+    """Error returned by the client library."""
+
+    def __init__(self, error: Dict[str, Any]):
+        super().__init__(error.get("message"))
+        self.code: int = error.get("code", 0)
+        self.message: str = error.get("message", "")
+        self.data: Any = error.get("data")
+
+
+def _any(value: Any) -> Any:
+    return value
+
+
+def _optional(convert: Callable[[Any], Any]) -> Callable[[Any], Any]:
+    return lambda value: None if value is None else convert(value)
+
+
+def _array(convert: Callable[[Any], Any]) -> Callable[[Any], Any]:
+    return lambda value: [convert(item) for item in value]
+
+
+def _object(fields: Dict[str, Any]) -> Dict[str, Any]:
+    return {name: value for name, value in fields.items() if value is not None}
+
+
+def _to_json(value: Any) -> Any:
+    if hasattr(value, "to_json"):
+        return value.to_json()
+    if isinstance(value, Enum):
+        return value.value
+    if isinstance(value, list):
+        return [_to_json(item) for item in value]
+    if isinstance(value, dict):
+        return {key: _to_json(item) for key, item in value.items()}
+    return value
+
+
+class _StringData(ctypes.Structure):
+    _fields_ = [("content", ctypes.c_void_p), ("len", ctypes.c_uint32)]
+
+
+def _string_data(text: str):
+    data = text.encode("utf-8")
+    buffer = ctypes.create_string_buffer(data, len(data))
+    return _StringData(ctypes.cast(buffer, ctypes.c_void_p), len(data)), buffer
+
+
+_RESPONSE_HANDLER = ctypes.CFUNCTYPE(
+    None, ctypes.c_uint32, _StringData, ctypes.c_uint32, ctypes.c_bool
+)
+
+_RESPONSE_SUCCESS = 0
+_RESPONSE_ERROR = 1
+_RESPONSE_APP_REQUEST = 3
+_RESPONSE_APP_NOTIFY = 4
+
+
+def _library_name() -> str:
+    if sys.platform == "darwin":
+        return "libtvm_client.dylib"
+    if sys.platform == "win32":
+        return "tvm_client.dll"
+    return "libtvm_client.so"
+
+
+class _Request:
+    def __init__(self, callback, app_object):
+        self.callback = callback
+        self.app_object = app_object
+        self.result = None
+        self.error = None
+        self.done = threading.Event()
+
+
+class _ClientCore:
+    def __init__(self, config: Optional[Dict[str, Any]] = None, library: Optional[str] = None):
+        lib = ctypes.CDLL(library or _library_name())
+        lib.tc_create_context.argtypes = [_StringData]
+        lib.tc_create_context.restype = ctypes.c_void_p
+        lib.tc_read_string.argtypes = [ctypes.c_void_p]
+        lib.tc_read_string.restype = _StringData
+        lib.tc_destroy_string.argtypes = [ctypes.c_void_p]
+        lib.tc_destroy_string.restype = None
+        lib.tc_destroy_context.argtypes = [ctypes.c_uint32]
+        lib.tc_destroy_context.restype = None
+        lib.tc_request.argtypes = [
+            ctypes.c_uint32, _StringData, _StringData, ctypes.c_uint32, _RESPONSE_HANDLER
+        ]
+        lib.tc_request.restype = None
+        self._lib = lib
+        self._lock = threading.Lock()
+        self._requests: Dict[int, _Request] = {}
+        self._next_request_id = 1
+        self._handler = _RESPONSE_HANDLER(self._on_response)
+
+        config_data, _config_buffer = _string_data(json.dumps(_to_json(config or {})))
+        response_ptr = lib.tc_create_context(config_data)
+        response = lib.tc_read_string(response_ptr)
+        response = json.loads(ctypes.string_at(response.content, response.len).decode("utf-8"))
+        lib.tc_destroy_string(response_ptr)
+        if "error" in response:
+            raise TvmClientError(response["error"])
+        self._context = response["result"]
+
+    def close(self):
+        if self._context is not None:
+            self._lib.tc_destroy_context(self._context)
+            self._context = None
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, *_args):
+        self.close()
+
+    def request(self, function: str, params: Any = None, callback=None, app_object=None) -> Any:
+        """Calls API function and waits for its result. `callback` receives
+        function events as `(params, response_type)`."""
+        request = _Request(callback, app_object)
+        with self._lock:
+            request_id = self._next_request_id
+            self._next_request_id += 1
+            self._requests[request_id] = request
+        params_json = "" if params is None else json.dumps(_to_json(params))
+        function_data, _function_buffer = _string_data(function)
+        params_data, _params_buffer = _string_data(params_json)
+        self._lib.tc_request(self._context, function_data, params_data, request_id, self._handler)
+        request.done.wait()
+        if request.error is not None:
+            raise TvmClientError(request.error)
+        return request.result
+
+    def _on_response(self, request_id, params_json, response_type, finished):
+        with self._lock:
+            request = self._requests.get(request_id)
+            if finished:
+                self._requests.pop(request_id, None)
+        if request is None:
+            return
+        text = ctypes.string_at(params_json.content, params_json.len).decode("utf-8") \
+            if params_json.len else ""
+        value = json.loads(text) if text else None
+        if response_type == _RESPONSE_SUCCESS:
+            request.result = value
+            request.done.set()
+        elif response_type == _RESPONSE_ERROR:
+            request.error = value
+            request.done.set()
+        elif response_type in (_RESPONSE_APP_REQUEST, _RESPONSE_APP_NOTIFY):
+            if request.app_object is not None:
+                threading.Thread(
+                    target=self._dispatch_app_request,
+                    args=(request.app_object, value, response_type),
+                    daemon=True,
+                ).start()
+        elif response_type >= 100 and request.callback is not None:
+            request.callback(value, response_type)
+
+    def _dispatch_app_request(self, app_object, value, response_type):
+        obj, params_type, methods = app_object
+        app_request_id = None
+        if response_type == _RESPONSE_APP_REQUEST:
+            app_request_id = value["app_request_id"]
+            value = value["request_data"]
+        try:
+            result = getattr(obj, methods[value["type"]])(params_type.from_json(value))
+            result = {"type": value["type"]} if result is None else _to_json(result)
+            app_result = {"type": "Ok", "result": result}
+        except Exception as error:
+            app_result = {"type": "Error", "text": str(error)}
+        if app_request_id is not None:
+            self.request(
+                "client.resolve_app_request",
+                {"app_request_id": app_request_id, "result": app_result},
+            )
+"#;
+
+fn ident(name: &str) -> String {
+    let mut ident: String =
+        name.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    if KEYWORDS.contains(&ident.as_str()) {
+        ident.push('_');
+    }
+    ident
+}
+
+fn string_literal(value: &str) -> String {
+    serde_json::Value::String(value.to_string()).to_string()
+}
+
+fn docstring(code: &mut String, indent: &str, lines: &[String]) {
+    if lines.is_empty() {
+        return;
+    }
+    let text = lines
+        .join(&format!("\n{}", indent))
+        .replace('\\', "\\\\")
+        .replace("\"\"\"", "\\\"\\\"\\\"");
+    let mut text = text.replace(&format!("\n{}\n", indent), "\n\n").trim_end().to_string();
+    if text.ends_with('"') {
+        text.pop();
+        text.push_str("\\\"");
+    }
+    writeln!(code, "{}\"\"\"{}\"\"\"", indent, text).unwrap();
+}
+
+struct Generator<'a> {
+    api: &'a API,
+    code: String,
+}
+
+impl<'a> Generator<'a> {
+    fn type_hint(&self, ty: &Type) -> String {
+        match ty {
+            Type::None => "None".to_string(),
+            Type::Any => "Any".to_string(),
+            Type::Boolean => "bool".to_string(),
+            Type::String => "str".to_string(),
+            Type::Number { number_type: NumberType::Float, .. } => "float".to_string(),
+            Type::Number { .. } | Type::BigInt { .. } => "int".to_string(),
+            Type::Ref { name } => match find_type(self.api, name) {
+                Some(field) => field.name.clone(),
+                None => "Any".to_string(),
+            },
+            Type::Optional { inner } => format!("Optional[{}]", self.type_hint(inner)),
+            Type::Array { item } => format!("List[{}]", self.type_hint(item)),
+            Type::Struct { .. } => "Dict[str, Any]".to_string(),
+            Type::EnumOfConsts { .. } => "str".to_string(),
+            Type::EnumOfTypes { .. } => "Dict[str, Any]".to_string(),
+            Type::Generic { name, args } if name == "Box" && !args.is_empty() => {
+                self.type_hint(&args[0])
+            }
+            Type::Generic { .. } => "Any".to_string(),
+        }
+    }
+
+    /// Returns Python expression of the function converting JSON value into
+    /// the value of the type.
+    fn converter(&self, ty: &Type) -> String {
+        match ty {
+            Type::Ref { name } => match find_type(self.api, name) {
+                Some(Field {
+                    name, value: Type::Struct { .. } | Type::EnumOfTypes { .. }, ..
+                }) => {
+                    format!("{}.from_json", name)
+                }
+                Some(Field { name, value: Type::EnumOfConsts { .. }, .. }) => name.clone(),
+                _ => "_any".to_string(),
+            },
+            Type::Optional { inner } => format!("_optional({})", self.converter(inner)),
+            Type::Array { item } => format!("_array({})", self.converter(item)),
+            Type::Generic { name, args } if name == "Box" && !args.is_empty() => {
+                self.converter(&args[0])
+            }
+            _ => "_any".to_string(),
+        }
+    }
+
+    fn dataclass(
+        &mut self,
+        name: &str,
+        base: Option<&str>,
+        tag: Option<&str>,
+        fields: &[Field],
+        doc: Vec<String>,
+    ) {
+        let base = base.map(|base| format!("({})", base)).unwrap_or_default();
+        writeln!(self.code, "\n\n@dataclass(kw_only=True)\nclass {}{}:", name, base).unwrap();
+        docstring(&mut self.code, INDENT, &doc);
+        for field in fields {
+            let hint = self.type_hint(&field.value);
+            let default = if matches!(field.value, Type::Optional { .. }) { " = None" } else { "" };
+            writeln!(self.code, "{}{}: {}{}", INDENT, ident(&field.name), hint, default).unwrap();
+            docstring(&mut self.code, INDENT, &doc_lines(&field.summary, &field.description));
+        }
+
+        writeln!(self.code, "\n{}def to_json(self) -> Dict[str, Any]:", INDENT).unwrap();
+        let mut items = Vec::new();
+        if let Some(tag) = tag {
+            items.push(format!("\"type\": {}", string_literal(tag)));
+        }
+        for field in fields {
+            items.push(format!(
+                "{}: _to_json(self.{})",
+                string_literal(&field.name),
+                ident(&field.name)
+            ));
+        }
+        writeln!(self.code, "{0}{0}return _object({{{1}}})", INDENT, items.join(", ")).unwrap();
+
+        writeln!(self.code, "\n{}@staticmethod", INDENT).unwrap();
+        writeln!(self.code, "{}def from_json(data: Dict[str, Any]) -> {}:", INDENT, name).unwrap();
+        let args = fields
+            .iter()
+            .map(|field| {
+                format!(
+                    "{}={}(data.get({}))",
+                    ident(&field.name),
+                    self.converter(&field.value),
+                    string_literal(&field.name)
+                )
+            })
+            .collect::<Vec<_>>();
+        writeln!(self.code, "{0}{0}return {1}({2})", INDENT, name, args.join(", ")).unwrap();
+    }
+
+    fn type_def(&mut self, ty: &Field) {
+        let doc = doc_lines(&ty.summary, &ty.description);
+        match &ty.value {
+            Type::Struct { fields } => self.dataclass(&ty.name, None, None, fields, doc),
+            Type::EnumOfConsts { consts } => {
+                let base = match consts.first().map(|c| &c.value) {
+                    Some(ConstValue::Number(_)) => "int, Enum",
+                    Some(ConstValue::Bool(_)) => "Enum",
+                    _ => "str, Enum",
+                };
+                writeln!(self.code, "\n\nclass {}({}):", ty.name, base).unwrap();
+                docstring(&mut self.code, INDENT, &doc);
+                for c in consts {
+                    let value = match &c.value {
+                        ConstValue::None => string_literal(&c.name),
+                        ConstValue::String(value) => string_literal(value),
+                        ConstValue::Bool(value) if value == "true" => "True".to_string(),
+                        ConstValue::Bool(_) => "False".to_string(),
+                        ConstValue::Number(value) => value.clone(),
+                    };
+                    writeln!(self.code, "{}{} = {}", INDENT, ident(&c.name), value).unwrap();
+                }
+            }
+            Type::EnumOfTypes { types } => {
+                writeln!(self.code, "\n\nclass {}:", ty.name).unwrap();
+                let mut doc = doc;
+                doc.push(String::new());
+                doc.push(format!(
+                    "One of {}.",
+                    types
+                        .iter()
+                        .map(|variant| format!("`{}`", variant_type_name(&ty.name, &variant.name)))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+                docstring(&mut self.code, INDENT, &doc);
+                writeln!(self.code, "\n{}_variants: Dict[str, Any] = {{}}", INDENT).unwrap();
+                writeln!(self.code, "\n{}@classmethod", INDENT).unwrap();
+                writeln!(
+                    self.code,
+                    "{}def from_json(cls, data: Dict[str, Any]) -> {}:",
+                    INDENT, ty.name
+                )
+                .unwrap();
+                writeln!(
+                    self.code,
+                    "{0}{0}return cls._variants[data[\"type\"]].from_json(data)",
+                    INDENT
+                )
+                .unwrap();
+                for variant in types {
+                    let name = variant_type_name(&ty.name, &variant.name);
+                    let doc = doc_lines(&variant.summary, &variant.description);
+                    match &variant.value {
+                        Type::Struct { fields } => {
+                            self.dataclass(&name, Some(&ty.name), Some(&variant.name), fields, doc)
+                        }
+                        value => {
+                            let fields = [Field {
+                                name: "value".to_string(),
+                                value: value.clone(),
+                                summary: None,
+                                description: None,
+                            }];
+                            self.dataclass(
+                                &name,
+                                Some(&ty.name),
+                                Some(&variant.name),
+                                &fields,
+                                doc,
+                            );
+                            if matches!(value, Type::Ref { .. }) {
+                                self.flatten_ref_variant(&name, &variant.name, value);
+                            }
+                        }
+                    }
+                }
+                let variants = types
+                    .iter()
+                    .map(|variant| {
+                        format!(
+                            "{}: {}",
+                            string_literal(&variant.name),
+                            variant_type_name(&ty.name, &variant.name)
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                writeln!(self.code, "\n\n{}._variants = {{{}}}", ty.name, variants.join(", "))
+                    .unwrap();
+            }
+            value => {
+                writeln!(self.code, "\n\n{} = {}", ty.name, self.type_hint(value)).unwrap();
+            }
+        }
+    }
+
+    /// Variant referring to a struct is serialized with the struct fields
+    /// next to the `type` tag.
+    fn flatten_ref_variant(&mut self, name: &str, tag: &str, value: &Type) {
+        let converter = self.converter(value);
+        writeln!(
+            self.code,
+            "\n\n{0}.to_json = lambda self: {{\"type\": {1}, **_to_json(self.value)}}\n\
+             {0}.from_json = staticmethod(lambda data: {0}(value={2}(data)))",
+            name,
+            string_literal(tag),
+            converter
+        )
+        .unwrap();
+    }
+
+    fn app_object(&mut self, obj: &AppObject) {
+        writeln!(self.code, "\n\nclass {}:", obj.name).unwrap();
+        let mut doc = doc_lines(&obj.summary, &obj.description);
+        doc.push(String::new());
+        doc.push(
+            "Implemented by the application. Methods raise an exception to report an error."
+                .to_string(),
+        );
+        docstring(&mut self.code, INDENT, &doc);
+        for function in &obj.functions {
+            let result = function.result_type.as_deref().unwrap_or("None");
+            writeln!(
+                self.code,
+                "\n{}def {}(self, params: {}) -> {}:",
+                INDENT,
+                ident(&function.name),
+                function.params_type,
+                result
+            )
+            .unwrap();
+            docstring(&mut self.code, &INDENT.repeat(2), &doc_lines(&function.summary, &None));
+            writeln!(self.code, "{0}{0}raise NotImplementedError", INDENT).unwrap();
+        }
+        let methods = obj
+            .functions
+            .iter()
+            .map(|function| {
+                format!(
+                    "{}: {}",
+                    string_literal(&function.variant),
+                    string_literal(&ident(&function.name))
+                )
+            })
+            .collect::<Vec<_>>();
+        writeln!(
+            self.code,
+            "\n\n_{}_METHODS = {{{}}}",
+            pascal_to_snake(&obj.name).to_uppercase(),
+            methods.join(", ")
+        )
+        .unwrap();
+    }
+
+    fn module(&mut self, module: &Module) -> CliResult<()> {
+        writeln!(self.code, "\n\nclass {}Module:", crate::binding::to_pascal(&module.name))
+            .unwrap();
+        docstring(&mut self.code, INDENT, &doc_lines(&module.summary, &module.description));
+        writeln!(self.code, "\n{}def __init__(self, client: _ClientCore):", INDENT).unwrap();
+        writeln!(self.code, "{0}{0}self._client = client", INDENT).unwrap();
+        for function in &module.functions {
+            let info = function_info(function, self.api)?;
+            let mut params = vec!["self".to_string()];
+            let mut args = vec![string_literal(&format!("{}.{}", module.name, function.name))];
+            if let Some(field) = info.params {
+                params.push(format!("params: {}", self.type_hint(&field.value)));
+                args.push("params".to_string());
+            } else {
+                args.push("None".to_string());
+            }
+            if let Some(obj) = &info.app_object {
+                params.push(format!("obj: {}", obj.name));
+                args.push(format!(
+                    "app_object=(obj, {}, _{}_METHODS)",
+                    obj.params_type,
+                    pascal_to_snake(&obj.name).to_uppercase()
+                ));
+            } else if info.has_response_handler {
+                params.push("callback: Optional[Callable[[Any, int], None]] = None".to_string());
+                args.push("callback=callback".to_string());
+            }
+            let result = result_type(function);
+            writeln!(
+                self.code,
+                "\n{}def {}({}) -> {}:",
+                INDENT,
+                ident(&function.name),
+                params.join(", "),
+                self.type_hint(result)
+            )
+            .unwrap();
+            docstring(
+                &mut self.code,
+                &INDENT.repeat(2),
+                &doc_lines(&function.summary, &function.description),
+            );
+            let call = format!("self._client.request({})", args.join(", "));
+            match result {
+                Type::None => writeln!(self.code, "{0}{0}{1}", INDENT, call).unwrap(),
+                result => writeln!(
+                    self.code,
+                    "{0}{0}return {1}({2})",
+                    INDENT,
+                    self.converter(result),
+                    call
+                )
+                .unwrap(),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Generates Python module `tvm_client.py` working with the client library
+/// through `ctypes`. The module requires Python 3.10 or newer.
+pub fn generate(api: &API) -> CliResult<String> {
+    let mut generator = Generator { api, code: String::new() };
+    writeln!(
+        generator.code,
+        "# Generated by tvm_api_gen from TVM SDK API {}. Do not edit.\n\
+         \"\"\"Python binding of TVM SDK client library. Requires Python 3.10 or newer.\"\"\"",
+        api.version
+    )
+    .unwrap();
+    generator.code.push_str(RUNTIME);
+
+    let mut app_objects = HashSet::new();
+    for module in &api.modules {
+        writeln!(generator.code, "\n\n# {} module", module.name).unwrap();
+        for ty in &module.types {
+            generator.type_def(ty);
+        }
+        for function in &module.functions {
+            if let Some(obj) = function_info(function, api)?.app_object {
+                if app_objects.insert(obj.name.clone()) {
+                    generator.app_object(&obj);
+                }
+            }
+        }
+    }
+    for module in &api.modules {
+        generator.module(module)?;
+    }
+
+    writeln!(generator.code, "\n\nclass TvmClient(_ClientCore):").unwrap();
+    docstring(
+        &mut generator.code,
+        INDENT,
+        &["Client context with API modules. Call `close` or use `with` to release it.".to_string()],
+    );
+    writeln!(
+        generator.code,
+        "\n{}def __init__(self, config: Optional[Dict[str, Any]] = None, library: Optional[str] = None):",
+        INDENT
+    )
+    .unwrap();
+    writeln!(generator.code, "{0}{0}super().__init__(config, library)", INDENT).unwrap();
+    for module in &api.modules {
+        writeln!(
+            generator.code,
+            "{0}{0}self.{1} = {2}Module(self)",
+            INDENT,
+            ident(&module.name),
+            crate::binding::to_pascal(&module.name)
+        )
+        .unwrap();
+    }
+    Ok(generator.code)
+}
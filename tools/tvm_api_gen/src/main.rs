@@ -13,6 +13,9 @@ extern crate serde_json;
 extern crate serde_derive;
 
 mod api;
+mod binding;
+mod binding_go;
+mod binding_python;
 mod command_line;
 mod errors;
 mod request;
@@ -22,6 +25,7 @@ Usage:  tvm_api_gen [OPTIONS] <command> [args...]
 
 Commands:
     api      Exports tvm client api JSON
+    binding  Generates client binding for another language
     request  Executes tvm client api function
 
 api [OPTIONS]
@@ -31,6 +35,19 @@ Options:
 Example:
     tvm_api_gen api -o ~/tvm_sdk_api
 
+binding [OPTIONS]
+Options:
+    -l, --language string  Binding language: `python` or `go`.
+    -o, --out-dir string   Path to folder where the `tvm_client.py` or `tvm_client.go`
+                           will be stored. If omitted, then binding will be printed
+                           to console.
+    -i, --api string       Path to `api.json` exported with `api` command.
+                           If omitted, then api of the linked client is used.
+    -p, --package string   Go package name. Default is `tvmclient`.
+Example:
+    tvm_api_gen binding -l python -o ~/tvm_sdk_python
+    tvm_api_gen binding -l go -i api.json -p tvm -o ~/go/src/tvm
+
 request <function> [params...]
     function  Any possible api function name in form of `module.function`.
     params    All params collected as a JSON5 function parameters.
@@ -59,6 +76,7 @@ fn main() {
     let cmd = args.get(1).map(|x| x.as_str());
     let result = match cmd.unwrap_or("") {
         "api" => api::command(&args[2..]),
+        "binding" => binding::command(&args[2..]),
         "request" => request::command(&args[2..]),
         _ => {
             print_usage_and_exit();
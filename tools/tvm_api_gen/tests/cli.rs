@@ -1,2 +1,67 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+
 #[test]
 fn test_config() {}
+
+#[test]
+fn test_binding() {
+    let api = concat!(env!("CARGO_MANIFEST_DIR"), "/../api.json");
+    Command::cargo_bin("tvm-api-gen")
+        .unwrap()
+        .args(["binding", "-l", "python", "-i", api])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("class TvmClient(_ClientCore):"))
+        .stdout(predicate::str::contains("class AppSigningBox:"))
+        .stdout(predicate::str::contains(
+            "def generate_random_bytes(self, params: ParamsOfGenerateRandomBytes)",
+        ));
+    Command::cargo_bin("tvm-api-gen")
+        .unwrap()
+        .args(["binding", "-l", "go", "-p", "tvm", "-i", api])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("package tvm"))
+        .stdout(predicate::str::contains(
+            "func (c *Client) CryptoGenerateRandomBytes(params *ParamsOfGenerateRandomBytes) (*ResultOfGenerateRandomBytes, error)",
+        ));
+    Command::cargo_bin("tvm-api-gen")
+        .unwrap()
+        .args(["binding", "-l", "java", "-i", api])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unsupported language: java"));
+}
+
+#[test]
+fn test_python_binding_import() {
+    let python = std::process::Command::new("python3")
+        .args(["-c", "import sys; sys.exit(sys.version_info < (3, 10))"])
+        .status();
+    if !matches!(python, Ok(status) if status.success()) {
+        eprintln!("Python 3.10 or newer is not available, skipped");
+        return;
+    }
+    let api = concat!(env!("CARGO_MANIFEST_DIR"), "/../api.json");
+    let out_dir = std::env::temp_dir().join(format!("tvm_api_gen_python_{}", std::process::id()));
+    Command::cargo_bin("tvm-api-gen")
+        .unwrap()
+        .args(["binding", "-l", "python", "-i", api, "-o"])
+        .arg(&out_dir)
+        .assert()
+        .success();
+    Command::new("python3")
+        .current_dir(&out_dir)
+        .args([
+            "-c",
+            "import tvm_client\n\
+             params = tvm_client.ParamsOfGenerateRandomBytes(length=8)\n\
+             assert tvm_client.ParamsOfGenerateRandomBytes.from_json(params.to_json()) == params\n\
+             signer = tvm_client.Signer.from_json({'type': 'External', 'public_key': 'ab'})\n\
+             assert isinstance(signer, tvm_client.SignerExternalVariant)",
+        ])
+        .assert()
+        .success();
+    std::fs::remove_dir_all(&out_dir).unwrap();
+}
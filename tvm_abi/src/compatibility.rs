@@ -0,0 +1,281 @@
+// Copyright (C) 2019-2022 TON Labs. All Rights Reserved.
+//
+// Licensed under the SOFTWARE EVALUATION License (the "License"); you may not
+// use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific TON DEV software governing permissions and
+// limitations under the License.
+
+//! Compatibility check of two ABI versions of the same contract.
+//!
+//! A change is breaking if messages, events or storage encoded with the old
+//! ABI can not be handled with the new one (or vice versa), e.g. a removed
+//! function, a changed parameter type or a shifted storage field.
+
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::contract::Contract;
+use crate::event::Event;
+use crate::function::Function;
+use crate::param::Param;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeKind {
+    /// Existing clients and stored data stay compatible.
+    Compatible,
+    /// Existing clients stay compatible, but stored data must be extended on
+    /// upgrade.
+    StorageExtension,
+    /// Existing clients or stored data are affected.
+    Breaking,
+}
+
+impl Display for ChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChangeKind::Compatible => write!(f, "compatible"),
+            ChangeKind::StorageExtension => write!(f, "storage extension"),
+            ChangeKind::Breaking => write!(f, "breaking"),
+        }
+    }
+}
+
+/// Difference between two ABI versions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AbiChange {
+    pub kind: ChangeKind,
+    /// Location of the change, e.g. `functions.transfer`.
+    pub path: String,
+    pub message: String,
+}
+
+impl Display for AbiChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}: {}", self.kind, self.path, self.message)
+    }
+}
+
+#[derive(Default)]
+struct Changes(Vec<AbiChange>);
+
+impl Changes {
+    fn breaking(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.0.push(AbiChange {
+            kind: ChangeKind::Breaking,
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+
+    fn compatible(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.0.push(AbiChange {
+            kind: ChangeKind::Compatible,
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+
+    fn storage_extension(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.0.push(AbiChange {
+            kind: ChangeKind::StorageExtension,
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+}
+
+/// Compares `new` ABI of a contract with the `old` one and returns the list
+/// of changes.
+pub fn check_compatibility(old: &Contract, new: &Contract) -> Vec<AbiChange> {
+    let mut changes = Changes::default();
+
+    let (old_version, new_version) = (old.version(), new.version());
+    if old_version.major != new_version.major || new_version < old_version {
+        changes.breaking("version", format!("version changed {} -> {}", old_version, new_version));
+    } else if new_version != old_version {
+        changes
+            .compatible("version", format!("version changed {} -> {}", old_version, new_version));
+    }
+
+    if old.header() != new.header() {
+        changes.breaking(
+            "header",
+            format!(
+                "header changed ({}) -> ({})",
+                signature(old.header()),
+                signature(new.header())
+            ),
+        );
+    }
+
+    compare_functions("functions", old.functions(), new.functions(), true, &mut changes);
+    compare_functions("getters", old.getters(), new.getters(), false, &mut changes);
+    compare_events(old.events(), new.events(), &mut changes);
+    compare_data(old, new, &mut changes);
+    compare_fields(old, new, &mut changes);
+
+    changes.0
+}
+
+fn compare_functions(
+    section: &str,
+    old: &HashMap<String, Function>,
+    new: &HashMap<String, Function>,
+    compare_ids: bool,
+    changes: &mut Changes,
+) {
+    for name in names(old, new) {
+        let path = format!("{}.{}", section, name);
+        match (old.get(name), new.get(name)) {
+            (Some(_), None) => changes.breaking(path, "removed"),
+            (None, Some(_)) => changes.compatible(path, "added"),
+            (Some(old), Some(new)) => {
+                if old.inputs != new.inputs {
+                    changes.breaking(
+                        &path,
+                        format!(
+                            "inputs changed ({}) -> ({})",
+                            signature(&old.inputs),
+                            signature(&new.inputs)
+                        ),
+                    );
+                }
+                if old.outputs != new.outputs {
+                    changes.breaking(
+                        &path,
+                        format!(
+                            "outputs changed ({}) -> ({})",
+                            signature(&old.outputs),
+                            signature(&new.outputs)
+                        ),
+                    );
+                }
+                if compare_ids && old.get_input_id() != new.get_input_id() {
+                    changes.breaking(
+                        &path,
+                        format!(
+                            "id changed 0x{:08x} -> 0x{:08x}",
+                            old.get_input_id(),
+                            new.get_input_id()
+                        ),
+                    );
+                }
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+fn compare_events(
+    old: &HashMap<String, Event>,
+    new: &HashMap<String, Event>,
+    changes: &mut Changes,
+) {
+    for name in names(old, new) {
+        let path = format!("events.{}", name);
+        match (old.get(name), new.get(name)) {
+            (Some(_), None) => changes.breaking(path, "removed"),
+            (None, Some(_)) => changes.compatible(path, "added"),
+            (Some(old), Some(new)) => {
+                if old.inputs != new.inputs {
+                    changes.breaking(
+                        &path,
+                        format!(
+                            "inputs changed ({}) -> ({})",
+                            signature(&old.inputs),
+                            signature(&new.inputs)
+                        ),
+                    );
+                }
+                if old.get_id() != new.get_id() {
+                    changes.breaking(
+                        &path,
+                        format!("id changed 0x{:08x} -> 0x{:08x}", old.get_id(), new.get_id()),
+                    );
+                }
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+fn compare_data(old: &Contract, new: &Contract, changes: &mut Changes) {
+    let (old_data, new_data) = (old.data(), new.data());
+    for name in names(old_data, new_data) {
+        let path = format!("data.{}", name);
+        match (old_data.get(name), new_data.get(name)) {
+            (Some(_), None) => changes.breaking(path, "removed"),
+            (None, Some(_)) => changes.compatible(path, "added"),
+            (Some(old), Some(new)) => {
+                if old.key != new.key {
+                    changes.breaking(&path, format!("key changed {} -> {}", old.key, new.key));
+                }
+                if old.value.kind != new.value.kind {
+                    changes.breaking(
+                        &path,
+                        format!("type changed {} -> {}", old.value.kind, new.value.kind),
+                    );
+                }
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// Storage fields are packed sequentially, so the layout is compatible as
+/// long as the old fields stay a prefix of the new ones. Appended fields do
+/// not affect clients, but the stored data must be extended on upgrade.
+fn compare_fields(old: &Contract, new: &Contract, changes: &mut Changes) {
+    let (old_fields, new_fields) = (old.fields(), new.fields());
+    for (index, (old_field, new_field)) in old_fields.iter().zip(new_fields).enumerate() {
+        let path = format!("fields.{}", old_field.name);
+        if old_field.kind != new_field.kind {
+            changes.breaking(
+                path,
+                format!(
+                    "field #{} changed {} {} -> {} {}",
+                    index, old_field.kind, old_field.name, new_field.kind, new_field.name
+                ),
+            );
+        } else if old_field.name != new_field.name {
+            changes.compatible(
+                path,
+                format!("field #{} renamed {} -> {}", index, old_field.name, new_field.name),
+            );
+        } else if old.init_fields().contains(&old_field.name)
+            != new.init_fields().contains(&new_field.name)
+        {
+            changes.compatible(path, format!("field #{} `init` flag changed", index));
+        }
+    }
+    for (index, field) in old_fields.iter().enumerate().skip(new_fields.len()) {
+        changes.breaking(format!("fields.{}", field.name), format!("field #{} removed", index));
+    }
+    for (index, field) in new_fields.iter().enumerate().skip(old_fields.len()) {
+        changes.storage_extension(
+            format!("fields.{}", field.name),
+            format!("field #{} appended", index),
+        );
+    }
+}
+
+fn names<'a, T>(old: &'a HashMap<String, T>, new: &'a HashMap<String, T>) -> BTreeSet<&'a String> {
+    old.keys().chain(new.keys()).collect()
+}
+
+fn signature(params: &[Param]) -> String {
+    params
+        .iter()
+        .map(|param| format!("{} {}", param.kind, param.name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+#[path = "tests/test_compatibility.rs"]
+mod tests;
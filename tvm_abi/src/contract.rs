@@ -17,6 +17,7 @@ use std::io;
 use serde::de::Error as SerdeError;
 use tvm_block::MsgAddressInt;
 use tvm_block::Serializable;
+use tvm_types::BLS_SIG_LEN;
 use tvm_types::BuilderData;
 use tvm_types::ED25519_PUBLIC_KEY_LENGTH;
 use tvm_types::ED25519_SIGNATURE_LENGTH;
@@ -33,10 +34,11 @@ use crate::function::Function;
 use crate::param::Param;
 use crate::param::SerdeParam;
 use crate::param_type::ParamType;
+use crate::token::FieldPath;
 use crate::token::Token;
 
 pub const MIN_SUPPORTED_VERSION: AbiVersion = ABI_VERSION_1_0;
-pub const MAX_SUPPORTED_VERSION: AbiVersion = ABI_VERSION_2_4;
+pub const MAX_SUPPORTED_VERSION: AbiVersion = ABI_VERSION_2_5;
 
 pub const ABI_VERSION_1_0: AbiVersion = AbiVersion::from_parts(1, 0);
 pub const ABI_VERSION_2_0: AbiVersion = AbiVersion::from_parts(2, 0);
@@ -44,10 +46,71 @@ pub const ABI_VERSION_2_1: AbiVersion = AbiVersion::from_parts(2, 1);
 pub const ABI_VERSION_2_2: AbiVersion = AbiVersion::from_parts(2, 2);
 pub const ABI_VERSION_2_3: AbiVersion = AbiVersion::from_parts(2, 3);
 pub const ABI_VERSION_2_4: AbiVersion = AbiVersion::from_parts(2, 4);
+pub const ABI_VERSION_2_5: AbiVersion = AbiVersion::from_parts(2, 5);
 
 pub type PublicKeyData = [u8; ED25519_PUBLIC_KEY_LENGTH];
 pub type SignatureData = [u8; ED25519_SIGNATURE_LENGTH];
 
+/// Signature scheme of the message body signature
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SignatureScheme {
+    #[default]
+    Ed25519,
+    /// BLS12-381 in the min-pk mode of `tvm_types::bls`
+    Bls,
+    /// ECDSA over secp256k1, `r || s`
+    Secp256k1,
+}
+
+impl SignatureScheme {
+    /// Length of the signature in bytes
+    pub fn signature_len(&self) -> usize {
+        match self {
+            SignatureScheme::Ed25519 => ED25519_SIGNATURE_LENGTH,
+            SignatureScheme::Bls => BLS_SIG_LEN,
+            SignatureScheme::Secp256k1 => 64,
+        }
+    }
+}
+
+/// Message body signature tagged with the scheme it was made with
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemeSignature {
+    scheme: SignatureScheme,
+    data: Vec<u8>,
+}
+
+impl SchemeSignature {
+    /// Fails if the signature length does not match the scheme
+    pub fn new(scheme: SignatureScheme, data: Vec<u8>) -> Result<Self> {
+        if data.len() != scheme.signature_len() {
+            fail!(AbiError::InvalidInputData {
+                msg: format!(
+                    "{:?} signature must be {} bytes, got {}",
+                    scheme,
+                    scheme.signature_len(),
+                    data.len()
+                )
+            });
+        }
+        Ok(Self { scheme, data })
+    }
+
+    pub fn scheme(&self) -> SignatureScheme {
+        self.scheme
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl From<&SignatureData> for SchemeSignature {
+    fn from(signature: &SignatureData) -> Self {
+        Self { scheme: SignatureScheme::Ed25519, data: signature.to_vec() }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
 pub struct AbiVersion {
     pub major: u8,
@@ -188,7 +251,7 @@ fn bool_true() -> bool {
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Deserialize)]
-struct SerdeContract {
+pub(crate) struct SerdeContract {
     /// ABI version up to 2.
     #[serde(rename = "ABI version")]
     pub abi_version: Option<u8>,
@@ -203,6 +266,9 @@ struct SerdeContract {
     pub header: Vec<Param>,
     /// Contract functions.
     pub functions: Vec<SerdeFunction>,
+    /// Contract getters.
+    #[serde(default)]
+    pub getters: Vec<SerdeFunction>,
     /// Contract events.
     #[serde(default)]
     pub events: Vec<SerdeEvent>,
@@ -228,6 +294,8 @@ pub struct Contract {
     header: Vec<Param>,
     /// Contract functions.
     functions: HashMap<String, Function>,
+    /// Contract getters.
+    getters: HashMap<String, Function>,
     /// Contract events.
     events: HashMap<String, Event>,
     /// Contract initial data.
@@ -285,6 +353,7 @@ impl Contract {
             abi_version: version,
             header: serde_contract.header,
             functions: HashMap::new(),
+            getters: HashMap::new(),
             events: HashMap::new(),
             data: HashMap::new(),
             fields: Vec::new(),
@@ -300,6 +369,19 @@ impl Contract {
             );
         }
 
+        let getters = if Self::getters_supported_in_version(&version) {
+            serde_contract.getters
+        } else {
+            Vec::new()
+        };
+        for getter in getters {
+            Self::check_params_support(&version, getter.inputs.iter())?;
+            Self::check_params_support(&version, getter.outputs.iter())?;
+            result
+                .getters
+                .insert(getter.name.clone(), Function::from_serde(version, getter, Vec::new()));
+        }
+
         for event in serde_contract.events {
             Self::check_params_support(&version, event.inputs.iter())?;
             result.events.insert(event.name.clone(), Event::from_serde(version, event));
@@ -378,6 +460,11 @@ impl Contract {
         &self.functions
     }
 
+    /// Returns getters collection
+    pub fn getters(&self) -> &HashMap<String, Function> {
+        &self.getters
+    }
+
     /// Returns header parameters set
     pub fn header(&self) -> &Vec<Param> {
         &self.header
@@ -398,6 +485,11 @@ impl Contract {
         &self.fields
     }
 
+    /// Returns names of storage fields with `init == true`
+    pub fn init_fields(&self) -> &HashSet<String> {
+        &self.init_fields
+    }
+
     /// Returns version
     pub fn version(&self) -> &AbiVersion {
         &self.abi_version
@@ -463,6 +555,14 @@ impl Contract {
         Ok(())
     }
 
+    pub fn getters_supported_in_version(abi_version: &AbiVersion) -> bool {
+        abi_version >= &ABI_VERSION_2_3
+    }
+
+    pub fn getters_supported(&self) -> bool {
+        Self::getters_supported_in_version(&self.abi_version)
+    }
+
     pub fn init_fields_supported_in_version(abi_version: &AbiVersion) -> bool {
         abi_version >= &ABI_VERSION_2_4
     }
@@ -546,7 +646,7 @@ impl Contract {
     /// Add sign to messsage body returned by `prepare_input_for_sign` function
     pub fn add_sign_to_encoded_input(
         &self,
-        signature: &SignatureData,
+        signature: impl Into<SchemeSignature>,
         public_key: Option<&PublicKeyData>,
         function_call: SliceData,
     ) -> Result<BuilderData> {
@@ -600,6 +700,12 @@ impl Contract {
         TokenValue::decode_params(&self.fields, data, &self.abi_version, allow_partial)
     }
 
+    /// Decode single value of account storage addressed by field `path`,
+    /// e.g. `balances[0:1234...]`, without decoding the other fields
+    pub fn decode_storage_field(&self, data: SliceData, path: &str) -> Result<Option<TokenValue>> {
+        TokenValue::decode_path(&self.fields, data, &FieldPath::parse(path)?, &self.abi_version)
+    }
+
     /// Get signature and signed hash from function call data
     pub fn get_signature_data(
         &self,
@@ -608,6 +714,17 @@ impl Contract {
     ) -> Result<(Vec<u8>, Vec<u8>)> {
         Function::get_signature_data(&self.abi_version, cursor, address)
     }
+
+    /// Get signature of the given scheme and signed hash from function call
+    /// data
+    pub fn get_signature_data_with_scheme(
+        &self,
+        scheme: SignatureScheme,
+        cursor: SliceData,
+        address: Option<MsgAddressInt>,
+    ) -> Result<(Vec<u8>, Vec<u8>)> {
+        Function::get_signature_data_with_scheme(&self.abi_version, scheme, cursor, address)
+    }
 }
 
 #[cfg(test)]
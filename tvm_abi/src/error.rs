@@ -76,4 +76,7 @@ pub enum AbiError {
 
     #[error("Wrong data layout")]
     WrongDataLayout,
+
+    #[error("Invalid field path `{}`: {}", path, msg)]
+    InvalidFieldPath { path: String, msg: String },
 }
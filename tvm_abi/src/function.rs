@@ -29,7 +29,8 @@ use tvm_types::sha256_digest;
 
 use crate::ParamType;
 use crate::PublicKeyData;
-use crate::SignatureData;
+use crate::SchemeSignature;
+use crate::SignatureScheme;
 use crate::contract::ABI_VERSION_1_0;
 use crate::contract::ABI_VERSION_2_3;
 use crate::contract::AbiVersion;
@@ -250,7 +251,7 @@ impl Function {
                     let signature = key.sign(&hash);
                     Self::fill_sign(
                         &self.abi_version,
-                        Some(&signature),
+                        Some(&(&signature).into()),
                         Some(&key.verifying_key()),
                         builder,
                     )?
@@ -341,14 +342,25 @@ impl Function {
 
     pub fn get_signature_data(
         abi_version: &AbiVersion,
+        cursor: SliceData,
+        address: Option<MsgAddressInt>,
+    ) -> Result<(Vec<u8>, Vec<u8>)> {
+        Self::get_signature_data_with_scheme(abi_version, SignatureScheme::Ed25519, cursor, address)
+    }
+
+    /// Get signature of the given scheme and signed hash from function call
+    /// data
+    pub fn get_signature_data_with_scheme(
+        abi_version: &AbiVersion,
+        scheme: SignatureScheme,
         mut cursor: SliceData,
         address: Option<MsgAddressInt>,
     ) -> Result<(Vec<u8>, Vec<u8>)> {
         let signature = if abi_version == &ABI_VERSION_1_0 {
             SliceData::load_cell(cursor.checked_drain_reference()?)?
-                .get_next_bytes(ED25519_SIGNATURE_LENGTH)?
+                .get_next_bytes(scheme.signature_len())?
         } else if cursor.get_next_bit()? {
-            cursor.get_next_bytes(ED25519_SIGNATURE_LENGTH)?
+            cursor.get_next_bytes(scheme.signature_len())?
         } else {
             return Err(AbiError::InvalidData { msg: "No signature".to_owned() }.into());
         };
@@ -375,6 +387,20 @@ impl Function {
         internal: bool,
         reserve_sign: bool,
         address: Option<MsgAddressInt>,
+    ) -> Result<(BuilderData, Vec<u8>)> {
+        let sign_scheme = reserve_sign.then_some(SignatureScheme::Ed25519);
+        self.create_unsigned_call_with_scheme(header, input, internal, sign_scheme, address)
+    }
+
+    /// Same as `create_unsigned_call`, but reserves room for the signature of
+    /// `sign_scheme` in the first cell
+    pub fn create_unsigned_call_with_scheme(
+        &self,
+        header: &HashMap<String, TokenValue>,
+        input: &[Token],
+        internal: bool,
+        sign_scheme: Option<SignatureScheme>,
+        address: Option<MsgAddressInt>,
     ) -> Result<(BuilderData, Vec<u8>)> {
         let params = self.input_params();
 
@@ -388,6 +414,7 @@ impl Function {
         let mut remove_ref = false;
         let mut remove_bits = 0;
         if !internal {
+            let sign_bits = Self::sign_max_bits(&self.abi_version, sign_scheme);
             let mut sign_builder = BuilderData::new();
             if self.abi_version.major == 1 {
                 // reserve reference for sign
@@ -395,22 +422,14 @@ impl Function {
                 remove_ref = true;
             } else {
                 // reserve in-cell data
-                if reserve_sign {
+                if sign_scheme.is_some() {
                     if self.abi_version >= ABI_VERSION_2_3 {
-                        sign_builder.append_raw(
-                            &[0u8; MAX_DATA_BYTES],
-                            TokenValue::max_bit_size(&ParamType::Address, &self.abi_version),
-                        )?;
-                        remove_bits =
-                            TokenValue::max_bit_size(&ParamType::Address, &self.abi_version);
+                        sign_builder.append_raw(&[0u8; MAX_DATA_BYTES], sign_bits)?;
                     } else {
                         sign_builder.append_bit_one()?;
-                        sign_builder.append_raw(
-                            &[0u8; ED25519_SIGNATURE_LENGTH],
-                            ED25519_SIGNATURE_LENGTH * 8,
-                        )?;
-                        remove_bits = 1 + ED25519_SIGNATURE_LENGTH * 8;
+                        sign_builder.append_raw(&[0u8; MAX_DATA_BYTES], sign_bits - 1)?;
                     }
+                    remove_bits = sign_bits;
                 } else {
                     sign_builder.append_bit_zero()?;
                     remove_bits = 1;
@@ -420,11 +439,7 @@ impl Function {
                 0,
                 SerializedValue {
                     data: sign_builder,
-                    max_bits: if self.abi_version >= ABI_VERSION_2_3 {
-                        TokenValue::max_bit_size(&ParamType::Address, &self.abi_version)
-                    } else {
-                        1 + ED25519_SIGNATURE_LENGTH * 8
-                    },
+                    max_bits: sign_bits,
                     max_refs: if remove_ref { 1 } else { 0 },
                 },
             );
@@ -445,7 +460,7 @@ impl Function {
             builder = slice.as_builder();
         }
 
-        let hash = if self.abi_version >= ABI_VERSION_2_3 && reserve_sign {
+        let hash = if self.abi_version >= ABI_VERSION_2_3 && sign_scheme.is_some() {
             let address = address.ok_or(AbiError::AddressRequired)?;
             let mut address_builder = address.write_to_new_cell()?;
             address_builder.append_builder(&builder)?;
@@ -457,13 +472,40 @@ impl Function {
         Ok((builder, hash))
     }
 
-    /// Add sign to messsage body returned by `prepare_input_for_sign` function
+    // Bits reserved for the signature in the first cell of an external call.
+    // Signatures longer than ed25519 one take more than the standard room.
+    fn sign_max_bits(abi_version: &AbiVersion, sign_scheme: Option<SignatureScheme>) -> usize {
+        let bits = if abi_version >= &ABI_VERSION_2_3 {
+            TokenValue::max_bit_size(&ParamType::Address, abi_version)
+        } else {
+            1 + ED25519_SIGNATURE_LENGTH * 8
+        };
+        match sign_scheme {
+            // ABI 1.0 keeps the signature in a reference
+            Some(scheme) if abi_version.major != 1 => bits.max(1 + scheme.signature_len() * 8),
+            _ => bits,
+        }
+    }
+
+    /// Add sign to messsage body returned by `prepare_input_for_sign` function.
+    /// Public key is ed25519 only and can not go with signatures of other
+    /// schemes
     pub fn fill_sign(
         abi_version: &AbiVersion,
-        signature: Option<&SignatureData>,
+        signature: Option<&SchemeSignature>,
         public_key: Option<&PublicKeyData>,
         mut builder: BuilderData,
     ) -> Result<BuilderData> {
+        if let Some(signature) = signature {
+            if signature.scheme() != SignatureScheme::Ed25519 && public_key.is_some() {
+                fail!(AbiError::InvalidInputData {
+                    msg: format!(
+                        "public key can not be added to {:?} signature",
+                        signature.scheme()
+                    )
+                });
+            }
+        }
         if abi_version == &ABI_VERSION_1_0 {
             // sign in reference
             if builder.references_free() == 0 {
@@ -472,7 +514,7 @@ impl Function {
                 });
             }
             let cell = if let Some(signature) = signature {
-                let mut signature = signature.to_vec();
+                let mut signature = signature.data().to_vec();
                 if let Some(public_key) = public_key {
                     signature.extend_from_slice(public_key);
                 }
@@ -487,9 +529,9 @@ impl Function {
             // sign in cell body
             let mut sign_builder = BuilderData::new();
             if let Some(signature) = signature {
-                let len = signature.len() * 8;
+                let len = signature.data().len() * 8;
                 sign_builder.append_bit_one()?;
-                sign_builder.append_raw(signature, len)?;
+                sign_builder.append_raw(signature.data(), len)?;
             } else {
                 sign_builder.append_bit_zero()?;
             }
@@ -502,13 +544,13 @@ impl Function {
     /// Add sign to messsage body returned by `prepare_input_for_sign` function
     pub fn add_sign_to_encoded_input(
         abi_version: &AbiVersion,
-        signature: &SignatureData,
+        signature: impl Into<SchemeSignature>,
         public_key: Option<&PublicKeyData>,
         function_call: SliceData,
     ) -> Result<BuilderData> {
         let builder = function_call.as_builder();
 
-        Self::fill_sign(abi_version, Some(signature), public_key, builder)
+        Self::fill_sign(abi_version, Some(&signature.into()), public_key, builder)
     }
 
     /// Check if message body is related to this function
@@ -20,7 +20,8 @@ use tvm_types::Result;
 use tvm_types::SliceData;
 
 use crate::PublicKeyData;
-use crate::SignatureData;
+use crate::SchemeSignature;
+use crate::SignatureScheme;
 use crate::contract::Contract;
 use crate::error::AbiError;
 use crate::token::Detokenizer;
@@ -74,6 +75,26 @@ pub fn prepare_function_call_for_sign(
     header: Option<&str>,
     parameters: &str,
     address: Option<&str>,
+) -> Result<(BuilderData, Vec<u8>)> {
+    prepare_function_call_for_sign_with_scheme(
+        abi,
+        function,
+        header,
+        parameters,
+        address,
+        SignatureScheme::Ed25519,
+    )
+}
+
+/// Same as `prepare_function_call_for_sign`, but the message body is prepared
+/// for the signature of `scheme`
+pub fn prepare_function_call_for_sign_with_scheme(
+    abi: &str,
+    function: &str,
+    header: Option<&str>,
+    parameters: &str,
+    address: Option<&str>,
+    scheme: SignatureScheme,
 ) -> Result<(BuilderData, Vec<u8>)> {
     let contract = Contract::load(abi.as_bytes())?;
 
@@ -91,14 +112,20 @@ pub fn prepare_function_call_for_sign(
 
     let address = address.map(MsgAddressInt::from_str).transpose()?;
 
-    function.create_unsigned_call(&header_tokens, &input_tokens, false, true, address)
+    function.create_unsigned_call_with_scheme(
+        &header_tokens,
+        &input_tokens,
+        false,
+        Some(scheme),
+        address,
+    )
 }
 
 /// Add sign to messsage body returned by `prepare_function_call_for_sign`
 /// function
 pub fn add_sign_to_function_call(
     abi: &str,
-    signature: &SignatureData,
+    signature: impl Into<SchemeSignature>,
     public_key: Option<&PublicKeyData>,
     function_call: SliceData,
 ) -> Result<BuilderData> {
@@ -202,6 +229,18 @@ pub fn get_signature_data(
     contract.get_signature_data(cursor, address)
 }
 
+/// Get signature of the given scheme and signed hash from function call data
+pub fn get_signature_data_with_scheme(
+    abi: &str,
+    scheme: SignatureScheme,
+    cursor: SliceData,
+    address: Option<&str>,
+) -> Result<(Vec<u8>, Vec<u8>)> {
+    let contract = Contract::load(abi.as_bytes())?;
+    let address = address.map(MsgAddressInt::from_str).transpose()?;
+    contract.get_signature_data_with_scheme(scheme, cursor, address)
+}
+
 /// Encodes `parameters` for given `function` of contract described by `abi`
 /// into `BuilderData` which can be used as message body for calling contract
 pub fn encode_storage_fields(abi: &str, init_fields: Option<&str>) -> Result<BuilderData> {
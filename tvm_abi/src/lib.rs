@@ -9,6 +9,7 @@
 // See the License for the specific TON DEV software governing permissions and
 // limitations under the License.
 
+pub mod compatibility;
 pub mod contract;
 pub mod error;
 pub mod event;
@@ -18,11 +19,17 @@ pub mod json_abi;
 pub mod param;
 pub mod param_type;
 pub mod token;
+pub mod validation;
 
+pub use compatibility::AbiChange;
+pub use compatibility::ChangeKind;
+pub use compatibility::check_compatibility;
 pub use contract::Contract;
 pub use contract::DataItem;
 pub use contract::PublicKeyData;
+pub use contract::SchemeSignature;
 pub use contract::SignatureData;
+pub use contract::SignatureScheme;
 pub use error::*;
 pub use event::Event;
 pub use function::Function;
@@ -33,6 +40,9 @@ pub use param::Param;
 pub use param_type::ParamType;
 pub use token::Token;
 pub use token::TokenValue;
+pub use validation::AbiIssue;
+pub use validation::Severity;
+pub use validation::validate_abi;
 
 pub fn build_commit() -> Option<&'static str> {
     std::option_env!("BUILD_GIT_COMMIT")
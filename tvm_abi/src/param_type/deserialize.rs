@@ -143,6 +143,17 @@ pub fn read_type(name: &str) -> Result<ParamType> {
                 .map_err(|_| AbiError::InvalidName { name: name.to_owned() })?;
             ParamType::FixedBytes(len)
         }
+        s if s.starts_with("fixed") => {
+            let (size, decimals) = read_fixed_size(&s[5..], name)?;
+            ParamType::Fixed(size, decimals)
+        }
+        s if s.starts_with("ufixed") => {
+            let (size, decimals) = read_fixed_size(&s[6..], name)?;
+            ParamType::UFixed(size, decimals)
+        }
+        s if s.starts_with("enum(") && s.ends_with(')') => {
+            ParamType::Enum(read_enum_variants(&name[5..name.len() - 1], name)?)
+        }
         "time" => ParamType::Time,
         "expire" => ParamType::Expire,
         "pubkey" => ParamType::PublicKey,
@@ -152,8 +163,18 @@ pub fn read_type(name: &str) -> Result<ParamType> {
             ParamType::Optional(Box::new(inner_type))
         }
         s if s.starts_with("ref(") && s.ends_with(')') => {
-            let inner_type = read_type(&name[4..name.len() - 1])?;
-            ParamType::Ref(Box::new(inner_type))
+            let inner = &name[4..name.len() - 1];
+            match split_last_argument(inner) {
+                Some((inner, max_cells)) => {
+                    let max_cells = max_cells
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|max_cells| *max_cells > 0)
+                        .ok_or_else(|| AbiError::InvalidName { name: name.to_owned() })?;
+                    ParamType::LimitedRef(Box::new(read_type(inner)?), max_cells)
+                }
+                None => ParamType::Ref(Box::new(read_type(inner)?)),
+            }
         }
         _ => {
             fail!(AbiError::InvalidName { name: name.to_owned() });
@@ -162,3 +183,46 @@ pub fn read_type(name: &str) -> Result<ParamType> {
 
     Ok(result)
 }
+
+/// Reads `<M>x<N>` part of `fixed<M>x<N>` and `ufixed<M>x<N>` types.
+fn read_fixed_size(sizes: &str, name: &str) -> Result<(usize, usize)> {
+    let invalid_name = || AbiError::InvalidName { name: name.to_owned() };
+    let (size, decimals) = sizes.split_once('x').ok_or_else(invalid_name)?;
+    let size = size.parse::<usize>().map_err(|_| invalid_name())?;
+    let decimals = decimals.parse::<usize>().map_err(|_| invalid_name())?;
+    if size == 0 || size > 256 || decimals > 80 {
+        fail!(invalid_name());
+    }
+    Ok((size, decimals))
+}
+
+/// Reads comma separated variants of `enum(A,B,...)` type.
+fn read_enum_variants(variants: &str, name: &str) -> Result<Vec<String>> {
+    let variants = variants.split(',').map(|variant| variant.trim().to_owned()).collect::<Vec<_>>();
+    let valid_identifier = |variant: &String| {
+        variant.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && variant.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    };
+    if variants.len() > 256
+        || !variants.iter().all(valid_identifier)
+        || variants.iter().enumerate().any(|(i, variant)| variants[..i].contains(variant))
+    {
+        fail!(AbiError::InvalidName { name: name.to_owned() });
+    }
+    Ok(variants)
+}
+
+/// Splits `T,N` into `T` and `N` if there is a comma outside of parentheses.
+fn split_last_argument(arguments: &str) -> Option<(&str, &str)> {
+    let mut depth = 0usize;
+    let mut split = None;
+    for (i, c) in arguments.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => split = Some(i),
+            _ => {}
+        }
+    }
+    split.map(|i| (&arguments[..i], &arguments[i + 1..]))
+}
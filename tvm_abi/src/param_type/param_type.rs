@@ -22,6 +22,7 @@ use crate::contract::ABI_VERSION_1_0;
 use crate::contract::ABI_VERSION_2_0;
 use crate::contract::ABI_VERSION_2_1;
 use crate::contract::ABI_VERSION_2_4;
+use crate::contract::ABI_VERSION_2_5;
 use crate::contract::AbiVersion;
 
 /// Function and event param types.
@@ -67,6 +68,14 @@ pub enum ParamType {
     Optional(Box<ParamType>),
     /// Parameter stored in reference
     Ref(Box<ParamType>),
+    /// fixed<M>x<N>: signed decimal of M bits with N fractional digits.
+    Fixed(usize, usize),
+    /// ufixed<M>x<N>: unsigned decimal of M bits with N fractional digits.
+    UFixed(usize, usize),
+    /// enum(A,B,...): named variant encoded as its index.
+    Enum(Vec<String>),
+    /// ref(T,N): parameter stored in reference tree of at most N cells.
+    LimitedRef(Box<ParamType>, usize),
 }
 
 impl fmt::Display for ParamType {
@@ -113,6 +122,12 @@ impl ParamType {
                 format!("optional({})", param_type.type_signature())
             }
             ParamType::Ref(ref param_type) => format!("ref({})", param_type.type_signature()),
+            ParamType::Fixed(size, decimals) => format!("fixed{}x{}", size, decimals),
+            ParamType::UFixed(size, decimals) => format!("ufixed{}x{}", size, decimals),
+            ParamType::Enum(variants) => format!("enum({})", variants.join(",")),
+            ParamType::LimitedRef(ref param_type, max_cells) => {
+                format!("ref({},{})", param_type.type_signature(), max_cells)
+            }
         }
     }
 
@@ -131,6 +146,7 @@ impl ParamType {
             ParamType::Map(_, value_type) => value_type.set_components(components),
             ParamType::Optional(inner_type) => inner_type.set_components(components),
             ParamType::Ref(inner_type) => inner_type.set_components(components),
+            ParamType::LimitedRef(inner_type, _) => inner_type.set_components(components),
             _ => {
                 if !components.is_empty() {
                     Err(error!(AbiError::UnusedComponents))
@@ -152,6 +168,10 @@ impl ParamType {
             | ParamType::VarInt(_)
             | ParamType::VarUint(_) => abi_version >= &ABI_VERSION_2_1,
             ParamType::Ref(_) => abi_version >= &ABI_VERSION_2_4,
+            ParamType::Fixed(..)
+            | ParamType::UFixed(..)
+            | ParamType::Enum(_)
+            | ParamType::LimitedRef(..) => abi_version >= &ABI_VERSION_2_5,
             _ => abi_version >= &ABI_VERSION_1_0,
         }
     }
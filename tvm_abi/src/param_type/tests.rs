@@ -16,6 +16,7 @@ mod param_type_tests {
     use crate::contract::ABI_VERSION_2_0;
     use crate::contract::ABI_VERSION_2_1;
     use crate::contract::ABI_VERSION_2_4;
+    use crate::contract::ABI_VERSION_2_5;
 
     #[test]
     fn test_param_type_signature() {
@@ -112,6 +113,10 @@ mod param_type_tests {
         assert!(ParamType::String.is_supported(&ABI_VERSION_2_1));
         assert!(!ParamType::Ref(Box::new(ParamType::Bool)).is_supported(&ABI_VERSION_2_1));
         assert!(ParamType::Ref(Box::new(ParamType::Bool)).is_supported(&ABI_VERSION_2_4));
+        assert!(!ParamType::Fixed(64, 2).is_supported(&ABI_VERSION_2_4));
+        assert!(ParamType::Fixed(64, 2).is_supported(&ABI_VERSION_2_5));
+        assert!(!ParamType::Enum(vec!["A".to_owned()]).is_supported(&ABI_VERSION_2_4));
+        assert!(ParamType::LimitedRef(Box::new(ParamType::Cell), 4).is_supported(&ABI_VERSION_2_5));
     }
 }
 
@@ -166,6 +171,49 @@ mod deserialize_tests {
         );
     }
 
+    #[test]
+    fn abi_v2_5_types_deserialization() {
+        let s = r#"["fixed128x18", "ufixed64x0", "enum(Active, Frozen)", "ref(bytes,4)",
+            "ref(map(uint8,bool)[],2)", "ref(tuple)"]"#;
+        let deserialized: Vec<ParamType> = serde_json::from_str(s).unwrap();
+        assert_eq!(
+            deserialized,
+            vec![
+                ParamType::Fixed(128, 18),
+                ParamType::UFixed(64, 0),
+                ParamType::Enum(vec!["Active".to_owned(), "Frozen".to_owned()]),
+                ParamType::LimitedRef(Box::new(ParamType::Bytes), 4),
+                ParamType::LimitedRef(
+                    Box::new(ParamType::Array(Box::new(ParamType::Map(
+                        Box::new(ParamType::Uint(8)),
+                        Box::new(ParamType::Bool)
+                    )))),
+                    2
+                ),
+                ParamType::Ref(Box::new(ParamType::Tuple(vec![]))),
+            ]
+        );
+        // tuple signature lists components and is not a type name
+        for kind in &deserialized[..5] {
+            assert_eq!(&read_type(&kind.type_signature()).unwrap(), kind);
+        }
+
+        for name in [
+            "fixed0x2",
+            "fixed257x2",
+            "ufixed64x81",
+            "fixed64",
+            "enum()",
+            "enum(A,A)",
+            "enum(1A)",
+            "ref(bool,0)",
+            "ref(bool,x)",
+        ] {
+            let err = read_type(name).unwrap_err();
+            assert!(err.to_string().contains("Invalid name:"), "{}", name);
+        }
+    }
+
     #[test]
     fn param_type_deserialization_covers_string_deserializer_and_errors() {
         let parsed = ParamType::deserialize(StringDeserializer::<serde_json::Error>::new(
@@ -0,0 +1,153 @@
+use super::*;
+
+const OLD_ABI: &str = r#"{
+    "version": "2.3",
+    "header": ["time", "expire"],
+    "functions": [
+        { "name": "transfer", "inputs": [{ "name": "value", "type": "uint128" }], "outputs": [] },
+        { "name": "owner", "inputs": [], "outputs": [{ "name": "value", "type": "address" }] },
+        { "name": "close", "inputs": [], "outputs": [] }
+    ],
+    "getters": [{ "name": "balance", "inputs": [], "outputs": [{ "name": "value", "type": "uint128" }] }],
+    "events": [
+        { "name": "Transferred", "inputs": [{ "name": "value", "type": "uint128" }] },
+        { "name": "Closed", "inputs": [] }
+    ],
+    "fields": [
+        { "name": "_pubkey", "type": "uint256" },
+        { "name": "balance", "type": "uint128" },
+        { "name": "owner", "type": "address" }
+    ]
+}"#;
+
+fn load(abi: &str) -> Contract {
+    Contract::load(abi.as_bytes()).unwrap()
+}
+
+fn describe(changes: Vec<AbiChange>) -> Vec<String> {
+    changes.into_iter().map(|change| change.to_string()).collect()
+}
+
+#[test]
+fn same_abi_has_no_changes() {
+    assert_eq!(check_compatibility(&load(OLD_ABI), &load(OLD_ABI)), vec![]);
+}
+
+#[test]
+fn compatible_changes() {
+    let new_abi = r#"{
+        "version": "2.4",
+        "header": ["time", "expire"],
+        "functions": [
+            { "name": "transfer", "inputs": [{ "name": "value", "type": "uint128" }], "outputs": [] },
+            { "name": "owner", "inputs": [], "outputs": [{ "name": "value", "type": "address" }] },
+            { "name": "close", "inputs": [], "outputs": [] },
+            { "name": "open", "inputs": [], "outputs": [] }
+        ],
+        "getters": [{ "name": "balance", "inputs": [], "outputs": [{ "name": "value", "type": "uint128" }] }],
+        "events": [
+            { "name": "Transferred", "inputs": [{ "name": "value", "type": "uint128" }] },
+            { "name": "Closed", "inputs": [] }
+        ],
+        "fields": [
+            { "name": "_pubkey", "type": "uint256", "init": true },
+            { "name": "funds", "type": "uint128" },
+            { "name": "owner", "type": "address" }
+        ]
+    }"#;
+    let changes = check_compatibility(&load(OLD_ABI), &load(new_abi));
+    assert!(changes.iter().all(|change| change.kind == ChangeKind::Compatible));
+    assert_eq!(
+        describe(changes),
+        vec![
+            "compatible: version: version changed 2.3 -> 2.4",
+            "compatible: functions.open: added",
+            "compatible: fields._pubkey: field #0 `init` flag changed",
+            "compatible: fields.balance: field #1 renamed balance -> funds",
+        ]
+    );
+}
+
+#[test]
+fn appended_fields_are_storage_extension() {
+    let new_abi = OLD_ABI.replace(
+        r#"{ "name": "owner", "type": "address" }"#,
+        r#"{ "name": "owner", "type": "address" },
+        { "name": "opened", "type": "bool" }"#,
+    );
+    let changes = check_compatibility(&load(OLD_ABI), &load(&new_abi));
+    assert_eq!(describe(changes), vec!["storage extension: fields.opened: field #3 appended"]);
+}
+
+#[test]
+fn breaking_changes() {
+    let new_abi = r#"{
+        "version": "2.3",
+        "header": ["time", "expire", "pubkey"],
+        "functions": [
+            { "name": "transfer", "inputs": [{ "name": "value", "type": "uint64" }], "outputs": [] },
+            { "name": "owner", "inputs": [], "outputs": [{ "name": "value", "type": "uint256" }] }
+        ],
+        "getters": [{ "name": "balance", "inputs": [], "outputs": [{ "name": "value", "type": "uint64" }] }],
+        "events": [
+            { "name": "Transferred", "inputs": [{ "name": "amount", "type": "uint128" }] }
+        ],
+        "fields": [
+            { "name": "_pubkey", "type": "uint256" },
+            { "name": "owner", "type": "address" }
+        ]
+    }"#;
+    let changes = check_compatibility(&load(OLD_ABI), &load(new_abi));
+    assert!(changes.iter().all(|change| change.kind == ChangeKind::Breaking));
+    assert_eq!(
+        describe(changes),
+        vec![
+            "breaking: header: header changed (time time, expire expire) -> (time time, expire expire, pubkey pubkey)",
+            "breaking: functions.close: removed",
+            "breaking: functions.owner: outputs changed (address value) -> (uint256 value)",
+            "breaking: functions.owner: id changed 0x5f0bcfde -> 0x1178e9bd",
+            "breaking: functions.transfer: inputs changed (uint128 value) -> (uint64 value)",
+            "breaking: functions.transfer: id changed 0x03d1032b -> 0x3322d2c7",
+            "breaking: getters.balance: outputs changed (uint128 value) -> (uint64 value)",
+            "breaking: events.Closed: removed",
+            "breaking: events.Transferred: inputs changed (uint128 value) -> (uint128 amount)",
+            "breaking: fields.balance: field #1 changed uint128 balance -> address owner",
+            "breaking: fields.owner: field #2 removed",
+        ]
+    );
+}
+
+#[test]
+fn getters_are_ignored_before_abi_2_3() {
+    let old = load(
+        r#"{
+        "version": "2.2",
+        "functions": [],
+        "getters": [{ "name": "balance", "inputs": [], "outputs": [{ "name": "value", "type": "ref(uint128)" }] }]
+    }"#,
+    );
+    assert!(old.getters().is_empty());
+    let new = load(
+        r#"{
+        "version": "2.3",
+        "functions": [],
+        "getters": [{ "name": "balance", "inputs": [], "outputs": [{ "name": "value", "type": "uint128" }] }]
+    }"#,
+    );
+    assert_eq!(
+        describe(check_compatibility(&old, &new)),
+        vec![
+            "compatible: version: version changed 2.2 -> 2.3",
+            "compatible: getters.balance: added"
+        ]
+    );
+}
+
+#[test]
+fn major_version_change_is_breaking() {
+    let old = load(r#"{ "ABI version": 1, "functions": [] }"#);
+    let new = load(r#"{ "version": "2.0", "functions": [] }"#);
+    let changes = check_compatibility(&old, &new);
+    assert_eq!(changes[0].kind, ChangeKind::Breaking);
+    assert_eq!(changes[0].path, "version");
+}
@@ -0,0 +1,152 @@
+use super::*;
+
+fn errors(abi: &str) -> Vec<String> {
+    validate_abi(abi)
+        .unwrap()
+        .into_iter()
+        .filter(|issue| issue.severity == Severity::Error)
+        .map(|issue| issue.to_string())
+        .collect()
+}
+
+#[test]
+fn valid_abi_has_no_issues() {
+    let abi = r#"{
+        "version": "2.4",
+        "header": ["time", "expire", "pubkey"],
+        "functions": [
+            { "name": "transfer", "inputs": [{ "name": "value", "type": "uint128" }], "outputs": [] },
+            { "name": "balance", "inputs": [], "outputs": [{ "name": "value", "type": "uint128" }] }
+        ],
+        "events": [{ "name": "Transferred", "inputs": [{ "name": "value", "type": "uint128" }] }],
+        "fields": [
+            { "name": "_pubkey", "type": "uint256", "init": true },
+            { "name": "balance", "type": "uint128" }
+        ]
+    }"#;
+    assert_eq!(validate_abi(abi).unwrap(), vec![]);
+}
+
+#[test]
+fn version_rules() {
+    assert_eq!(errors(r#"{ "functions": [] }"#), vec!["error: version: no version in ABI JSON"]);
+    assert_eq!(
+        errors(r#"{ "version": "3.0", "functions": [] }"#),
+        vec!["error: version: ABI version 3.0 is not supported"]
+    );
+
+    let abi = r#"{
+        "ABI version": 1,
+        "header": ["time"],
+        "functions": [{ "name": "f", "inputs": [{ "name": "s", "type": "string" }] }],
+        "fields": [{ "name": "a", "type": "uint8" }]
+    }"#;
+    assert_eq!(
+        errors(abi),
+        vec![
+            "error: header: header parameters are not supported in ABI v1",
+            "error: functions.f.inputs.s: parameter type string is not supported in ABI v1.0",
+            "error: fields: storage fields are not supported in ABI v1.0",
+        ]
+    );
+
+    let abi = r#"{
+        "version": "2.3",
+        "functions": [],
+        "fields": [
+            { "name": "a", "type": "optional(ref(uint8))", "init": true }
+        ]
+    }"#;
+    assert_eq!(
+        errors(abi),
+        vec![
+            "error: fields.a: `init` fields are not supported in ABI v2.3",
+            "error: fields.a: parameter type ref(uint8) is not supported in ABI v2.3",
+        ]
+    );
+
+    let abi = r#"{
+        "version": "2.4",
+        "functions": [],
+        "data": [{ "key": 1, "name": "a", "type": "uint8" }, { "key": 1, "name": "b", "type": "uint8" }]
+    }"#;
+    assert_eq!(
+        errors(abi),
+        vec![
+            "error: data: initial data dictionary is not supported in ABI v2.4, use `init` fields",
+            "error: data.b: key 1 is already used by `a`",
+        ]
+    );
+}
+
+#[test]
+fn header_rules() {
+    let abi = r#"{
+        "version": "2.2",
+        "header": ["time", { "name": "when", "type": "time" }, { "name": "x", "type": "uint8" }],
+        "functions": []
+    }"#;
+    assert_eq!(
+        errors(abi),
+        vec![
+            "error: header.when: header parameter of type time is duplicated",
+            "error: header.x: header parameter type must be one of `time`, `expire`, `pubkey`, got uint8",
+        ]
+    );
+}
+
+#[test]
+fn name_and_id_collisions() {
+    let abi = r#"{
+        "version": "2.2",
+        "functions": [
+            { "name": "f", "inputs": [{ "name": "a", "type": "uint8" }, { "name": "a", "type": "bool" }] },
+            { "name": "f", "inputs": [] },
+            { "name": "g", "id": "0x00000001" },
+            { "name": "h", "id": "0x00000001" }
+        ],
+        "events": [
+            { "name": "E1", "id": "0x00000002" },
+            { "name": "E2", "id": "0x00000002" },
+            { "name": "E3", "id": "0x00000001" }
+        ]
+    }"#;
+    let issues = validate_abi(abi).unwrap().into_iter().map(|i| i.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        issues,
+        vec![
+            "error: functions.f: name is duplicated",
+            "error: functions.f.inputs.a: name is duplicated",
+            "error: functions.h: input id 0x00000001 collides with `g`",
+            "error: functions.h: output id 0x00000001 collides with `g`",
+            "error: events.E2: id 0x00000002 collides with `E1`",
+            "warning: events.E3: id 0x00000001 equals to the output id of function `g`, such messages are decoded as the function output",
+        ]
+    );
+}
+
+#[test]
+fn getters_are_ignored_before_abi_2_3() {
+    let getters = r#""getters": [
+        { "name": "g", "outputs": [{ "name": "a", "type": "ref(uint8)" }] },
+        { "name": "g" }
+    ]"#;
+    let abi = format!(r#"{{ "version": "2.2", "functions": [], {} }}"#, getters);
+    let issues = validate_abi(&abi).unwrap().into_iter().map(|i| i.to_string()).collect::<Vec<_>>();
+    assert_eq!(issues, vec!["warning: getters: getters are ignored in ABI v2.2"]);
+
+    let abi = format!(r#"{{ "version": "2.3", "functions": [], {} }}"#, getters);
+    assert_eq!(
+        errors(&abi),
+        vec![
+            "error: getters.g: name is duplicated",
+            "error: getters.g.outputs.a: parameter type ref(uint8) is not supported in ABI v2.3",
+        ]
+    );
+}
+
+#[test]
+fn malformed_json_fails() {
+    assert!(validate_abi("{").is_err());
+    assert!(validate_abi(r#"{ "version": "2.4" }"#).is_err());
+}
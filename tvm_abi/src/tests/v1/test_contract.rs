@@ -191,6 +191,7 @@ fn test_abi_parse() {
         abi_version: 1.into(),
         header,
         functions,
+        getters: HashMap::new(),
         events,
         data,
         fields: vec![],
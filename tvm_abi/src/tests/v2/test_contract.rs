@@ -211,8 +211,16 @@ fn test_abi_parse() {
 
     let init_fields = vec!["b".to_owned()].into_iter().collect();
 
-    let expected_contract =
-        Contract { abi_version, header, functions, events, data, fields, init_fields };
+    let expected_contract = Contract {
+        abi_version,
+        header,
+        functions,
+        getters: HashMap::new(),
+        events,
+        data,
+        fields,
+        init_fields,
+    };
 
     assert_eq!(parsed_contract, expected_contract);
 }
@@ -61,7 +61,7 @@ impl From<SliceData> for Cursor {
 
 impl TokenValue {
     /// Deserializes value from `SliceData` to `TokenValue`
-    fn read_from(
+    pub(super) fn read_from(
         param_type: &ParamType,
         mut cursor: Cursor,
         last: bool,
@@ -115,6 +115,23 @@ impl TokenValue {
             ParamType::Ref(inner_type) => {
                 Self::read_ref(inner_type, slice, last, abi_version, allow_partial)
             }
+            ParamType::Fixed(size, decimals) => {
+                let (number, slice) = Self::read_int_from_chain(*size, slice)?;
+                Ok((TokenValue::Fixed(Int { number, size: *size }, *decimals), slice))
+            }
+            ParamType::UFixed(size, decimals) => {
+                let (number, slice) = Self::read_uint_from_chain(*size, slice)?;
+                Ok((TokenValue::UFixed(Uint { number, size: *size }, *decimals), slice))
+            }
+            ParamType::Enum(variants) => Self::read_enum(variants, slice),
+            ParamType::LimitedRef(inner_type, max_cells) => Self::read_limited_ref(
+                inner_type,
+                *max_cells,
+                slice,
+                last,
+                abi_version,
+                allow_partial,
+            ),
         }?;
 
         if last {
@@ -127,7 +144,7 @@ impl TokenValue {
         Ok((value, cursor))
     }
 
-    fn check_layout(
+    pub(super) fn check_layout(
         param_type: &ParamType,
         original_cursor: Cursor,
         new_slice: &SliceData,
@@ -280,21 +297,10 @@ impl TokenValue {
             let mut index = BuilderData::new();
             index.append_u32(i as u32)?;
             match map.get(SliceData::load_builder(index)?) {
-                Ok(Some(mut item_slice)) => {
-                    let do_load_ref =
-                        if abi_version == &ABI_VERSION_1_0 || abi_version == &ABI_VERSION_2_0 {
-                            item_slice.remaining_bits() == 0
-                                && Self::max_bit_size(item_type, abi_version) != 0
-                        } else {
-                            let value_len = Self::max_bit_size(item_type, abi_version);
-                            Self::map_value_in_ref(32, value_len)
-                        };
-                    if do_load_ref {
-                        item_slice = SliceData::load_cell(item_slice.checked_drain_reference()?)?;
-                    }
+                Ok(Some(item_slice)) => {
                     let (token, _) = Self::read_from(
                         item_type,
-                        item_slice.into(),
+                        Self::load_array_item(item_type, item_slice, abi_version)?.into(),
                         true,
                         abi_version,
                         allow_partial,
@@ -311,6 +317,25 @@ impl TokenValue {
         Ok((result, cursor))
     }
 
+    /// Returns slice with the array item stored in the array dictionary value.
+    pub(super) fn load_array_item(
+        item_type: &ParamType,
+        item_slice: SliceData,
+        abi_version: &AbiVersion,
+    ) -> Result<SliceData> {
+        let do_load_ref = if abi_version == &ABI_VERSION_1_0 || abi_version == &ABI_VERSION_2_0 {
+            item_slice.remaining_bits() == 0 && Self::max_bit_size(item_type, abi_version) != 0
+        } else {
+            let value_len = Self::max_bit_size(item_type, abi_version);
+            Self::map_value_in_ref(32, value_len)
+        };
+        if do_load_ref {
+            Ok(SliceData::load_cell(item_slice.reference(0)?)?)
+        } else {
+            Ok(item_slice)
+        }
+    }
+
     fn read_array(
         item_type: &ParamType,
         mut cursor: SliceData,
@@ -343,7 +368,7 @@ impl TokenValue {
         Ok((TokenValue::FixedArray(item_type.clone(), result), cursor))
     }
 
-    fn read_cell(
+    pub(super) fn read_cell(
         mut cursor: SliceData,
         last: bool,
         abi_version: &AbiVersion,
@@ -369,29 +394,49 @@ impl TokenValue {
         allow_partial: bool,
     ) -> Result<(Self, SliceData)> {
         let bit_len = TokenValue::get_map_key_size(key_type)?;
-        let value_len = Self::max_bit_size(value_type, abi_version);
-        let value_in_ref = Self::map_value_in_ref(bit_len, value_len);
 
         cursor = find_next_bits(cursor, 1)?;
         let mut new_map = BTreeMap::new();
         let hashmap = HashmapE::with_hashmap(bit_len, cursor.get_dictionary()?.reference_opt(0));
-        hashmap.iterate_slices(|key, mut value| {
-            let key = Self::read_from(key_type, key.into(), true, abi_version, allow_partial)?.0;
-            let key = serde_json::to_value(&key)?
-                .as_str()
-                .ok_or(AbiError::InvalidData { msg: "Non-ordinary key".to_owned() })?
-                .to_owned();
-            if value_in_ref {
-                value = SliceData::load_cell(value.checked_drain_reference()?)?;
-            }
+        hashmap.iterate_slices(|key, value| {
+            let key = Self::read_map_key(key_type, key, abi_version)?;
             let value =
-                Self::read_from(value_type, value.into(), true, abi_version, allow_partial)?.0;
+                Self::read_map_value(key_type, value_type, value, abi_version, allow_partial)?;
             new_map.insert(key, value);
             Ok(true)
         })?;
         Ok((TokenValue::Map(key_type.clone(), value_type.clone(), new_map), cursor))
     }
 
+    /// Decodes map key from the dictionary key into its string representation
+    pub fn read_map_key(
+        key_type: &ParamType,
+        key: SliceData,
+        abi_version: &AbiVersion,
+    ) -> Result<String> {
+        let key = Self::read_from(key_type, key.into(), true, abi_version, true)?.0;
+        Ok(serde_json::to_value(&key)?
+            .as_str()
+            .ok_or(AbiError::InvalidData { msg: "Non-ordinary key".to_owned() })?
+            .to_owned())
+    }
+
+    /// Decodes map value from the dictionary value
+    pub fn read_map_value(
+        key_type: &ParamType,
+        value_type: &ParamType,
+        mut value: SliceData,
+        abi_version: &AbiVersion,
+        allow_partial: bool,
+    ) -> Result<Self> {
+        let bit_len = TokenValue::get_map_key_size(key_type)?;
+        let value_len = Self::max_bit_size(value_type, abi_version);
+        if Self::map_value_in_ref(bit_len, value_len) {
+            value = SliceData::load_cell(value.checked_drain_reference()?)?;
+        }
+        Ok(Self::read_from(value_type, value.into(), true, abi_version, allow_partial)?.0)
+    }
+
     pub fn read_bytes_from_chain(
         cursor: SliceData,
         last: bool,
@@ -533,6 +578,37 @@ impl TokenValue {
         Ok((TokenValue::Ref(Box::new(result)), cursor))
     }
 
+    fn read_limited_ref(
+        inner_type: &ParamType,
+        max_cells: usize,
+        cursor: SliceData,
+        last: bool,
+        abi_version: &AbiVersion,
+        allow_partial: bool,
+    ) -> Result<(Self, SliceData)> {
+        let (cell, cursor) = Self::read_cell(cursor, last, abi_version)?;
+        Self::check_cells_limit(&cell, max_cells)?;
+        let (result, _) = Self::read_from(
+            inner_type,
+            SliceData::load_cell(cell)?.into(),
+            true,
+            abi_version,
+            allow_partial,
+        )?;
+        Ok((TokenValue::LimitedRef(Box::new(result), max_cells), cursor))
+    }
+
+    fn read_enum(variants: &[String], cursor: SliceData) -> Result<(Self, SliceData)> {
+        let (index, cursor) = Self::read_uint_from_chain(8, cursor)?;
+        let index = index.to_u8().unwrap();
+        if index as usize >= variants.len() {
+            fail!(AbiError::InvalidData {
+                msg: format!("Enum variant index {} is out of range", index)
+            })
+        }
+        Ok((TokenValue::Enum(variants.to_vec(), index), cursor))
+    }
+
     /// Decodes provided params from SliceData
     pub fn decode_params(
         params: &[Param],
@@ -571,7 +647,7 @@ fn get_next_bits_from_chain(mut cursor: SliceData, bits: usize) -> Result<(Vec<u
     Ok((cursor.get_next_bits(bits)?, cursor))
 }
 
-fn find_next_bits(mut cursor: SliceData, bits: usize) -> Result<SliceData> {
+pub(super) fn find_next_bits(mut cursor: SliceData, bits: usize) -> Result<SliceData> {
     debug_assert!(bits != 0);
     let original = cursor.clone();
     if cursor.remaining_bits() == 0 {
@@ -188,7 +188,12 @@ impl Serialize for TokenValue {
             }
             TokenValue::PublicKey(key) => Token::detokenize_public_key(key, serializer),
             TokenValue::Optional(_, value) => value.serialize(serializer),
-            TokenValue::Ref(value) => value.serialize(serializer),
+            TokenValue::Ref(value) | TokenValue::LimitedRef(value, _) => {
+                value.serialize(serializer)
+            }
+            TokenValue::Fixed(..) | TokenValue::UFixed(..) | TokenValue::Enum(..) => {
+                serializer.serialize_str(&self.to_string())
+            }
         }
     }
 }
@@ -0,0 +1,321 @@
+// Copyright (C) 2019-2022 TON Labs. All Rights Reserved.
+//
+// Licensed under the SOFTWARE EVALUATION License (the "License"); you may not
+// use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific TON DEV software governing permissions and
+// limitations under the License.
+
+//! Lazy decoding of a single value addressed by a field path.
+//!
+//! Values preceding the requested one are skipped without decoding and
+//! dictionaries are searched by key, so decoding e.g. `balances[<address>]`
+//! doesn't materialize the whole `balances` map.
+
+use std::fmt;
+use std::str::FromStr;
+
+use tvm_types::BuilderData;
+use tvm_types::HashmapE;
+use tvm_types::IBitstring;
+use tvm_types::Result;
+use tvm_types::SliceData;
+use tvm_types::error;
+use tvm_types::fail;
+
+use super::deserialize::Cursor;
+use super::deserialize::find_next_bits;
+use crate::contract::ABI_VERSION_2_4;
+use crate::contract::AbiVersion;
+use crate::error::AbiError;
+use crate::param::Param;
+use crate::param_type::ParamType;
+use crate::token::TokenValue;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PathSegment {
+    /// Parameter or tuple component name
+    Field(String),
+    /// Map key or array index
+    Key(String),
+}
+
+/// Path to a value inside encoded parameters, e.g. `balances[0:1234...]`,
+/// `config.owners[2]` or `wallets[0:1234...].balance`.
+///
+/// Path starts with a parameter name followed by `.name` tuple component
+/// accessors and `[key]` map key or array index accessors. `optional` and
+/// `ref` values are transparent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldPath {
+    path: String,
+    segments: Vec<PathSegment>,
+}
+
+impl FieldPath {
+    pub fn parse(path: &str) -> Result<Self> {
+        let invalid =
+            |msg: &str| AbiError::InvalidFieldPath { path: path.to_owned(), msg: msg.to_owned() };
+        let mut segments = Vec::new();
+        let mut rest = path;
+        while !rest.is_empty() {
+            if let Some(key) = rest.strip_prefix('[') {
+                let end = key.find(']').ok_or_else(|| invalid("unclosed `[`"))?;
+                let key = key[..end].trim();
+                if key.is_empty() {
+                    fail!(invalid("empty key"));
+                }
+                segments.push(PathSegment::Key(key.to_owned()));
+                rest = &rest[end + 2..];
+            } else {
+                let name = match rest.strip_prefix('.') {
+                    Some(name) if !segments.is_empty() => name,
+                    Some(_) => fail!(invalid("path must start with a field name")),
+                    None if segments.is_empty() => rest,
+                    None => fail!(invalid("expected `.` or `[`")),
+                };
+                let end = name.find(['.', '[']).unwrap_or(name.len());
+                if end == 0 {
+                    fail!(invalid("empty field name"));
+                }
+                segments.push(PathSegment::Field(name[..end].to_owned()));
+                rest = &name[end..];
+            }
+        }
+        if !matches!(segments.first(), Some(PathSegment::Field(_))) {
+            fail!(invalid("path must start with a field name"));
+        }
+        Ok(Self { path: path.to_owned(), segments })
+    }
+
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+}
+
+impl FromStr for FieldPath {
+    type Err = tvm_types::Error;
+
+    fn from_str(path: &str) -> Result<Self> {
+        Self::parse(path)
+    }
+}
+
+impl fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.path)
+    }
+}
+
+impl TokenValue {
+    /// Decodes the value addressed by `path` from data encoded with `params`
+    /// layout. Returns `None` if the path leads to a missing map key, an
+    /// array index out of bounds or an empty optional.
+    pub fn decode_path(
+        params: &[Param],
+        data: SliceData,
+        path: &FieldPath,
+        abi_version: &AbiVersion,
+    ) -> Result<Option<Self>> {
+        let (name, segments) = match path.segments.split_first() {
+            Some((PathSegment::Field(name), segments)) => (name, segments),
+            _ => fail!(AbiError::InvalidFieldPath {
+                path: path.to_string(),
+                msg: "path must start with a field name".to_owned()
+            }),
+        };
+        let (param_type, cursor, last) =
+            Self::find_param(params, data.into(), true, name, path, abi_version)?;
+        Self::decode_at(param_type, cursor, last, segments, path, abi_version)
+    }
+
+    /// Skips parameters preceding the one with `name` and returns its type
+    /// with the cursor pointing to it.
+    fn find_param<'a>(
+        params: &'a [Param],
+        mut cursor: Cursor,
+        last: bool,
+        name: &str,
+        path: &FieldPath,
+        abi_version: &AbiVersion,
+    ) -> Result<(&'a ParamType, Cursor, bool)> {
+        for param in params {
+            let last = Some(param) == params.last() && last;
+            if param.name == name {
+                return Ok((&param.kind, cursor, last));
+            }
+            cursor = Self::skip_from(&param.kind, cursor, last, abi_version)?;
+        }
+        fail!(AbiError::InvalidFieldPath {
+            path: path.to_string(),
+            msg: format!("no field `{}`", name)
+        })
+    }
+
+    fn decode_at(
+        param_type: &ParamType,
+        cursor: Cursor,
+        last: bool,
+        segments: &[PathSegment],
+        path: &FieldPath,
+        abi_version: &AbiVersion,
+    ) -> Result<Option<Self>> {
+        let (segment, rest) = match segments.split_first() {
+            Some(split) => split,
+            None => {
+                return Self::read_from(param_type, cursor, last, abi_version, true)
+                    .map(|(value, _)| Some(value));
+            }
+        };
+        match (param_type, segment) {
+            (ParamType::Ref(inner_type) | ParamType::LimitedRef(inner_type, _), _) => {
+                let (cell, _) = Self::read_cell(cursor.slice, last, abi_version)?;
+                let cursor = SliceData::load_cell(cell)?.into();
+                Self::decode_at(inner_type, cursor, true, segments, path, abi_version)
+            }
+            (ParamType::Optional(inner_type), _) => {
+                let mut slice = find_next_bits(cursor.slice, 1)?;
+                if !slice.get_next_bit()? {
+                    return Ok(None);
+                }
+                if Self::is_large_optional(inner_type, abi_version) {
+                    slice = SliceData::load_cell(slice.checked_drain_reference()?)?;
+                }
+                Self::decode_at(inner_type, slice.into(), last, segments, path, abi_version)
+            }
+            (ParamType::Tuple(params), PathSegment::Field(name)) => {
+                let (param_type, cursor, last) =
+                    Self::find_param(params, cursor, last, name, path, abi_version)?;
+                Self::decode_at(param_type, cursor, last, rest, path, abi_version)
+            }
+            (ParamType::Map(key_type, value_type), PathSegment::Key(key)) => {
+                let key_len = Self::get_map_key_size(key_type)?;
+                let value_len = Self::max_bit_size(value_type, abi_version);
+                let mut slice = find_next_bits(cursor.slice, 1)?;
+                let map = HashmapE::with_hashmap(key_len, slice.get_dictionary()?.reference_opt(0));
+                match map.get(Self::write_map_key(key_type, key, abi_version)?)? {
+                    Some(mut value) => {
+                        if Self::map_value_in_ref(key_len, value_len) {
+                            value = SliceData::load_cell(value.checked_drain_reference()?)?;
+                        }
+                        Self::decode_at(value_type, value.into(), true, rest, path, abi_version)
+                    }
+                    None => Ok(None),
+                }
+            }
+            (ParamType::Array(item_type), PathSegment::Key(index)) => {
+                let mut slice = find_next_bits(cursor.slice, 32)?;
+                let size = slice.get_next_u32()? as usize;
+                Self::decode_array_item(item_type, slice, size, index, rest, path, abi_version)
+            }
+            (ParamType::FixedArray(item_type, size), PathSegment::Key(index)) => {
+                Self::decode_array_item(
+                    item_type,
+                    cursor.slice,
+                    *size,
+                    index,
+                    rest,
+                    path,
+                    abi_version,
+                )
+            }
+            (_, PathSegment::Field(name)) => fail!(AbiError::InvalidFieldPath {
+                path: path.to_string(),
+                msg: format!("field `{}` can not be taken from {}", name, param_type)
+            }),
+            (_, PathSegment::Key(key)) => fail!(AbiError::InvalidFieldPath {
+                path: path.to_string(),
+                msg: format!("key `{}` can not be taken from {}", key, param_type)
+            }),
+        }
+    }
+
+    fn decode_array_item(
+        item_type: &ParamType,
+        slice: SliceData,
+        size: usize,
+        index: &str,
+        segments: &[PathSegment],
+        path: &FieldPath,
+        abi_version: &AbiVersion,
+    ) -> Result<Option<Self>> {
+        let index = index.parse::<u32>().map_err(|_| AbiError::InvalidFieldPath {
+            path: path.to_string(),
+            msg: format!("invalid array index `{}`", index),
+        })?;
+        if index as usize >= size {
+            return Ok(None);
+        }
+        let mut slice = find_next_bits(slice, 1)?;
+        let map = HashmapE::with_hashmap(32, slice.get_dictionary()?.reference_opt(0));
+        let mut key = BuilderData::new();
+        key.append_u32(index)?;
+        match map.get(SliceData::load_builder(key)?)? {
+            Some(item) => {
+                let item = Self::load_array_item(item_type, item, abi_version)?;
+                Self::decode_at(item_type, item.into(), true, segments, path, abi_version)
+            }
+            None => fail!(AbiError::InvalidData {
+                msg: format!("Array doesn't contain item with index {}", index)
+            }),
+        }
+    }
+
+    /// Moves cursor over the value without decoding its referenced data.
+    fn skip_from(
+        param_type: &ParamType,
+        cursor: Cursor,
+        last: bool,
+        abi_version: &AbiVersion,
+    ) -> Result<Cursor> {
+        let slice = cursor.slice.clone();
+        let slice = match param_type {
+            ParamType::Tuple(params) => {
+                let mut cursor = cursor;
+                for param in params {
+                    let last = Some(param) == params.last() && last;
+                    cursor = Self::skip_from(&param.kind, cursor, last, abi_version)?;
+                }
+                return Ok(cursor);
+            }
+            ParamType::Map(..) | ParamType::FixedArray(..) => {
+                let mut slice = find_next_bits(slice, 1)?;
+                slice.get_dictionary()?;
+                slice
+            }
+            ParamType::Array(_) => {
+                let mut slice = find_next_bits(slice, 32)?;
+                slice.get_next_u32()?;
+                let mut slice = find_next_bits(slice, 1)?;
+                slice.get_dictionary()?;
+                slice
+            }
+            ParamType::Cell
+            | ParamType::Bytes
+            | ParamType::String
+            | ParamType::Ref(_)
+            | ParamType::LimitedRef(..) => Self::read_cell(slice, last, abi_version)?.1,
+            ParamType::FixedBytes(_) if abi_version < &ABI_VERSION_2_4 => {
+                Self::read_cell(slice, last, abi_version)?.1
+            }
+            ParamType::Optional(inner_type) => {
+                let mut slice = find_next_bits(slice, 1)?;
+                if !slice.get_next_bit()? {
+                    slice
+                } else if Self::is_large_optional(inner_type, abi_version) {
+                    slice.checked_drain_reference()?;
+                    slice
+                } else {
+                    Self::skip_from(inner_type, slice.into(), last, abi_version)?.slice
+                }
+            }
+            _ => return Self::read_from(param_type, cursor, last, abi_version, true).map(|x| x.1),
+        };
+        let mut cursor = Self::check_layout(param_type, cursor, &slice, abi_version, last)?;
+        cursor.slice = slice;
+        Ok(cursor)
+    }
+}
@@ -33,17 +33,21 @@ use crate::param_type::ParamType;
 
 mod deserialize;
 mod detokenizer;
+mod lazy;
 mod serialize;
 mod tokenizer;
 
 pub use self::deserialize::*;
 pub use self::detokenizer::*;
+pub use self::lazy::*;
 pub use self::serialize::*;
 pub use self::tokenizer::*;
 
 #[cfg(test)]
 mod test_encoding;
 #[cfg(test)]
+mod test_lazy;
+#[cfg(test)]
 mod tests;
 
 pub const STD_ADDRESS_BIT_LENGTH: usize = 267;
@@ -136,6 +140,20 @@ pub enum TokenValue {
     Optional(ParamType, Option<Box<TokenValue>>),
     /// Parameter stored in reference
     Ref(Box<TokenValue>),
+    /// fixed<M>x<N>: signed decimal with N fractional digits.
+    ///
+    /// Encoded as `int<M>` of the value multiplied by 10^N.
+    Fixed(Int, usize),
+    /// ufixed<M>x<N>: unsigned decimal with N fractional digits.
+    ///
+    /// Encoded as `uint<M>` of the value multiplied by 10^N.
+    UFixed(Uint, usize),
+    /// Enum variant index with the list of enum variants.
+    ///
+    /// Encoded as `uint8` index.
+    Enum(Vec<String>, u8),
+    /// Parameter stored in reference tree with limited number of cells
+    LimitedRef(Box<TokenValue>, usize),
 }
 
 impl fmt::Display for TokenValue {
@@ -172,7 +190,17 @@ impl fmt::Display for TokenValue {
             TokenValue::Token(g) => write!(f, "{}", g),
             TokenValue::Time(time) => write!(f, "{}", time),
             TokenValue::Expire(expire) => write!(f, "{}", expire),
-            TokenValue::Ref(value) => write!(f, "{}", value),
+            TokenValue::Ref(value) | TokenValue::LimitedRef(value, _) => write!(f, "{}", value),
+            TokenValue::Fixed(int, decimals) => {
+                write!(f, "{}", Self::format_decimal(&int.number, *decimals))
+            }
+            TokenValue::UFixed(uint, decimals) => {
+                write!(f, "{}", Self::format_decimal(&uint.number.clone().into(), *decimals))
+            }
+            TokenValue::Enum(variants, index) => match variants.get(*index as usize) {
+                Some(variant) => write!(f, "{}", variant),
+                None => write!(f, "{}", index),
+            },
             TokenValue::PublicKey(key) => {
                 if let Some(key) = key {
                     write!(f, "{}", hex::encode(key))
@@ -260,6 +288,20 @@ impl TokenValue {
                     false
                 }
             }
+            TokenValue::Fixed(int, decimals) => {
+                *param_type == ParamType::Fixed(int.size, *decimals)
+            }
+            TokenValue::UFixed(uint, decimals) => {
+                *param_type == ParamType::UFixed(uint.size, *decimals)
+            }
+            TokenValue::Enum(variants, _) => *param_type == ParamType::Enum(variants.clone()),
+            TokenValue::LimitedRef(value, max_cells) => {
+                if let ParamType::LimitedRef(ref param_type, size) = *param_type {
+                    size == *max_cells && value.type_check(param_type)
+                } else {
+                    false
+                }
+            }
         }
     }
 
@@ -294,6 +336,12 @@ impl TokenValue {
                 ParamType::Optional(Box::new(param_type.clone()))
             }
             TokenValue::Ref(value) => ParamType::Ref(Box::new(value.get_param_type())),
+            TokenValue::Fixed(int, decimals) => ParamType::Fixed(int.size, *decimals),
+            TokenValue::UFixed(uint, decimals) => ParamType::UFixed(uint.size, *decimals),
+            TokenValue::Enum(variants, _) => ParamType::Enum(variants.clone()),
+            TokenValue::LimitedRef(value, max_cells) => {
+                ParamType::LimitedRef(Box::new(value.get_param_type()), *max_cells)
+            }
         }
     }
 
@@ -343,7 +391,10 @@ impl TokenValue {
             | ParamType::Token
             | ParamType::Time
             | ParamType::Expire
-            | ParamType::PublicKey => 0,
+            | ParamType::PublicKey
+            | ParamType::Fixed(..)
+            | ParamType::UFixed(..)
+            | ParamType::Enum(_) => 0,
             ParamType::FixedBytes(_) if &ABI_VERSION_2_4 <= abi_version => 0,
             // reference serialized types
             ParamType::Array(_)
@@ -353,7 +404,8 @@ impl TokenValue {
             | ParamType::Map(_, _)
             | ParamType::Bytes
             | ParamType::FixedBytes(_)
-            | ParamType::Ref(_) => 1,
+            | ParamType::Ref(_)
+            | ParamType::LimitedRef(..) => 1,
             // tuple refs is sum of inner types refs
             ParamType::Tuple(params) => params
                 .iter()
@@ -388,7 +440,9 @@ impl TokenValue {
             ParamType::Time => 64,
             ParamType::Expire => 32,
             ParamType::PublicKey => 257,
-            ParamType::Ref(_) => 0,
+            ParamType::Ref(_) | ParamType::LimitedRef(..) => 0,
+            ParamType::Fixed(size, _) | ParamType::UFixed(size, _) => *size,
+            ParamType::Enum(_) => 8,
             ParamType::Tuple(params) => params
                 .iter()
                 .fold(0, |acc, param| acc + Self::max_bit_size(&param.kind, abi_version)),
@@ -437,7 +491,44 @@ impl TokenValue {
                     .collect(),
             ),
             ParamType::Optional(inner) => TokenValue::Optional(inner.as_ref().clone(), None),
+            ParamType::Fixed(size, decimals) => TokenValue::Fixed(Int::new(0, *size), *decimals),
+            ParamType::UFixed(size, decimals) => TokenValue::UFixed(Uint::new(0, *size), *decimals),
+            ParamType::Enum(variants) => TokenValue::Enum(variants.clone(), 0),
+            ParamType::LimitedRef(inner, max_cells) => {
+                TokenValue::LimitedRef(Box::new(Self::default_value(inner)), *max_cells)
+            }
+        }
+    }
+
+    /// Formats fixed-point number scaled by 10^`decimals` as a decimal string.
+    pub(crate) fn format_decimal(number: &BigInt, decimals: usize) -> String {
+        let digits = number.magnitude().to_str_radix(10);
+        let sign = if number.sign() == num_bigint::Sign::Minus { "-" } else { "" };
+        if decimals == 0 {
+            return format!("{}{}", sign, digits);
+        }
+        let digits = format!("{:0>width$}", digits, width = decimals + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - decimals);
+        format!("{}{}.{}", sign, integer, fraction)
+    }
+
+    /// Checks that the tree of `cell` contains at most `max_cells` cells.
+    pub(crate) fn check_cells_limit(cell: &Cell, max_cells: usize) -> Result<()> {
+        let mut count = 0;
+        let mut stack = vec![cell.clone()];
+        while let Some(cell) = stack.pop() {
+            count += 1;
+            if count > max_cells {
+                return Err(AbiError::InvalidData {
+                    msg: format!("Referenced value exceeds the limit of {} cells", max_cells),
+                }
+                .into());
+            }
+            for i in 0..cell.references_count() {
+                stack.push(cell.reference(i)?);
+            }
         }
+        Ok(())
     }
 }
 
@@ -177,6 +177,12 @@ impl TokenValue {
                 abi_version,
             ),
             TokenValue::Ref(value) => Self::write_ref(value, abi_version),
+            TokenValue::Fixed(int, _) => Self::write_int(int),
+            TokenValue::UFixed(uint, _) => Self::write_uint(uint),
+            TokenValue::Enum(_, index) => Self::write_uint(&Uint::new(*index as u128, 8)),
+            TokenValue::LimitedRef(value, max_cells) => {
+                Self::write_limited_ref(value, *max_cells, abi_version)
+            }
         }?;
 
         let param_type = self.get_param_type();
@@ -367,6 +373,29 @@ impl TokenValue {
         super::MAX_HASH_MAP_INFO_ABOUT_KEY + key_len + value_len > 1023
     }
 
+    /// Serializes map key given as a string into dictionary key.
+    pub fn write_map_key(
+        key_type: &ParamType,
+        key: &str,
+        abi_version: &AbiVersion,
+    ) -> Result<SliceData> {
+        let key = Tokenizer::tokenize_parameter(key_type, &key.into(), "map key")?;
+
+        let mut key_vec = key.write_to_cells(abi_version)?;
+        if key_vec.len() != 1 {
+            fail!(AbiError::InvalidData { msg: "Map key must be 1-cell length".to_owned() })
+        };
+        if &ParamType::Address == key_type
+            && key_vec[0].data.length_in_bits() != super::STD_ADDRESS_BIT_LENGTH
+        {
+            fail!(AbiError::InvalidData {
+                msg: "Only std non-anycast address can be used as map key".to_owned()
+            })
+        }
+
+        SliceData::load_builder(key_vec.pop().unwrap().data)
+    }
+
     fn write_map(
         key_type: &ParamType,
         value_type: &ParamType,
@@ -380,24 +409,11 @@ impl TokenValue {
         let mut hashmap = HashmapE::with_bit_len(key_len);
 
         for (key, value) in value.iter() {
-            let key = Tokenizer::tokenize_parameter(key_type, &key.as_str().into(), "map key")?;
-
-            let mut key_vec = key.write_to_cells(abi_version)?;
-            if key_vec.len() != 1 {
-                fail!(AbiError::InvalidData { msg: "Map key must be 1-cell length".to_owned() })
-            };
-            if &ParamType::Address == key_type
-                && key_vec[0].data.length_in_bits() != super::STD_ADDRESS_BIT_LENGTH
-            {
-                fail!(AbiError::InvalidData {
-                    msg: "Only std non-anycast address can be used as map key".to_owned()
-                })
-            }
+            let slice_key = Self::write_map_key(key_type, key, abi_version)?;
 
             let data =
                 Self::pack_cells_into_chain(value.write_to_cells(abi_version)?, abi_version)?;
 
-            let slice_key = SliceData::load_builder(key_vec.pop().unwrap().data)?;
             if value_in_ref {
                 hashmap.setref(slice_key, &data.into_cell()?)?;
             } else {
@@ -450,6 +466,18 @@ impl TokenValue {
         builder.checked_append_reference(value.into_cell()?)?;
         Ok(builder)
     }
+
+    fn write_limited_ref(
+        value: &TokenValue,
+        max_cells: usize,
+        abi_version: &AbiVersion,
+    ) -> Result<BuilderData> {
+        let cell = value.pack_into_chain(abi_version)?.into_cell()?;
+        Self::check_cells_limit(&cell, max_cells)?;
+        let mut builder = BuilderData::new();
+        builder.checked_append_reference(cell)?;
+        Ok(builder)
+    }
 }
 
 #[test]
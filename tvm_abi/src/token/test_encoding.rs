@@ -41,6 +41,7 @@ use crate::contract::ABI_VERSION_2_1;
 use crate::contract::ABI_VERSION_2_2;
 use crate::contract::ABI_VERSION_2_3;
 use crate::contract::ABI_VERSION_2_4;
+use crate::contract::ABI_VERSION_2_5;
 use crate::contract::AbiVersion;
 use crate::contract::MAX_SUPPORTED_VERSION;
 use crate::token::Cursor;
@@ -1360,3 +1361,81 @@ fn test_fixed_bytes() {
     );
     test_parameters_set(&tokens_from_values(values), None, builder_v24, &[ABI_VERSION_2_4]);
 }
+
+#[test]
+fn test_abi_v2_5_types() {
+    let mut builder = BuilderData::with_raw(vec![0x55; 127], 127 * 8).unwrap();
+    builder
+        .checked_append_reference(
+            BuilderData::with_raw(vec![0x55; 46], 46 * 8).unwrap().into_cell().unwrap(),
+        )
+        .unwrap();
+    let mut bytes_builder = BuilderData::with_raw(vec![0x55; 127], 127 * 8).unwrap();
+    bytes_builder.checked_append_reference(builder.into_cell().unwrap()).unwrap();
+    let mut ref_builder = BuilderData::new();
+    ref_builder.checked_append_reference(bytes_builder.into_cell().unwrap()).unwrap();
+
+    // test prefix with one ref and u32
+    let mut builder = BuilderData::new();
+    builder.append_u32(0).unwrap();
+    builder.checked_append_reference(Cell::default()).unwrap();
+
+    builder.append_i32(-1250).unwrap();
+    builder.append_u64(3_000_000).unwrap();
+    builder.append_u8(1).unwrap();
+    builder.checked_append_reference(ref_builder.into_cell().unwrap()).unwrap();
+
+    let variants = vec!["Active".to_owned(), "Frozen".to_owned()];
+    let values = vec![
+        TokenValue::Fixed(Int::new(-1250, 32), 2),
+        TokenValue::UFixed(Uint::new(3_000_000, 64), 6),
+        TokenValue::Enum(variants.clone(), 1),
+        TokenValue::LimitedRef(Box::new(TokenValue::Bytes(vec![0x55; 300])), 4),
+    ];
+
+    test_parameters_set(&tokens_from_values(values), None, builder.clone(), &[ABI_VERSION_2_5]);
+
+    // the referenced tree takes 4 cells
+    let tokens = tokens_from_values(vec![TokenValue::LimitedRef(
+        Box::new(TokenValue::Bytes(vec![0x55; 300])),
+        3,
+    )]);
+    assert!(matches!(
+        TokenValue::pack_values_into_chain(&tokens, vec![], &ABI_VERSION_2_5)
+            .unwrap_err()
+            .downcast::<AbiError>()
+            .unwrap(),
+        AbiError::InvalidData { .. },
+    ));
+
+    let mut slice = SliceData::load_builder(builder).unwrap();
+    slice.checked_drain_reference().unwrap();
+    slice.get_next_u32().unwrap();
+    let params = params_from_types(vec![
+        ParamType::Fixed(32, 2),
+        ParamType::UFixed(64, 6),
+        ParamType::Enum(variants.clone()),
+        ParamType::LimitedRef(Box::new(ParamType::Bytes), 3),
+    ]);
+    assert!(matches!(
+        TokenValue::decode_params(&params, slice.clone(), &ABI_VERSION_2_5, false)
+            .unwrap_err()
+            .downcast::<AbiError>()
+            .unwrap(),
+        AbiError::InvalidData { .. },
+    ));
+
+    // unknown enum variant index
+    let mut builder = BuilderData::new();
+    builder.append_u8(2).unwrap();
+    let params = params_from_types(vec![ParamType::Enum(variants)]);
+    assert!(
+        TokenValue::decode_params(
+            &params,
+            SliceData::load_builder(builder).unwrap(),
+            &ABI_VERSION_2_5,
+            false
+        )
+        .is_err()
+    );
+}
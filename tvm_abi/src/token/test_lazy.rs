@@ -0,0 +1,165 @@
+// Copyright (C) 2019-2022 TON Labs. All Rights Reserved.
+//
+// Licensed under the SOFTWARE EVALUATION License (the "License"); you may not
+// use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific TON DEV software governing permissions and
+// limitations under the License.
+
+use serde_json::json;
+use tvm_types::SliceData;
+
+use crate::AbiError;
+use crate::Contract;
+use crate::Param;
+use crate::TokenValue;
+use crate::contract::ABI_VERSION_2_1;
+use crate::contract::ABI_VERSION_2_4;
+use crate::contract::AbiVersion;
+use crate::token::Detokenizer;
+use crate::token::FieldPath;
+use crate::token::PathSegment;
+use crate::token::Tokenizer;
+
+const ADDRESS_1: &str = "0:1111111111111111111111111111111111111111111111111111111111111111";
+const ADDRESS_2: &str = "0:2222222222222222222222222222222222222222222222222222222222222222";
+
+const FIELDS: &str = r#"[
+    { "name": "_pubkey", "type": "uint256" },
+    { "name": "name", "type": "string" },
+    { "name": "balances", "type": "map(address,uint128)" },
+    { "name": "owners", "type": "uint64[]" },
+    { "name": "config", "type": "tuple", "components": [
+        { "name": "limit", "type": "uint32" },
+        { "name": "wallets", "type": "map(uint32,tuple)", "components": [
+            { "name": "owner", "type": "address" },
+            { "name": "frozen", "type": "bool" }
+        ] }
+    ] },
+    { "name": "pending", "type": "optional(uint8[2])" },
+    { "name": "empty", "type": "optional(uint8)" },
+    { "name": "total", "type": "uint128" }
+]"#;
+
+fn storage() -> serde_json::Value {
+    json!({
+        "_pubkey": "0x01",
+        "name": "some long name stored in a separate cell",
+        "balances": { ADDRESS_1: "100", ADDRESS_2: "200" },
+        "owners": ["1", "2", "3"],
+        "config": {
+            "limit": 5,
+            "wallets": { "7": { "owner": ADDRESS_2, "frozen": true } }
+        },
+        "pending": ["9", "10"],
+        "empty": null,
+        "total": "300"
+    })
+}
+
+fn encode(params: &[Param], version: &AbiVersion) -> SliceData {
+    let tokens = Tokenizer::tokenize_all_params(params, &storage()).unwrap();
+    let data = TokenValue::pack_values_into_chain(&tokens, vec![], version).unwrap();
+    SliceData::load_builder(data).unwrap()
+}
+
+fn decode(
+    path: &str,
+    params: &[Param],
+    data: &SliceData,
+    version: &AbiVersion,
+) -> serde_json::Value {
+    match TokenValue::decode_path(params, data.clone(), &path.parse().unwrap(), version).unwrap() {
+        Some(value) => serde_json::to_value(value).unwrap(),
+        None => serde_json::Value::Null,
+    }
+}
+
+#[test]
+fn test_field_path_parsing() {
+    let path = FieldPath::parse("config.wallets[7].owner").unwrap();
+    assert_eq!(
+        path.segments(),
+        &[
+            PathSegment::Field("config".to_owned()),
+            PathSegment::Field("wallets".to_owned()),
+            PathSegment::Key("7".to_owned()),
+            PathSegment::Field("owner".to_owned()),
+        ]
+    );
+    assert_eq!(path.to_string(), "config.wallets[7].owner");
+    assert_eq!(
+        FieldPath::parse(&format!("balances[{}]", ADDRESS_1)).unwrap().segments()[1],
+        PathSegment::Key(ADDRESS_1.to_owned())
+    );
+
+    for path in ["", "[1]", ".a", "a..b", "a[1", "a[]", "a[1]b", "a."] {
+        assert!(
+            matches!(
+                FieldPath::parse(path).unwrap_err().downcast::<AbiError>().unwrap(),
+                AbiError::InvalidFieldPath { .. }
+            ),
+            "{}",
+            path
+        );
+    }
+}
+
+#[test]
+fn test_decode_path() {
+    let params: Vec<Param> = serde_json::from_str(FIELDS).unwrap();
+    for version in [ABI_VERSION_2_1, ABI_VERSION_2_4] {
+        let data = encode(&params, &version);
+        let full = Detokenizer::detokenize_to_json_value(
+            &TokenValue::decode_params(&params, data.clone(), &version, false).unwrap(),
+        )
+        .unwrap();
+
+        for field in ["_pubkey", "name", "balances", "owners", "config", "pending", "total"] {
+            assert_eq!(decode(field, &params, &data, &version), full[field]);
+        }
+        assert_eq!(decode(&format!("balances[{}]", ADDRESS_2), &params, &data, &version), "200");
+        assert_eq!(decode(&format!("balances[{}]", ADDRESS_1), &params, &data, &version), "100");
+        assert_eq!(
+            decode(
+                "balances[0:3333333333333333333333333333333333333333333333333333333333333333]",
+                &params,
+                &data,
+                &version
+            ),
+            json!(null)
+        );
+        assert_eq!(decode("owners[2]", &params, &data, &version), "3");
+        assert_eq!(decode("owners[3]", &params, &data, &version), json!(null));
+        assert_eq!(decode("config.limit", &params, &data, &version), "5");
+        assert_eq!(decode("config.wallets[7].owner", &params, &data, &version), ADDRESS_2);
+        assert_eq!(decode("config.wallets[7].frozen", &params, &data, &version), true);
+        assert_eq!(decode("config.wallets[8].frozen", &params, &data, &version), json!(null));
+        assert_eq!(decode("pending[1]", &params, &data, &version), "10");
+        assert_eq!(decode("empty", &params, &data, &version), json!(null));
+
+        for path in ["unknown", "total.value", "total[1]", "owners[x]", "config.wallets[x]"] {
+            assert!(
+                TokenValue::decode_path(&params, data.clone(), &path.parse().unwrap(), &version)
+                    .is_err(),
+                "{}",
+                path
+            );
+        }
+    }
+}
+
+#[test]
+fn test_contract_decode_storage_field() {
+    let abi = format!(r#"{{ "version": "2.4", "functions": [], "fields": {} }}"#, FIELDS);
+    let contract = Contract::load(abi.as_bytes()).unwrap();
+    let data = encode(contract.fields(), contract.version());
+    assert_eq!(
+        contract.decode_storage_field(data.clone(), &format!("balances[{}]", ADDRESS_1)).unwrap(),
+        Some(TokenValue::Uint(crate::Uint::new(100, 128)))
+    );
+    assert!(contract.decode_storage_field(data, "balances[").is_err());
+}
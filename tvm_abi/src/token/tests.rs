@@ -905,6 +905,67 @@ mod tokenize_tests {
         );
     }
 
+    #[test]
+    fn test_tokenize_abi_v2_5_types() {
+        let input = r#"{
+            "a": "-12.5",
+            "b": 3,
+            "c": "0.000001",
+            "d": "Frozen",
+            "e": 0,
+            "f": "abcd"
+        }"#;
+
+        let variants = vec!["Active".to_owned(), "Frozen".to_owned()];
+        let params = vec![
+            Param::new("a", ParamType::Fixed(32, 2)),
+            Param::new("b", ParamType::UFixed(64, 6)),
+            Param::new("c", ParamType::UFixed(64, 6)),
+            Param::new("d", ParamType::Enum(variants.clone())),
+            Param::new("e", ParamType::Enum(variants.clone())),
+            Param::new("f", ParamType::LimitedRef(Box::new(ParamType::Bytes), 2)),
+        ];
+
+        let expected_tokens = vec![
+            Token::new("a", TokenValue::Fixed(Int::new(-1250, 32), 2)),
+            Token::new("b", TokenValue::UFixed(Uint::new(3_000_000, 64), 6)),
+            Token::new("c", TokenValue::UFixed(Uint::new(1, 64), 6)),
+            Token::new("d", TokenValue::Enum(variants.clone(), 1)),
+            Token::new("e", TokenValue::Enum(variants.clone(), 0)),
+            Token::new(
+                "f",
+                TokenValue::LimitedRef(Box::new(TokenValue::Bytes(vec![0xab, 0xcd])), 2),
+            ),
+        ];
+
+        let tokens =
+            Tokenizer::tokenize_all_params(&params, &serde_json::from_str(input).unwrap()).unwrap();
+        assert_eq!(tokens, expected_tokens);
+
+        assert_eq!(
+            Detokenizer::detokenize_to_json_value(&tokens).unwrap(),
+            serde_json::json!({
+                "a": "-12.50",
+                "b": "3.000000",
+                "c": "0.000001",
+                "d": "Frozen",
+                "e": "Active",
+                "f": "abcd",
+            })
+        );
+
+        let tokenize = |kind: ParamType, value: serde_json::Value| {
+            Tokenizer::tokenize_parameter(&kind, &value, "a")
+        };
+        assert!(tokenize(ParamType::Fixed(8, 2), serde_json::json!("1.234")).is_err());
+        assert!(tokenize(ParamType::Fixed(8, 2), serde_json::json!("1.28")).is_err());
+        assert!(tokenize(ParamType::Fixed(8, 2), serde_json::json!("-1.28")).is_ok());
+        assert!(tokenize(ParamType::Fixed(8, 2), serde_json::json!("1e2")).is_err());
+        assert!(tokenize(ParamType::UFixed(8, 2), serde_json::json!("-0.01")).is_err());
+        assert!(tokenize(ParamType::Enum(variants.clone()), serde_json::json!("Deleted")).is_err());
+        assert!(tokenize(ParamType::Enum(variants), serde_json::json!(2)).is_err());
+    }
+
     #[test]
     fn test_unknown_param() {
         let input = r#"{
@@ -68,6 +68,14 @@ impl Tokenizer {
             ParamType::PublicKey => Self::tokenize_public_key(value, name),
             ParamType::Optional(param_type) => Self::tokenize_optional(param_type, value, name),
             ParamType::Ref(param_type) => Self::tokenize_ref(param_type, value, name),
+            ParamType::Fixed(size, decimals) => Self::tokenize_fixed(*size, *decimals, value, name),
+            ParamType::UFixed(size, decimals) => {
+                Self::tokenize_ufixed(*size, *decimals, value, name)
+            }
+            ParamType::Enum(variants) => Self::tokenize_enum(variants, value, name),
+            ParamType::LimitedRef(param_type, max_cells) => {
+                Self::tokenize_limited_ref(param_type, *max_cells, value, name)
+            }
         }
     }
 
@@ -316,6 +324,107 @@ impl Tokenizer {
         }
     }
 
+    /// Tries to read decimal number from `Value` and scale it by
+    /// 10^`decimals`
+    fn read_decimal(decimals: usize, value: &Value, name: &str) -> Result<BigInt> {
+        let string = match value {
+            Value::Number(number) => number.to_string(),
+            Value::String(string) => string.clone(),
+            _ => fail!(AbiError::WrongDataFormat {
+                val: value.clone(),
+                name: name.to_string(),
+                expected: "decimal number or string with encoded decimal number".to_string()
+            }),
+        };
+        let invalid = |err: &str| AbiError::InvalidParameterValue {
+            val: value.clone(),
+            name: name.to_string(),
+            err: err.to_string(),
+        };
+        let (negative, unsigned) = match string.strip_prefix('-') {
+            Some(unsigned) => (true, unsigned),
+            None => (false, string.as_str()),
+        };
+        let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        if integer.is_empty()
+            || !integer.bytes().all(|c| c.is_ascii_digit())
+            || !fraction.bytes().all(|c| c.is_ascii_digit())
+        {
+            fail!(invalid("can not parse decimal number from string"))
+        }
+        if fraction.len() > decimals {
+            fail!(invalid(&format!("more than {} fractional digits", decimals)))
+        }
+        let digits = format!("{}{:0<width$}", integer, fraction, width = decimals);
+        let number = BigInt::parse_bytes(digits.as_bytes(), 10)
+            .ok_or_else(|| invalid("can not parse decimal number from string"))?;
+        Ok(if negative { -number } else { number })
+    }
+
+    /// Tries to parse a value as signed fixed-point number.
+    fn tokenize_fixed(
+        size: usize,
+        decimals: usize,
+        value: &Value,
+        name: &str,
+    ) -> Result<TokenValue> {
+        let number = Self::read_decimal(decimals, value, name)?;
+
+        if !Self::check_int_size(&number, size) {
+            fail!(AbiError::InvalidParameterValue {
+                val: value.clone(),
+                name: name.to_string(),
+                err: "provided number is out of type range".to_string()
+            })
+        } else {
+            Ok(TokenValue::Fixed(Int { number, size }, decimals))
+        }
+    }
+
+    /// Tries to parse a value as unsigned fixed-point number.
+    fn tokenize_ufixed(
+        size: usize,
+        decimals: usize,
+        value: &Value,
+        name: &str,
+    ) -> Result<TokenValue> {
+        let number = Self::read_decimal(decimals, value, name)?.to_biguint();
+
+        match number {
+            Some(number) if Self::check_uint_size(&number, size) => {
+                Ok(TokenValue::UFixed(Uint { number, size }, decimals))
+            }
+            _ => fail!(AbiError::InvalidParameterValue {
+                val: value.clone(),
+                name: name.to_string(),
+                err: "provided number is out of type range".to_string()
+            }),
+        }
+    }
+
+    /// Tries to parse a value as enum variant name or index.
+    fn tokenize_enum(variants: &[String], value: &Value, name: &str) -> Result<TokenValue> {
+        let index = match value {
+            Value::String(variant) => variants.iter().position(|v| v == variant),
+            Value::Number(number) => {
+                number.as_u64().map(|index| index as usize).filter(|index| *index < variants.len())
+            }
+            _ => fail!(AbiError::WrongDataFormat {
+                val: value.clone(),
+                name: name.to_string(),
+                expected: "enum variant name or index".to_string()
+            }),
+        };
+        match index {
+            Some(index) => Ok(TokenValue::Enum(variants.to_vec(), index as u8)),
+            None => fail!(AbiError::InvalidParameterValue {
+                val: value.clone(),
+                name: name.to_string(),
+                err: format!("unknown enum variant, expected one of {}", variants.join(", "))
+            }),
+        }
+    }
+
     fn tokenize_varuint(size: usize, value: &Value, name: &str) -> Result<TokenValue> {
         let number = Self::read_uint(value, name)?;
 
@@ -510,6 +619,18 @@ impl Tokenizer {
         Ok(TokenValue::Ref(Box::new(Self::tokenize_parameter(inner_type, value, name)?)))
     }
 
+    fn tokenize_limited_ref(
+        inner_type: &ParamType,
+        max_cells: usize,
+        value: &Value,
+        name: &str,
+    ) -> Result<TokenValue> {
+        Ok(TokenValue::LimitedRef(
+            Box::new(Self::tokenize_parameter(inner_type, value, name)?),
+            max_cells,
+        ))
+    }
+
     fn tokenize_address(value: &Value, name: &str) -> Result<TokenValue> {
         let address =
             MsgAddress::from_str(value.as_str().ok_or_else(|| AbiError::WrongDataFormat {
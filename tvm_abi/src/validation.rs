@@ -0,0 +1,338 @@
+// Copyright (C) 2019-2022 TON Labs. All Rights Reserved.
+//
+// Licensed under the SOFTWARE EVALUATION License (the "License"); you may not
+// use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific TON DEV software governing permissions and
+// limitations under the License.
+
+//! ABI JSON validation against the rules of the declared ABI version.
+//!
+//! Unlike `Contract::load`, which stops on the first problem and silently
+//! accepts some inconsistencies (e.g. duplicated names or colliding ids), the
+//! validator reports all found issues.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::Display;
+
+use tvm_types::Result;
+
+use crate::contract::ABI_VERSION_2_1;
+use crate::contract::AbiVersion;
+use crate::contract::Contract;
+use crate::contract::SerdeContract;
+use crate::error::AbiError;
+use crate::event::Event;
+use crate::function::Function;
+use crate::param::Param;
+use crate::param_type::ParamType;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// ABI can be used but is likely to be a mistake.
+    Warning,
+    /// ABI violates the rules of its version.
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Issue found by the ABI validator.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AbiIssue {
+    pub severity: Severity,
+    /// Location of the issue, e.g. `functions.transfer.inputs.value`.
+    pub path: String,
+    pub message: String,
+}
+
+impl Display for AbiIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}: {}", self.severity, self.path, self.message)
+    }
+}
+
+#[derive(Default)]
+struct Issues(Vec<AbiIssue>);
+
+impl Issues {
+    fn error(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.0.push(AbiIssue {
+            severity: Severity::Error,
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+
+    fn warning(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.0.push(AbiIssue {
+            severity: Severity::Warning,
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+}
+
+/// Validates ABI JSON and returns the list of found issues. Fails only if
+/// the JSON can not be parsed as an ABI at all.
+pub fn validate_abi(abi: &str) -> Result<Vec<AbiIssue>> {
+    let contract: SerdeContract =
+        serde_json::from_str(abi).map_err(|err| AbiError::SerdeError { err })?;
+    let mut issues = Issues::default();
+
+    let version = match check_version(&contract, &mut issues) {
+        Some(version) => version,
+        None => return Ok(issues.0),
+    };
+
+    check_header(&version, &contract.header, &mut issues);
+
+    let mut functions = Vec::new();
+    check_unique_names("functions", contract.functions.iter().map(|f| &f.name), &mut issues);
+    for function in &contract.functions {
+        let path = format!("functions.{}", function.name);
+        check_params(&version, &format!("{}.inputs", path), &function.inputs, &mut issues);
+        check_params(&version, &format!("{}.outputs", path), &function.outputs, &mut issues);
+        functions.push(Function::from_serde(version, function.clone(), Vec::new()));
+    }
+    check_id_collisions(
+        "functions",
+        "input id",
+        functions.iter().map(|f| (&f.name, f.get_input_id())),
+        &mut issues,
+    );
+    check_id_collisions(
+        "functions",
+        "output id",
+        functions.iter().map(|f| (&f.name, f.get_output_id())),
+        &mut issues,
+    );
+
+    check_getters(&version, &contract, &mut issues);
+
+    let mut events = Vec::new();
+    check_unique_names("events", contract.events.iter().map(|e| &e.name), &mut issues);
+    for event in &contract.events {
+        let path = format!("events.{}.inputs", event.name);
+        check_params(&version, &path, &event.inputs, &mut issues);
+        events.push(Event::from_serde(version, event.clone()));
+    }
+    check_id_collisions("events", "id", events.iter().map(|e| (&e.name, e.get_id())), &mut issues);
+    for event in &events {
+        if let Some(function) = functions.iter().find(|f| f.get_output_id() == event.get_id()) {
+            issues.warning(
+                format!("events.{}", event.name),
+                format!(
+                    "id 0x{:08x} equals to the output id of function `{}`, \
+                     such messages are decoded as the function output",
+                    event.get_id(),
+                    function.name
+                ),
+            );
+        }
+    }
+
+    check_data(&version, &contract, &mut issues);
+    check_fields(&version, &contract, &mut issues);
+
+    Ok(issues.0)
+}
+
+fn check_version(contract: &SerdeContract, issues: &mut Issues) -> Option<AbiVersion> {
+    let version = match (&contract.version, contract.abi_version) {
+        (Some(version), abi_version) => match AbiVersion::parse(version) {
+            Ok(parsed) => {
+                if abi_version.is_some_and(|major| major != parsed.major) {
+                    issues.warning(
+                        "ABI version",
+                        format!("`ABI version` doesn't match `version` {}", version),
+                    );
+                }
+                parsed
+            }
+            Err(err) => {
+                issues.error("version", err.to_string());
+                return None;
+            }
+        },
+        (None, Some(major)) => AbiVersion::from_parts(major, 0),
+        (None, None) => {
+            issues.error("version", "no version in ABI JSON");
+            return None;
+        }
+    };
+    if !version.is_supported() {
+        issues.error("version", format!("ABI version {} is not supported", version));
+        return None;
+    }
+    Some(version)
+}
+
+fn check_header(version: &AbiVersion, header: &[Param], issues: &mut Issues) {
+    if version.major == 1 {
+        if !header.is_empty() {
+            issues.error("header", "header parameters are not supported in ABI v1");
+        }
+        return;
+    }
+    check_unique_names("header", header.iter().map(|p| &p.name), issues);
+    let mut kinds = Vec::new();
+    for param in header {
+        let path = format!("header.{}", param.name);
+        match param.kind {
+            ParamType::Time | ParamType::Expire | ParamType::PublicKey => {
+                if kinds.contains(&param.kind) {
+                    issues.error(
+                        path,
+                        format!("header parameter of type {} is duplicated", param.kind),
+                    );
+                }
+                kinds.push(param.kind.clone());
+            }
+            _ => issues.error(
+                path,
+                format!(
+                    "header parameter type must be one of `time`, `expire`, `pubkey`, got {}",
+                    param.kind
+                ),
+            ),
+        }
+    }
+}
+
+fn check_data(version: &AbiVersion, contract: &SerdeContract, issues: &mut Issues) {
+    if contract.data.is_empty() {
+        return;
+    }
+    if !Contract::data_map_supported_in_version(version) {
+        issues.error(
+            "data",
+            format!(
+                "initial data dictionary is not supported in ABI v{}, use `init` fields",
+                version
+            ),
+        );
+    }
+    check_unique_names("data", contract.data.iter().map(|d| &d.value.name), issues);
+    let mut keys = HashMap::new();
+    for item in &contract.data {
+        let path = format!("data.{}", item.value.name);
+        check_param_type(version, &path, &item.value.kind, issues);
+        if let Some(other) = keys.insert(item.key, &item.value.name) {
+            issues.error(path, format!("key {} is already used by `{}`", item.key, other));
+        }
+    }
+}
+
+fn check_getters(version: &AbiVersion, contract: &SerdeContract, issues: &mut Issues) {
+    if contract.getters.is_empty() {
+        return;
+    }
+    if !Contract::getters_supported_in_version(version) {
+        issues.warning("getters", format!("getters are ignored in ABI v{}", version));
+        return;
+    }
+    check_unique_names("getters", contract.getters.iter().map(|f| &f.name), issues);
+    for getter in &contract.getters {
+        let path = format!("getters.{}", getter.name);
+        check_params(version, &format!("{}.inputs", path), &getter.inputs, issues);
+        check_params(version, &format!("{}.outputs", path), &getter.outputs, issues);
+    }
+}
+
+fn check_fields(version: &AbiVersion, contract: &SerdeContract, issues: &mut Issues) {
+    if contract.fields.is_empty() {
+        return;
+    }
+    if version < &ABI_VERSION_2_1 {
+        issues.error("fields", format!("storage fields are not supported in ABI v{}", version));
+    }
+    check_unique_names("fields", contract.fields.iter().map(|f| &f.name), issues);
+    for field in &contract.fields {
+        let path = format!("fields.{}", field.name);
+        if field.init && !Contract::init_fields_supported_in_version(version) {
+            issues.error(&path, format!("`init` fields are not supported in ABI v{}", version));
+        }
+        match Param::from_serde(field.clone()) {
+            Ok(param) => check_param_type(version, &path, &param.kind, issues),
+            Err(err) => issues.error(path, err),
+        }
+    }
+}
+
+fn check_params(version: &AbiVersion, path: &str, params: &[Param], issues: &mut Issues) {
+    check_unique_names(path, params.iter().map(|p| &p.name), issues);
+    for param in params {
+        check_param_type(version, &format!("{}.{}", path, param.name), &param.kind, issues);
+    }
+}
+
+fn check_param_type(version: &AbiVersion, path: &str, kind: &ParamType, issues: &mut Issues) {
+    if !kind.is_supported(version) {
+        issues.error(path, format!("parameter type {} is not supported in ABI v{}", kind, version));
+        return;
+    }
+    match kind {
+        ParamType::Tuple(params) => check_params(version, path, params, issues),
+        ParamType::Array(item) | ParamType::FixedArray(item, _) => {
+            check_param_type(version, path, item, issues)
+        }
+        ParamType::Optional(inner) | ParamType::Ref(inner) | ParamType::LimitedRef(inner, _) => {
+            check_param_type(version, path, inner, issues)
+        }
+        ParamType::Map(key, value) => {
+            check_param_type(version, path, key, issues);
+            check_param_type(version, path, value, issues);
+        }
+        _ => {}
+    }
+}
+
+fn check_unique_names<'a>(
+    path: &str,
+    names: impl Iterator<Item = &'a String>,
+    issues: &mut Issues,
+) {
+    let mut seen = HashSet::new();
+    for name in names {
+        if name.is_empty() {
+            issues.error(path, "name is empty");
+        } else if !seen.insert(name) {
+            issues.error(format!("{}.{}", path, name), "name is duplicated");
+        }
+    }
+}
+
+fn check_id_collisions<'a>(
+    path: &str,
+    id_name: &str,
+    ids: impl Iterator<Item = (&'a String, u32)>,
+    issues: &mut Issues,
+) {
+    let mut seen = HashMap::new();
+    for (name, id) in ids {
+        if let Some(other) = seen.insert(id, name) {
+            if other != name {
+                issues.error(
+                    format!("{}.{}", path, name),
+                    format!("{} 0x{:08x} collides with `{}`", id_name, id, other),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/test_validation.rs"]
+mod tests;
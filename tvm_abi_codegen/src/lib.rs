@@ -0,0 +1,371 @@
+// Copyright (C) 2019-2022 TON Labs. All Rights Reserved.
+//
+// Licensed under the SOFTWARE EVALUATION License (the "License"); you may not
+// use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific TON DEV software governing permissions and
+// limitations under the License.
+
+//! Generator of typed Rust contract bindings from ABI JSON.
+//!
+//! For every ABI function the generator emits `<Function>Input` and
+//! `<Function>Output` structs, for every event an `<Event>Event` struct and a
+//! `<Contract>Event` enum, and a `<Contract>` struct with typed
+//! `encode_<function>`, `<function>` (process message), `run_<function>` and
+//! `decode_event` methods built on `tvm_client::abi::ContractBinding`.
+//!
+//! The generated code refers to `tvm_client`, `serde_json` and, for integers
+//! wider than 128 bits, `num-bigint`, so the crate including it must depend on
+//! them. Typical `build.rs`:
+//!
+//! ```ignore
+//! fn main() {
+//!     let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("wallet.rs");
+//!     tvm_abi_codegen::generate_bindings_for("abi/Wallet.abi.json".as_ref(), "Wallet", &out)
+//!         .unwrap();
+//!     println!("cargo:rerun-if-changed=abi/Wallet.abi.json");
+//! }
+//! ```
+//!
+//! and in the crate: `include!(concat!(env!("OUT_DIR"), "/wallet.rs"));`.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::process::Command;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use tvm_abi::Contract;
+use tvm_abi::Param;
+use tvm_abi::ParamType;
+use tvm_types::Result;
+use tvm_types::error;
+use tvm_types::fail;
+
+#[cfg(test)]
+mod tests;
+
+/// Generates bindings for the contract ABI.
+pub fn generate(abi_json: &str, contract_name: &str) -> Result<TokenStream> {
+    let contract = Contract::load(abi_json.as_bytes())?;
+    let mut generator = Generator::default();
+    let contract_ident = generator.type_ident(&to_camel_case(contract_name))?;
+
+    let mut functions = contract.functions().values().collect::<Vec<_>>();
+    functions.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut methods = Vec::new();
+    for function in functions {
+        let type_name = to_camel_case(&function.name);
+        let input = generator.gen_struct(&format!("{}Input", type_name), &function.inputs)?;
+        let output = generator.gen_struct(&format!("{}Output", type_name), &function.outputs)?;
+        let name = &function.name;
+        let snake = to_snake_case(&function.name);
+        let process = no_conflict_ident(&snake);
+        let encode = no_conflict_ident(&format!("encode_{}", snake));
+        let run = no_conflict_ident(&format!("run_{}", snake));
+        let doc_encode = format!("Encodes external inbound message calling `{}`.", name);
+        let doc_process = format!("Calls `{}` and waits for the transaction.", name);
+        let doc_run = format!("Runs `{}` locally on the account BOC.", name);
+        methods.push(quote! {
+            #[doc = #doc_encode]
+            pub async fn #encode(
+                &self,
+                input: &#input,
+                signer: ::tvm_client::abi::Signer,
+            ) -> ::tvm_client::error::ClientResult<::tvm_client::abi::ResultOfEncodeMessage> {
+                self.binding.encode_message(#name, input, signer).await
+            }
+
+            #[doc = #doc_process]
+            pub async fn #process(
+                &self,
+                input: &#input,
+                signer: ::tvm_client::abi::Signer,
+            ) -> ::tvm_client::error::ClientResult<(
+                #output,
+                ::tvm_client::processing::ResultOfProcessMessage,
+            )> {
+                self.binding.process_message(#name, input, signer).await
+            }
+
+            #[doc = #doc_run]
+            pub async fn #run(
+                &self,
+                account: String,
+                input: &#input,
+            ) -> ::tvm_client::error::ClientResult<#output> {
+                self.binding.run_tvm(account, #name, input).await
+            }
+        });
+    }
+
+    let mut events = contract.events().values().collect::<Vec<_>>();
+    events.sort_by(|a, b| a.name.cmp(&b.name));
+    if !events.is_empty() {
+        let enum_ident = generator.type_ident(&format!("{}Event", contract_ident))?;
+        let mut variants = Vec::new();
+        let mut names = Vec::new();
+        let mut structs = Vec::new();
+        for event in events {
+            let type_name = to_camel_case(&event.name);
+            structs.push(generator.gen_struct(&format!("{}Event", type_name), &event.inputs)?);
+            variants.push(no_conflict_ident(&type_name));
+            names.push(event.name.as_str());
+        }
+        generator.items.push(quote! {
+            #[derive(Clone, Debug, PartialEq)]
+            pub enum #enum_ident {
+                #(#variants(#structs),)*
+            }
+        });
+        methods.push(quote! {
+            /// Decodes event of the contract from the message BOC encoded with
+            /// `base64`. Returns `None` for other messages.
+            pub fn decode_event(
+                &self,
+                message: &str,
+            ) -> ::tvm_client::error::ClientResult<Option<#enum_ident>> {
+                let body = self.binding.decode_message(message)?;
+                if body.body_type != ::tvm_client::abi::MessageBodyType::Event {
+                    return Ok(None);
+                }
+                let value = body.value.unwrap_or_default();
+                Ok(match body.name.as_str() {
+                    #(#names => Some(#enum_ident::#variants(
+                        ::tvm_client::abi::AbiValue::from_abi(&value)?,
+                    )),)*
+                    _ => None,
+                })
+            }
+        });
+    }
+
+    let items = &generator.items;
+    Ok(quote! {
+        #(#items)*
+
+        #[derive(Clone)]
+        pub struct #contract_ident {
+            binding: ::tvm_client::abi::ContractBinding,
+        }
+
+        impl #contract_ident {
+            pub const ABI: &'static str = #abi_json;
+
+            pub fn new(
+                context: ::std::sync::Arc<::tvm_client::ClientContext>,
+                address: Option<String>,
+            ) -> Self {
+                Self {
+                    binding: ::tvm_client::abi::ContractBinding::new(
+                        context,
+                        ::tvm_client::abi::Abi::Json(Self::ABI.to_owned()),
+                        address,
+                    ),
+                }
+            }
+
+            /// Sets destination dapp_id used when processing messages.
+            pub fn with_dapp_id(mut self, dapp_id: impl Into<String>) -> Self {
+                self.binding = self.binding.with_dapp_id(dapp_id);
+                self
+            }
+
+            pub fn binding(&self) -> &::tvm_client::abi::ContractBinding {
+                &self.binding
+            }
+
+            #(#methods)*
+        }
+    })
+}
+
+/// Reads the ABI file and writes generated bindings into `out_path`.
+pub fn generate_bindings_for(abi_path: &Path, contract_name: &str, out_path: &Path) -> Result<()> {
+    let abi_json = std::fs::read_to_string(abi_path)
+        .map_err(|err| error!("Unable to read ABI {}: {}", abi_path.display(), err))?;
+    let tokens = generate(&abi_json, contract_name)?;
+    std::fs::write(out_path, tokens.to_string())
+        .map_err(|err| error!("Unable to write bindings {}: {}", out_path.display(), err))?;
+    reformat(out_path);
+    Ok(())
+}
+
+fn reformat(filename: &Path) {
+    if !cfg!(feature = "reformat") {
+        return;
+    }
+    match Command::new("rustfmt").arg("--edition").arg("2021").arg(filename).status() {
+        Ok(status) if status.success() => {}
+        Ok(status) => println!("cargo:warning=rustfmt exited with {}", status),
+        Err(err) => println!("cargo:warning=rustfmt is not available: {}", err),
+    }
+}
+
+#[derive(Default)]
+struct Generator {
+    items: Vec<TokenStream>,
+    types: HashSet<String>,
+}
+
+impl Generator {
+    fn type_ident(&mut self, name: &str) -> Result<syn::Ident> {
+        if !self.types.insert(name.to_owned()) {
+            fail!("Generated type name `{}` is ambiguous", name)
+        }
+        Ok(no_conflict_ident(name))
+    }
+
+    fn gen_struct(&mut self, name: &str, params: &[Param]) -> Result<syn::Ident> {
+        let ident = self.type_ident(name)?;
+        let mut fields = Vec::new();
+        let mut types = Vec::new();
+        let mut names = Vec::new();
+        for param in params {
+            let field_type = format!("{}{}", name, to_camel_case(&param.name));
+            types.push(self.rust_type(&param.kind, &field_type)?);
+            fields.push(no_conflict_ident(&to_snake_case(&param.name)));
+            names.push(param.name.as_str());
+        }
+        let (to_abi, from_abi) = if params.is_empty() {
+            (
+                quote!(::serde_json::Value::Object(Default::default())),
+                quote! {
+                    let _ = value;
+                    Ok(Self {})
+                },
+            )
+        } else {
+            (
+                quote! {
+                    let mut map = ::serde_json::Map::new();
+                    #(map.insert(
+                        #names.to_owned(),
+                        ::tvm_client::abi::AbiValue::to_abi(&self.#fields),
+                    );)*
+                    ::serde_json::Value::Object(map)
+                },
+                quote! {
+                    Ok(Self {
+                        #(#fields: ::tvm_client::abi::abi_field(value, #names)?,)*
+                    })
+                },
+            )
+        };
+        self.items.push(quote! {
+            #[derive(Clone, Debug, Default, PartialEq)]
+            pub struct #ident {
+                #(pub #fields: #types,)*
+            }
+
+            impl ::tvm_client::abi::AbiValue for #ident {
+                fn to_abi(&self) -> ::serde_json::Value {
+                    #to_abi
+                }
+
+                fn from_abi(
+                    value: &::serde_json::Value,
+                ) -> ::tvm_client::error::ClientResult<Self> {
+                    #from_abi
+                }
+            }
+        });
+        Ok(ident)
+    }
+
+    fn rust_type(&mut self, kind: &ParamType, name: &str) -> Result<TokenStream> {
+        Ok(match kind {
+            ParamType::Uint(size) => match size {
+                0..=8 => quote!(u8),
+                9..=16 => quote!(u16),
+                17..=32 => quote!(u32),
+                33..=64 => quote!(u64),
+                65..=128 => quote!(u128),
+                _ => quote!(::num_bigint::BigUint),
+            },
+            ParamType::Int(size) => match size {
+                0..=8 => quote!(i8),
+                9..=16 => quote!(i16),
+                17..=32 => quote!(i32),
+                33..=64 => quote!(i64),
+                65..=128 => quote!(i128),
+                _ => quote!(::num_bigint::BigInt),
+            },
+            ParamType::VarUint(size) if size.saturating_sub(1) * 8 <= 128 => quote!(u128),
+            ParamType::VarUint(_) => quote!(::num_bigint::BigUint),
+            ParamType::VarInt(size) if size.saturating_sub(1) * 8 <= 128 => quote!(i128),
+            ParamType::VarInt(_) => quote!(::num_bigint::BigInt),
+            ParamType::Bool => quote!(bool),
+            ParamType::Tuple(params) => {
+                let ident = self.gen_struct(name, params)?;
+                quote!(#ident)
+            }
+            ParamType::Array(item) | ParamType::FixedArray(item, _) => {
+                let item = self.rust_type(item, name)?;
+                quote!(Vec<#item>)
+            }
+            ParamType::Map(key, value) => {
+                let key = self.rust_type(key, name)?;
+                let value = self.rust_type(value, name)?;
+                quote!(::std::collections::BTreeMap<#key, #value>)
+            }
+            ParamType::Cell | ParamType::Address | ParamType::String => quote!(String),
+            ParamType::Bytes | ParamType::FixedBytes(_) | ParamType::PublicKey => {
+                quote!(::tvm_client::abi::AbiBytes)
+            }
+            ParamType::Token => quote!(u128),
+            ParamType::Time => quote!(u64),
+            ParamType::Expire => quote!(u32),
+            ParamType::Optional(inner) => {
+                let inner = self.rust_type(inner, name)?;
+                quote!(Option<#inner>)
+            }
+            ParamType::Ref(inner) | ParamType::LimitedRef(inner, _) => {
+                self.rust_type(inner, name)?
+            }
+            ParamType::Fixed(..) | ParamType::UFixed(..) | ParamType::Enum(_) => quote!(String),
+        })
+    }
+}
+
+fn no_conflict_ident(s: &str) -> syn::Ident {
+    let mut candidate: String = s.into();
+    loop {
+        match syn::parse_str(&candidate) {
+            Ok(i) => return i,
+            Err(_) => candidate.push('_'),
+        }
+    }
+}
+
+fn to_snake_case(ident: &str) -> String {
+    let mut result = String::new();
+    let mut prev_lower = false;
+    for c in ident.chars() {
+        if c.is_ascii_uppercase() && prev_lower {
+            result.push('_');
+        }
+        prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        result.push(c.to_ascii_lowercase());
+    }
+    result
+}
+
+fn to_camel_case(ident: &str) -> String {
+    let mut result = String::new();
+    let mut upper = true;
+    for c in ident.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            result.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
@@ -0,0 +1,108 @@
+// Copyright (C) 2019-2022 TON Labs. All Rights Reserved.
+//
+// Licensed under the SOFTWARE EVALUATION License (the "License"); you may not
+// use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific TON DEV software governing permissions and
+// limitations under the License.
+
+use super::*;
+
+const EVENTS_ABI: &str =
+    include_str!("../../tvm_client/src/tests/contracts/abi_v2/Events.abi.json");
+
+const TYPES_ABI: &str = r#"{
+    "ABI version": 2,
+    "version": "2.4",
+    "header": ["time", "expire"],
+    "functions": [
+        {
+            "name": "setOwners",
+            "inputs": [
+                {"name":"owners","type":"map(address,tuple)","components":[
+                    {"name":"pubkey","type":"uint256"},
+                    {"name":"weight","type":"uint8"}
+                ]},
+                {"name":"limit","type":"optional(varuint16)"},
+                {"name":"type","type":"bytes"}
+            ],
+            "outputs": [
+                {"name":"ids","type":"int64[]"}
+            ]
+        }
+    ],
+    "events": [],
+    "fields": []
+}"#;
+
+fn generated_items(abi: &str, contract: &str) -> Vec<String> {
+    let file = syn::parse_file(&generate(abi, contract).unwrap().to_string()).unwrap();
+    file.items
+        .iter()
+        .filter_map(|item| match item {
+            syn::Item::Struct(item) => Some(item.ident.to_string()),
+            syn::Item::Enum(item) => Some(item.ident.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_names() {
+    assert_eq!(to_snake_case("sendAllMoney"), "send_all_money");
+    assert_eq!(to_snake_case("getID"), "get_id");
+    assert_eq!(to_snake_case("_pubkey"), "_pubkey");
+    assert_eq!(to_snake_case("value0"), "value0");
+    assert_eq!(to_camel_case("returnValue"), "ReturnValue");
+    assert_eq!(to_camel_case("dest_addr"), "DestAddr");
+    assert_eq!(no_conflict_ident("type").to_string(), "type_");
+}
+
+#[test]
+fn test_generate_events() {
+    let items = generated_items(EVENTS_ABI, "Events");
+    for name in [
+        "ConstructorInput",
+        "EmitValueInput",
+        "ReturnValueInput",
+        "ReturnValueOutput",
+        "SendAllMoneyInput",
+        "EventThrownEvent",
+        "EventsEvent",
+        "Events",
+    ] {
+        assert!(items.contains(&name.to_owned()), "{} is not generated", name);
+    }
+
+    let code = generate(EVENTS_ABI, "Events").unwrap().to_string();
+    assert!(code.contains("pub async fn return_value"));
+    assert!(code.contains("pub async fn encode_send_all_money"));
+    assert!(code.contains("pub async fn run_return_value"));
+    assert!(code.contains("pub fn decode_event"));
+    assert_eq!(code, generate(EVENTS_ABI, "Events").unwrap().to_string());
+}
+
+#[test]
+fn test_generate_types() {
+    let items = generated_items(TYPES_ABI, "Multisig");
+    assert!(items.contains(&"SetOwnersInputOwners".to_owned()));
+    assert!(!items.contains(&"MultisigEvent".to_owned()));
+
+    let code = generate(TYPES_ABI, "Multisig").unwrap().to_string().replace(' ', "");
+    assert!(code.contains("pubowners:::std::collections::BTreeMap<String,SetOwnersInputOwners>"));
+    assert!(code.contains("publimit:Option<u128>"));
+    assert!(code.contains("pubtype_:::tvm_client::abi::AbiBytes"));
+    assert!(code.contains("pubpubkey:::num_bigint::BigUint"));
+    assert!(code.contains("pubweight:u8"));
+    assert!(code.contains("pubids:Vec<i64>"));
+    assert!(code.contains("\"type\".to_owned()"));
+}
+
+#[test]
+fn test_generate_invalid_abi() {
+    assert!(generate("{}", "Empty").is_err());
+    assert!(generate(EVENTS_ABI, "ReturnValueInput").is_err());
+}
@@ -0,0 +1,28 @@
+// Copyright (C) 2019-2022 TON Labs. All Rights Reserved.
+//
+// Licensed under the SOFTWARE EVALUATION License (the "License"); you may not
+// use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific TON DEV software governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+fn main() {
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    for (abi, contract, file) in [
+        ("../../tvm_client/src/tests/contracts/abi_v2/Events.abi.json", "Events", "events.rs"),
+        ("abi/Types.abi.json", "Types", "types.rs"),
+    ] {
+        tvm_abi_codegen::generate_bindings_for(
+            abi.as_ref(),
+            contract,
+            &Path::new(&out_dir).join(file),
+        )
+        .unwrap();
+        println!("cargo:rerun-if-changed={}", abi);
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright (C) 2019-2022 TON Labs. All Rights Reserved.
+//
+// Licensed under the SOFTWARE EVALUATION License (the "License"); you may not
+// use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific TON DEV software governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use num_bigint::BigInt;
+use num_bigint::BigUint;
+use tvm_client::ClientConfig;
+use tvm_client::ClientContext;
+use tvm_client::abi::AbiBytes;
+use tvm_client::abi::AbiValue;
+use tvm_client::abi::Signer;
+use tvm_client::crypto::generate_random_sign_keys;
+
+use crate::events::Events;
+use crate::events::ReturnValueInput;
+use crate::types::SetOwnersInput;
+use crate::types::SetOwnersInputOwners;
+
+const ADDRESS: &str = "0:05beb555e942fa744fd96f45a9ea9d0a8248208ca12421947c06e59bc997d309";
+
+#[tokio::test]
+async fn test_encode_and_decode() {
+    let context = Arc::new(ClientContext::new(ClientConfig::default()).unwrap());
+    let keys = generate_random_sign_keys(context.clone()).unwrap();
+    let events = Events::new(context, Some(ADDRESS.to_owned()));
+
+    let input = ReturnValueInput { id: BigUint::from(5u8) };
+    let encoded = events.encode_return_value(&input, Signer::Keys { keys }).await.unwrap();
+    assert_eq!(encoded.address, ADDRESS);
+
+    let decoded = events.binding().decode_message(&encoded.message).unwrap();
+    assert_eq!(decoded.name, "returnValue");
+    assert_eq!(ReturnValueInput::from_abi(&decoded.value.unwrap()).unwrap(), input);
+    assert_eq!(events.decode_event(&encoded.message).unwrap(), None);
+}
+
+#[test]
+fn test_types_round_trip() {
+    let owners = BTreeMap::from([(
+        ADDRESS.to_owned(),
+        SetOwnersInputOwners { pubkey: BigUint::from(u128::MAX) << 100, weight: 3 },
+    )]);
+    let input = SetOwnersInput {
+        owners,
+        limit: Some(1_000_000),
+        type_: AbiBytes(vec![1, 2, 3]),
+        delta: BigInt::from(-7),
+        flags: vec![true, false],
+    };
+    let value = input.to_abi();
+    assert_eq!(value["type"], "010203");
+    assert_eq!(SetOwnersInput::from_abi(&value).unwrap(), input);
+}
@@ -841,8 +841,42 @@ impl Handlers {
                 .set(0x00, Loader::hashcu)
                 .set(0x01, Loader::hashsu)
                 .set(0x02, Loader::sha256u)
+                .set_range(0x04..0x08, Loader::hashext)
                 .set(0x10, Loader::chksignu)
                 .set(0x11, Loader::chksigns)
+                .set(0x12, Loader::ecrecover)
+                .set(0x14, Loader::p256_chksignu)
+                .set(0x15, Loader::p256_chksigns)
+                .set(0x16, Loader::secp256k1_chksignu)
+                .set(0x17, Loader::secp256k1_chksigns)
+                .add_subset(
+                    0x30,
+                    Handlers::new()
+                        .set(0x00, Loader::bls_verify)
+                        .set(0x01, Loader::bls_aggregate)
+                        .set(0x02, Loader::bls_fastaggregateverify)
+                        .set(0x03, Loader::bls_aggregateverify)
+                        .set(0x10, Loader::bls_g1_add)
+                        .set(0x11, Loader::bls_g1_sub)
+                        .set(0x12, Loader::bls_g1_neg)
+                        .set(0x13, Loader::bls_g1_mul)
+                        .set(0x14, Loader::bls_g1_multiexp)
+                        .set(0x15, Loader::bls_g1_zero)
+                        .set(0x16, Loader::bls_map_to_g1)
+                        .set(0x17, Loader::bls_g1_ingroup)
+                        .set(0x18, Loader::bls_g1_iszero)
+                        .set(0x20, Loader::bls_g2_add)
+                        .set(0x21, Loader::bls_g2_sub)
+                        .set(0x22, Loader::bls_g2_neg)
+                        .set(0x23, Loader::bls_g2_mul)
+                        .set(0x24, Loader::bls_g2_multiexp)
+                        .set(0x25, Loader::bls_g2_zero)
+                        .set(0x26, Loader::bls_map_to_g2)
+                        .set(0x27, Loader::bls_g2_ingroup)
+                        .set(0x28, Loader::bls_g2_iszero)
+                        .set(0x30, Loader::bls_pairing)
+                        .set(0x31, Loader::bls_pushr),
+                )
                 .set(0x40, Loader::cdatasizeq)
                 .set(0x41, Loader::cdatasize)
                 .set(0x42, Loader::sdatasizeq)
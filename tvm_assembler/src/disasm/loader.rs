@@ -69,6 +69,18 @@ macro_rules! create_handler_2t {
     };
 }
 
+macro_rules! create_handler_3 {
+    ($func_name:ident, $opc:literal, $mnemonic:literal) => {
+        pub(super) fn $func_name(&mut self, slice: &mut SliceData) -> Result<Instruction> {
+            let opc = slice.get_next_int(24)?;
+            if opc != $opc {
+                fail!("invalid opcode");
+            }
+            Ok(Instruction::new($mnemonic))
+        }
+    };
+}
+
 macro_rules! create_handler_2r {
     ($func_name:ident, $opc:literal, $mnemonic:literal) => {
         pub(super) fn $func_name(&mut self, slice: &mut SliceData) -> Result<Instruction> {
@@ -142,6 +154,8 @@ pub struct Loader {
     handlers: Handlers,
     collapse: bool,
     history: HashMap<UInt256, Code>,
+    code_page: isize,
+    extensions: HashMap<isize, Vec<(Vec<u8>, &'static str)>>,
 }
 
 impl Loader {
@@ -1091,6 +1105,64 @@ impl Loader {
 
     create_handler_2!(chksigns, 0xf911, "CHKSIGNS");
 
+    create_handler_2!(ecrecover, 0xf912, "ECRECOVER");
+
+    create_handler_2!(p256_chksignu, 0xf914, "P256_CHKSIGNU");
+
+    create_handler_2!(p256_chksigns, 0xf915, "P256_CHKSIGNS");
+
+    create_handler_2!(secp256k1_chksignu, 0xf916, "SECP256K1_CHKSIGNU");
+
+    create_handler_2!(secp256k1_chksigns, 0xf917, "SECP256K1_CHKSIGNS");
+
+    create_handler_3!(bls_verify, 0xf93000, "BLS_VERIFY");
+
+    create_handler_3!(bls_aggregate, 0xf93001, "BLS_AGGREGATE");
+
+    create_handler_3!(bls_fastaggregateverify, 0xf93002, "BLS_FASTAGGREGATEVERIFY");
+
+    create_handler_3!(bls_aggregateverify, 0xf93003, "BLS_AGGREGATEVERIFY");
+
+    create_handler_3!(bls_g1_add, 0xf93010, "BLS_G1_ADD");
+
+    create_handler_3!(bls_g1_sub, 0xf93011, "BLS_G1_SUB");
+
+    create_handler_3!(bls_g1_neg, 0xf93012, "BLS_G1_NEG");
+
+    create_handler_3!(bls_g1_mul, 0xf93013, "BLS_G1_MUL");
+
+    create_handler_3!(bls_g1_multiexp, 0xf93014, "BLS_G1_MULTIEXP");
+
+    create_handler_3!(bls_g1_zero, 0xf93015, "BLS_G1_ZERO");
+
+    create_handler_3!(bls_map_to_g1, 0xf93016, "BLS_MAP_TO_G1");
+
+    create_handler_3!(bls_g1_ingroup, 0xf93017, "BLS_G1_INGROUP");
+
+    create_handler_3!(bls_g1_iszero, 0xf93018, "BLS_G1_ISZERO");
+
+    create_handler_3!(bls_g2_add, 0xf93020, "BLS_G2_ADD");
+
+    create_handler_3!(bls_g2_sub, 0xf93021, "BLS_G2_SUB");
+
+    create_handler_3!(bls_g2_neg, 0xf93022, "BLS_G2_NEG");
+
+    create_handler_3!(bls_g2_mul, 0xf93023, "BLS_G2_MUL");
+
+    create_handler_3!(bls_g2_multiexp, 0xf93024, "BLS_G2_MULTIEXP");
+
+    create_handler_3!(bls_g2_zero, 0xf93025, "BLS_G2_ZERO");
+
+    create_handler_3!(bls_map_to_g2, 0xf93026, "BLS_MAP_TO_G2");
+
+    create_handler_3!(bls_g2_ingroup, 0xf93027, "BLS_G2_INGROUP");
+
+    create_handler_3!(bls_g2_iszero, 0xf93028, "BLS_G2_ISZERO");
+
+    create_handler_3!(bls_pairing, 0xf93030, "BLS_PAIRING");
+
+    create_handler_3!(bls_pushr, 0xf93031, "BLS_PUSHR");
+
     create_handler_2!(cdatasizeq, 0xf940, "CDATASIZEQ");
 
     create_handler_2!(cdatasize, 0xf941, "CDATASIZE");
@@ -1117,8 +1189,54 @@ impl Loader {
 
     create_handler_2!(debug_on, 0xfe1f, "DEBUGON");
 
+    pub(super) fn hashext(&mut self, slice: &mut SliceData) -> Result<Instruction> {
+        let opc = slice.get_next_int(16)?;
+        let name = match opc {
+            0xf904 => "HASHEXT",
+            0xf905 => "HASHEXTR",
+            0xf906 => "HASHEXTA",
+            0xf907 => "HASHEXTAR",
+            _ => fail!("invalid opcode"),
+        };
+        let hash_id = slice.get_next_byte()?;
+        Ok(Instruction::new(name).with_param(InstructionParameter::Integer(hash_id as isize)))
+    }
+
     pub fn new(collapse: bool) -> Self {
-        Self { handlers: Handlers::new_code_page_0(), collapse, history: HashMap::new() }
+        Self {
+            handlers: Handlers::new_code_page_0(),
+            collapse,
+            history: HashMap::new(),
+            code_page: 0,
+            extensions: HashMap::new(),
+        }
+    }
+
+    /// Decodes `prefix` as parameterless instruction `name` of extension
+    /// code page `code_page`. The page is tracked by following `SETCP`
+    /// instructions in the order they are loaded.
+    pub fn add_extension(
+        &mut self,
+        code_page: isize,
+        prefix: &[u8],
+        name: &'static str,
+    ) -> &mut Self {
+        self.extensions.entry(code_page).or_default().push((prefix.to_vec(), name));
+        self
+    }
+
+    fn load_extension(&mut self, slice: &mut SliceData) -> Result<Option<Instruction>> {
+        let Some(extension) = self.extensions.get(&self.code_page) else {
+            return Ok(None);
+        };
+        let bytes = slice.get_bytestring(0);
+        match extension.iter().find(|(prefix, _)| bytes.starts_with(prefix)) {
+            Some((prefix, name)) => {
+                slice.move_by(prefix.len() * 8)?;
+                Ok(Some(Instruction::new(name)))
+            }
+            None => Ok(None),
+        }
     }
 
     pub fn load(&mut self, slice: &mut SliceData, inline: bool) -> Result<Code> {
@@ -1165,8 +1283,18 @@ impl Loader {
         let mut code = Code::new();
         while slice.remaining_bits() > 0 {
             let mut bytecode = slice.clone();
-            let handler = self.handlers.get_handler(&mut slice.clone())?;
-            let mut insn = handler(self, slice)?;
+            let mut insn = match self.load_extension(slice)? {
+                Some(insn) => insn,
+                None => {
+                    let handler = self.handlers.get_handler(&mut slice.clone())?;
+                    handler(self, slice)?
+                }
+            };
+            if let ("SETCP", Some(InstructionParameter::Integer(code_page))) =
+                (insn.name(), insn.params().first())
+            {
+                self.code_page = *code_page;
+            }
 
             assert_eq!(bytecode.cell(), slice.cell());
             let bits = bytecode.remaining_bits() - slice.remaining_bits();
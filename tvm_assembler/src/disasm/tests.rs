@@ -103,6 +103,12 @@ fn fragments() -> Status {
     check_fragment("e300", "IFREF {\n  ;; missing cell\n}\n")?;
     check_fragment("e30f", "IFREFELSEREF {\n  ;; missing cell\n}{\n  ;; missing cell\n}\n")?;
     check_fragment("f4a420", "DICTPUSHCONST 32 ;; missing dict ref\n")?;
+    check_fragment("f90401", "HASHEXT 1\n")?;
+    check_fragment("f907ff", "HASHEXTAR 255\n")?;
+    check_fragment("f912", "ECRECOVER\n")?;
+    check_fragment("f916", "SECP256K1_CHKSIGNU\n")?;
+    check_fragment("f93000", "BLS_VERIFY\n")?;
+    check_fragment("f93031", "BLS_PUSHR\n")?;
     check_fragment("ff77", "SETCP 119\n")?;
     Ok(())
 }
@@ -13,6 +13,7 @@ use std::collections::HashMap;
 use std::ops::RangeInclusive;
 
 pub use debug::DbgInfo;
+use debug::DbgNode;
 use tvm_types::BuilderData;
 use tvm_types::Cell;
 use tvm_types::SliceData;
@@ -43,6 +44,32 @@ type CompileResult = Result<(), OperationError>;
 type CompileHandler =
     fn(&mut Engine, &[&str], destination: &mut Units, pos: DbgPos) -> CompileResult;
 
+/// Compilation rule of a command
+#[derive(Clone)]
+enum CompileRule {
+    Handler(CompileHandler),
+    /// Parameterless command of an extension code page
+    Extension(Vec<u8>),
+}
+
+impl CompileRule {
+    fn compile(
+        &self,
+        engine: &mut Engine,
+        par: &[&str],
+        destination: &mut Units,
+        pos: DbgPos,
+    ) -> CompileResult {
+        match self {
+            CompileRule::Handler(handler) => handler(engine, par, destination, pos),
+            CompileRule::Extension(code) => {
+                par.assert_empty()?;
+                destination.write_command(code, DbgNode::from(pos))
+            }
+        }
+    }
+}
+
 // CompileError::Operation handlers
 // ***********************************************************
 trait EnsureParametersCountInRange {
@@ -80,7 +107,7 @@ struct CommandContext {
     char_no_cmd: usize,
     line_no_par: usize,
     char_no_par: usize,
-    rule_option: Option<CompileHandler>,
+    rule_option: Option<CompileRule>,
 }
 
 impl CommandContext {
@@ -88,7 +115,7 @@ impl CommandContext {
         operation: String,
         char_no_cmd: usize,
         line_no_cmd: usize,
-        rule_option: Option<CompileHandler>,
+        rule_option: Option<CompileRule>,
     ) -> Self {
         Self { operation, line_no_cmd, char_no_cmd, line_no_par: 0, char_no_par: 0, rule_option }
     }
@@ -112,7 +139,7 @@ impl CommandContext {
         par: &mut Vec<Token>,
         engine: &mut Engine,
     ) -> Result<(), CompileError> {
-        let rule = match self.rule_option.as_ref() {
+        let rule = match self.rule_option.clone() {
             Some(rule) => rule,
             None => return Ok(()),
         };
@@ -124,7 +151,7 @@ impl CommandContext {
                 filename: engine.source_name.clone(),
                 line: self.line_no_cmd,
             });
-            match rule(engine, &par, destination, pos) {
+            match rule.compile(engine, &par, destination, pos) {
                 Ok(_) => break,
                 Err(OperationError::TooManyParameters) if n != 0 => {
                     n -= 1;
@@ -170,6 +197,7 @@ pub struct Engine {
     char_no: usize,
     source_name: String,
     handlers: HashMap<&'static str, CompileHandler>,
+    extension_commands: HashMap<String, Vec<u8>>,
     named_units: HashMap<String, Unit>,
     dbgpos: Option<DbgPos>,
 }
@@ -195,6 +223,7 @@ impl Engine {
             char_no: 1,
             source_name: source_name.to_string(),
             handlers: HashMap::new(),
+            extension_commands: HashMap::new(),
             named_units: HashMap::new(),
             dbgpos: None,
         };
@@ -203,6 +232,25 @@ impl Engine {
         ret
     }
 
+    /// Registers a parameterless command of an extension code page compiled
+    /// to `code`. The command is accepted anywhere in the source, selecting
+    /// the code page with `SETCP` is up to the program.
+    pub fn add_extension_command(&mut self, name: &str, code: &[u8]) -> Result<(), OperationError> {
+        let name = name.to_ascii_uppercase();
+        if code.is_empty() {
+            return Err(OperationError::Internal(format!("command {} has empty code", name)));
+        }
+        if self.handlers.contains_key(name.as_str()) || self.extension_commands.contains_key(&name)
+        {
+            return Err(OperationError::Internal(format!(
+                "command {} is already registered",
+                name
+            )));
+        }
+        self.extension_commands.insert(name, code.to_vec());
+        Ok(())
+    }
+
     fn is_whitespace(x: char) -> bool {
         matches!(x, ' ' | '\n' | '\r' | '\t')
     }
@@ -359,7 +407,12 @@ impl Engine {
                 // otherwise `.inline setCode` won't work since setCode gets matched as an insn
                 None
             } else {
-                self.handlers.get(token.as_str())
+                self.handlers
+                    .get(token.as_str())
+                    .map(|handler| CompileRule::Handler(*handler))
+                    .or_else(|| {
+                        self.extension_commands.get(&token).cloned().map(CompileRule::Extension)
+                    })
             };
             was_dot_inline = token == ".INLINE";
             match rule {
@@ -373,7 +426,7 @@ impl Engine {
                             .with_filename(self.source_name.clone()));
                     }
                 }
-                Some(&new_rule) => {
+                Some(new_rule) => {
                     if !toplevel && token == ".FRAGMENT" {
                         return Err(CompileError::syntax(
                             y,
@@ -473,6 +526,7 @@ mod gosh_zk_opcode_tests {
             ("POSEIDON", [0xC7, 0x32]),
             ("ZKHALO2VERIFY", [0xC7, 0x49]),
             ("ZKHALO2VERIFYWITHVK", [0xC7, 0x4A]),
+            ("VERGRTH16VK", [0xC7, 0x4B]),
             ("CHKHISTPROOF", [0xC7, 0x50]),
         ] {
             let compiled =
@@ -488,3 +542,32 @@ mod gosh_zk_opcode_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod extension_command_tests {
+    use super::*;
+    use crate::disasm::loader::Loader;
+
+    #[test]
+    fn extension_command_round_trip() {
+        let mut engine = Engine::new("");
+        engine.add_extension_command("hostop", &[0xF9, 0xFF]).unwrap();
+        assert!(engine.add_extension_command("HOSTOP", &[0xF9, 0xFE]).is_err());
+        assert!(engine.add_extension_command("ADD", &[0xF9, 0xFE]).is_err());
+        assert!(engine.add_extension_command("EMPTY", &[]).is_err());
+
+        let (code, _) = engine.build(None, "SETCP 1 HOSTOP SETCP 0").unwrap().finalize();
+        assert_eq!(code.get_bytestring(0), vec![0xFF, 0x01, 0xF9, 0xFF, 0xFF, 0x00]);
+        assert!(engine.build(None, "HOSTOP 1").is_err());
+
+        let mut loader = Loader::new(false);
+        loader.add_extension(1, &[0xF9, 0xFF], "HOSTOP");
+        let text = loader.load(&mut code.clone(), false).unwrap().print("", false, 0);
+        assert_eq!(text, "SETCP 1\nHOSTOP\nSETCP 0\n");
+
+        // without SETCP the prefix is not an extension instruction
+        let mut slice = SliceData::new(vec![0xF9, 0xFF, 0x80]);
+        let text = loader.load(&mut slice, false).unwrap().print("", false, 0);
+        assert_ne!(text, "HOSTOP\n");
+    }
+}
@@ -116,6 +116,10 @@ pub(super) fn parse_const_i8(par: &str) -> Result<u8, ParameterError> {
     parse_range(-128i16..=127)(par).map(|e| e as u8)
 }
 
+pub(super) fn parse_const_u8(par: &str) -> Result<u8, ParameterError> {
+    parse_range(0u8..=255)(par)
+}
+
 pub(super) fn parse_const_u8_plus_one(par: &str) -> Result<u8, ParameterError> {
     parse_range(1u16..=256)(par).map(|e| (e - 1) as u8)
 }
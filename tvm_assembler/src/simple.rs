@@ -63,6 +63,30 @@ impl Engine {
                 c2 = parse_const_u4_plus_one => 0x55, (c1 << 4) | c2
         BLKSWX                               => 0x63
         BLOCKLT                              => 0xF8, 0x24
+        BLS_AGGREGATE                        => 0xF9, 0x30, 0x01
+        BLS_AGGREGATEVERIFY                  => 0xF9, 0x30, 0x03
+        BLS_FASTAGGREGATEVERIFY              => 0xF9, 0x30, 0x02
+        BLS_G1_ADD                           => 0xF9, 0x30, 0x10
+        BLS_G1_INGROUP                       => 0xF9, 0x30, 0x17
+        BLS_G1_ISZERO                        => 0xF9, 0x30, 0x18
+        BLS_G1_MUL                           => 0xF9, 0x30, 0x13
+        BLS_G1_MULTIEXP                      => 0xF9, 0x30, 0x14
+        BLS_G1_NEG                           => 0xF9, 0x30, 0x12
+        BLS_G1_SUB                           => 0xF9, 0x30, 0x11
+        BLS_G1_ZERO                          => 0xF9, 0x30, 0x15
+        BLS_G2_ADD                           => 0xF9, 0x30, 0x20
+        BLS_G2_INGROUP                       => 0xF9, 0x30, 0x27
+        BLS_G2_ISZERO                        => 0xF9, 0x30, 0x28
+        BLS_G2_MUL                           => 0xF9, 0x30, 0x23
+        BLS_G2_MULTIEXP                      => 0xF9, 0x30, 0x24
+        BLS_G2_NEG                           => 0xF9, 0x30, 0x22
+        BLS_G2_SUB                           => 0xF9, 0x30, 0x21
+        BLS_G2_ZERO                          => 0xF9, 0x30, 0x25
+        BLS_MAP_TO_G1                        => 0xF9, 0x30, 0x16
+        BLS_MAP_TO_G2                        => 0xF9, 0x30, 0x26
+        BLS_PAIRING                          => 0xF9, 0x30, 0x30
+        BLS_PUSHR                            => 0xF9, 0x30, 0x31
+        BLS_VERIFY                           => 0xF9, 0x30, 0x00
         BOOLAND                              => 0xED, 0xF0
         BOOLEVAL                             => 0xED, 0xF9
         BOOLOR                               => 0xED, 0xF1
@@ -250,6 +274,7 @@ impl Engine {
         DUMPSTKTOP z = parse_const_u4_1_14   => 0xFE, z
         DUP                                  => 0x20
         DUP2                                 => 0x5C
+        ECRECOVER                            => 0xF9, 0x12
         ENDC                                 => 0xC9
         ENDCST                               => 0xCD
         ENDXC                                => 0xCF, 0x23
@@ -276,6 +301,30 @@ impl Engine {
         GREATER                              => 0xBC
         GTINT z = parse_const_i8             => 0xC2, z
         HASHCU                               => 0xF9, 0x00
+        HASHEXT z = parse_const_u8           => 0xF9, 0x04, z
+        HASHEXT_BLAKE2B                      => 0xF9, 0x04, 0x02
+        HASHEXT_KECCAK256                    => 0xF9, 0x04, 0x03
+        HASHEXT_KECCAK512                    => 0xF9, 0x04, 0x04
+        HASHEXT_SHA256                       => 0xF9, 0x04, 0x00
+        HASHEXT_SHA512                       => 0xF9, 0x04, 0x01
+        HASHEXTA z = parse_const_u8          => 0xF9, 0x06, z
+        HASHEXTA_BLAKE2B                     => 0xF9, 0x06, 0x02
+        HASHEXTA_KECCAK256                   => 0xF9, 0x06, 0x03
+        HASHEXTA_KECCAK512                   => 0xF9, 0x06, 0x04
+        HASHEXTA_SHA256                      => 0xF9, 0x06, 0x00
+        HASHEXTA_SHA512                      => 0xF9, 0x06, 0x01
+        HASHEXTAR z = parse_const_u8         => 0xF9, 0x07, z
+        HASHEXTAR_BLAKE2B                    => 0xF9, 0x07, 0x02
+        HASHEXTAR_KECCAK256                  => 0xF9, 0x07, 0x03
+        HASHEXTAR_KECCAK512                  => 0xF9, 0x07, 0x04
+        HASHEXTAR_SHA256                     => 0xF9, 0x07, 0x00
+        HASHEXTAR_SHA512                     => 0xF9, 0x07, 0x01
+        HASHEXTR z = parse_const_u8          => 0xF9, 0x05, z
+        HASHEXTR_BLAKE2B                     => 0xF9, 0x05, 0x02
+        HASHEXTR_KECCAK256                   => 0xF9, 0x05, 0x03
+        HASHEXTR_KECCAK512                   => 0xF9, 0x05, 0x04
+        HASHEXTR_SHA256                      => 0xF9, 0x05, 0x00
+        HASHEXTR_SHA512                      => 0xF9, 0x05, 0x01
         HASHSU                               => 0xF9, 0x01
         IF                                   => 0xDE
         IFBITJMP n = parse_const_u5          => 0xE3, 0x80 | n
@@ -407,6 +456,8 @@ impl Engine {
         OVER2                                => 0x5D
         ONLYTOPX                             => 0x6A
         ONLYX                                => 0x6B
+        P256_CHKSIGNS                        => 0xF9, 0x15
+        P256_CHKSIGNU                        => 0xF9, 0x14
         PAIR                                 => 0x6F, 0x02
         PARSEMSGADDR                         => 0xFA, 0x42
         PARSEMSGADDRQ                        => 0xFA, 0x43
@@ -632,6 +683,8 @@ impl Engine {
         SDSKIPLAST                           => 0xD7, 0x23
         SDSUBSTR                             => 0xD7, 0x24
         SECOND                               => 0x6F, 0x11
+        SECP256K1_CHKSIGNS                   => 0xF9, 0x17
+        SECP256K1_CHKSIGNU                   => 0xF9, 0x16
         SEMPTY                               => 0xC7, 0x00
         SENDRAWMSG                           => 0xFB, 0x00
         SEQNO                                => 0xF8, 0x2D
@@ -862,6 +915,7 @@ impl Engine {
         CALCMINERREWARD                      => 0xC7, 0x48
         ZKHALO2VERIFY                        => 0xC7, 0x49
         ZKHALO2VERIFYWITHVK                  => 0xC7, 0x4A
+        VERGRTH16VK                          => 0xC7, 0x4B
         CHKHISTPROOF                         => 0xC7, 0x50
     }
 
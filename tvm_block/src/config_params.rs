@@ -390,6 +390,11 @@ pub enum GlobalCapabilities {
     CapFastFinality = 0x0001_0000_0000,
     CapTvmV19 = 0x0002_0000_0000, // TVM v1.9.x improvemements
     CapSmft = 0x0004_0000_0000,
+    CapEcdsa = 0x0008_0000_0000, // secp256k1 and P-256 signature opcodes
+    CapBls = 0x0010_0000_0000,   // BLS12-381 signature and pairing opcodes
+    CapHashExt = 0x0020_0000_0000, // HASHEXT hashing opcodes
+    CapWasmDeterminism = 0x0040_0000_0000, // WASM random seed from RANDSEED, clock in nanoseconds
+    CapGroth16WithVk = 0x0080_0000_0000, // VERGRTH16VK with caller-supplied verifying keys
 }
 
 impl ConfigParams {
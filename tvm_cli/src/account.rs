@@ -12,6 +12,8 @@ use std::collections::BTreeMap;
 use std::str::FromStr;
 use std::sync::Arc;
 
+use chrono::Local;
+use chrono::TimeZone;
 use serde_json::Value;
 use serde_json::json;
 use tvm_block::Account;
@@ -25,7 +27,9 @@ use tvm_client::net::ParamsOfSubscribeCollection;
 use tvm_client::net::ResultOfSubscription;
 use tvm_client::net::query_collection;
 use tvm_client::utils::ParamsOfCalcStorageFee;
+use tvm_client::utils::ParamsOfForecastStorageFee;
 use tvm_client::utils::calc_storage_fee;
+use tvm_client::utils::forecast_storage_fee;
 use tvm_types::base64_decode;
 
 use crate::config::Config;
@@ -36,6 +40,7 @@ use crate::helpers::create_client_verbose;
 use crate::helpers::json_account;
 use crate::helpers::print_account;
 use crate::helpers::query_account_field;
+use crate::run::prepare_execution_options;
 
 const DEFAULT_PATH: &str = ".";
 
@@ -371,6 +376,60 @@ pub async fn calc_storage(config: &Config, addr: &str, period: u32) -> Result<()
     Ok(())
 }
 
+pub async fn forecast_storage(
+    config: &Config,
+    addr: &str,
+    target_time: Option<u32>,
+    bc_config: Option<&str>,
+) -> Result<(), String> {
+    let client = create_client_verbose(config)?;
+
+    if !config.is_json {
+        println!("Processing...");
+    }
+
+    let boc = query_account_field(client.clone(), addr, "boc").await?;
+    let blockchain_config =
+        prepare_execution_options(bc_config)?.and_then(|options| options.blockchain_config);
+
+    let res = forecast_storage_fee(
+        client.clone(),
+        ParamsOfForecastStorageFee {
+            account: boc,
+            target_time,
+            blockchain_config,
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(|e| format!("failed to forecast storage fee: {}", e))?;
+
+    if !config.is_json {
+        let format_time = |time: Option<u32>| match time {
+            Some(time) => match Local.timestamp_opt(time as i64, 0).single() {
+                Some(date) => format!("{} ({})", date.format("%Y-%m-%d %H:%M:%S"), time),
+                None => time.to_string(),
+            },
+            None => "never".to_owned(),
+        };
+        println!("Accrued storage fee: {} nanovmshells", res.accrued_fee);
+        println!("Frozen at:           {}", format_time(res.frozen_at));
+        println!("Deleted at:          {}", format_time(res.deleted_at));
+        if let Some(top_up) = &res.top_up {
+            println!(
+                "Top up to survive until {}: {} nanovmshells",
+                format_time(target_time),
+                top_up
+            );
+        }
+    } else {
+        let res = serde_json::to_string_pretty(&res)
+            .map_err(|e| format!("failed to serialize the result: {}", e))?;
+        println!("{}", res);
+    }
+    Ok(())
+}
+
 pub async fn dump_accounts(
     config: &Config,
     addresses: Vec<String>,
@@ -267,6 +267,7 @@ pub async fn process_message(
                 message_encode_params: msg.clone(),
                 send_events: true,
                 dapp_id,
+                dry_run: false,
             },
             |_| async move {},
         )
@@ -27,9 +27,13 @@ use crate::helpers::WORD_COUNT;
 use crate::helpers::check_dir;
 use crate::helpers::create_client_local;
 use crate::helpers::read_keys;
+use crate::keystore::KEYSTORE_KEYS_PREFIX;
+use crate::keystore::load_keypair_from_keystore;
 
 pub fn load_keypair(keys: &str) -> Result<KeyPair, String> {
-    if keys.find(' ').is_none() {
+    if let Some(alias) = keys.strip_prefix(KEYSTORE_KEYS_PREFIX) {
+        load_keypair_from_keystore(alias)
+    } else if keys.find(' ').is_none() {
         let keys = read_keys(keys)?;
         Ok(keys)
     } else {
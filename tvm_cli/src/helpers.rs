@@ -76,6 +76,7 @@ pub const WORD_COUNT: u8 = 12;
 
 const DEPRECATED_CONFIG_BASE_NAME: &str = "tonos-cli.conf.json";
 const CONFIG_BASE_NAME: &str = "tvm-cli.conf.json";
+const KEYSTORE_BASE_NAME: &str = "tvm-cli.keystore.json";
 const DEPRECATED_GLOBAL_CONFIG_PATH: &str = ".tonos-cli.global.conf.json";
 const GLOBAL_CONFIG_PATH: &str = ".tvm-cli.global.conf.json";
 
@@ -97,6 +98,13 @@ pub fn default_config_name() -> String {
     }
 }
 
+pub fn default_keystore_path() -> String {
+    match env::current_dir() {
+        Ok(dir) => dir.join(PathBuf::from(KEYSTORE_BASE_NAME)).to_string_lossy().into_owned(),
+        Err(_) => KEYSTORE_BASE_NAME.to_string(),
+    }
+}
+
 pub fn global_config_path() -> String {
     match env::current_exe() {
         Ok(exe_path) => {
@@ -0,0 +1,283 @@
+// Copyright 2018-2023 EverX.
+//
+// Licensed under the SOFTWARE EVALUATION License (the "License"); you may not
+// use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific TON DEV software governing permissions and
+// limitations under the License.
+
+use std::env;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use clap::Arg;
+use clap::ArgMatches;
+use clap::Command;
+use serde_json::json;
+use tvm_client::crypto::KeyPair;
+use tvm_client::crypto::Keystore;
+use tvm_client::crypto::ParamsOfKeystoreAddKey;
+use tvm_client::crypto::ParamsOfKeystoreGetKey;
+use tvm_client::crypto::ParamsOfKeystoreListKeys;
+use tvm_client::crypto::ParamsOfKeystoreRemoveKey;
+use tvm_client::crypto::keystore_add_key;
+use tvm_client::crypto::keystore_get_key;
+use tvm_client::crypto::keystore_list_keys;
+use tvm_client::crypto::keystore_remove_key;
+use tvm_types::base64_encode;
+
+use crate::config::Config;
+use crate::crypto::load_keypair;
+use crate::helpers::create_client_local;
+use crate::helpers::default_keystore_path;
+use crate::test::create_test_sign_command;
+use crate::test::print_signature;
+use crate::test::read_data_to_sign;
+
+/// Prefix of the `--keys` value that addresses a key pair in the keystore by
+/// alias, e.g. `--keys keystore:main`.
+pub const KEYSTORE_KEYS_PREFIX: &str = "keystore:";
+
+const KEYSTORE_ENV: &str = "TVM_KEYSTORE";
+const KEYSTORE_PASSWORD_ENV: &str = "TVM_KEYSTORE_PASSWORD";
+
+static KEYSTORE_PATH: OnceLock<String> = OnceLock::new();
+
+/// Global `--keystore` argument. It is shared by the keystore commands and
+/// by every command that accepts `--keys keystore:<alias>`.
+pub fn create_keystore_arg<'b>() -> Arg<'b> {
+    Arg::new("KEYSTORE")
+        .long("--keystore")
+        .takes_value(true)
+        .global(true)
+        .help("Path to the keystore file. Can be set with TVM_KEYSTORE environment variable. Default is ./tvm-cli.keystore.json.")
+}
+
+/// Remembers the keystore path passed with `--keystore`.
+pub(crate) fn set_keystore_path(path: Option<&str>) {
+    if let Some(path) = path {
+        let _ = KEYSTORE_PATH.set(path.to_owned());
+    }
+}
+
+fn keystore_path() -> String {
+    KEYSTORE_PATH
+        .get()
+        .cloned()
+        .or_else(|| env::var(KEYSTORE_ENV).ok())
+        .unwrap_or_else(default_keystore_path)
+}
+
+pub fn create_keystore_command<'b>() -> Command<'b> {
+    let alias_arg =
+        Arg::new("ALIAS").takes_value(true).required(true).help("Alias of the key pair.");
+
+    Command::new("keystore")
+        .about("Keystore commands. Key pairs are stored encrypted with a password and are addressed by alias. Use `--keys keystore:<alias>` to sign messages with a stored key pair. The password is read from TVM_KEYSTORE_PASSWORD environment variable or prompted.")
+        .subcommand(Command::new("create")
+            .about("Creates an empty keystore file."))
+        .subcommand(Command::new("import")
+            .about("Encrypts a key pair and adds it to the keystore.")
+            .arg(alias_arg.clone())
+            .arg(Arg::new("KEYS")
+                .long("--keys")
+                .takes_value(true)
+                .required(true)
+                .help("Seed phrase or path to the file with keypair."))
+            .arg(Arg::new("FORCE")
+                .long("--force")
+                .help("Replace the key pair if the alias is already used.")))
+        .subcommand(Command::new("export")
+            .about("Decrypts a key pair from the keystore and saves it to the file or prints it.")
+            .arg(alias_arg.clone())
+            .arg(Arg::new("KEY_FILE")
+                .takes_value(true)
+                .long("--output")
+                .short('o')
+                .help("Path to the file where to store the keypair.")))
+        .subcommand(Command::new("list")
+            .about("Prints aliases and public keys of the stored key pairs."))
+        .subcommand(Command::new("remove")
+            .about("Removes a key pair from the keystore.")
+            .arg(alias_arg.clone()))
+        .subcommand(create_test_sign_command()
+            .about("Generates the ED25519 signature for bytestring with a key pair from the keystore.")
+            .arg(alias_arg))
+}
+
+pub fn keystore_command(m: &ArgMatches, config: &Config) -> Result<(), String> {
+    let path = keystore_path();
+    if m.subcommand_matches("create").is_some() {
+        return create_keystore(&path, config);
+    }
+    if let Some(m) = m.subcommand_matches("import") {
+        return import_key(m, &path, config);
+    }
+    if let Some(m) = m.subcommand_matches("export") {
+        return export_key(m, &path, config);
+    }
+    if m.subcommand_matches("list").is_some() {
+        return list_keys(&path, config);
+    }
+    if let Some(m) = m.subcommand_matches("remove") {
+        return remove_key(m, &path, config);
+    }
+    if let Some(m) = m.subcommand_matches("sign") {
+        let data = read_data_to_sign(m)?;
+        let pair = get_key(&path, m.value_of("ALIAS").unwrap())?;
+        return print_signature(&data, &pair, config);
+    }
+    Err("unknown keystore command".to_owned())
+}
+
+/// Loads a key pair addressed as `keystore:<alias>` from the keystore set with
+/// `--keystore`, TVM_KEYSTORE or the default one.
+pub fn load_keypair_from_keystore(alias: &str) -> Result<KeyPair, String> {
+    get_key(&keystore_path(), alias)
+}
+
+fn read_password(prompt: &str) -> Result<String, String> {
+    if let Ok(password) = env::var(KEYSTORE_PASSWORD_ENV) {
+        return Ok(base64_encode(password));
+    }
+    let password = rpassword::prompt_password(prompt)
+        .map_err(|e| format!("failed to read password: {}", e))?;
+    Ok(base64_encode(password))
+}
+
+fn read_new_password(alias: &str) -> Result<String, String> {
+    if env::var(KEYSTORE_PASSWORD_ENV).is_ok() {
+        return read_password("");
+    }
+    let password = read_password(&format!("Enter new password for {}: ", alias))?;
+    let confirmation = read_password(&format!("Confirm password for {}: ", alias))?;
+    if password != confirmation {
+        return Err("passwords do not match".to_owned());
+    }
+    Ok(password)
+}
+
+fn read_keystore(path: &str) -> Result<Keystore, String> {
+    let keystore = std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read the keystore file {}: {}", path, e))?;
+    serde_json::from_str(&keystore).map_err(|e| format!("failed to load the keystore: {}", e))
+}
+
+fn write_keystore(path: &str, keystore: &Keystore) -> Result<(), String> {
+    let keystore = serde_json::to_string_pretty(keystore)
+        .map_err(|e| format!("failed to serialize the keystore: {}", e))?;
+    std::fs::write(path, keystore)
+        .map_err(|e| format!("failed to write the keystore file {}: {}", path, e))
+}
+
+fn get_key(path: &str, alias: &str) -> Result<KeyPair, String> {
+    let keystore = read_keystore(path)?;
+    let password = read_password(&format!("Enter password for {}: ", alias))?;
+    keystore_get_key(
+        create_client_local()?,
+        ParamsOfKeystoreGetKey { keystore, alias: alias.to_owned(), password },
+    )
+    .map_err(|e| format!("failed to decrypt the keypair: {}", e))
+}
+
+fn create_keystore(path: &str, config: &Config) -> Result<(), String> {
+    if Path::new(path).exists() {
+        return Err(format!("keystore file {} already exists", path));
+    }
+    write_keystore(path, &Keystore::default())?;
+    if !config.is_json {
+        println!("Keystore successfully created at {}.", path);
+        println!("Succeeded.");
+    } else {
+        println!("{:#}", json!({ "keystore": path }));
+    }
+    Ok(())
+}
+
+fn import_key(m: &ArgMatches, path: &str, config: &Config) -> Result<(), String> {
+    let keystore = read_keystore(path)?;
+    let alias = m.value_of("ALIAS").unwrap();
+    let keys = load_keypair(m.value_of("KEYS").unwrap())?;
+    let public = keys.public.clone();
+    let password = read_new_password(alias)?;
+    let result = keystore_add_key(
+        create_client_local()?,
+        ParamsOfKeystoreAddKey {
+            keystore: Some(keystore),
+            alias: alias.to_owned(),
+            keys,
+            password,
+            kdf_params: None,
+            overwrite: Some(m.is_present("FORCE")),
+        },
+    )
+    .map_err(|e| format!("failed to add the keypair: {}", e))?;
+    write_keystore(path, &result.keystore)?;
+    if !config.is_json {
+        println!("Keypair {} successfully added to {}.", alias, path);
+        println!("Public key: {}", public);
+        println!("Succeeded.");
+    } else {
+        println!("{:#}", json!({ "alias": alias, "public": public }));
+    }
+    Ok(())
+}
+
+fn export_key(m: &ArgMatches, path: &str, config: &Config) -> Result<(), String> {
+    let keys = get_key(path, m.value_of("ALIAS").unwrap())?;
+    let keys_json = serde_json::to_string_pretty(&keys)
+        .map_err(|e| format!("failed to serialize the keypair: {}", e))?;
+    if let Some(keys_path) = m.value_of("KEY_FILE") {
+        std::fs::write(keys_path, &keys_json)
+            .map_err(|e| format!("failed to create file with keys: {}", e))?;
+        if !config.is_json {
+            println!("Keypair successfully saved to {}.", keys_path);
+            println!("Succeeded.");
+        }
+    } else {
+        if !config.is_json {
+            print!("Keypair: ");
+        }
+        println!("{}", keys_json);
+    }
+    Ok(())
+}
+
+fn list_keys(path: &str, config: &Config) -> Result<(), String> {
+    let keystore = read_keystore(path)?;
+    let result = keystore_list_keys(create_client_local()?, ParamsOfKeystoreListKeys { keystore })
+        .map_err(|e| format!("failed to list the keystore: {}", e))?;
+    if !config.is_json {
+        for key in &result.keys {
+            println!("{}: {}", key.alias, key.public);
+        }
+    } else {
+        println!(
+            "{:#}",
+            serde_json::to_value(&result.keys)
+                .map_err(|e| format!("failed to serialize the result: {}", e))?
+        );
+    }
+    Ok(())
+}
+
+fn remove_key(m: &ArgMatches, path: &str, config: &Config) -> Result<(), String> {
+    let keystore = read_keystore(path)?;
+    let alias = m.value_of("ALIAS").unwrap();
+    let result = keystore_remove_key(
+        create_client_local()?,
+        ParamsOfKeystoreRemoveKey { keystore, alias: alias.to_owned() },
+    )
+    .map_err(|e| format!("failed to remove the keypair: {}", e))?;
+    write_keystore(path, &result.keystore)?;
+    if !config.is_json {
+        println!("Keypair {} successfully removed from {}.", alias, path);
+        println!("Succeeded.");
+    } else {
+        println!("{{}}");
+    }
+    Ok(())
+}
@@ -32,6 +32,7 @@ mod depool_abi;
 mod genaddr;
 mod getconfig;
 mod helpers;
+mod keystore;
 mod message;
 mod multisig;
 mod replay;
@@ -45,6 +46,7 @@ use std::env;
 use std::process::exit;
 
 use account::calc_storage;
+use account::forecast_storage;
 use account::get_account;
 use account::wait_for_change;
 use call::call_contract;
@@ -75,6 +77,9 @@ use helpers::contract_data_from_matches_or_config_alias;
 use helpers::create_client_local;
 use helpers::load_abi;
 use helpers::query_raw;
+use keystore::create_keystore_arg;
+use keystore::create_keystore_command;
+use keystore::keystore_command;
 use multisig::create_multisig_command;
 use multisig::multisig_command;
 use replay::fetch_block_command;
@@ -779,6 +784,27 @@ async fn main_internal() -> Result<(), String> {
                         .help("Time period in seconds (default value is 1 year)."),
                 ),
         )
+        .subcommand(
+            Command::new("forecast")
+                .allow_hyphen_values(true)
+                .about("Predicts when the account will be frozen and deleted for unpaid storage fees and how much it must be topped up to survive until the target time.")
+                .version(version_string)
+                .author(author)
+                .arg(address_arg.clone())
+                .arg(
+                    Arg::new("TARGET")
+                        .long("--target")
+                        .short('t')
+                        .takes_value(true)
+                        .help("Unix time the account must survive until."),
+                )
+                .arg(
+                    Arg::new("BCCONFIG")
+                        .long("--bc_config")
+                        .takes_value(true)
+                        .help("Path to the file with blockchain config. Network config is used by default."),
+                ),
+        )
         .subcommand(deploy_cmd.clone().about(
             "Executes deploy locally, calculates fees and prints table of fees in nanovmshells.",
         ))
@@ -966,6 +992,7 @@ async fn main_internal() -> Result<(), String> {
                 .takes_value(true)
                 .global(true),
         )
+        .arg(create_keystore_arg())
         .subcommand(version_cmd)
         .subcommand(genphrase_cmd)
         .subcommand(genpubkey_cmd)
@@ -987,6 +1014,7 @@ async fn main_internal() -> Result<(), String> {
         .subcommand(fee_cmd)
         .subcommand(proposal_cmd)
         .subcommand(create_multisig_command())
+        .subcommand(create_keystore_command())
         .subcommand(create_depool_command())
         .subcommand(create_decode_command())
         .subcommand(create_debot_command())
@@ -1036,6 +1064,7 @@ async fn main_internal() -> Result<(), String> {
         helpers::init_log_file(path)?;
         helpers::log_startup_info();
     }
+    keystore::set_keystore_path(find_arg_value(&matches, "KEYSTORE"));
 
     command_parser(&matches, is_json).await.map_err(|e| {
         if e.is_empty() {
@@ -1121,6 +1150,9 @@ async fn command_parser(matches: &ArgMatches, is_json: bool) -> Result<(), Strin
         if let Some(m) = m.subcommand_matches("storage") {
             return storage_command(m, config).await;
         }
+        if let Some(m) = m.subcommand_matches("forecast") {
+            return forecast_command(m, config).await;
+        }
         if let Some(m) = m.subcommand_matches("deploy") {
             return deploy_command(m, &mut full_config, DeployType::Fee).await;
         }
@@ -1148,6 +1180,9 @@ async fn command_parser(matches: &ArgMatches, is_json: bool) -> Result<(), Strin
     if let Some(m) = matches.subcommand_matches("multisig") {
         return multisig_command(m, config).await;
     }
+    if let Some(m) = matches.subcommand_matches("keystore") {
+        return keystore_command(m, config);
+    }
     if let Some(m) = matches.subcommand_matches("depool") {
         return depool_command(m, config).await;
     }
@@ -1704,6 +1739,22 @@ async fn storage_command(matches: &ArgMatches, config: &Config) -> Result<(), St
     calc_storage(config, address.as_str(), period).await
 }
 
+async fn forecast_command(matches: &ArgMatches, config: &Config) -> Result<(), String> {
+    let address = matches.value_of("ADDRESS");
+    let target = matches.value_of("TARGET");
+    let bc_config = matches.value_of("BCCONFIG");
+    if !config.is_json {
+        print_args!(address, target, bc_config);
+    }
+    let address = SdkAddress::validate(address.unwrap())?;
+    let target = target
+        .map(|val| {
+            u32::from_str_radix(val, 10).map_err(|e| format!("failed to parse target time: {}", e))
+        })
+        .transpose()?;
+    forecast_storage(config, address.as_str(), target, bc_config).await
+}
+
 async fn proposal_create_command(matches: &ArgMatches, config: &Config) -> Result<(), String> {
     let address = matches.value_of("ADDRESS");
     let dest = matches.value_of("DEST");
@@ -15,6 +15,8 @@ use std::str::FromStr;
 use clap::Arg;
 use clap::ArgMatches;
 use clap::Command;
+use serde::Deserialize;
+use serde::Serialize;
 use serde_json::json;
 use tvm_client::abi::Abi;
 use tvm_client::abi::AbiContract;
@@ -22,6 +24,14 @@ use tvm_client::abi::AbiParam;
 use tvm_client::abi::CallSet;
 use tvm_client::abi::ParamsOfEncodeMessageBody;
 use tvm_client::abi::encode_message_body;
+use tvm_client::crypto::CustodianSignature;
+use tvm_client::crypto::ParamsOfMultisigSign;
+use tvm_client::crypto::ParamsOfMultisigVerify;
+use tvm_client::crypto::get_signing_box;
+use tvm_client::crypto::multisig_sign;
+use tvm_client::crypto::multisig_verify;
+use tvm_client::crypto::remove_signing_box;
+use tvm_types::base64_encode;
 
 use crate::call;
 use crate::config::Config;
@@ -31,6 +41,7 @@ use crate::deploy::prepare_deploy_message_params;
 use crate::helpers::SdkAddress;
 use crate::helpers::create_client_local;
 use crate::helpers::create_client_verbose;
+use crate::helpers::decode_data;
 use crate::helpers::load_file_with_url;
 use crate::helpers::now_ms;
 
@@ -402,6 +413,13 @@ pub fn create_multisig_command<'b>() -> Command<'b> {
         .takes_value(true)
         .help("Path to the file with a keypair.");
 
+    let signatures_arg = Arg::new("SIGNATURES")
+        .long("--signatures")
+        .short('s')
+        .takes_value(true)
+        .required(true)
+        .help("Path to the file with collected signatures.");
+
     Command::new("multisig")
         .about("Multisignature wallet commands.")
         .allow_negative_numbers(true)
@@ -454,6 +472,35 @@ pub fn create_multisig_command<'b>() -> Command<'b> {
                 .short('c')
                 .help("Number of confirmations required for executing transaction. Default value is 1."))
             .arg(v2_arg))
+        .subcommand(Command::new("sign")
+            .about("Signs data with a custodian key and adds the signature to the file with collected signatures. The file is created if it does not exist.")
+            .arg(Arg::new("DATA")
+                .long("--data")
+                .short('d')
+                .takes_value(true)
+                .required(true)
+                .help("Data to sign, base64 or hex encoded."))
+            .arg(Arg::new("KEYS")
+                .long("--keys")
+                .short('k')
+                .takes_value(true)
+                .required(true)
+                .help("Seed phrase or path to the file with custodian keypair."))
+            .arg(signatures_arg.clone()))
+        .subcommand(Command::new("verify")
+            .about("Verifies signatures collected from wallet custodians.")
+            .arg(signatures_arg)
+            .arg(Arg::new("OWNERS")
+                .long("--owners")
+                .short('o')
+                .takes_value(true)
+                .required(true)
+                .help("Array of wallet custodians public keys."))
+            .arg(Arg::new("CONFIRMS")
+                .long("--confirms")
+                .short('c')
+                .takes_value(true)
+                .help("Number of confirmations required. Default value is 1.")))
 }
 
 pub async fn multisig_command(m: &ArgMatches, config: &Config) -> Result<(), String> {
@@ -463,6 +510,12 @@ pub async fn multisig_command(m: &ArgMatches, config: &Config) -> Result<(), Str
     if let Some(m) = m.subcommand_matches("deploy") {
         return multisig_deploy_command(m, config).await;
     }
+    if let Some(m) = m.subcommand_matches("sign") {
+        return multisig_sign_command(m, config).await;
+    }
+    if let Some(m) = m.subcommand_matches("verify") {
+        return multisig_verify_command(m, config);
+    }
     Err("unknown multisig command".to_owned())
 }
 
@@ -578,3 +631,110 @@ async fn multisig_deploy_command(matches: &ArgMatches, config: &Config) -> Resul
 
     Ok(())
 }
+
+#[derive(Serialize, Deserialize, Default)]
+struct CollectedSignatures {
+    data: String,
+    signatures: Vec<CustodianSignature>,
+}
+
+fn load_signatures(path: &str) -> Result<CollectedSignatures, String> {
+    let signatures = std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read the signatures file {}: {}", path, e))?;
+    serde_json::from_str(&signatures).map_err(|e| format!("failed to load signatures: {}", e))
+}
+
+async fn multisig_sign_command(matches: &ArgMatches, config: &Config) -> Result<(), String> {
+    let data = base64_encode(decode_data(matches.value_of("DATA").unwrap(), "data")?);
+    let path = matches.value_of("SIGNATURES").unwrap();
+    let collected = if std::path::Path::new(path).exists() {
+        let collected = load_signatures(path)?;
+        if collected.data != data {
+            return Err(format!("signatures in {} are collected for another data", path));
+        }
+        collected
+    } else {
+        CollectedSignatures { data: data.clone(), ..Default::default() }
+    };
+    let keys = load_keypair(matches.value_of("KEYS").unwrap())?;
+    let client = create_client_local()?;
+    let signing_box = get_signing_box(client.clone(), keys)
+        .await
+        .map_err(|e| format!("failed to create signing box: {}", e))?;
+    let result = multisig_sign(
+        client.clone(),
+        ParamsOfMultisigSign {
+            unsigned: data.clone(),
+            signing_box: signing_box.handle.clone(),
+            signatures: collected.signatures,
+        },
+    )
+    .await
+    .map_err(|e| format!("failed to sign: {}", e));
+    let _ = remove_signing_box(client, signing_box);
+    let collected = CollectedSignatures { data, signatures: result?.signatures };
+    let signatures = serde_json::to_string_pretty(&collected)
+        .map_err(|e| format!("failed to serialize signatures: {}", e))?;
+    std::fs::write(path, signatures)
+        .map_err(|e| format!("failed to write the signatures file {}: {}", path, e))?;
+    if !config.is_json {
+        println!("Signature successfully added to {}.", path);
+        println!("Signatures collected: {}", collected.signatures.len());
+    } else {
+        println!("{:#}", json!({ "signatures": collected.signatures.len() }));
+    }
+    Ok(())
+}
+
+fn multisig_verify_command(matches: &ArgMatches, config: &Config) -> Result<(), String> {
+    let collected = load_signatures(matches.value_of("SIGNATURES").unwrap())?;
+    let owners = matches.value_of("OWNERS").unwrap();
+    let custodians: Vec<String> = serde_json::from_str::<Vec<String>>(owners)
+        .unwrap_or_else(|_| owners.split(',').map(|owner| owner.trim().to_owned()).collect())
+        .into_iter()
+        .map(|owner| owner.trim_start_matches("0x").to_owned())
+        .collect();
+    let required = matches
+        .value_of("CONFIRMS")
+        .map(|confirms| {
+            confirms.parse::<u32>().map_err(|e| format!("failed to parse confirms: {}", e))
+        })
+        .transpose()?
+        .unwrap_or(1);
+    let result = multisig_verify(
+        create_client_local()?,
+        ParamsOfMultisigVerify {
+            unsigned: collected.data,
+            signatures: collected.signatures,
+            custodians,
+            required,
+        },
+    )
+    .map_err(|e| format!("failed to verify signatures: {}", e))?;
+    if !config.is_json {
+        for custodian in &result.confirmed {
+            println!("Confirmed by: {}", custodian);
+        }
+        for rejected in &result.rejected {
+            println!("Rejected signature of: {}", rejected.public);
+        }
+        println!("Confirmations: {}/{}", result.confirmed.len(), required);
+    } else {
+        println!(
+            "{:#}",
+            json!({
+                "succeeded": result.succeeded,
+                "confirmed": result.confirmed,
+                "rejected": result.rejected.iter().map(|r| &r.public).collect::<Vec<_>>(),
+            })
+        );
+    }
+    if !result.succeeded {
+        return Err(format!(
+            "not enough confirmations: {} of {} required",
+            result.confirmed.len(),
+            required
+        ));
+    }
+    Ok(())
+}
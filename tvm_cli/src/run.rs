@@ -220,7 +220,9 @@ async fn run(
     Ok(())
 }
 
-fn prepare_execution_options(bc_config: Option<&str>) -> Result<Option<ExecutionOptions>, String> {
+pub fn prepare_execution_options(
+    bc_config: Option<&str>,
+) -> Result<Option<ExecutionOptions>, String> {
     if let Some(config) = bc_config {
         let mut bytes = std::fs::read(config)
             .map_err(|e| format!("Failed to read data from file {config}: {e}"))?;
@@ -30,6 +30,7 @@ use tvm_client::abi::ParamsOfEncodeMessage;
 use tvm_client::abi::Signer as AbiSigner;
 use tvm_client::abi::encode_internal_message;
 use tvm_client::abi::encode_message;
+use tvm_client::crypto::KeyPair;
 use tvm_types::BuilderData;
 use tvm_types::SliceData;
 use tvm_types::base64_encode;
@@ -370,30 +371,38 @@ async fn test_ticktock(matches: &ArgMatches, config: &Config) -> Result<(), Stri
 }
 
 pub fn test_sign_command(matches: &ArgMatches, config: &Config) -> Result<(), String> {
-    let data = if let Some(data) = matches.value_of("DATA") {
-        decode_data(data, "data")?
+    let data = read_data_to_sign(matches)?;
+    let pair = match matches.value_of("KEYS") {
+        Some(keys) => crypto::load_keypair(keys)?,
+        None => match &config.keys_path {
+            Some(keys) => crypto::load_keypair(keys)?,
+            None => return Err("nor signing keys in the params neither in the config".to_string()),
+        },
+    };
+    print_signature(&data, &pair, config)
+}
+
+pub fn read_data_to_sign(matches: &ArgMatches) -> Result<Vec<u8>, String> {
+    if let Some(data) = matches.value_of("DATA") {
+        decode_data(data, "data")
     } else if let Some(data) = matches.value_of("CELL") {
         let data = decode_data(data, "cell")?;
         let cell = read_single_root_boc(data)
             .map_err(|err| format!("Cannot deserialize tree of cells {}", err))?;
         if cell.references_count() == 0 && (cell.bit_length() % 8) == 0 {
             // sign data
-            cell.data().to_vec()
+            Ok(cell.data().to_vec())
         } else {
-            cell.repr_hash().into_vec()
+            Ok(cell.repr_hash().into_vec())
         }
     } else {
-        return Err("nor data neither cell parameter".to_string());
-    };
-    let pair = match matches.value_of("KEYS") {
-        Some(keys) => crypto::load_keypair(keys)?,
-        None => match &config.keys_path {
-            Some(keys) => crypto::load_keypair(keys)?,
-            None => return Err("nor signing keys in the params neither in the config".to_string()),
-        },
-    };
+        Err("nor data neither cell parameter".to_string())
+    }
+}
+
+pub fn print_signature(data: &[u8], pair: &KeyPair, config: &Config) -> Result<(), String> {
     let key = pair.decode().map_err(|err| format!("cannot decode keypair {}", err))?;
-    let signature = ed25519_sign_with_secret(&key.to_bytes(), &data)
+    let signature = ed25519_sign_with_secret(&key.to_bytes(), data)
         .map_err(|e| format!("Failed to sign: {e}"))?;
     let signature = base64_encode(signature.as_ref());
     if config.is_json {
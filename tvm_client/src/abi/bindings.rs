@@ -0,0 +1,350 @@
+// Copyright 2018-2021 TON Labs LTD.
+//
+// Licensed under the SOFTWARE EVALUATION License (the "License"); you may not
+// use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific TON DEV software governing permissions and
+// limitations under the License.
+
+//! Runtime support for typed contract bindings generated by
+//! `tvm_abi_codegen`.
+//!
+//! Generated structs convert to and from the JSON representation used by
+//! `abi.encode_message` and `abi.decode_message` with [`AbiValue`], and
+//! generated contract methods delegate to [`ContractBinding`].
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use num_bigint::BigInt;
+use num_bigint::BigUint;
+use num_traits::NumCast;
+use serde_json::Value;
+
+use crate::abi::Abi;
+use crate::abi::CallSet;
+use crate::abi::DecodedMessageBody;
+use crate::abi::Error;
+use crate::abi::ParamsOfDecodeMessage;
+use crate::abi::ParamsOfEncodeMessage;
+use crate::abi::ResultOfEncodeMessage;
+use crate::abi::Signer;
+use crate::abi::decode_message;
+use crate::abi::encode_message;
+use crate::client::ClientContext;
+use crate::encoding::decode_abi_bigint;
+use crate::encoding::hex_decode;
+use crate::error::ClientResult;
+use crate::processing::ParamsOfProcessMessage;
+use crate::processing::ResultOfProcessMessage;
+use crate::processing::process_message;
+use crate::tvm::ParamsOfRunTvm;
+use crate::tvm::run_tvm;
+
+/// Value that can be passed to and returned from ABI functions.
+///
+/// ABI integers are represented with the smallest fitting primitive type,
+/// `BigUint` or `BigInt`, addresses and cells with `String`, bytes and
+/// public keys with [`AbiBytes`] (empty public key means no key), arrays with
+/// `Vec`, maps with `BTreeMap` and optionals with `Option`.
+pub trait AbiValue: Sized {
+    /// Converts the value into the JSON accepted by the ABI encoder.
+    fn to_abi(&self) -> Value;
+
+    /// Converts the JSON returned by the ABI decoder into the value.
+    fn from_abi(value: &Value) -> ClientResult<Self>;
+}
+
+/// Reads and converts the named field of the decoded ABI tuple. Missing field
+/// is read as `null`.
+pub fn abi_field<T: AbiValue>(value: &Value, name: &str) -> ClientResult<T> {
+    T::from_abi(value.get(name).unwrap_or(&Value::Null)).map_err(|mut err| {
+        err.message_mut().insert_str(0, &format!("field `{}`: ", name));
+        err
+    })
+}
+
+fn unexpected(expected: &str, value: &Value) -> crate::error::ClientError {
+    Error::invalid_data_for_decode(format!("expected {}, found {}", expected, value))
+}
+
+fn abi_number<N: NumCast>(value: &Value, expected: &str) -> ClientResult<N> {
+    let number = match value {
+        Value::String(string) => decode_abi_bigint(string)?,
+        Value::Number(number) => match (number.as_u64(), number.as_i64()) {
+            (Some(number), _) => BigInt::from(number),
+            (_, Some(number)) => BigInt::from(number),
+            _ => return Err(unexpected(expected, value)),
+        },
+        _ => return Err(unexpected(expected, value)),
+    };
+    NumCast::from(number).ok_or_else(|| unexpected(expected, value))
+}
+
+macro_rules! abi_value_for_number {
+    ($($t:ty),*) => {
+        $(
+            impl AbiValue for $t {
+                fn to_abi(&self) -> Value {
+                    Value::String(self.to_string())
+                }
+
+                fn from_abi(value: &Value) -> ClientResult<Self> {
+                    abi_number(value, stringify!($t))
+                }
+            }
+        )*
+    };
+}
+
+abi_value_for_number!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl AbiValue for BigInt {
+    fn to_abi(&self) -> Value {
+        Value::String(self.to_string())
+    }
+
+    fn from_abi(value: &Value) -> ClientResult<Self> {
+        match value {
+            Value::String(string) => decode_abi_bigint(string),
+            Value::Number(_) => abi_number::<i64>(value, "integer").map(BigInt::from),
+            _ => Err(unexpected("integer", value)),
+        }
+    }
+}
+
+impl AbiValue for BigUint {
+    fn to_abi(&self) -> Value {
+        Value::String(self.to_string())
+    }
+
+    fn from_abi(value: &Value) -> ClientResult<Self> {
+        BigInt::from_abi(value)?.to_biguint().ok_or_else(|| unexpected("unsigned integer", value))
+    }
+}
+
+impl AbiValue for bool {
+    fn to_abi(&self) -> Value {
+        Value::Bool(*self)
+    }
+
+    fn from_abi(value: &Value) -> ClientResult<Self> {
+        value.as_bool().ok_or_else(|| unexpected("bool", value))
+    }
+}
+
+impl AbiValue for String {
+    fn to_abi(&self) -> Value {
+        Value::String(self.clone())
+    }
+
+    fn from_abi(value: &Value) -> ClientResult<Self> {
+        value.as_str().map(str::to_owned).ok_or_else(|| unexpected("string", value))
+    }
+}
+
+/// ABI `bytes`, `fixedbytes<N>` or `pubkey` value. Encoded with `hex`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AbiBytes(pub Vec<u8>);
+
+impl From<Vec<u8>> for AbiBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<&[u8]> for AbiBytes {
+    fn from(bytes: &[u8]) -> Self {
+        Self(bytes.to_vec())
+    }
+}
+
+impl std::ops::Deref for AbiBytes {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AbiValue for AbiBytes {
+    fn to_abi(&self) -> Value {
+        Value::String(hex::encode(&self.0))
+    }
+
+    fn from_abi(value: &Value) -> ClientResult<Self> {
+        hex_decode(value.as_str().ok_or_else(|| unexpected("hex string", value))?).map(Self)
+    }
+}
+
+impl<T: AbiValue> AbiValue for Vec<T> {
+    fn to_abi(&self) -> Value {
+        Value::Array(self.iter().map(AbiValue::to_abi).collect())
+    }
+
+    fn from_abi(value: &Value) -> ClientResult<Self> {
+        value
+            .as_array()
+            .ok_or_else(|| unexpected("array", value))?
+            .iter()
+            .map(T::from_abi)
+            .collect()
+    }
+}
+
+impl<T: AbiValue> AbiValue for Option<T> {
+    fn to_abi(&self) -> Value {
+        self.as_ref().map(AbiValue::to_abi).unwrap_or(Value::Null)
+    }
+
+    fn from_abi(value: &Value) -> ClientResult<Self> {
+        match value {
+            Value::Null => Ok(None),
+            value => T::from_abi(value).map(Some),
+        }
+    }
+}
+
+impl<K: AbiValue + Ord, V: AbiValue> AbiValue for BTreeMap<K, V> {
+    fn to_abi(&self) -> Value {
+        Value::Object(
+            self.iter()
+                .map(|(key, value)| {
+                    let key = match key.to_abi() {
+                        Value::String(key) => key,
+                        key => key.to_string(),
+                    };
+                    (key, value.to_abi())
+                })
+                .collect(),
+        )
+    }
+
+    fn from_abi(value: &Value) -> ClientResult<Self> {
+        value
+            .as_object()
+            .ok_or_else(|| unexpected("map", value))?
+            .iter()
+            .map(|(key, value)| {
+                Ok((K::from_abi(&Value::String(key.clone()))?, V::from_abi(value)?))
+            })
+            .collect()
+    }
+}
+
+/// Untyped part of the generated contract bindings: ABI, address and client
+/// context.
+#[derive(Clone)]
+pub struct ContractBinding {
+    context: Arc<ClientContext>,
+    abi: Abi,
+    address: Option<String>,
+    dapp_id: String,
+}
+
+impl ContractBinding {
+    pub fn new(context: Arc<ClientContext>, abi: Abi, address: Option<String>) -> Self {
+        Self { context, abi, address, dapp_id: String::new() }
+    }
+
+    /// Sets destination dapp_id used by `process_message`.
+    pub fn with_dapp_id(mut self, dapp_id: impl Into<String>) -> Self {
+        self.dapp_id = dapp_id.into();
+        self
+    }
+
+    pub fn context(&self) -> &Arc<ClientContext> {
+        &self.context
+    }
+
+    pub fn abi(&self) -> &Abi {
+        &self.abi
+    }
+
+    pub fn address(&self) -> Option<&str> {
+        self.address.as_deref()
+    }
+
+    /// Returns parameters of `abi.encode_message` calling `function` with
+    /// `input`.
+    pub fn encode_params(
+        &self,
+        function: &str,
+        input: &impl AbiValue,
+        signer: Signer,
+    ) -> ParamsOfEncodeMessage {
+        ParamsOfEncodeMessage {
+            abi: self.abi.clone(),
+            address: self.address.clone(),
+            call_set: CallSet::some_with_function_and_input(function, input.to_abi()),
+            signer,
+            ..Default::default()
+        }
+    }
+
+    /// Encodes external inbound message calling `function`.
+    pub async fn encode_message(
+        &self,
+        function: &str,
+        input: &impl AbiValue,
+        signer: Signer,
+    ) -> ClientResult<ResultOfEncodeMessage> {
+        encode_message(self.context.clone(), self.encode_params(function, input, signer)).await
+    }
+
+    /// Sends external inbound message calling `function`, waits for the
+    /// transaction and decodes the function output.
+    pub async fn process_message<O: AbiValue>(
+        &self,
+        function: &str,
+        input: &impl AbiValue,
+        signer: Signer,
+    ) -> ClientResult<(O, ResultOfProcessMessage)> {
+        let result = process_message(
+            self.context.clone(),
+            ParamsOfProcessMessage {
+                message_encode_params: self.encode_params(function, input, signer),
+                send_events: false,
+                dapp_id: self.dapp_id.clone(),
+                dry_run: false,
+            },
+            |_| async {},
+        )
+        .await?;
+        let output = result.decoded.as_ref().and_then(|decoded| decoded.output.as_ref());
+        Ok((O::from_abi(output.unwrap_or(&Value::Null))?, result))
+    }
+
+    /// Runs `function` locally on the `account` BOC encoded with `base64`
+    /// and decodes the function output. The message is not signed.
+    pub async fn run_tvm<O: AbiValue>(
+        &self,
+        account: String,
+        function: &str,
+        input: &impl AbiValue,
+    ) -> ClientResult<O> {
+        let message = self.encode_message(function, input, Signer::None).await?.message;
+        let result = run_tvm(
+            self.context.clone(),
+            ParamsOfRunTvm { message, account, abi: Some(self.abi.clone()), ..Default::default() },
+        )
+        .await?;
+        let output = result.decoded.and_then(|decoded| decoded.output);
+        O::from_abi(output.as_ref().unwrap_or(&Value::Null))
+    }
+
+    /// Decodes message BOC encoded with `base64` using the contract ABI.
+    pub fn decode_message(&self, message: &str) -> ClientResult<DecodedMessageBody> {
+        decode_message(
+            self.context.clone(),
+            ParamsOfDecodeMessage {
+                abi: self.abi.clone(),
+                message: message.to_string(),
+                ..Default::default()
+            },
+        )
+    }
+}
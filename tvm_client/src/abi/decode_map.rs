@@ -0,0 +1,400 @@
+// Copyright 2018-2021 TON Labs LTD.
+//
+// Licensed under the SOFTWARE EVALUATION License (the "License"); you may not
+// use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific TON DEV software governing permissions and
+// limitations under the License.
+//
+
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use serde_json::Value;
+use tvm_abi::ParamType;
+use tvm_abi::TokenValue;
+use tvm_abi::contract::AbiVersion;
+use tvm_abi::contract::MAX_SUPPORTED_VERSION;
+use tvm_types::BuilderData;
+use tvm_types::HashmapE;
+use tvm_types::HashmapSubtree;
+use tvm_types::HashmapType;
+use tvm_types::IBitstring;
+use tvm_types::SliceData;
+
+use crate::abi::Error;
+use crate::abi::types::AbiParam;
+use crate::boc::internal::deserialize_cell_from_boc;
+use crate::client::ClientContext;
+use crate::error::ClientResult;
+
+const DEFAULT_ITEMS_LIMIT: u32 = 50;
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone, Debug)]
+pub struct AbiMap {
+    /// Dictionary root cell BOC or BOC handle.
+    ///
+    /// The map is empty if not specified.
+    pub boc: Option<String>,
+
+    /// ABI type of the map key: `int<N>`, `uint<N>` or `address`.
+    pub key_type: String,
+
+    /// ABI type of the map value.
+    pub value_type: String,
+
+    /// Components of the map value if its type is `tuple`.
+    #[serde(default)]
+    pub value_components: Vec<AbiParam>,
+
+    /// ABI version of the contract the map belongs to, e.g. `2.3`.
+    ///
+    /// Affects the layout of some value types. The latest supported version
+    /// is used if not specified.
+    pub abi_version: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone, Debug, PartialEq)]
+pub struct MapItem {
+    /// Map key in the same representation as in the decoded map JSON.
+    pub key: String,
+
+    /// Decoded map value.
+    pub value: Value,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone, Debug, PartialEq)]
+pub struct ResultOfMapGetItem {
+    /// Found map item.
+    pub item: Option<MapItem>,
+}
+
+struct MapDictionary {
+    dict: HashmapE,
+    key_type: ParamType,
+    value_type: ParamType,
+    signed_keys: bool,
+    abi_version: AbiVersion,
+}
+
+impl MapDictionary {
+    fn new(context: &ClientContext, map: &AbiMap) -> ClientResult<Self> {
+        let key_type = serde_json::from_value::<ParamType>(Value::from(map.key_type.as_str()))
+            .map_err(Error::invalid_json)?;
+        let bit_len = TokenValue::get_map_key_size(&key_type).map_err(Error::invalid_json)?;
+        let value: tvm_abi::Param = AbiParam {
+            name: "value".to_owned(),
+            param_type: map.value_type.clone(),
+            components: map.value_components.clone(),
+            init: false,
+        }
+        .try_into()?;
+        let abi_version = match &map.abi_version {
+            Some(version) => AbiVersion::parse(version).map_err(Error::invalid_abi)?,
+            None => MAX_SUPPORTED_VERSION,
+        };
+        let root = match &map.boc {
+            Some(boc) => Some(deserialize_cell_from_boc(context, boc, "map")?.1),
+            None => None,
+        };
+        Ok(Self {
+            dict: HashmapE::with_hashmap(bit_len, root),
+            signed_keys: matches!(key_type, ParamType::Int(_)),
+            key_type,
+            value_type: value.kind,
+            abi_version,
+        })
+    }
+
+    /// Leaves only items with keys starting with `prefix_bits`.
+    fn with_prefix(mut self, prefix_bits: Option<&str>) -> ClientResult<Self> {
+        let Some(prefix_bits) = prefix_bits else {
+            return Ok(self);
+        };
+        let mut prefix = BuilderData::new();
+        for bit in prefix_bits.chars() {
+            match bit {
+                '0' | '1' => prefix.append_bit_bool(bit == '1'),
+                _ => {
+                    return Err(Error::invalid_data_for_decode(format!(
+                        "prefix_bits must be a binary string, got `{}`",
+                        prefix_bits
+                    )));
+                }
+            }
+            .map_err(Error::invalid_data_for_decode)?;
+        }
+        let prefix = SliceData::load_builder(prefix).map_err(Error::invalid_data_for_decode)?;
+        self.dict.subtree_with_prefix(&prefix, &mut 0).map_err(Error::invalid_data_for_decode)?;
+        Ok(self)
+    }
+
+    fn encode_key(&self, key: &str) -> ClientResult<SliceData> {
+        TokenValue::write_map_key(&self.key_type, key, &self.abi_version)
+            .map_err(Error::invalid_data_for_decode)
+    }
+
+    fn decode_item(&self, key: BuilderData, value: SliceData) -> ClientResult<MapItem> {
+        let key = SliceData::load_builder(key).map_err(Error::invalid_data_for_decode)?;
+        let key = TokenValue::read_map_key(&self.key_type, key, &self.abi_version)
+            .map_err(Error::invalid_data_for_decode)?;
+        let value = TokenValue::read_map_value(
+            &self.key_type,
+            &self.value_type,
+            value,
+            &self.abi_version,
+            false,
+        )
+        .map_err(Error::invalid_data_for_decode)?;
+        let value = serde_json::to_value(value).map_err(Error::invalid_data_for_decode)?;
+        Ok(MapItem { key, value })
+    }
+
+    /// Compares dictionary keys in the dictionary order.
+    fn compare_keys(&self, left: &SliceData, right: &SliceData) -> Ordering {
+        let bits = |key: &SliceData| {
+            let mut bits = key.get_bytestring(0);
+            if self.signed_keys && !bits.is_empty() {
+                bits[0] ^= 0x80;
+            }
+            bits
+        };
+        bits(left).cmp(&bits(right))
+    }
+
+    /// Visits items starting from `from_key` in ascending order (descending
+    /// if `reverse`) until `to_key` is passed or `visit` returns `false`.
+    fn scan(
+        &self,
+        from_key: Option<&str>,
+        to_key: Option<&str>,
+        reverse: bool,
+        mut visit: impl FnMut(BuilderData, SliceData) -> ClientResult<bool>,
+    ) -> ClientResult<()> {
+        let to_key = to_key.map(|key| self.encode_key(key)).transpose()?;
+        let mut item = match from_key {
+            Some(key) => {
+                self.dict.find_leaf(self.encode_key(key)?, !reverse, true, self.signed_keys, &mut 0)
+            }
+            None => self.dict.get_min_max(!reverse, self.signed_keys, &mut 0),
+        }
+        .map_err(Error::invalid_data_for_decode)?;
+        while let Some((key, value)) = item {
+            let key_slice =
+                SliceData::load_builder(key.clone()).map_err(Error::invalid_data_for_decode)?;
+            if let Some(to_key) = &to_key {
+                let passed = match self.compare_keys(&key_slice, to_key) {
+                    Ordering::Greater => !reverse,
+                    Ordering::Less => reverse,
+                    Ordering::Equal => false,
+                };
+                if passed {
+                    break;
+                }
+            }
+            if !visit(key, value)? {
+                break;
+            }
+            item = self
+                .dict
+                .find_leaf(key_slice, !reverse, false, self.signed_keys, &mut 0)
+                .map_err(Error::invalid_data_for_decode)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone, Debug)]
+pub struct ParamsOfMapGet {
+    /// Map to search in.
+    pub map: AbiMap,
+
+    /// Map key in the same representation as in the decoded map JSON.
+    pub key: String,
+}
+
+/// Returns the map item with the specified key.
+///
+/// Only the found value is decoded, so the function can be used with maps of
+/// any size.
+#[api_function]
+pub fn map_get(
+    context: Arc<ClientContext>,
+    params: ParamsOfMapGet,
+) -> ClientResult<ResultOfMapGetItem> {
+    let map = MapDictionary::new(&context, &params.map)?;
+    let key = map.encode_key(&params.key)?;
+    let item = match map.dict.get(key.clone()).map_err(Error::invalid_data_for_decode)? {
+        Some(value) => Some(map.decode_item(key.as_builder(), value)?),
+        None => None,
+    };
+    Ok(ResultOfMapGetItem { item })
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone, Debug)]
+pub struct ParamsOfMapGetItems {
+    /// Map to iterate.
+    pub map: AbiMap,
+
+    /// Key to start the iteration from, inclusive.
+    ///
+    /// If not specified the iteration starts from the first map item. To get
+    /// the next page pass `next_key` of the previous result.
+    pub from_key: Option<String>,
+
+    /// Key to stop the iteration at, inclusive.
+    pub to_key: Option<String>,
+
+    /// Binary string with leading bits of the dictionary key, e.g. `0110`.
+    ///
+    /// If specified only items whose keys start with these bits are iterated.
+    pub prefix_bits: Option<String>,
+
+    /// Iterate in descending key order. Default is `false`.
+    ///
+    /// In descending order `to_key` should be less than `from_key`.
+    pub reverse: Option<bool>,
+
+    /// Maximum number of returned items. Default is 50.
+    pub limit: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone, Debug, PartialEq)]
+pub struct ResultOfMapGetItems {
+    /// Map items in the iteration order.
+    pub items: Vec<MapItem>,
+
+    /// Key of the first item of the next page.
+    ///
+    /// `None` if there are no more items.
+    pub next_key: Option<String>,
+}
+
+/// Returns a page of map items ordered by key.
+///
+/// Signed integer keys are ordered as numbers, unsigned integer and address
+/// keys are ordered by their binary representation. Items are found by
+/// dictionary traversal and only the returned values are decoded, so large
+/// maps can be paged through without decoding the whole map.
+#[api_function]
+pub fn map_get_items(
+    context: Arc<ClientContext>,
+    params: ParamsOfMapGetItems,
+) -> ClientResult<ResultOfMapGetItems> {
+    let map =
+        MapDictionary::new(&context, &params.map)?.with_prefix(params.prefix_bits.as_deref())?;
+    let limit = params.limit.unwrap_or(DEFAULT_ITEMS_LIMIT).max(1) as usize;
+    let mut result = ResultOfMapGetItems::default();
+    map.scan(
+        params.from_key.as_deref(),
+        params.to_key.as_deref(),
+        params.reverse.unwrap_or_default(),
+        |key, value| {
+            if result.items.len() == limit {
+                let key = SliceData::load_builder(key).map_err(Error::invalid_data_for_decode)?;
+                result.next_key = Some(
+                    TokenValue::read_map_key(&map.key_type, key, &map.abi_version)
+                        .map_err(Error::invalid_data_for_decode)?,
+                );
+                return Ok(false);
+            }
+            result.items.push(map.decode_item(key, value)?);
+            Ok(true)
+        },
+    )?;
+    Ok(result)
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone, Debug)]
+pub struct ParamsOfMapGetMinMax {
+    /// Map to search in.
+    pub map: AbiMap,
+
+    /// Binary string with leading bits of the dictionary key, e.g. `0110`.
+    ///
+    /// If specified only items whose keys start with these bits are searched.
+    pub prefix_bits: Option<String>,
+}
+
+/// Returns the map item with the minimal key.
+#[api_function]
+pub fn map_get_min(
+    context: Arc<ClientContext>,
+    params: ParamsOfMapGetMinMax,
+) -> ClientResult<ResultOfMapGetItem> {
+    get_min_max(&context, params, true)
+}
+
+/// Returns the map item with the maximal key.
+#[api_function]
+pub fn map_get_max(
+    context: Arc<ClientContext>,
+    params: ParamsOfMapGetMinMax,
+) -> ClientResult<ResultOfMapGetItem> {
+    get_min_max(&context, params, false)
+}
+
+fn get_min_max(
+    context: &ClientContext,
+    params: ParamsOfMapGetMinMax,
+    min: bool,
+) -> ClientResult<ResultOfMapGetItem> {
+    let map =
+        MapDictionary::new(context, &params.map)?.with_prefix(params.prefix_bits.as_deref())?;
+    let item = match map
+        .dict
+        .get_min_max(min, map.signed_keys, &mut 0)
+        .map_err(Error::invalid_data_for_decode)?
+    {
+        Some((key, value)) => Some(map.decode_item(key, value)?),
+        None => None,
+    };
+    Ok(ResultOfMapGetItem { item })
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone, Debug)]
+pub struct ParamsOfMapCount {
+    /// Map to count items in.
+    pub map: AbiMap,
+
+    /// Minimal key of counted items, inclusive.
+    pub from_key: Option<String>,
+
+    /// Maximal key of counted items, inclusive.
+    pub to_key: Option<String>,
+
+    /// Binary string with leading bits of the dictionary key, e.g. `0110`.
+    ///
+    /// If specified only items whose keys start with these bits are counted.
+    pub prefix_bits: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone, Debug, PartialEq)]
+pub struct ResultOfMapCount {
+    /// Number of map items.
+    pub count: u32,
+}
+
+/// Returns the number of map items. Values are not decoded.
+#[api_function]
+pub fn map_count(
+    context: Arc<ClientContext>,
+    params: ParamsOfMapCount,
+) -> ClientResult<ResultOfMapCount> {
+    let map =
+        MapDictionary::new(&context, &params.map)?.with_prefix(params.prefix_bits.as_deref())?;
+    let count = if params.from_key.is_none() && params.to_key.is_none() {
+        map.dict.len().map_err(Error::invalid_data_for_decode)?
+    } else {
+        let mut count = 0;
+        map.scan(params.from_key.as_deref(), params.to_key.as_deref(), false, |_, _| {
+            count += 1;
+            Ok(true)
+        })?;
+        count
+    };
+    Ok(ResultOfMapCount { count: count as u32 })
+}
@@ -16,6 +16,7 @@ use crate::abi::types::Abi;
 use crate::boc::internal::deserialize_cell_from_boc;
 use crate::boc::internal::deserialize_object_from_boc;
 use crate::client::ClientContext;
+use crate::crypto::SignatureScheme;
 use crate::encoding::decode_abi_number;
 use crate::encoding::slice_from_cell;
 use crate::error::ClientResult;
@@ -350,6 +351,9 @@ pub struct ParamsOfGetSignatureData {
     /// Signature ID to be used in unsigned data preparing when
     /// CapSignatureWithId capability is enabled
     pub signature_id: Option<i32>,
+
+    /// Signature scheme of the message signature. Default is `Ed25519`.
+    pub signature_scheme: Option<SignatureScheme>,
 }
 
 #[derive(Serialize, Deserialize, ApiType, Default)]
@@ -375,8 +379,9 @@ pub async fn get_signature_data(
         let address = message.dst().ok_or_else(|| {
             Error::invalid_message_for_decode("Message has no destination address")
         })?;
+        let scheme = params.signature_scheme.unwrap_or_default();
         let (signature, hash) = abi
-            .get_signature_data(body, Some(address))
+            .get_signature_data_with_scheme(scheme.into(), body, Some(address))
             .map_err(Error::invalid_message_for_decode)?;
         let unsigned = extend_data_to_sign(&context, params.signature_id, Some(hash)).await?;
         Ok(ResultOfGetSignatureData {
@@ -19,6 +19,7 @@ use crate::abi::Signer;
 use crate::abi::internal::add_sign_to_message;
 use crate::abi::internal::add_sign_to_message_body;
 use crate::abi::internal::create_tvc_image;
+use crate::abi::internal::ed25519_signature;
 use crate::abi::internal::try_to_sign_message;
 use crate::abi::internal::update_pubkey;
 use crate::boc::internal::deserialize_cell_from_boc;
@@ -308,6 +309,7 @@ fn encode_deploy(
     call_set: &CallSet,
     pubkey: Option<&str>,
     signer: &Signer,
+    sign_scheme: tvm_abi::SignatureScheme,
     processing_try_index: Option<u8>,
     src_address: MsgAddressExt,
 ) -> ClientResult<(Vec<u8>, Option<Vec<u8>>, MsgAddressInt)> {
@@ -331,7 +333,7 @@ fn encode_deploy(
             (message.serialized_message, None, address)
         }
         _ => {
-            let unsigned = tvm_sdk::Contract::get_deploy_message_bytes_for_signing(
+            let unsigned = tvm_sdk::Contract::get_deploy_message_bytes_for_signing_with_scheme(
                 &call_set.to_function_call_set(
                     pubkey,
                     processing_try_index,
@@ -342,6 +344,7 @@ fn encode_deploy(
                 image,
                 workchain,
                 src_address,
+                sign_scheme,
             )
             .map_err(Error::encode_deploy_message_failed)?;
             (unsigned.message, Some(unsigned.data_to_sign), address)
@@ -422,12 +425,14 @@ fn encode_empty_int_deploy(
     ))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn encode_run(
     context: Arc<ClientContext>,
     params: &ParamsOfEncodeMessage,
     abi: &str,
     call_set: &CallSet,
     pubkey: Option<&str>,
+    sign_scheme: tvm_abi::SignatureScheme,
     processing_try_index: Option<u8>,
     src_address: MsgAddressExt,
 ) -> ClientResult<(Vec<u8>, Option<Vec<u8>>, MsgAddressInt)> {
@@ -452,7 +457,7 @@ fn encode_run(
             (message.serialized_message, None, dst_address)
         }
         _ => {
-            let unsigned = tvm_sdk::Contract::get_call_message_bytes_for_signing(
+            let unsigned = tvm_sdk::Contract::get_call_message_bytes_for_signing_with_scheme(
                 dst_address.clone(),
                 src_address,
                 &call_set.to_function_call_set(
@@ -462,6 +467,7 @@ fn encode_run(
                     abi,
                     false,
                 )?,
+                sign_scheme,
             )
             .map_err(|err| Error::encode_run_message_failed(err, Some(&call_set.function_name)))?;
 
@@ -530,6 +536,8 @@ pub async fn encode_message(
     let abi_string = params.abi.json_string()?;
 
     let public = params.signer.resolve_public_key(context.clone()).await?;
+    let sign_scheme: tvm_abi::SignatureScheme =
+        params.signer.resolve_signature_scheme(context.clone()).await?.into();
     let (message, data_to_sign, address) = if let Some(deploy_set) = params.deploy_set {
         let workchain = deploy_set.workchain_id.unwrap_or(context.config.abi.workchain);
         let mut image = create_tvc_image(
@@ -554,6 +562,7 @@ pub async fn encode_message(
                 call_set,
                 public.as_deref(),
                 &params.signer,
+                sign_scheme,
                 params.processing_try_index,
                 bm_license_address,
             )?
@@ -567,6 +576,7 @@ pub async fn encode_message(
             &abi_string,
             call_set,
             public.as_deref(),
+            sign_scheme,
             params.processing_try_index,
             bm_license_address,
         )?
@@ -814,6 +824,8 @@ pub async fn encode_message_body(
     let abi = params.abi.json_string()?;
 
     let public = params.signer.resolve_public_key(context.clone()).await?;
+    let sign_scheme: tvm_abi::SignatureScheme =
+        params.signer.resolve_signature_scheme(context.clone()).await?.into();
     let call = params.call_set.to_function_call_set(
         public.as_deref(),
         params.processing_try_index,
@@ -848,12 +860,13 @@ pub async fn encode_message_body(
             )
             .map(|body| (body, None))
         } else {
-            tvm_abi::prepare_function_call_for_sign(
+            tvm_abi::prepare_function_call_for_sign_with_scheme(
                 &abi,
                 &func,
                 call.header.as_deref(),
                 &call.input,
                 params.address.as_deref(),
+                sign_scheme,
             )
             .map(|(body, data_to_sign)| (body, Some(data_to_sign)))
         }
@@ -868,9 +881,9 @@ pub async fn encode_message_body(
     .map_err(|err| Error::encode_run_message_failed(err, Some(&func)))?;
     let data_to_sign = extend_data_to_sign(&context, params.signature_id, data_to_sign).await?;
     if let Some(unsigned) = &data_to_sign {
-        if let Some(signature) = params.signer.sign(context.clone(), unsigned).await? {
+        if let Some(signature) = params.signer.sign_message(context.clone(), unsigned).await? {
             let pubkey = public.map(|string| hex_decode(&string)).transpose()?;
-            let body = add_sign_to_message_body(&abi, &signature, pubkey.as_deref(), &body)?;
+            let body = add_sign_to_message_body(&abi, signature, pubkey.as_deref(), &body)?;
             return Ok(ResultOfEncodeMessageBody { body: base64_encode(body), data_to_sign: None });
         }
     }
@@ -916,7 +929,7 @@ pub fn attach_signature(
     let (boc, _) = deserialize_cell_from_boc(&context, &params.message, "message")?;
     let signed = add_sign_to_message(
         &params.abi.json_string()?,
-        &hex_decode(&params.signature)?,
+        ed25519_signature(&params.signature)?,
         Some(&hex_decode(&params.public_key)?),
         &boc.bytes("message")?,
     )?;
@@ -943,7 +956,7 @@ pub struct ParamsOfAttachSignatureToMessageBody {
     pub signature: String,
 }
 
-#[derive(Serialize, Deserialize, ApiType, Default)]
+#[derive(Serialize, Deserialize, ApiType, Default, Debug)]
 pub struct ResultOfAttachSignatureToMessageBody {
     pub body: String,
 }
@@ -957,7 +970,7 @@ pub fn attach_signature_to_message_body(
     let (boc, _) = deserialize_cell_from_boc(&context, &params.message, "message body")?;
     let signed = add_sign_to_message_body(
         &params.abi.json_string()?,
-        &hex_decode(&params.signature)?,
+        ed25519_signature(&params.signature)?,
         Some(&hex_decode(&params.public_key)?),
         &boc.bytes("message body")?,
     )?;
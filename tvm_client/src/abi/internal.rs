@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use serde_json::Value;
 use tvm_abi::PublicKeyData;
+use tvm_abi::SchemeSignature;
 use tvm_sdk::ContractImage;
 use tvm_types::Cell;
 
@@ -17,13 +18,17 @@ use crate::error::ClientResult;
 /// Returns signed message encoded with `base64`.
 pub(crate) fn add_sign_to_message(
     abi: &str,
-    signature: &[u8],
+    signature: SchemeSignature,
     public_key: Option<&[u8]>,
     unsigned_message: &[u8],
 ) -> ClientResult<Vec<u8>> {
-    let signed =
-        tvm_sdk::Contract::add_sign_to_message(abi, signature, public_key, unsigned_message)
-            .map_err(Error::attach_signature_failed)?;
+    let signed = tvm_sdk::Contract::add_sign_to_message_with_scheme(
+        abi,
+        signature,
+        public_key,
+        unsigned_message,
+    )
+    .map_err(Error::attach_signature_failed)?;
     Ok(signed.serialized_message)
 }
 
@@ -31,7 +36,7 @@ pub(crate) fn add_sign_to_message(
 /// Returns signed message encoded with `base64`.
 pub(crate) fn add_sign_to_message_body(
     abi: &str,
-    signature: &[u8],
+    signature: SchemeSignature,
     public_key: Option<&[u8]>,
     unsigned_body: &[u8],
 ) -> ClientResult<Vec<u8>> {
@@ -39,7 +44,7 @@ pub(crate) fn add_sign_to_message_body(
         .map_err(Error::attach_signature_failed)?;
     let body = tvm_abi::add_sign_to_function_call(
         abi,
-        signature.try_into().map_err(Error::attach_signature_failed)?,
+        signature,
         public_key
             .map(|slice| slice.try_into())
             .transpose()
@@ -51,6 +56,12 @@ pub(crate) fn add_sign_to_message_body(
         .map_err(Error::attach_signature_failed)
 }
 
+/// Decodes `hex` encoded ed25519 signature supplied by an application.
+pub(crate) fn ed25519_signature(signature: &str) -> ClientResult<SchemeSignature> {
+    SchemeSignature::new(tvm_abi::SignatureScheme::Ed25519, hex_decode(signature)?)
+        .map_err(Error::attach_signature_failed)
+}
+
 pub(crate) async fn try_to_sign_message(
     context: Arc<ClientContext>,
     abi: &str,
@@ -59,13 +70,13 @@ pub(crate) async fn try_to_sign_message(
     signer: &Signer,
 ) -> ClientResult<(Vec<u8>, Option<Vec<u8>>)> {
     if let Some(unsigned) = &data_to_sign {
-        if let Some(signature) = signer.sign(context.clone(), unsigned).await? {
+        if let Some(signature) = signer.sign_message(context.clone(), unsigned).await? {
             let pubkey = signer
                 .resolve_public_key(context)
                 .await?
                 .map(|string| hex_decode(&string))
                 .transpose()?;
-            let message = add_sign_to_message(abi, &signature, pubkey.as_deref(), &message)?;
+            let message = add_sign_to_message(abi, signature, pubkey.as_deref(), &message)?;
             return Ok((message, None));
         }
     }
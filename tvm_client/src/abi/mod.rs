@@ -12,8 +12,10 @@
 #[cfg(test)]
 mod tests;
 
+pub(crate) mod bindings;
 pub(crate) mod decode_boc;
 pub(crate) mod decode_data;
+pub(crate) mod decode_map;
 pub(crate) mod decode_message;
 pub(crate) mod encode_account;
 pub(crate) mod encode_boc;
@@ -26,12 +28,30 @@ mod internal;
 mod signing;
 mod types;
 
+pub use bindings::AbiBytes;
+pub use bindings::AbiValue;
+pub use bindings::ContractBinding;
+pub use bindings::abi_field;
 pub use decode_boc::ParamsOfDecodeBoc;
 pub use decode_boc::ResultOfDecodeBoc;
 pub use decode_boc::decode_boc;
 pub use decode_data::ParamsOfDecodeAccountData;
 pub use decode_data::ResultOfDecodeAccountData;
 pub use decode_data::decode_account_data;
+pub use decode_map::AbiMap;
+pub use decode_map::MapItem;
+pub use decode_map::ParamsOfMapCount;
+pub use decode_map::ParamsOfMapGet;
+pub use decode_map::ParamsOfMapGetItems;
+pub use decode_map::ParamsOfMapGetMinMax;
+pub use decode_map::ResultOfMapCount;
+pub use decode_map::ResultOfMapGetItem;
+pub use decode_map::ResultOfMapGetItems;
+pub use decode_map::map_count;
+pub use decode_map::map_get;
+pub use decode_map::map_get_items;
+pub use decode_map::map_get_max;
+pub use decode_map::map_get_min;
 pub use decode_message::DataLayout;
 pub use decode_message::DecodedMessageBody;
 pub use decode_message::MessageBodyType;
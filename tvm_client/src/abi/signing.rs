@@ -1,9 +1,13 @@
 use std::sync::Arc;
 
+use tvm_abi::SchemeSignature;
 use tvm_types::base64_encode;
 
 use crate::ClientContext;
+use crate::abi::Error;
 use crate::crypto::KeyPair;
+use crate::crypto::RegisteredSigningBox;
+use crate::crypto::SignatureScheme;
 use crate::crypto::SigningBoxHandle;
 use crate::error::ClientResult;
 
@@ -21,6 +25,8 @@ pub enum Signer {
     Keys { keys: KeyPair },
     /// Signing Box interface is provided for signing, allows Dapps to sign
     /// messages using external APIs, such as HSM, cold wallet, etc.
+    /// The signature is made with the signature scheme of the box. The
+    /// `pubkey` header is filled for ed25519 boxes only.
     SigningBox { handle: SigningBoxHandle },
 }
 
@@ -52,6 +58,36 @@ impl Signer {
         }
     }
 
+    /// Signs ABI message data and tags the signature with the signer scheme
+    pub(crate) async fn sign_message(
+        &self,
+        context: Arc<ClientContext>,
+        data_to_sign: &[u8],
+    ) -> ClientResult<Option<SchemeSignature>> {
+        let Some(signature) = self.sign(context.clone(), data_to_sign).await? else {
+            return Ok(None);
+        };
+        let scheme = self.resolve_signature_scheme(context).await?;
+        SchemeSignature::new(scheme.into(), signature)
+            .map(Some)
+            .map_err(Error::attach_signature_failed)
+    }
+
+    pub async fn resolve_signature_scheme(
+        &self,
+        context: Arc<ClientContext>,
+    ) -> ClientResult<SignatureScheme> {
+        match self {
+            Signer::SigningBox { handle } => crate::crypto::signing_box_get_scheme(
+                context,
+                RegisteredSigningBox { handle: handle.clone() },
+            )
+            .await
+            .map(|result| result.scheme),
+            _ => Ok(SignatureScheme::Ed25519),
+        }
+    }
+
     pub async fn resolve_public_key(
         &self,
         context: Arc<ClientContext>,
@@ -60,12 +96,19 @@ impl Signer {
             Signer::None => Ok(None),
             Signer::Keys { keys } => Ok(Some(keys.public.clone())),
             Signer::External { public_key } => Ok(Some(public_key.clone())),
-            Signer::SigningBox { handle } => crate::crypto::signing_box_get_public_key(
-                context,
-                crate::crypto::RegisteredSigningBox { handle: handle.clone() },
-            )
-            .await
-            .map(|result| Some(result.pubkey)),
+            Signer::SigningBox { handle } => {
+                // ABI `pubkey` header holds an ed25519 key only
+                if self.resolve_signature_scheme(context.clone()).await? != SignatureScheme::Ed25519
+                {
+                    return Ok(None);
+                }
+                crate::crypto::signing_box_get_public_key(
+                    context,
+                    RegisteredSigningBox { handle: handle.clone() },
+                )
+                .await
+                .map(|result| Some(result.pubkey))
+            }
         }
     }
 }
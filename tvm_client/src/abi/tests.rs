@@ -39,6 +39,7 @@ use crate::boc::parse_message;
 use crate::boc::tvc::resolve_state_init_cell;
 use crate::crypto::KeyPair;
 use crate::encoding::account_decode;
+use crate::error::ClientResult;
 use crate::tests::EVENTS;
 use crate::tests::EVENTS_OLD;
 use crate::tests::HELLO;
@@ -327,6 +328,123 @@ fn test_encode_v2_params(ethalons: EncodeCheckEthalons, signature_id: Option<i32
     assert_eq!(no_pubkey_body.body, extract_body(no_pubkey.message));
 }
 
+#[test]
+fn attach_signature_of_invalid_length() {
+    TestClient::init_log();
+    let client = TestClient::new();
+    let (abi, _) = TestClient::package(EVENTS_OLD, Some(2));
+    let keys = client.generate_sign_keys();
+
+    let unsigned: ResultOfEncodeMessageBody = client
+        .request(
+            "abi.encode_message_body",
+            ParamsOfEncodeMessageBody {
+                abi: abi.clone(),
+                call_set: CallSet::some_with_function_and_input(
+                    "returnValue",
+                    json!({
+                        "id": "0"
+                    }),
+                )
+                .unwrap(),
+                is_internal: false,
+                signer: Signer::External { public_key: keys.public.clone() },
+                address: Some(
+                    "0:05beb555e942fa744fd96f45a9ea9d0a8248208ca12421947c06e59bc997d309".into(),
+                ),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let signature = client.sign_detached(&unsigned.data_to_sign.unwrap(), &keys);
+
+    let error = client
+        .request::<_, ResultOfAttachSignatureToMessageBody>(
+            "abi.attach_signature_to_message_body",
+            ParamsOfAttachSignatureToMessageBody {
+                abi,
+                public_key: keys.public.clone(),
+                message: unsigned.body,
+                signature: signature[..64].to_owned(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(error.code(), ErrorCode::AttachSignatureFailed as u32);
+}
+
+fn encode_with_signature_scheme(scheme: crate::crypto::SignatureScheme) {
+    TestClient::init_log();
+    let client = TestClient::new();
+    let (abi, _) = TestClient::package(EVENTS_OLD, Some(2));
+    let address = "0:05beb555e942fa744fd96f45a9ea9d0a8248208ca12421947c06e59bc997d309";
+
+    let keys: KeyPair = client
+        .request(
+            "crypto.generate_signature_scheme_keypair",
+            crate::crypto::ParamsOfGenerateSignatureSchemeKeypair { scheme },
+        )
+        .unwrap();
+    let signing_box: crate::crypto::RegisteredSigningBox = client
+        .request(
+            "crypto.get_signing_box_with_scheme",
+            crate::crypto::ParamsOfGetSigningBoxWithScheme { scheme, keys: keys.clone() },
+        )
+        .unwrap();
+
+    let signed: ResultOfEncodeMessage = client
+        .request(
+            "abi.encode_message",
+            ParamsOfEncodeMessage {
+                address: Some(address.into()),
+                abi: abi.clone(),
+                call_set: CallSet::some_with_function_and_input(
+                    "returnValue",
+                    json!({
+                        "id": "0"
+                    }),
+                ),
+                signer: Signer::SigningBox { handle: signing_box.handle },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert!(signed.data_to_sign.is_none());
+
+    let sign: ResultOfGetSignatureData = client
+        .request(
+            "abi.get_signature_data",
+            ParamsOfGetSignatureData {
+                abi,
+                message: signed.message,
+                signature_scheme: Some(scheme),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let verified: crate::crypto::ResultOfVerifySignatureWithScheme = client
+        .request(
+            "crypto.verify_signature_with_scheme",
+            crate::crypto::ParamsOfVerifySignatureWithScheme {
+                scheme,
+                unsigned: sign.unsigned,
+                signature: sign.signature,
+                public: keys.public.clone(),
+            },
+        )
+        .unwrap();
+    assert!(verified.succeeded);
+}
+
+#[test]
+fn encode_with_bls_signing_box() {
+    encode_with_signature_scheme(crate::crypto::SignatureScheme::Bls);
+}
+
+#[test]
+fn encode_with_secp256k1_signing_box() {
+    encode_with_signature_scheme(crate::crypto::SignatureScheme::Secp256k1);
+}
+
 #[test]
 fn decode_v2() {
     TestClient::init_log();
@@ -1542,3 +1660,269 @@ fn test_abi_v24_changes() {
         .unwrap_err();
     assert_eq!(error.code(), ErrorCode::PubKeyNotSupported as u32);
 }
+
+#[test]
+fn abi_value_round_trip() {
+    use std::collections::BTreeMap;
+
+    use num_bigint::BigUint;
+
+    assert_eq!(u8::from_abi(&json!("0x10")).unwrap(), 16);
+    assert_eq!(i32::from_abi(&json!(-5)).unwrap(), -5);
+    assert!(u8::from_abi(&json!("256")).is_err());
+    assert_eq!(
+        BigUint::from_abi(&json!(format!("0x{:0>64}", "ff"))).unwrap(),
+        BigUint::from(255u32)
+    );
+    assert_eq!(AbiBytes::from_abi(&json!("0102")).unwrap(), AbiBytes(vec![1, 2]));
+
+    let mut map = BTreeMap::new();
+    map.insert(1u32, vec![Some(true), None]);
+    map.insert(20u32, vec![]);
+    let value = map.to_abi();
+    assert_eq!(value, json!({ "1": [true, null], "20": [] }));
+    assert_eq!(BTreeMap::<u32, Vec<Option<bool>>>::from_abi(&value).unwrap(), map);
+
+    let error = abi_field::<u64>(&json!({ "id": "x" }), "id").unwrap_err();
+    assert!(error.message().starts_with("field `id`: "));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn contract_binding() {
+    struct ReturnValueInput {
+        id: u128,
+    }
+
+    impl AbiValue for ReturnValueInput {
+        fn to_abi(&self) -> Value {
+            json!({ "id": self.id.to_abi() })
+        }
+
+        fn from_abi(value: &Value) -> ClientResult<Self> {
+            Ok(Self { id: abi_field(value, "id")? })
+        }
+    }
+
+    let client = TestClient::new();
+    let (abi, _) = TestClient::package(EVENTS, Some(2));
+    let binding = ContractBinding::new(
+        client.context(),
+        abi,
+        Some("0:05beb555e942fa744fd96f45a9ea9d0a8248208ca12421947c06e59bc997d309".into()),
+    );
+
+    let encoded = binding
+        .encode_message(
+            "returnValue",
+            &ReturnValueInput { id: 5 },
+            Signer::Keys { keys: client.generate_sign_keys() },
+        )
+        .await
+        .unwrap();
+    let decoded = binding.decode_message(&encoded.message).unwrap();
+    assert_eq!(decoded.name, "returnValue");
+    assert_eq!(ReturnValueInput::from_abi(&decoded.value.unwrap()).unwrap().id, 5);
+}
+
+fn encode_map_root(
+    map_type: tvm_abi::ParamType,
+    value: Value,
+    abi_version: &tvm_abi::contract::AbiVersion,
+) -> String {
+    let token = tvm_abi::token::Tokenizer::tokenize_parameter(&map_type, &value, "map").unwrap();
+    let data = token.pack_into_chain(abi_version).unwrap();
+    serialize_cell_to_base64(&data.references()[0], "map").unwrap()
+}
+
+#[test]
+fn test_map_iteration() {
+    let client = TestClient::new();
+    let boc = encode_map_root(
+        serde_json::from_value(json!("map(int16,uint128)")).unwrap(),
+        json!({ "-300": 1, "-2": 2, "0": 3, "5": 4, "7": 5, "1000": 6 }),
+        &tvm_abi::contract::MAX_SUPPORTED_VERSION,
+    );
+    let map = AbiMap {
+        boc: Some(boc),
+        key_type: "int16".to_owned(),
+        value_type: "uint128".to_owned(),
+        value_components: vec![],
+        abi_version: None,
+    };
+    let items = |from: Option<&str>, to: Option<&str>, reverse: bool, limit: u32| {
+        client
+            .request::<_, ResultOfMapGetItems>(
+                "abi.map_get_items",
+                ParamsOfMapGetItems {
+                    map: map.clone(),
+                    from_key: from.map(|key| key.to_owned()),
+                    to_key: to.map(|key| key.to_owned()),
+                    reverse: Some(reverse),
+                    limit: Some(limit),
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+    };
+    let keys = |result: &ResultOfMapGetItems| {
+        result.items.iter().map(|item| item.key.clone()).collect::<Vec<_>>()
+    };
+
+    let page = items(None, None, false, 4);
+    assert_eq!(keys(&page), vec!["-300", "-2", "0", "5"]);
+    assert_eq!(page.items[1].value, json!("2"));
+    assert_eq!(page.next_key.as_deref(), Some("7"));
+    let page = items(page.next_key.as_deref(), None, false, 4);
+    assert_eq!(keys(&page), vec!["7", "1000"]);
+    assert_eq!(page.next_key, None);
+
+    assert_eq!(keys(&items(Some("-1"), Some("7"), false, 10)), vec!["0", "5", "7"]);
+    assert_eq!(keys(&items(Some("6"), Some("-2"), true, 10)), vec!["5", "0", "-2"]);
+    assert_eq!(keys(&items(Some("1001"), None, false, 10)), Vec::<String>::new());
+
+    // non-negative keys start with 0 bit
+    let page = client
+        .request::<_, ResultOfMapGetItems>(
+            "abi.map_get_items",
+            ParamsOfMapGetItems {
+                map: map.clone(),
+                prefix_bits: Some("0".to_owned()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(keys(&page), vec!["0", "5", "7", "1000"]);
+
+    let get = |key: &str| {
+        client
+            .request::<_, ResultOfMapGetItem>(
+                "abi.map_get",
+                ParamsOfMapGet { map: map.clone(), key: key.to_owned() },
+            )
+            .unwrap()
+            .item
+    };
+    assert_eq!(get("5"), Some(MapItem { key: "5".to_owned(), value: json!("4") }));
+    assert_eq!(get("6"), None);
+
+    let min_max = |function: &str| {
+        client
+            .request::<_, ResultOfMapGetItem>(
+                function,
+                ParamsOfMapGetMinMax { map: map.clone(), prefix_bits: None },
+            )
+            .unwrap()
+            .item
+            .unwrap()
+            .key
+    };
+    assert_eq!(min_max("abi.map_get_min"), "-300");
+    assert_eq!(min_max("abi.map_get_max"), "1000");
+
+    let count = |from: Option<&str>, to: Option<&str>| {
+        client
+            .request::<_, ResultOfMapCount>(
+                "abi.map_count",
+                ParamsOfMapCount {
+                    map: map.clone(),
+                    from_key: from.map(|key| key.to_owned()),
+                    to_key: to.map(|key| key.to_owned()),
+                    prefix_bits: None,
+                },
+            )
+            .unwrap()
+            .count
+    };
+    assert_eq!(count(None, None), 6);
+    assert_eq!(count(Some("-2"), Some("5")), 3);
+
+    let empty = client
+        .request::<_, ResultOfMapCount>(
+            "abi.map_count",
+            ParamsOfMapCount { map: AbiMap { boc: None, ..map.clone() }, ..Default::default() },
+        )
+        .unwrap();
+    assert_eq!(empty.count, 0);
+}
+
+#[test]
+fn test_map_get_tuple_value() {
+    let client = TestClient::new();
+    let address = "0:1111111111111111111111111111111111111111111111111111111111111111";
+    let map_type = tvm_abi::ParamType::Map(
+        Box::new(tvm_abi::ParamType::Address),
+        Box::new(tvm_abi::ParamType::Tuple(vec![
+            tvm_abi::Param::new("a", tvm_abi::ParamType::Uint(8)),
+            tvm_abi::Param::new("b", tvm_abi::ParamType::String),
+        ])),
+    );
+    let boc = encode_map_root(
+        map_type,
+        json!({ address: { "a": 7, "b": "seven" } }),
+        &tvm_abi::contract::MAX_SUPPORTED_VERSION,
+    );
+    let item = client
+        .request::<_, ResultOfMapGetItem>(
+            "abi.map_get",
+            ParamsOfMapGet {
+                map: AbiMap {
+                    boc: Some(boc),
+                    key_type: "address".to_owned(),
+                    value_type: "tuple".to_owned(),
+                    value_components: vec![
+                        AbiParam {
+                            name: "a".to_owned(),
+                            param_type: "uint8".to_owned(),
+                            ..Default::default()
+                        },
+                        AbiParam {
+                            name: "b".to_owned(),
+                            param_type: "string".to_owned(),
+                            ..Default::default()
+                        },
+                    ],
+                    abi_version: None,
+                },
+                key: address.to_owned(),
+            },
+        )
+        .unwrap()
+        .item;
+    assert_eq!(
+        item,
+        Some(MapItem { key: address.to_owned(), value: json!({ "a": "7", "b": "seven" }) })
+    );
+}
+
+#[test]
+fn test_map_get_with_abi_version() {
+    let client = TestClient::new();
+    let map_type = tvm_abi::ParamType::Map(
+        Box::new(tvm_abi::ParamType::Uint(8)),
+        Box::new(tvm_abi::ParamType::FixedBytes(4)),
+    );
+    // fixed bytes are stored in a reference before ABI 2.4 and inline since
+    let boc =
+        encode_map_root(map_type, json!({ "1": "01020304" }), &tvm_abi::contract::ABI_VERSION_2_3);
+    let get = |abi_version: Option<&str>| {
+        client.request::<_, ResultOfMapGetItem>(
+            "abi.map_get",
+            ParamsOfMapGet {
+                map: AbiMap {
+                    boc: Some(boc.clone()),
+                    key_type: "uint8".to_owned(),
+                    value_type: "fixedbytes4".to_owned(),
+                    value_components: vec![],
+                    abi_version: abi_version.map(|version| version.to_owned()),
+                },
+                key: "1".to_owned(),
+            },
+        )
+    };
+    assert_eq!(
+        get(Some("2.3")).unwrap().item,
+        Some(MapItem { key: "1".to_owned(), value: json!("01020304") })
+    );
+    assert!(get(None).is_err());
+    assert_eq!(get(Some("x.y")).unwrap_err().code(), ErrorCode::InvalidAbi as u32);
+}
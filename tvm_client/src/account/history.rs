@@ -0,0 +1,517 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+
+use serde_json::Value;
+use serde_json::json;
+use tvm_block::Account;
+use tvm_block::ConfigParams;
+use tvm_block::Deserializable;
+use tvm_block::Message;
+use tvm_block::Serializable;
+use tvm_block::Transaction;
+use tvm_block::TransactionDescr;
+use tvm_executor::BlockchainConfig;
+use tvm_executor::ExecuteParams;
+use tvm_executor::OrdinaryTransactionExecutor;
+use tvm_executor::TransactionExecutor;
+use tvm_types::Cell;
+use tvm_types::UInt256;
+
+use crate::ClientContext;
+use crate::boc::internal::deserialize_object_from_boc;
+use crate::boc::internal::serialize_object_to_base64;
+use crate::client::LocalStorage;
+use crate::client::storage::InMemoryKeyValueStorage;
+use crate::client::storage::KeyValueStorage;
+use crate::encoding::decode_abi_number;
+use crate::error::ClientResult;
+use crate::net::OrderBy;
+use crate::net::ParamsOfQueryCollection;
+use crate::net::SortDirection;
+use crate::net::query_collection;
+use crate::tvm::Error;
+use crate::tvm::types::resolve_network_params;
+
+const TRANSACTIONS_PAGE_SIZE: u32 = 50;
+
+const CONFIG_ADDRESS: &str = "-1:5555555555555555555555555555555555555555555555555555555555555555";
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone, Debug)]
+pub struct ParamsOfGetAccountAt {
+    /// Account address.
+    pub address: String,
+    /// Logical time of the state. The state after the last transaction with
+    /// `lt` not greater than this value is returned.
+    pub lt: Option<String>,
+    /// Unix time of the state. The state after the last transaction with
+    /// `now` not greater than this value is returned. Ignored if `lt` is
+    /// specified. The current state is returned if neither `lt` nor `time`
+    /// is specified.
+    pub time: Option<u32>,
+    /// Account BOC to start replaying from. Must be a state preceding the
+    /// requested one. If omitted, the closest preceding state stored in the
+    /// local storage by previous calls is used, or the zerostate or the
+    /// non-existing account if there is none.
+    pub base_account: Option<String>,
+    /// Blockchain config BOC used to execute all transactions. If omitted,
+    /// the config account is replayed along with the account and the config
+    /// of each block is taken from its state.
+    pub blockchain_config: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone, Debug)]
+pub struct ResultOfGetAccountAt {
+    /// Account BOC at the requested point.
+    pub boc: String,
+    /// Logical time of the last transaction applied to the account.
+    pub last_trans_lt: String,
+    /// Number of transactions replayed locally to reconstruct the state.
+    pub replayed_transactions: u32,
+}
+
+/// Reconstructs the account state at the specified logical time or unix time.
+///
+/// Starts from the closest preceding known state and replays the account
+/// transactions locally with the ordinary transaction executor. Each replayed
+/// transaction is checked against the old and new account state hashes
+/// recorded in its `state_update`. Unless `blockchain_config` is provided, the
+/// config account transactions are replayed too, so every transaction is
+/// executed with the config that was in effect in its block.
+///
+/// If `local_storage_path` is set in the client config, fetched and
+/// reconstructed states are kept in the local storage and serve as starting
+/// points for subsequent calls.
+#[api_function]
+pub async fn get_account_at(
+    context: Arc<ClientContext>,
+    params: ParamsOfGetAccountAt,
+) -> ClientResult<ResultOfGetAccountAt> {
+    let target_lt = match (&params.lt, params.time) {
+        (Some(lt), _) => Some(decode_abi_number::<u64>(lt)?),
+        (None, Some(time)) => last_transaction_lt_before(&context, &params.address, time).await?,
+        (None, None) => Some(u64::MAX),
+    };
+    let Some(target_lt) = target_lt else {
+        return account_result(&Account::default(), 0);
+    };
+
+    let storage = account_states_storage(&context).await?;
+    let base = match &params.base_account {
+        Some(boc) => deserialize_object_from_boc::<Account>(&context, boc, "account")?.object,
+        None => {
+            if let Some(account) = query_current_account(&context, &params.address).await? {
+                store_state(storage.as_ref(), &params.address, &account).await?;
+                if account.last_tr_time().unwrap_or_default() <= target_lt {
+                    return account_result(&account, 0);
+                }
+            }
+            closest_state(&context, storage.as_ref(), &params.address, target_lt).await?
+        }
+    };
+
+    let network_params =
+        resolve_network_params(&context, params.blockchain_config.clone(), None).await?;
+    let mut account = TransactionChain::load(&context, &params.address, base, target_lt).await?;
+    let mut config_account = match (&params.blockchain_config, account.next_lt()) {
+        (None, Some(first_lt)) => {
+            let last_lt = account.last_lt().unwrap_or(first_lt);
+            let base = closest_state(&context, storage.as_ref(), CONFIG_ADDRESS, first_lt).await?;
+            Some(TransactionChain::load(&context, CONFIG_ADDRESS, base, last_lt).await?)
+        }
+        _ => None,
+    };
+
+    let replayed = replay_chains(
+        &mut account,
+        config_account.as_mut(),
+        network_params.blockchain_config.as_ref().clone(),
+        network_params.global_id,
+    )?;
+
+    let result = Account::construct_from_cell(account.root).map_err(Error::invalid_account_boc)?;
+    store_state(storage.as_ref(), &params.address, &result).await?;
+    if let Some(config_account) = config_account {
+        let config_account = Account::construct_from_cell(config_account.root)
+            .map_err(Error::invalid_account_boc)?;
+        store_state(storage.as_ref(), CONFIG_ADDRESS, &config_account).await?;
+    }
+    account_result(&result, replayed)
+}
+
+pub(crate) struct ChainTransaction {
+    pub(crate) id: String,
+    pub(crate) block_lt: u64,
+    pub(crate) transaction: Transaction,
+}
+
+/// Account state with the transactions to be replayed over it.
+pub(crate) struct TransactionChain {
+    pub(crate) root: Cell,
+    pub(crate) transactions: VecDeque<ChainTransaction>,
+}
+
+impl TransactionChain {
+    async fn load(
+        context: &Arc<ClientContext>,
+        address: &str,
+        base: Account,
+        to_lt: u64,
+    ) -> ClientResult<Self> {
+        let mut from_lt = base.last_tr_time().unwrap_or_default();
+        let mut transactions = VecDeque::new();
+        loop {
+            let page = query_transactions(context, address, from_lt, to_lt).await?;
+            let page_len = page.len();
+            for transaction in page {
+                from_lt = transaction.transaction.logical_time();
+                transactions.push_back(transaction);
+            }
+            if page_len < TRANSACTIONS_PAGE_SIZE as usize {
+                break;
+            }
+        }
+        let root = base.serialize().map_err(Error::invalid_account_boc)?;
+        Ok(Self { root, transactions })
+    }
+
+    fn next_lt(&self) -> Option<u64> {
+        self.transactions.front().map(|transaction| transaction.transaction.logical_time())
+    }
+
+    fn last_lt(&self) -> Option<u64> {
+        self.transactions.back().map(|transaction| transaction.transaction.logical_time())
+    }
+
+    fn next_block_lt(&self) -> Option<u64> {
+        self.transactions.front().map(|transaction| transaction.block_lt)
+    }
+
+    fn replay_next(&mut self, config: &BlockchainConfig, signature_id: i32) -> ClientResult<()> {
+        if let Some(next) = self.transactions.pop_front() {
+            replay_transaction(config, signature_id, &mut self.root, &next.id, &next.transaction)?;
+        }
+        Ok(())
+    }
+}
+
+/// Replays the account transactions in logical time order together with the
+/// config account transactions preceding them. The config is rebuilt from the
+/// config account state at the start of every block, as `tvm_cli replay`
+/// does. Returns the number of replayed account transactions.
+pub(crate) fn replay_chains(
+    account: &mut TransactionChain,
+    mut config_account: Option<&mut TransactionChain>,
+    mut config: BlockchainConfig,
+    signature_id: i32,
+) -> ClientResult<u32> {
+    let mut block_lt = None;
+    let mut replayed = 0;
+    while let Some(account_lt) = account.next_lt() {
+        let from_config = config_account
+            .as_deref()
+            .and_then(TransactionChain::next_lt)
+            .is_some_and(|lt| lt < account_lt);
+        if let Some(config_account) = config_account.as_deref() {
+            let next = if from_config { config_account } else { &*account };
+            if block_lt != next.next_block_lt() {
+                block_lt = next.next_block_lt();
+                config = construct_blockchain_config(&config_account.root)?;
+            }
+        }
+        match config_account.as_deref_mut() {
+            Some(config_account) if from_config => {
+                config_account.replay_next(&config, signature_id)?
+            }
+            _ => {
+                account.replay_next(&config, signature_id)?;
+                replayed += 1;
+            }
+        }
+    }
+    Ok(replayed)
+}
+
+fn construct_blockchain_config(config_account: &Cell) -> ClientResult<BlockchainConfig> {
+    let config_account = Account::construct_from_cell(config_account.clone())
+        .map_err(Error::can_not_read_blockchain_config)?;
+    let config_cell = config_account
+        .get_data()
+        .ok_or_else(|| Error::can_not_read_blockchain_config("config account has no data"))?
+        .reference(0)
+        .map_err(Error::can_not_read_blockchain_config)?;
+    let config_params =
+        ConfigParams::with_address_and_params(UInt256::with_array([0x55; 32]), Some(config_cell));
+    BlockchainConfig::with_config(config_params).map_err(Error::can_not_read_blockchain_config)
+}
+
+/// Executes the transaction over the account state and verifies the state
+/// hashes before and after execution against the transaction `state_update`.
+pub(crate) fn replay_transaction(
+    config: &BlockchainConfig,
+    signature_id: i32,
+    account_root: &mut Cell,
+    transaction_id: &str,
+    transaction: &Transaction,
+) -> ClientResult<()> {
+    let state_update = transaction.read_state_update().map_err(Error::can_not_read_transaction)?;
+    check_hash(transaction_id, "old", &state_update.old_hash, account_root)?;
+
+    match transaction.read_description().map_err(Error::can_not_read_transaction)? {
+        TransactionDescr::Ordinary(_) => {}
+        _ => {
+            return Err(Error::can_not_read_transaction(format!(
+                "transaction {} is not ordinary and can not be replayed",
+                transaction_id
+            )));
+        }
+    }
+    let message = transaction
+        .in_msg_cell()
+        .map(|cell| Message::construct_from_cell(cell).map_err(Error::can_not_read_transaction))
+        .transpose()?;
+    let params = ExecuteParams {
+        block_unixtime: transaction.now(),
+        block_lt: transaction.logical_time(),
+        last_tr_lt: Arc::new(AtomicU64::new(transaction.logical_time())),
+        signature_id,
+        ..ExecuteParams::default()
+    };
+    let executor = OrdinaryTransactionExecutor::new(config.clone());
+    executor
+        .execute_with_libs_and_params(message.as_ref(), account_root, params)
+        .map_err(Error::unknown_execution_error)?;
+
+    check_hash(transaction_id, "new", &state_update.new_hash, account_root)
+}
+
+fn check_hash(
+    transaction_id: &str,
+    hash_kind: &str,
+    expected: &tvm_types::UInt256,
+    account_root: &Cell,
+) -> ClientResult<()> {
+    let actual = account_root.repr_hash();
+    if &actual != expected {
+        return Err(Error::account_state_mismatch(
+            transaction_id,
+            hash_kind,
+            &expected.as_hex_string(),
+            &actual.as_hex_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the storage of account states. States are kept between calls only
+/// in the local storage: without `local_storage_path` every call starts with an
+/// empty in-memory storage, so the client context does not grow with the
+/// number of requested accounts and states.
+pub(crate) async fn account_states_storage(
+    context: &Arc<ClientContext>,
+) -> ClientResult<Arc<dyn KeyValueStorage>> {
+    let Some(path) = &context.config.local_storage_path else {
+        return Ok(Arc::new(InMemoryKeyValueStorage::new()));
+    };
+    if let Some(storage) = context.account_states.read().await.as_ref() {
+        return Ok(Arc::clone(storage));
+    }
+
+    let network_uid = context.net.get_current_network_uid().await.map_err(Error::internal_error)?;
+    let zerostate_hash = network_uid.zerostate_root_hash.as_slice();
+    let storage_name = format!("account_states/{}", hex::encode(&zerostate_hash[..4]));
+    let new_storage = Arc::new(LocalStorage::new(Some(path.clone()), storage_name).await?)
+        as Arc<dyn KeyValueStorage>;
+
+    let mut write_guard = context.account_states.write().await;
+    if let Some(storage) = write_guard.as_ref() {
+        return Ok(Arc::clone(storage));
+    }
+    *write_guard = Some(Arc::clone(&new_storage));
+    Ok(new_storage)
+}
+
+fn state_key_prefix(address: &str) -> String {
+    format!("account_{}", address.replace([':', '-'], "_"))
+}
+
+async fn stored_state_lts(storage: &dyn KeyValueStorage, address: &str) -> ClientResult<Vec<u64>> {
+    let key = format!("{}_lts", state_key_prefix(address));
+    Ok(storage
+        .get_str(&key)
+        .await?
+        .and_then(|lts| serde_json::from_str(&lts).ok())
+        .unwrap_or_default())
+}
+
+/// Saves the account state as a starting point for later replays.
+pub(crate) async fn store_state(
+    storage: &dyn KeyValueStorage,
+    address: &str,
+    account: &Account,
+) -> ClientResult<()> {
+    let lt = account.last_tr_time().unwrap_or_default();
+    if lt == 0 {
+        return Ok(());
+    }
+    let mut lts = stored_state_lts(storage, address).await?;
+    let Err(index) = lts.binary_search(&lt) else {
+        return Ok(());
+    };
+    let boc = account.write_to_bytes().map_err(Error::invalid_account_boc)?;
+    let prefix = state_key_prefix(address);
+    storage.put_bin(&format!("{}_{}", prefix, lt), &boc).await?;
+    lts.insert(index, lt);
+    let lts = serde_json::to_string(&lts).map_err(Error::internal_error)?;
+    storage.put_str(&format!("{}_lts", prefix), &lts).await
+}
+
+/// Returns the stored account state with the greatest last transaction lt
+/// not greater than `lt`.
+pub(crate) async fn stored_state(
+    storage: &dyn KeyValueStorage,
+    address: &str,
+    lt: u64,
+) -> ClientResult<Option<Account>> {
+    let lts = stored_state_lts(storage, address).await?;
+    let Some(state_lt) = lts.iter().rev().find(|state_lt| **state_lt <= lt) else {
+        return Ok(None);
+    };
+    let key = format!("{}_{}", state_key_prefix(address), state_lt);
+    storage
+        .get_bin(&key)
+        .await?
+        .map(|boc| Account::construct_from_bytes(&boc).map_err(Error::invalid_account_boc))
+        .transpose()
+}
+
+/// Returns the closest stored account state preceding `lt`, falling back to
+/// the zerostate and then to the non-existing account.
+async fn closest_state(
+    context: &Arc<ClientContext>,
+    storage: &dyn KeyValueStorage,
+    address: &str,
+    lt: u64,
+) -> ClientResult<Account> {
+    if let Some(account) = stored_state(storage, address, lt).await? {
+        return Ok(account);
+    }
+    Ok(query_zerostate_account(context, address).await.unwrap_or_default())
+}
+
+fn account_result(
+    account: &Account,
+    replayed_transactions: u32,
+) -> ClientResult<ResultOfGetAccountAt> {
+    Ok(ResultOfGetAccountAt {
+        boc: serialize_object_to_base64(account, "account")?,
+        last_trans_lt: account.last_tr_time().unwrap_or_default().to_string(),
+        replayed_transactions,
+    })
+}
+
+async fn last_transaction_lt_before(
+    context: &Arc<ClientContext>,
+    address: &str,
+    time: u32,
+) -> ClientResult<Option<u64>> {
+    let result = query_collection(
+        context.clone(),
+        ParamsOfQueryCollection {
+            collection: "transactions".to_string(),
+            filter: Some(json!({
+                "account_addr": { "eq": address },
+                "now": { "le": time },
+            })),
+            result: "lt(format:DEC)".to_string(),
+            order: Some(vec![OrderBy { path: "lt".to_string(), direction: SortDirection::DESC }]),
+            limit: Some(1),
+        },
+    )
+    .await?
+    .result;
+    result.first().map(|transaction| decode_lt(&transaction["lt"])).transpose()
+}
+
+async fn query_current_account(
+    context: &Arc<ClientContext>,
+    address: &str,
+) -> ClientResult<Option<Account>> {
+    let result = query_collection(
+        context.clone(),
+        ParamsOfQueryCollection {
+            collection: "accounts".to_string(),
+            filter: Some(json!({ "id": { "eq": address } })),
+            result: "boc".to_string(),
+            limit: Some(1),
+            ..Default::default()
+        },
+    )
+    .await?
+    .result;
+    result.first().and_then(|account| account["boc"].as_str()).map(construct_account).transpose()
+}
+
+/// Returns the account from the zerostate. The zerostate is filtered by the
+/// account address, so its accounts are fetched only if it contains the
+/// requested one.
+async fn query_zerostate_account(context: &Arc<ClientContext>, address: &str) -> Option<Account> {
+    let result = query_collection(
+        context.clone(),
+        ParamsOfQueryCollection {
+            collection: "zerostates".to_string(),
+            filter: Some(json!({ "accounts": { "any": { "id": { "eq": address } } } })),
+            result: "accounts { id boc }".to_string(),
+            limit: Some(1),
+            ..Default::default()
+        },
+    )
+    .await
+    .ok()?
+    .result;
+    let accounts = result.first()?["accounts"].as_array()?;
+    let account = accounts.iter().find(|account| account["id"] == address)?;
+    construct_account(account["boc"].as_str()?).ok()
+}
+
+async fn query_transactions(
+    context: &Arc<ClientContext>,
+    address: &str,
+    from_lt: u64,
+    to_lt: u64,
+) -> ClientResult<Vec<ChainTransaction>> {
+    let result = query_collection(
+        context.clone(),
+        ParamsOfQueryCollection {
+            collection: "transactions".to_string(),
+            filter: Some(json!({
+                "account_addr": { "eq": address },
+                "lt": { "gt": from_lt.to_string(), "le": to_lt.to_string() },
+            })),
+            result: "id block { start_lt } boc".to_string(),
+            order: Some(vec![OrderBy { path: "lt".to_string(), direction: SortDirection::ASC }]),
+            limit: Some(TRANSACTIONS_PAGE_SIZE),
+        },
+    )
+    .await?
+    .result;
+    result
+        .iter()
+        .map(|transaction| {
+            let id = transaction["id"].as_str().unwrap_or_default().to_string();
+            let block_lt = decode_lt(&transaction["block"]["start_lt"])?;
+            let boc = transaction["boc"].as_str().unwrap_or_default();
+            let transaction =
+                Transaction::construct_from_base64(boc).map_err(Error::can_not_read_transaction)?;
+            Ok(ChainTransaction { id, block_lt, transaction })
+        })
+        .collect()
+}
+
+fn construct_account(boc: &str) -> ClientResult<Account> {
+    Account::construct_from_base64(boc).map_err(Error::invalid_account_boc)
+}
+
+fn decode_lt(value: &Value) -> ClientResult<u64> {
+    decode_abi_number(value.as_str().unwrap_or_default())
+}
@@ -7,7 +7,11 @@ use crate::ClientContext;
 use crate::error::ClientError;
 use crate::error::ClientResult;
 
+pub(crate) mod history;
 mod validate;
+pub use history::ParamsOfGetAccountAt;
+pub use history::ResultOfGetAccountAt;
+pub use history::get_account_at;
 pub use validate::validate_hex_id;
 
 #[cfg(test)]
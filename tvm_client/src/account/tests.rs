@@ -261,3 +261,245 @@ async fn get_account_rejects_empty_dapp_id_on_v3() {
     assert!(err.message().contains("dapp_id"));
     handle.abort();
 }
+
+#[test]
+fn replay_transactions_verifies_state_hashes() {
+    use std::sync::atomic::AtomicU64;
+
+    use tvm_block::Account;
+    use tvm_block::CurrencyCollection;
+    use tvm_block::InternalMessageHeader;
+    use tvm_block::Message;
+    use tvm_block::MsgAddressInt;
+    use tvm_block::Serializable;
+    use tvm_executor::BlockchainConfig;
+    use tvm_executor::ExecuteParams;
+    use tvm_executor::OrdinaryTransactionExecutor;
+    use tvm_executor::TransactionExecutor;
+
+    use crate::account::history::replay_transaction;
+
+    let config = BlockchainConfig::default();
+    let src = MsgAddressInt::with_standart(None, 0, [0x11; 32].into()).unwrap();
+    let dst = MsgAddressInt::with_standart(None, 0, [0x22; 32].into()).unwrap();
+    let executor = OrdinaryTransactionExecutor::new(config.clone());
+
+    let mut initial = Account::with_address_and_ballance(&dst, &CurrencyCollection::with_grams(1));
+    initial.set_last_paid(1_700_000_000);
+    let initial = initial.serialize().unwrap();
+    let mut account_root = initial.clone();
+    let mut history = vec![];
+    for i in 0..3u64 {
+        let header = InternalMessageHeader::with_addresses_and_bounce(
+            src.clone(),
+            dst.clone(),
+            CurrencyCollection::with_grams(1_000_000_000),
+            false,
+        );
+        let message = Message::with_int_header(header);
+        let params = ExecuteParams {
+            block_unixtime: 1_700_000_000 + i as u32 * 100,
+            block_lt: (i + 1) * 1_000_000,
+            last_tr_lt: Arc::new(AtomicU64::new((i + 1) * 1_000_000)),
+            ..ExecuteParams::default()
+        };
+        let (transaction, _) = executor
+            .execute_with_libs_and_params(Some(&message), &mut account_root, params)
+            .unwrap();
+        assert_ne!(history.last().map_or(&initial, |(_, state)| state), &account_root);
+        history.push((transaction, account_root.clone()));
+    }
+
+    let mut replayed = initial.clone();
+    for (i, (transaction, state)) in history.iter().enumerate() {
+        replay_transaction(&config, 0, &mut replayed, &i.to_string(), transaction).unwrap();
+        assert_eq!(replayed.repr_hash(), state.repr_hash());
+    }
+
+    // Skipping a transaction breaks the chain of recorded state hashes
+    let mut replayed = initial;
+    let err = replay_transaction(&config, 0, &mut replayed, "1", &history[1].0).unwrap_err();
+    assert_eq!(err.code(), crate::tvm::ErrorCode::AccountStateMismatch as u32);
+    assert_eq!(err.data()["transaction_id"], "1");
+}
+
+#[tokio::test]
+async fn closest_stored_state_is_used() {
+    use tvm_block::Account;
+    use tvm_block::CurrencyCollection;
+    use tvm_block::MsgAddressInt;
+
+    use crate::account::history::store_state;
+    use crate::account::history::stored_state;
+    use crate::client::storage::InMemoryKeyValueStorage;
+
+    let address = "0:2222222222222222222222222222222222222222222222222222222222222222";
+    let addr = MsgAddressInt::with_standart(None, 0, [0x22; 32].into()).unwrap();
+    let storage = InMemoryKeyValueStorage::new();
+    for (lt, balance) in [(30, 3), (10, 1), (20, 2)] {
+        let mut account =
+            Account::with_address_and_ballance(&addr, &CurrencyCollection::with_grams(balance));
+        account.set_last_tr_time(lt);
+        store_state(&storage, address, &account).await.unwrap();
+    }
+
+    let stored_lt = |account: Option<Account>| account.and_then(|account| account.last_tr_time());
+    assert_eq!(stored_lt(stored_state(&storage, address, 5).await.unwrap()), None);
+    assert_eq!(stored_lt(stored_state(&storage, address, 10).await.unwrap()), Some(10));
+    assert_eq!(stored_lt(stored_state(&storage, address, 29).await.unwrap()), Some(20));
+    assert_eq!(stored_lt(stored_state(&storage, address, u64::MAX).await.unwrap()), Some(30));
+    let other = "-1:2222222222222222222222222222222222222222222222222222222222222222";
+    assert!(stored_state(&storage, other, u64::MAX).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn account_states_are_not_kept_in_context_without_local_storage() {
+    use tvm_block::Account;
+    use tvm_block::CurrencyCollection;
+    use tvm_block::MsgAddressInt;
+
+    use crate::account::history::account_states_storage;
+    use crate::account::history::store_state;
+    use crate::account::history::stored_state;
+
+    let context = Arc::new(ClientContext::new(ClientConfig::default()).unwrap());
+    let address = "0:2222222222222222222222222222222222222222222222222222222222222222";
+    let addr = MsgAddressInt::with_standart(None, 0, [0x22; 32].into()).unwrap();
+    let mut account = Account::with_address_and_ballance(&addr, &CurrencyCollection::with_grams(1));
+    account.set_last_tr_time(10);
+    let storage = account_states_storage(&context).await.unwrap();
+    store_state(storage.as_ref(), address, &account).await.unwrap();
+    assert!(stored_state(storage.as_ref(), address, u64::MAX).await.unwrap().is_some());
+
+    assert!(context.account_states.read().await.is_none());
+    let storage = account_states_storage(&context).await.unwrap();
+    assert!(stored_state(storage.as_ref(), address, u64::MAX).await.unwrap().is_none());
+}
+
+#[test]
+fn replay_tracks_config_account() {
+    use std::collections::VecDeque;
+    use std::sync::atomic::AtomicU64;
+
+    use tvm_block::Account;
+    use tvm_block::ConfigParam18;
+    use tvm_block::ConfigParamEnum;
+    use tvm_block::CurrencyCollection;
+    use tvm_block::Deserializable;
+    use tvm_block::InternalMessageHeader;
+    use tvm_block::Message;
+    use tvm_block::MsgAddressInt;
+    use tvm_block::Serializable;
+    use tvm_block::StateInit;
+    use tvm_block::StoragePrices;
+    use tvm_executor::BlockchainConfig;
+    use tvm_executor::ExecuteParams;
+    use tvm_executor::OrdinaryTransactionExecutor;
+    use tvm_executor::TransactionExecutor;
+    use tvm_types::BuilderData;
+    use tvm_types::Cell;
+    use tvm_types::HashmapType;
+
+    use crate::account::history::ChainTransaction;
+    use crate::account::history::TransactionChain;
+    use crate::account::history::replay_chains;
+
+    let mut config_params = tvm_block::ConfigParams::construct_from_bytes(include_bytes!(
+        "../boc/test_data/block_config.boc"
+    ))
+    .unwrap();
+    // Storage prices differing from the default config ones
+    let mut storage_prices = ConfigParam18::default();
+    storage_prices
+        .insert(&StoragePrices {
+            utime_since: 0,
+            bit_price_ps: 2,
+            cell_price_ps: 1000,
+            mc_bit_price_ps: 2000,
+            mc_cell_price_ps: 1_000_000,
+        })
+        .unwrap();
+    config_params.set_config(ConfigParamEnum::ConfigParam18(storage_prices)).unwrap();
+    let mut config_data = BuilderData::new();
+    config_data
+        .checked_append_reference(config_params.config_params.data().unwrap().clone())
+        .unwrap();
+    let mut state_init = StateInit::default();
+    state_init.set_code(Cell::default());
+    state_init.set_data(config_data.into_cell().unwrap());
+    let config_addr = MsgAddressInt::with_standart(None, -1, [0x55; 32].into()).unwrap();
+    let config_account = Account::active_by_init_code_hash(
+        config_addr.clone(),
+        CurrencyCollection::with_grams(1_000_000_000),
+        1_700_000_000,
+        state_init,
+        false,
+    )
+    .unwrap();
+    let src = MsgAddressInt::with_standart(None, 0, [0x11; 32].into()).unwrap();
+    let addr = MsgAddressInt::with_standart(None, 0, [0x22; 32].into()).unwrap();
+    let mut account = Account::with_address_and_ballance(&addr, &CurrencyCollection::with_grams(1));
+    account.set_last_paid(1_700_000_000);
+    account.update_storage_stat().unwrap();
+
+    // Account transactions are executed with the config stored in the config
+    // account, interleaved with a config account transaction
+    let executor =
+        OrdinaryTransactionExecutor::new(BlockchainConfig::with_config(config_params).unwrap());
+    let initial_account = account.serialize().unwrap();
+    let initial_config_account = config_account.serialize().unwrap();
+    let mut account_root = initial_account.clone();
+    let mut config_account_root = initial_config_account.clone();
+    let mut account_transactions = VecDeque::new();
+    let mut config_transactions = VecDeque::new();
+    for (i, dst) in [&addr, &config_addr, &addr].into_iter().enumerate() {
+        let lt = (i as u64 + 1) * 1_000_000;
+        let header = InternalMessageHeader::with_addresses_and_bounce(
+            src.clone(),
+            dst.clone(),
+            CurrencyCollection::with_grams(1_000_000_000),
+            false,
+        );
+        let params = ExecuteParams {
+            block_unixtime: 1_700_000_000 + i as u32 * 10_000_000,
+            block_lt: lt,
+            last_tr_lt: Arc::new(AtomicU64::new(lt)),
+            ..ExecuteParams::default()
+        };
+        let (root, transactions) = if dst == &addr {
+            (&mut account_root, &mut account_transactions)
+        } else {
+            (&mut config_account_root, &mut config_transactions)
+        };
+        let (transaction, _) = executor
+            .execute_with_libs_and_params(Some(&Message::with_int_header(header)), root, params)
+            .unwrap();
+        transactions.push_back(ChainTransaction { id: i.to_string(), block_lt: lt, transaction });
+    }
+
+    let chain = |root: &Cell, transactions: &VecDeque<ChainTransaction>| TransactionChain {
+        root: root.clone(),
+        transactions: transactions
+            .iter()
+            .map(|next| ChainTransaction {
+                id: next.id.clone(),
+                block_lt: next.block_lt,
+                transaction: next.transaction.clone(),
+            })
+            .collect(),
+    };
+    let mut account = chain(&initial_account, &account_transactions);
+    let mut config_account = chain(&initial_config_account, &config_transactions);
+    let replayed =
+        replay_chains(&mut account, Some(&mut config_account), BlockchainConfig::default(), 0)
+            .unwrap();
+    assert_eq!(replayed, 2);
+    assert_eq!(account.root.repr_hash(), account_root.repr_hash());
+    assert_eq!(config_account.root.repr_hash(), config_account_root.repr_hash());
+
+    // Without the config account the provided config is used for every
+    // transaction and the recorded state hashes do not match
+    let mut account = chain(&initial_account, &account_transactions);
+    let err = replay_chains(&mut account, None, BlockchainConfig::default(), 0).unwrap_err();
+    assert_eq!(err.code(), crate::tvm::ErrorCode::AccountStateMismatch as u32);
+}
@@ -94,6 +94,9 @@ pub struct ClientContext {
     // proofs module
     pub(crate) proofs_storage: RwLock<Option<Arc<dyn KeyValueStorage>>>,
 
+    // account module
+    pub(crate) account_states: RwLock<Option<Arc<dyn KeyValueStorage>>>,
+
     // debot module
     pub(crate) debots: LockfreeMap<u32, Mutex<DEngine>>,
 }
@@ -155,6 +158,7 @@ impl ClientContext {
             bocs,
             app_requests: Mutex::new(HashMap::new()),
             proofs_storage: Default::default(),
+            account_states: Default::default(),
             derived_keys: DerivedKeys::new(env),
             next_id: AtomicU32::new(1),
         })
@@ -15,6 +15,7 @@ use crate::crypto::Error;
 use crate::crypto::MnemonicDictionary;
 use crate::crypto::RegisteredEncryptionBox;
 use crate::crypto::RegisteredSigningBox;
+use crate::crypto::ResultOfMnemonicSplitShares;
 use crate::crypto::SigningBox;
 use crate::crypto::boxes::crypto_box::encryption::decrypt_secret;
 use crate::crypto::boxes::crypto_box::encryption::encrypt_secret;
@@ -28,6 +29,8 @@ use crate::crypto::internal::SecretString;
 use crate::crypto::mnemonic::mnemonics;
 use crate::crypto::register_encryption_box;
 use crate::crypto::register_signing_box;
+use crate::crypto::shamir::combine_seed_phrase_shares;
+use crate::crypto::shamir::split_seed_phrase;
 use crate::encoding::base64_decode;
 use crate::error::ClientResult;
 
@@ -115,6 +118,15 @@ pub enum CryptoBoxSecret {
     /// on your side.
     PredefinedSeedPhrase { phrase: String, dictionary: MnemonicDictionary, wordcount: u8 },
 
+    /// Restores crypto box instance from shares of a seed phrase, produced by
+    /// `mnemonic_split_shares` or `get_crypto_box_seed_phrase_shares`.
+    /// At least `threshold` shares are required. Dictionary and word count
+    /// are stored in the shares.
+    ///
+    /// This type should be used only upon the first wallet initialization, all
+    /// further initializations should use `EncryptedSecret` type instead.
+    SeedPhraseShares { shares: Vec<String> },
+
     /// Use this type for wallet reinitializations, when you already have
     /// `encrypted_secret` on hands. To get `encrypted_secret`, use
     /// `get_crypto_box_info` function after you initialized your crypto box
@@ -208,6 +220,18 @@ pub async fn create_crypto_box(
             .await?
         }
 
+        CryptoBoxSecret::SeedPhraseShares { shares } => {
+            let (phrase, dictionary, wordcount) =
+                combine_seed_phrase_shares(&context.config.crypto, shares)?;
+            encrypt_secret(
+                context.clone(),
+                &SecretInternal::SeedPhrase { phrase, dictionary, wordcount },
+                &password_provider,
+                &params.secret_encryption_salt,
+            )
+            .await?
+        }
+
         CryptoBoxSecret::EncryptedSecret { encrypted_secret } => {
             SecretBuf(base64_decode(encrypted_secret)?)
         }
@@ -268,6 +292,45 @@ pub async fn get_crypto_box_seed_phrase(
     Ok(ResultOfGetCryptoBoxSeedPhrase { phrase: phrase.0.clone(), dictionary, wordcount })
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ParamsOfGetCryptoBoxSeedPhraseShares {
+    /// Crypto Box Handle.
+    pub handle: u32,
+    /// Number of shares required to restore the seed phrase.
+    pub threshold: u8,
+    /// Total number of shares. Maximum is 16.
+    pub share_count: u8,
+}
+
+/// Get Crypto Box Seed Phrase Shares.
+///
+/// Splits the seed phrase into `share_count` shares, any `threshold` of which
+/// restore the crypto box with `SeedPhraseShares` secret type.
+///
+/// Attention! Hand the shares out to their keepers and overwrite them with
+/// zeroes ASAP.
+#[api_function]
+pub async fn get_crypto_box_seed_phrase_shares(
+    context: Arc<ClientContext>,
+    params: ParamsOfGetCryptoBoxSeedPhraseShares,
+) -> ClientResult<ResultOfMnemonicSplitShares> {
+    let seed_phrase = get_crypto_box_seed_phrase(
+        context.clone(),
+        RegisteredCryptoBox { handle: CryptoBoxHandle(params.handle) },
+    )
+    .await?;
+    Ok(ResultOfMnemonicSplitShares {
+        shares: split_seed_phrase(
+            &context.config.crypto,
+            &seed_phrase.phrase,
+            Some(seed_phrase.dictionary),
+            Some(seed_phrase.wordcount),
+            params.threshold,
+            params.share_count,
+        )?,
+    })
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
 pub struct ResultOfGetCryptoBoxInfo {
     /// Secret (seed phrase) encrypted with salt and password.
@@ -15,6 +15,9 @@ use crate::client::ClientContext;
 use crate::crypto::CryptoBoxHandle;
 use crate::crypto::Error;
 use crate::crypto::KeyPair;
+use crate::crypto::SignatureScheme;
+use crate::crypto::internal::SecretBuf;
+use crate::crypto::internal::hex_decode_secret;
 use crate::error::ClientResult;
 
 #[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
@@ -32,6 +35,10 @@ pub trait SigningBox: Send + Sync {
     async fn get_public_key(&self, context: Arc<ClientContext>) -> ClientResult<Vec<u8>>;
     /// Sign data with key pair
     async fn sign(&self, context: Arc<ClientContext>, unsigned: &[u8]) -> ClientResult<Vec<u8>>;
+    /// Signature scheme of the key pair
+    fn scheme(&self) -> SignatureScheme {
+        SignatureScheme::Ed25519
+    }
     /// Zeroize all secret data
     async fn drop_secret(&self, _crypto_box_handle: CryptoBoxHandle) {
         // Not implemented by default, but must be implemented for signing boxes
@@ -64,6 +71,34 @@ impl SigningBox for KeysSigningBox {
     }
 }
 
+pub(crate) struct SchemeSigningBox {
+    scheme: SignatureScheme,
+    secret: SecretBuf,
+}
+
+impl SchemeSigningBox {
+    pub fn new(scheme: SignatureScheme, secret: SecretBuf) -> ClientResult<Self> {
+        // fail early on a malformed secret
+        scheme.public_key(&secret.0)?;
+        Ok(Self { scheme, secret })
+    }
+}
+
+#[async_trait::async_trait]
+impl SigningBox for SchemeSigningBox {
+    async fn get_public_key(&self, _context: Arc<ClientContext>) -> ClientResult<Vec<u8>> {
+        self.scheme.public_key(&self.secret.0)
+    }
+
+    async fn sign(&self, _context: Arc<ClientContext>, unsigned: &[u8]) -> ClientResult<Vec<u8>> {
+        self.scheme.sign(&self.secret.0, unsigned)
+    }
+
+    fn scheme(&self) -> SignatureScheme {
+        self.scheme
+    }
+}
+
 /// Creates a default signing box implementation.
 #[api_function]
 pub async fn get_signing_box(
@@ -77,6 +112,36 @@ pub async fn get_signing_box(
     Ok(RegisteredSigningBox { handle: SigningBoxHandle(id) })
 }
 
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ParamsOfGetSigningBoxWithScheme {
+    /// Signature scheme of the key pair.
+    pub scheme: SignatureScheme,
+    /// Key pair of the signature scheme. Keys are encoded with `hex`.
+    pub keys: KeyPair,
+}
+
+/// Creates a signing box for the key pair of the given signature scheme.
+///
+/// Messages encoded with `Signer::SigningBox` get the signature of the box
+/// scheme. The `pubkey` header of ABI is ed25519 only, so it is not filled for
+/// other schemes and the contract must get the signer's key elsewhere.
+#[api_function]
+pub async fn get_signing_box_with_scheme(
+    context: Arc<ClientContext>,
+    params: ParamsOfGetSigningBoxWithScheme,
+) -> ClientResult<RegisteredSigningBox> {
+    let secret = hex_decode_secret(&params.keys.secret)
+        .map_err(|err| Error::invalid_secret_key(err, &params.keys.secret))?;
+    let public = params.scheme.public_key(&secret.0)?;
+    if !params.keys.public.is_empty() && hex::encode(public) != params.keys.public.to_lowercase() {
+        return Err(Error::invalid_public_key(
+            "public key does not match the secret key",
+            &params.keys.public,
+        ));
+    }
+    register_signing_box(context, SchemeSigningBox::new(params.scheme, secret)?).await
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
 pub struct RegisteredSigningBox {
     /// Handle of the signing box.
@@ -117,6 +182,27 @@ pub async fn signing_box_get_public_key(
     Ok(ResultOfSigningBoxGetPublicKey { pubkey: hex::encode(key) })
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ResultOfSigningBoxGetScheme {
+    /// Signature scheme of the signing box.
+    pub scheme: SignatureScheme,
+}
+
+/// Returns signature scheme of signing box.
+#[api_function]
+pub async fn signing_box_get_scheme(
+    context: Arc<ClientContext>,
+    params: RegisteredSigningBox,
+) -> ClientResult<ResultOfSigningBoxGetScheme> {
+    let signing_box = context
+        .boxes
+        .signing_boxes
+        .get(&params.handle.0)
+        .ok_or(Error::signing_box_not_registered(params.handle.0))?;
+
+    Ok(ResultOfSigningBoxGetScheme { scheme: signing_box.1.scheme() })
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
 pub struct ParamsOfSigningBoxSign {
     /// Signing Box handle.
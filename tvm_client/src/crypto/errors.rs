@@ -1,6 +1,7 @@
 use std::fmt::Display;
 
 use super::CipherMode;
+use super::SignatureScheme;
 use super::keys::strip_secret;
 use crate::error::ClientError;
 
@@ -38,6 +39,15 @@ pub enum ErrorCode {
     CryptoBoxSecretSerializationError = 132,
     CryptoBoxSecretDeserializationError = 133,
     InvalidNonceSize = 134,
+    KeystoreUnsupportedVersion = 135,
+    KeystoreAliasNotFound = 136,
+    KeystoreAliasAlreadyExists = 137,
+    KeystoreInvalidPassword = 138,
+    InvalidShamirParams = 139,
+    InvalidShamirShare = 140,
+    ShamirRecoveryFailed = 141,
+    UnsupportedSignatureScheme = 142,
+    InvalidSignatureSchemeKey = 143,
 }
 
 pub struct Error;
@@ -224,4 +234,55 @@ impl Error {
             ),
         )
     }
+
+    pub fn keystore_unsupported_version(actual: u32, expected: u32) -> ClientError {
+        error(
+            ErrorCode::KeystoreUnsupportedVersion,
+            format!("Unsupported keystore version {}. Expected {}.", actual, expected),
+        )
+    }
+
+    pub fn keystore_alias_not_found(alias: &str) -> ClientError {
+        error(ErrorCode::KeystoreAliasNotFound, format!("Keystore has no key with alias {}", alias))
+    }
+
+    pub fn keystore_alias_already_exists(alias: &str) -> ClientError {
+        error(
+            ErrorCode::KeystoreAliasAlreadyExists,
+            format!("Keystore already has a key with alias {}", alias),
+        )
+    }
+
+    pub fn keystore_invalid_password(alias: &str) -> ClientError {
+        error(
+            ErrorCode::KeystoreInvalidPassword,
+            format!("Invalid password for keystore key {}", alias),
+        )
+    }
+
+    pub fn invalid_shamir_params(reason: &str) -> ClientError {
+        error(ErrorCode::InvalidShamirParams, format!("Invalid secret sharing params: {}", reason))
+    }
+
+    pub fn invalid_shamir_share(reason: &str) -> ClientError {
+        error(ErrorCode::InvalidShamirShare, format!("Invalid secret share: {}", reason))
+    }
+
+    pub fn shamir_recovery_failed(reason: &str) -> ClientError {
+        error(ErrorCode::ShamirRecoveryFailed, format!("Secret recovery failed: {}", reason))
+    }
+
+    pub fn unsupported_signature_scheme(scheme: &SignatureScheme) -> ClientError {
+        error(
+            ErrorCode::UnsupportedSignatureScheme,
+            format!("Signature scheme {:?} is not supported on this platform", scheme),
+        )
+    }
+
+    pub fn invalid_signature_scheme_key<E: Display>(
+        scheme: &SignatureScheme,
+        err: E,
+    ) -> ClientError {
+        error(ErrorCode::InvalidSignatureSchemeKey, format!("Invalid {:?} key: {}", scheme, err))
+    }
 }
@@ -0,0 +1,416 @@
+// Copyright 2018-2021 TON Labs LTD.
+//
+// Licensed under the SOFTWARE EVALUATION License (the "License"); you may not
+// use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific TON DEV software governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use ed25519_dalek::SigningKey;
+use hmac::Hmac;
+use hmac::Mac;
+use rand::RngCore;
+use sha2::Sha256;
+use tvm_types::base64_encode;
+use zeroize::ZeroizeOnDrop;
+
+use crate::client::ClientContext;
+use crate::crypto::Error;
+use crate::crypto::KeyPair;
+use crate::crypto::ParamsOfChaCha20;
+use crate::crypto::RegisteredSigningBox;
+use crate::crypto::SigningBoxHandle;
+use crate::crypto::boxes::signing_box::KeysSigningBox;
+use crate::crypto::chacha20;
+use crate::crypto::internal::SecretBuf;
+use crate::crypto::internal::key256;
+use crate::encoding::base64_decode;
+use crate::encoding::hex_decode;
+use crate::error::ClientResult;
+
+/// Current version of the keystore format.
+pub const KEYSTORE_VERSION: u32 = 1;
+
+const DERIVED_KEY_LEN: usize = 64;
+const SALT_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+fn default_keystore_version() -> u32 {
+    KEYSTORE_VERSION
+}
+
+/// Key derivation function used to turn a password into an encryption key.
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, PartialEq)]
+#[serde(tag = "type")]
+pub enum KeystoreKdf {
+    /// `scrypt` key derivation, see `crypto.scrypt`.
+    Scrypt {
+        /// The log2 of the Scrypt parameter `N`.
+        log_n: u8,
+        /// The Scrypt parameter `r`.
+        r: u32,
+        /// The Scrypt parameter `p`.
+        p: u32,
+        /// Random salt. Encoded with `base64`.
+        salt: String,
+    },
+}
+
+/// Cipher used to encrypt the secret key.
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, PartialEq)]
+#[serde(tag = "type")]
+pub enum KeystoreCipher {
+    /// `chacha20` stream cipher, see `crypto.chacha20`.
+    ChaCha20 {
+        /// 96-bit nonce. Encoded with `hex`.
+        nonce: String,
+    },
+}
+
+/// Single encrypted key pair stored in a keystore.
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, PartialEq)]
+pub struct KeystoreEntry {
+    /// Alias the key pair is addressed by.
+    pub alias: String,
+    /// Public key. Encoded with `hex`.
+    pub public: String,
+    /// Key derivation function and its parameters.
+    pub kdf: KeystoreKdf,
+    /// Cipher and its parameters.
+    pub cipher: KeystoreCipher,
+    /// Encrypted secret key. Encoded with `base64`.
+    pub ciphertext: String,
+    /// HMAC-SHA256 of the cipher parameters and the ciphertext computed with
+    /// the second half of the derived key. Encoded with `hex`.
+    pub mac: String,
+}
+
+/// Keystore holding several encrypted key pairs addressed by alias.
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, PartialEq)]
+pub struct Keystore {
+    /// Version of the keystore format.
+    #[serde(default = "default_keystore_version")]
+    pub version: u32,
+    /// Encrypted key pairs.
+    #[serde(default)]
+    pub accounts: Vec<KeystoreEntry>,
+}
+
+impl Default for Keystore {
+    fn default() -> Self {
+        Self { version: KEYSTORE_VERSION, accounts: Vec::new() }
+    }
+}
+
+impl Keystore {
+    fn check_version(&self) -> ClientResult<()> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(Error::keystore_unsupported_version(self.version, KEYSTORE_VERSION));
+        }
+        Ok(())
+    }
+
+    fn find(&self, alias: &str) -> ClientResult<&KeystoreEntry> {
+        self.accounts
+            .iter()
+            .find(|entry| entry.alias == alias)
+            .ok_or_else(|| Error::keystore_alias_not_found(alias))
+    }
+}
+
+/// Scrypt parameters used for a new keystore entry.
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, PartialEq)]
+pub struct KeystoreScryptParams {
+    /// The log2 of the Scrypt parameter `N`. Default is `15`.
+    pub log_n: Option<u8>,
+    /// The Scrypt parameter `r`. Default is `8`.
+    pub r: Option<u32>,
+    /// The Scrypt parameter `p`. Default is `1`.
+    pub p: Option<u32>,
+}
+
+fn derive_key(password: &[u8], kdf: &KeystoreKdf) -> ClientResult<SecretBuf> {
+    match kdf {
+        KeystoreKdf::Scrypt { log_n, r, p, salt } => {
+            let params = scrypt::Params::new(*log_n, *r, *p, DERIVED_KEY_LEN)
+                .map_err(Error::scrypt_failed)?;
+            let salt = base64_decode(salt)?;
+            let mut key = SecretBuf(vec![0; DERIVED_KEY_LEN]);
+            scrypt::scrypt(password, &salt, &params, &mut key.0).map_err(Error::scrypt_failed)?;
+            Ok(key)
+        }
+    }
+}
+
+fn calc_mac(mac_key: &[u8], cipher: &KeystoreCipher, ciphertext: &[u8]) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(mac_key).unwrap();
+    match cipher {
+        KeystoreCipher::ChaCha20 { nonce } => mac.update(nonce.as_bytes()),
+    }
+    mac.update(ciphertext);
+    mac
+}
+
+fn apply_cipher(
+    context: Arc<ClientContext>,
+    cipher: &KeystoreCipher,
+    key: &[u8],
+    data: &[u8],
+) -> ClientResult<SecretBuf> {
+    match cipher {
+        KeystoreCipher::ChaCha20 { nonce } => {
+            let result = chacha20(
+                context,
+                ParamsOfChaCha20 {
+                    data: base64_encode(data),
+                    key: hex::encode(key),
+                    nonce: nonce.clone(),
+                },
+            )?;
+            Ok(SecretBuf(base64_decode(&result.data)?))
+        }
+    }
+}
+
+fn encrypt_entry(
+    context: Arc<ClientContext>,
+    alias: &str,
+    keys: &KeyPair,
+    password: &[u8],
+    params: Option<&KeystoreScryptParams>,
+) -> ClientResult<KeystoreEntry> {
+    let sign_key = keys.decode()?;
+    let mut rng = rand::thread_rng();
+    let mut salt = [0u8; SALT_LEN];
+    rng.fill_bytes(&mut salt);
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill_bytes(&mut nonce);
+
+    let kdf = KeystoreKdf::Scrypt {
+        log_n: params.and_then(|params| params.log_n).unwrap_or(15),
+        r: params.and_then(|params| params.r).unwrap_or(8),
+        p: params.and_then(|params| params.p).unwrap_or(1),
+        salt: base64_encode(salt),
+    };
+    let cipher = KeystoreCipher::ChaCha20 { nonce: hex::encode(nonce) };
+    let derived_key = derive_key(password, &kdf)?;
+    let (cipher_key, mac_key) = derived_key.split_at(DERIVED_KEY_LEN / 2);
+    let secret = SecretBuf(sign_key.to_bytes().to_vec());
+    let ciphertext = apply_cipher(context, &cipher, cipher_key, &secret)?;
+    let mac = calc_mac(mac_key, &cipher, &ciphertext).finalize().into_bytes();
+
+    Ok(KeystoreEntry {
+        alias: alias.to_string(),
+        public: hex::encode(sign_key.verifying_key().as_bytes()),
+        kdf,
+        cipher,
+        ciphertext: base64_encode(&ciphertext.0),
+        mac: hex::encode(mac),
+    })
+}
+
+fn decrypt_entry(
+    context: Arc<ClientContext>,
+    entry: &KeystoreEntry,
+    password: &[u8],
+) -> ClientResult<SigningKey> {
+    let derived_key = derive_key(password, &entry.kdf)?;
+    let (cipher_key, mac_key) = derived_key.split_at(DERIVED_KEY_LEN / 2);
+    let ciphertext = base64_decode(&entry.ciphertext)?;
+    calc_mac(mac_key, &entry.cipher, &ciphertext)
+        .verify_slice(&hex_decode(&entry.mac)?)
+        .map_err(|_| Error::keystore_invalid_password(&entry.alias))?;
+
+    let secret = apply_cipher(context, &entry.cipher, cipher_key, &ciphertext)?;
+    let sign_key = SigningKey::from_bytes(&key256(&secret)?.0);
+    if hex::encode(sign_key.verifying_key().as_bytes()) != entry.public {
+        return Err(Error::invalid_public_key(
+            "public key doesn't correspond to secret key",
+            &entry.public,
+        ));
+    }
+    Ok(sign_key)
+}
+
+//------------------------------------------------------------------------------- keystore_add_key
+
+#[derive(Serialize, Deserialize, ApiType, Default, ZeroizeOnDrop)]
+pub struct ParamsOfKeystoreAddKey {
+    /// Keystore to add the key pair to. If not specified, a new empty
+    /// keystore is created.
+    #[zeroize(skip)]
+    pub keystore: Option<Keystore>,
+    /// Alias for the key pair. Must be unique within the keystore.
+    #[zeroize(skip)]
+    pub alias: String,
+    /// Key pair to be stored.
+    #[zeroize(skip)]
+    pub keys: KeyPair,
+    /// Password used to encrypt the key pair. Must be encoded with `base64`.
+    pub password: String,
+    /// Scrypt parameters. Recommended values are used if not specified.
+    #[zeroize(skip)]
+    pub kdf_params: Option<KeystoreScryptParams>,
+    /// Replace an existing key pair with the same alias. Default is `false`.
+    #[zeroize(skip)]
+    pub overwrite: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default)]
+pub struct ResultOfKeystoreAddKey {
+    /// Updated keystore.
+    pub keystore: Keystore,
+}
+
+/// Encrypts a key pair with a password and adds it to a keystore.
+///
+/// The secret key is encrypted with `chacha20` using the first half of a key
+/// derived from the password with `scrypt`. The second half of the derived
+/// key is used to compute an HMAC-SHA256 over the ciphertext, so a wrong
+/// password is detected before the secret is decrypted.
+#[api_function]
+pub fn keystore_add_key(
+    context: Arc<ClientContext>,
+    params: ParamsOfKeystoreAddKey,
+) -> ClientResult<ResultOfKeystoreAddKey> {
+    let mut keystore = params.keystore.clone().unwrap_or_default();
+    keystore.check_version()?;
+    let overwrite = params.overwrite.unwrap_or(false);
+    if !overwrite && keystore.accounts.iter().any(|entry| entry.alias == params.alias) {
+        return Err(Error::keystore_alias_already_exists(&params.alias));
+    }
+    let password = SecretBuf(base64_decode(&params.password)?);
+    let entry =
+        encrypt_entry(context, &params.alias, &params.keys, &password, params.kdf_params.as_ref())?;
+    match keystore.accounts.iter_mut().find(|existing| existing.alias == params.alias) {
+        Some(existing) => *existing = entry,
+        None => keystore.accounts.push(entry),
+    }
+    Ok(ResultOfKeystoreAddKey { keystore })
+}
+
+//------------------------------------------------------------------------------- keystore_get_key
+
+#[derive(Serialize, Deserialize, ApiType, Default, ZeroizeOnDrop)]
+pub struct ParamsOfKeystoreGetKey {
+    /// Keystore containing the key pair.
+    #[zeroize(skip)]
+    pub keystore: Keystore,
+    /// Alias of the key pair.
+    #[zeroize(skip)]
+    pub alias: String,
+    /// Password the key pair was encrypted with. Must be encoded with
+    /// `base64`.
+    pub password: String,
+}
+
+/// Decrypts a key pair stored in a keystore.
+///
+/// Attention! Store the returned key pair in your application for a very
+/// short period of time and overwrite it with zeroes ASAP.
+#[api_function]
+pub fn keystore_get_key(
+    context: Arc<ClientContext>,
+    params: ParamsOfKeystoreGetKey,
+) -> ClientResult<KeyPair> {
+    params.keystore.check_version()?;
+    let entry = params.keystore.find(&params.alias)?;
+    let password = SecretBuf(base64_decode(&params.password)?);
+    let sign_key = decrypt_entry(context, entry, &password)?;
+    Ok(KeyPair::new(entry.public.clone(), hex::encode(sign_key.to_bytes())))
+}
+
+//----------------------------------------------------------------------- keystore_get_signing_box
+
+/// Decrypts a key pair stored in a keystore and registers a signing box
+/// with it, so the secret key never leaves the core library.
+#[api_function]
+pub async fn keystore_get_signing_box(
+    context: Arc<ClientContext>,
+    params: ParamsOfKeystoreGetKey,
+) -> ClientResult<RegisteredSigningBox> {
+    params.keystore.check_version()?;
+    let entry = params.keystore.find(&params.alias)?;
+    let password = SecretBuf(base64_decode(&params.password)?);
+    let signing_box = KeysSigningBox::new(decrypt_entry(context.clone(), entry, &password)?);
+    let id = context.get_next_id();
+    context.boxes.signing_boxes.insert(id, Box::new(signing_box));
+
+    Ok(RegisteredSigningBox { handle: SigningBoxHandle(id) })
+}
+
+//----------------------------------------------------------------------------- keystore_list_keys
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfKeystoreListKeys {
+    /// Keystore to list.
+    pub keystore: Keystore,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct KeystoreKeyInfo {
+    /// Alias of the key pair.
+    pub alias: String,
+    /// Public key. Encoded with `hex`.
+    pub public: String,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfKeystoreListKeys {
+    /// Aliases and public keys of all stored key pairs.
+    pub keys: Vec<KeystoreKeyInfo>,
+}
+
+/// Lists key pairs stored in a keystore without decrypting them.
+#[api_function]
+pub fn keystore_list_keys(
+    _context: Arc<ClientContext>,
+    params: ParamsOfKeystoreListKeys,
+) -> ClientResult<ResultOfKeystoreListKeys> {
+    params.keystore.check_version()?;
+    Ok(ResultOfKeystoreListKeys {
+        keys: params
+            .keystore
+            .accounts
+            .iter()
+            .map(|entry| KeystoreKeyInfo {
+                alias: entry.alias.clone(),
+                public: entry.public.clone(),
+            })
+            .collect(),
+    })
+}
+
+//---------------------------------------------------------------------------- keystore_remove_key
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfKeystoreRemoveKey {
+    /// Keystore to remove the key pair from.
+    pub keystore: Keystore,
+    /// Alias of the key pair.
+    pub alias: String,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfKeystoreRemoveKey {
+    /// Updated keystore.
+    pub keystore: Keystore,
+}
+
+/// Removes a key pair from a keystore.
+#[api_function]
+pub fn keystore_remove_key(
+    _context: Arc<ClientContext>,
+    params: ParamsOfKeystoreRemoveKey,
+) -> ClientResult<ResultOfKeystoreRemoveKey> {
+    params.keystore.check_version()?;
+    params.keystore.find(&params.alias)?;
+    let mut keystore = params.keystore;
+    keystore.accounts.retain(|entry| entry.alias != params.alias);
+    Ok(ResultOfKeystoreRemoveKey { keystore })
+}
@@ -23,6 +23,7 @@ use crate::client::ClientContext;
 use crate::crypto;
 use crate::crypto::CryptoConfig;
 use crate::crypto::hdkey::HDPrivateKey;
+use crate::crypto::internal::SecretBuf;
 use crate::crypto::internal::hex_decode_secret;
 use crate::crypto::internal::hmac_sha512;
 use crate::crypto::internal::key256;
@@ -265,6 +266,7 @@ pub trait CryptoMnemonic {
         path: &str,
     ) -> ClientResult<KeyPair>;
     fn phrase_from_entropy(&self, entropy: &[u8]) -> ClientResult<String>;
+    fn entropy_from_phrase(&self, phrase: &str) -> ClientResult<SecretBuf>;
     fn is_phrase_valid(&self, phrase: &str) -> ClientResult<bool>;
 }
 
@@ -329,6 +331,12 @@ impl CryptoMnemonic for Bip39Mnemonic {
         Ok(mnemonic.phrase().into())
     }
 
+    fn entropy_from_phrase(&self, phrase: &str) -> ClientResult<SecretBuf> {
+        let mnemonic = Mnemonic::from_phrase(phrase, self.language)
+            .map_err(|_| crypto::Error::bip39_invalid_phrase(phrase))?;
+        Ok(SecretBuf(mnemonic.entropy().to_vec()))
+    }
+
     fn is_phrase_valid(&self, phrase: &str) -> ClientResult<bool> {
         Ok(Mnemonic::validate(phrase, self.language).is_ok())
     }
@@ -358,6 +366,23 @@ impl TonMnemonic {
         words
     }
 
+    fn bytes_from_words(&self, words: &[&str]) -> ClientResult<SecretBuf> {
+        let mut bytes = SecretBuf(vec![0; (self.word_count as usize * 11).div_ceil(8)]);
+        for (i, word) in words.iter().enumerate() {
+            let word_i = TVM_WORDS
+                .iter()
+                .position(|w| w == word)
+                .ok_or_else(|| crypto::Error::bip39_invalid_phrase(word))?;
+            for j in 0usize..11 {
+                let offset = i * 11 + j;
+                if word_i & (1 << j) != 0 {
+                    bytes.0[offset / 8] |= (1 << (offset & 7)) as u8;
+                }
+            }
+        }
+        Ok(bytes)
+    }
+
     fn entropy_from_string(string: &str) -> [u8; 64] {
         hmac_sha512(string.as_bytes(), &[])
     }
@@ -420,7 +445,9 @@ impl CryptoMnemonic for TonMnemonic {
     }
 
     fn phrase_from_entropy(&self, entropy: &[u8]) -> ClientResult<String> {
-        if entropy.len() != 24 * 11 / 8 {
+        if entropy.len() != 24 * 11 / 8
+            && entropy.len() != (self.word_count as usize * 11).div_ceil(8)
+        {
             return Err(crypto::Error::mnemonic_from_entropy_failed("Invalid entropy size"));
         }
         let phrase = self.words_from_bytes(entropy).join(" ");
@@ -431,12 +458,17 @@ impl CryptoMnemonic for TonMnemonic {
         }
     }
 
+    fn entropy_from_phrase(&self, phrase: &str) -> ClientResult<SecretBuf> {
+        check_phrase(self, phrase)?;
+        self.bytes_from_words(&phrase.split(' ').collect::<Vec<_>>())
+    }
+
     fn is_phrase_valid(&self, phrase: &str) -> ClientResult<bool> {
         Ok(self.internal_is_phrase_valid(phrase))
     }
 }
 
-static TVM_WORDS: [&str; 2048] = [
+pub(super) static TVM_WORDS: [&str; 2048] = [
     "abandon", "ability", "able", "about", "above", "absent", "absorb", "abstract", "absurd",
     "abuse", "access", "accident", "account", "accuse", "achieve", "acid", "acoustic", "acquire",
     "across", "act", "action", "actor", "actress", "actual", "adapt", "add", "addict", "address",
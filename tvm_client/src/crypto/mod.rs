@@ -16,9 +16,13 @@ pub(crate) mod hash;
 pub(crate) mod hdkey;
 pub(crate) mod internal;
 pub(crate) mod keys;
+pub(crate) mod keystore;
 pub(crate) mod math;
 pub(crate) mod mnemonic;
+pub(crate) mod multisig;
 pub(crate) mod nacl;
+pub(crate) mod shamir;
+pub(crate) mod signature_scheme;
 
 pub use errors::Error;
 pub use errors::ErrorCode;
@@ -40,6 +44,7 @@ pub use crate::crypto::boxes::crypto_box::CryptoBoxSecret;
 pub use crate::crypto::boxes::crypto_box::NaclBoxParamsCB;
 pub use crate::crypto::boxes::crypto_box::NaclSecretBoxParamsCB;
 pub use crate::crypto::boxes::crypto_box::ParamsOfCreateCryptoBox;
+pub use crate::crypto::boxes::crypto_box::ParamsOfGetCryptoBoxSeedPhraseShares;
 pub use crate::crypto::boxes::crypto_box::ParamsOfGetSigningBoxFromCryptoBox;
 pub use crate::crypto::boxes::crypto_box::RegisteredCryptoBox;
 pub use crate::crypto::boxes::crypto_box::ResultOfGetCryptoBoxInfo;
@@ -49,6 +54,7 @@ pub use crate::crypto::boxes::crypto_box::clear_crypto_box_secret_cache;
 pub use crate::crypto::boxes::crypto_box::create_crypto_box;
 pub use crate::crypto::boxes::crypto_box::get_crypto_box_info;
 pub use crate::crypto::boxes::crypto_box::get_crypto_box_seed_phrase;
+pub use crate::crypto::boxes::crypto_box::get_crypto_box_seed_phrase_shares;
 pub use crate::crypto::boxes::crypto_box::get_encryption_box_from_crypto_box;
 pub use crate::crypto::boxes::crypto_box::get_signing_box_from_crypto_box;
 pub use crate::crypto::boxes::crypto_box::remove_crypto_box;
@@ -79,16 +85,20 @@ pub use crate::crypto::boxes::encryption_box::nacl_secret_box::NaclSecretBoxPara
 pub use crate::crypto::boxes::encryption_box::nacl_secret_box::NaclSecretEncryptionBox;
 pub use crate::crypto::boxes::encryption_box::register_encryption_box;
 pub use crate::crypto::boxes::encryption_box::remove_encryption_box;
+pub use crate::crypto::boxes::signing_box::ParamsOfGetSigningBoxWithScheme;
 pub use crate::crypto::boxes::signing_box::ParamsOfSigningBoxSign;
 pub use crate::crypto::boxes::signing_box::RegisteredSigningBox;
 pub use crate::crypto::boxes::signing_box::ResultOfSigningBoxGetPublicKey;
+pub use crate::crypto::boxes::signing_box::ResultOfSigningBoxGetScheme;
 pub use crate::crypto::boxes::signing_box::ResultOfSigningBoxSign;
 pub use crate::crypto::boxes::signing_box::SigningBox;
 pub use crate::crypto::boxes::signing_box::SigningBoxHandle;
 pub use crate::crypto::boxes::signing_box::get_signing_box;
+pub use crate::crypto::boxes::signing_box::get_signing_box_with_scheme;
 pub use crate::crypto::boxes::signing_box::register_signing_box;
 pub use crate::crypto::boxes::signing_box::remove_signing_box;
 pub use crate::crypto::boxes::signing_box::signing_box_get_public_key;
+pub use crate::crypto::boxes::signing_box::signing_box_get_scheme;
 pub use crate::crypto::boxes::signing_box::signing_box_sign;
 pub use crate::crypto::encscrypt::ParamsOfScrypt;
 pub use crate::crypto::encscrypt::ResultOfScrypt;
@@ -123,6 +133,25 @@ pub use crate::crypto::keys::convert_public_key_to_tvm_safe_format;
 pub use crate::crypto::keys::generate_random_sign_keys;
 pub use crate::crypto::keys::sign;
 pub use crate::crypto::keys::verify_signature;
+pub use crate::crypto::keystore::KEYSTORE_VERSION;
+pub use crate::crypto::keystore::Keystore;
+pub use crate::crypto::keystore::KeystoreCipher;
+pub use crate::crypto::keystore::KeystoreEntry;
+pub use crate::crypto::keystore::KeystoreKdf;
+pub use crate::crypto::keystore::KeystoreKeyInfo;
+pub use crate::crypto::keystore::KeystoreScryptParams;
+pub use crate::crypto::keystore::ParamsOfKeystoreAddKey;
+pub use crate::crypto::keystore::ParamsOfKeystoreGetKey;
+pub use crate::crypto::keystore::ParamsOfKeystoreListKeys;
+pub use crate::crypto::keystore::ParamsOfKeystoreRemoveKey;
+pub use crate::crypto::keystore::ResultOfKeystoreAddKey;
+pub use crate::crypto::keystore::ResultOfKeystoreListKeys;
+pub use crate::crypto::keystore::ResultOfKeystoreRemoveKey;
+pub use crate::crypto::keystore::keystore_add_key;
+pub use crate::crypto::keystore::keystore_get_key;
+pub use crate::crypto::keystore::keystore_get_signing_box;
+pub use crate::crypto::keystore::keystore_list_keys;
+pub use crate::crypto::keystore::keystore_remove_key;
 pub use crate::crypto::math::ParamsOfFactorize;
 pub use crate::crypto::math::ParamsOfGenerateRandomBytes;
 pub use crate::crypto::math::ParamsOfModularPower;
@@ -150,6 +179,14 @@ pub use crate::crypto::mnemonic::mnemonic_from_entropy;
 pub use crate::crypto::mnemonic::mnemonic_from_random;
 pub use crate::crypto::mnemonic::mnemonic_verify;
 pub use crate::crypto::mnemonic::mnemonic_words;
+pub use crate::crypto::multisig::CustodianSignature;
+pub use crate::crypto::multisig::ParamsOfMultisigSign;
+pub use crate::crypto::multisig::ParamsOfMultisigVerify;
+pub use crate::crypto::multisig::ResultOfMultisigSign;
+pub use crate::crypto::multisig::ResultOfMultisigVerify;
+pub use crate::crypto::multisig::multisig_sign;
+pub use crate::crypto::multisig::multisig_verify;
+pub use crate::crypto::nacl::NaclDetachedSignature;
 pub use crate::crypto::nacl::ParamsOfNaclBox;
 pub use crate::crypto::nacl::ParamsOfNaclBoxKeyPairFromSecret;
 pub use crate::crypto::nacl::ParamsOfNaclBoxOpen;
@@ -158,6 +195,7 @@ pub use crate::crypto::nacl::ParamsOfNaclSecretBoxOpen;
 pub use crate::crypto::nacl::ParamsOfNaclSign;
 pub use crate::crypto::nacl::ParamsOfNaclSignDetached;
 pub use crate::crypto::nacl::ParamsOfNaclSignDetachedVerify;
+pub use crate::crypto::nacl::ParamsOfNaclSignDetachedVerifyBatch;
 pub use crate::crypto::nacl::ParamsOfNaclSignKeyPairFromSecret;
 pub use crate::crypto::nacl::ParamsOfNaclSignOpen;
 pub use crate::crypto::nacl::ResultOfNaclBox;
@@ -165,6 +203,7 @@ pub use crate::crypto::nacl::ResultOfNaclBoxOpen;
 pub use crate::crypto::nacl::ResultOfNaclSign;
 pub use crate::crypto::nacl::ResultOfNaclSignDetached;
 pub use crate::crypto::nacl::ResultOfNaclSignDetachedVerify;
+pub use crate::crypto::nacl::ResultOfNaclSignDetachedVerifyBatch;
 pub use crate::crypto::nacl::ResultOfNaclSignOpen;
 pub use crate::crypto::nacl::nacl_box;
 pub use crate::crypto::nacl::nacl_box_keypair;
@@ -175,8 +214,22 @@ pub use crate::crypto::nacl::nacl_secret_box_open;
 pub use crate::crypto::nacl::nacl_sign;
 pub use crate::crypto::nacl::nacl_sign_detached;
 pub use crate::crypto::nacl::nacl_sign_detached_verify;
+pub use crate::crypto::nacl::nacl_sign_detached_verify_batch;
 pub use crate::crypto::nacl::nacl_sign_keypair_from_secret_key;
 pub use crate::crypto::nacl::nacl_sign_open;
+pub use crate::crypto::shamir::MAX_SHARE_COUNT;
+pub use crate::crypto::shamir::ParamsOfMnemonicCombineShares;
+pub use crate::crypto::shamir::ParamsOfMnemonicSplitShares;
+pub use crate::crypto::shamir::ResultOfMnemonicCombineShares;
+pub use crate::crypto::shamir::ResultOfMnemonicSplitShares;
+pub use crate::crypto::shamir::mnemonic_combine_shares;
+pub use crate::crypto::shamir::mnemonic_split_shares;
+pub use crate::crypto::signature_scheme::ParamsOfGenerateSignatureSchemeKeypair;
+pub use crate::crypto::signature_scheme::ParamsOfVerifySignatureWithScheme;
+pub use crate::crypto::signature_scheme::ResultOfVerifySignatureWithScheme;
+pub use crate::crypto::signature_scheme::SignatureScheme;
+pub use crate::crypto::signature_scheme::generate_signature_scheme_keypair;
+pub use crate::crypto::signature_scheme::verify_signature_with_scheme;
 
 pub fn default_mnemonic_word_count() -> u8 {
     12
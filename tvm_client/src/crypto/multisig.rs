@@ -0,0 +1,145 @@
+// Copyright 2018-2021 TON Labs LTD.
+//
+// Licensed under the SOFTWARE EVALUATION License (the "License"); you may not
+// use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific TON DEV software governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use crate::client::ClientContext;
+use crate::crypto::NaclDetachedSignature;
+use crate::crypto::ParamsOfSigningBoxSign;
+use crate::crypto::RegisteredSigningBox;
+use crate::crypto::SigningBoxHandle;
+use crate::crypto::internal::decode_public_key;
+use crate::crypto::nacl::verify_detached_batch;
+use crate::crypto::signing_box_get_public_key;
+use crate::crypto::signing_box_sign;
+use crate::error::ClientResult;
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct CustodianSignature {
+    /// Custodian's public key - unprefixed 0-padded to 64 symbols hex string.
+    pub public: String,
+    /// Signature. Encoded with `hex`.
+    pub signature: String,
+}
+
+//----------------------------------------------------------------------------------- multisig_sign
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default)]
+pub struct ParamsOfMultisigSign {
+    /// Data signed by all custodians. Encoded with `base64`.
+    pub unsigned: String,
+    /// Signing box of the custodian.
+    pub signing_box: SigningBoxHandle,
+    /// Signatures collected so far.
+    pub signatures: Vec<CustodianSignature>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default)]
+pub struct ResultOfMultisigSign {
+    /// Collected signatures including the new one. A previous signature of the
+    /// same custodian is replaced.
+    pub signatures: Vec<CustodianSignature>,
+}
+
+/// Adds a custodian signature to the collected signatures.
+#[api_function]
+pub async fn multisig_sign(
+    context: Arc<ClientContext>,
+    params: ParamsOfMultisigSign,
+) -> ClientResult<ResultOfMultisigSign> {
+    let public = signing_box_get_public_key(
+        context.clone(),
+        RegisteredSigningBox { handle: params.signing_box.clone() },
+    )
+    .await?
+    .pubkey;
+    let signature = signing_box_sign(
+        context,
+        ParamsOfSigningBoxSign { signing_box: params.signing_box, unsigned: params.unsigned },
+    )
+    .await?
+    .signature;
+    let mut signatures = params.signatures;
+    signatures.retain(|item| !item.public.eq_ignore_ascii_case(&public));
+    signatures.push(CustodianSignature { public, signature });
+    Ok(ResultOfMultisigSign { signatures })
+}
+
+//--------------------------------------------------------------------------------- multisig_verify
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default)]
+pub struct ParamsOfMultisigVerify {
+    /// Data signed by custodians. Encoded with `base64`.
+    pub unsigned: String,
+    /// Collected signatures.
+    pub signatures: Vec<CustodianSignature>,
+    /// Public keys of the wallet custodians.
+    pub custodians: Vec<String>,
+    /// Number of confirmations required.
+    pub required: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default)]
+pub struct ResultOfMultisigVerify {
+    /// `true` if at least `required` custodians confirmed the data.
+    pub succeeded: bool,
+    /// Public keys of custodians with valid signatures.
+    pub confirmed: Vec<String>,
+    /// Signatures that are invalid, duplicated or made by a key that is
+    /// not a custodian.
+    pub rejected: Vec<CustodianSignature>,
+}
+
+/// Verifies signatures collected from multisig wallet custodians.
+///
+/// All signatures are verified in one batch. Each custodian is counted once.
+#[api_function]
+pub fn multisig_verify(
+    _context: Arc<ClientContext>,
+    params: ParamsOfMultisigVerify,
+) -> ClientResult<ResultOfMultisigVerify> {
+    let custodians = params
+        .custodians
+        .iter()
+        .map(|custodian| decode_public_key(custodian))
+        .collect::<ClientResult<Vec<_>>>()?;
+    let mut rejected = Vec::new();
+    let mut candidates = Vec::new();
+    for item in params.signatures {
+        match decode_public_key(&item.public) {
+            Ok(public) if custodians.contains(&public) => candidates.push((public, item)),
+            _ => rejected.push(item),
+        }
+    }
+    let batch: Vec<NaclDetachedSignature> = candidates
+        .iter()
+        .map(|(_, item)| NaclDetachedSignature {
+            unsigned: params.unsigned.clone(),
+            signature: item.signature.clone(),
+            public: item.public.clone(),
+        })
+        .collect();
+    let invalid = verify_detached_batch(&batch);
+    let mut confirmed = Vec::new();
+    for (index, (public, item)) in candidates.into_iter().enumerate() {
+        let public = hex::encode(public.as_bytes());
+        if invalid.contains(&(index as u32)) || confirmed.contains(&public) {
+            rejected.push(item);
+        } else {
+            confirmed.push(public);
+        }
+    }
+    Ok(ResultOfMultisigVerify {
+        succeeded: confirmed.len() >= params.required as usize,
+        confirmed,
+        rejected,
+    })
+}
@@ -195,6 +195,86 @@ pub fn nacl_sign_detached_verify(
     Ok(ResultOfNaclSignDetachedVerify { succeeded })
 }
 
+//----------------------------------------------------------------- nacl_sign_detached_verify_batch
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct NaclDetachedSignature {
+    /// Unsigned data. Encoded with `base64`.
+    pub unsigned: String,
+    /// Signature. Encoded with `hex`.
+    pub signature: String,
+    /// Signer's public key - unprefixed 0-padded to 64 symbols hex string.
+    pub public: String,
+}
+
+/// ParamsOfNaclSignDetachedVerifyBatch
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfNaclSignDetachedVerifyBatch {
+    /// Signatures that must be verified.
+    pub signatures: Vec<NaclDetachedSignature>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfNaclSignDetachedVerifyBatch {
+    /// `true` if all signatures are valid.
+    pub succeeded: bool,
+    /// Indices of signatures that failed verification or could not be
+    /// decoded.
+    pub invalid: Vec<u32>,
+}
+
+/// Verifies many signatures at once.
+///
+/// All signatures are checked with a single randomized batch equation, which
+/// is several times faster than checking them one by one. If the batch fails,
+/// signatures are checked one by one to find the invalid ones.
+#[api_function]
+pub fn nacl_sign_detached_verify_batch(
+    _context: std::sync::Arc<ClientContext>,
+    params: ParamsOfNaclSignDetachedVerifyBatch,
+) -> ClientResult<ResultOfNaclSignDetachedVerifyBatch> {
+    let invalid = verify_detached_batch(&params.signatures);
+    Ok(ResultOfNaclSignDetachedVerifyBatch { succeeded: invalid.is_empty(), invalid })
+}
+
+/// Returns indices of invalid signatures.
+pub(crate) fn verify_detached_batch(signatures: &[NaclDetachedSignature]) -> Vec<u32> {
+    let mut invalid = Vec::new();
+    let mut indices = Vec::with_capacity(signatures.len());
+    let mut messages = Vec::with_capacity(signatures.len());
+    let mut decoded_signatures = Vec::with_capacity(signatures.len());
+    let mut keys = Vec::with_capacity(signatures.len());
+    for (index, item) in signatures.iter().enumerate() {
+        let decoded = decode_public_key(&item.public).and_then(|public| {
+            let signature = key512(&hex_decode(&item.signature)?)?;
+            Ok((
+                public,
+                ed25519_dalek::Signature::from_bytes(&signature.0),
+                base64_decode(&item.unsigned)?,
+            ))
+        });
+        match decoded {
+            Ok((public, signature, message)) => {
+                indices.push(index as u32);
+                keys.push(public);
+                decoded_signatures.push(signature);
+                messages.push(message);
+            }
+            Err(_) => invalid.push(index as u32),
+        }
+    }
+    let messages: Vec<&[u8]> = messages.iter().map(|message| message.as_slice()).collect();
+    if ed25519_dalek::verify_batch(&messages, &decoded_signatures, &keys).is_err() {
+        for (i, index) in indices.into_iter().enumerate() {
+            if keys[i].verify(messages[i], &decoded_signatures[i]).is_err() {
+                invalid.push(index);
+            }
+        }
+        invalid.sort_unstable();
+    }
+    invalid
+}
+
 // Box
 #[allow(clippy::type_complexity)]
 fn prepare_to_convert(
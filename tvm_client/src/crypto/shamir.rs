@@ -0,0 +1,374 @@
+// Copyright 2018-2021 TON Labs LTD.
+//
+// Licensed under the SOFTWARE EVALUATION License (the "License"); you may not
+// use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific TON DEV software governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use hmac::Hmac;
+use hmac::Mac;
+use rand::RngCore;
+use sha2::Digest;
+use sha2::Sha256;
+use zeroize::ZeroizeOnDrop;
+
+use crate::client::ClientContext;
+use crate::crypto::CryptoConfig;
+use crate::crypto::Error;
+use crate::crypto::MnemonicDictionary;
+use crate::crypto::internal::SecretBuf;
+use crate::crypto::internal::SecretString;
+use crate::crypto::mnemonic::TVM_WORDS;
+use crate::crypto::mnemonic::mnemonics;
+use crate::error::ClientResult;
+
+/// Maximum number of shares. Share index is stored in 4 bits.
+pub const MAX_SHARE_COUNT: u8 = 16;
+
+const DIGEST_INDEX: u8 = 254;
+const SECRET_INDEX: u8 = 255;
+const DIGEST_LEN: usize = 4;
+// Shortest entropy of a supported seed phrase (12 BIP-39 words).
+const MIN_ENTROPY_LEN: usize = 16;
+const CHECKSUM_LEN: usize = 4;
+// identifier (2 bytes), threshold and index (1 byte), dictionary (1 byte),
+// word count (1 byte)
+const HEADER_LEN: usize = 5;
+const BITS_PER_WORD: usize = 11;
+
+// Exponent and logarithm tables for GF(256) with the Rijndael polynomial
+// x^8 + x^4 + x^3 + x + 1, as used by SLIP-0039.
+const fn gf256_tables() -> ([u8; 255], [u8; 256]) {
+    let mut exp = [0u8; 255];
+    let mut log = [0u8; 256];
+    let mut x: u16 = 1;
+    let mut i = 0;
+    while i < 255 {
+        exp[i] = x as u8;
+        log[x as usize] = i as u8;
+        // multiply by the generator 3
+        x ^= x << 1;
+        if x & 0x100 != 0 {
+            x ^= 0x11b;
+        }
+        i += 1;
+    }
+    (exp, log)
+}
+
+static GF256: ([u8; 255], [u8; 256]) = gf256_tables();
+
+/// Evaluates at `x` the polynomial of the lowest degree passing through the
+/// points `shares`. All shares must have distinct indices and equal length.
+fn interpolate(shares: &[(u8, &[u8])], x: u8) -> SecretBuf {
+    if let Some((_, value)) = shares.iter().find(|(index, _)| *index == x) {
+        return SecretBuf(value.to_vec());
+    }
+    let (exp, log) = &GF256;
+    let log_product: i32 = shares.iter().map(|(index, _)| log[(index ^ x) as usize] as i32).sum();
+    let mut result = SecretBuf(vec![0; shares[0].1.len()]);
+    for (index, value) in shares {
+        let log_basis = (log_product
+            - log[(index ^ x) as usize] as i32
+            - shares
+                .iter()
+                .filter(|(other, _)| other != index)
+                .map(|(other, _)| log[(index ^ other) as usize] as i32)
+                .sum::<i32>())
+        .rem_euclid(255);
+        for (byte, y) in result.0.iter_mut().zip(value.iter()) {
+            if *y != 0 {
+                *byte ^= exp[((log[*y as usize] as i32 + log_basis) % 255) as usize];
+            }
+        }
+    }
+    result
+}
+
+fn random_bytes(len: usize) -> SecretBuf {
+    let mut bytes = SecretBuf(vec![0; len]);
+    rand::thread_rng().fill_bytes(&mut bytes.0);
+    bytes
+}
+
+fn create_digest(random_part: &[u8], secret: &[u8]) -> [u8; DIGEST_LEN] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(random_part).unwrap();
+    mac.update(secret);
+    let mut digest = [0u8; DIGEST_LEN];
+    digest.copy_from_slice(&mac.finalize().into_bytes()[..DIGEST_LEN]);
+    digest
+}
+
+/// Splits `secret` into `share_count` shares so that any `threshold` of them
+/// recover it. Like in SLIP-0039 the secret is stored at x = 255 and a digest
+/// of the secret at x = 254, so that recovery from wrong shares is detected.
+fn split_secret(threshold: u8, share_count: u8, secret: &[u8]) -> Vec<SecretBuf> {
+    if threshold == 1 {
+        return (0..share_count).map(|_| SecretBuf(secret.to_vec())).collect();
+    }
+    let random_share_count = threshold - 2;
+    let mut shares: Vec<SecretBuf> =
+        (0..random_share_count).map(|_| random_bytes(secret.len())).collect();
+    let random_part = random_bytes(secret.len() - DIGEST_LEN);
+    let mut digest_share = SecretBuf(create_digest(&random_part, secret).to_vec());
+    digest_share.0.extend_from_slice(&random_part);
+
+    let mut base_shares: Vec<(u8, &[u8])> =
+        shares.iter().enumerate().map(|(index, share)| (index as u8, share.0.as_slice())).collect();
+    base_shares.push((DIGEST_INDEX, &digest_share));
+    base_shares.push((SECRET_INDEX, secret));
+    let interpolated: Vec<SecretBuf> =
+        (random_share_count..share_count).map(|index| interpolate(&base_shares, index)).collect();
+    shares.extend(interpolated);
+    shares
+}
+
+fn recover_secret(threshold: u8, shares: &[(u8, &[u8])]) -> ClientResult<SecretBuf> {
+    if threshold == 1 {
+        return Ok(SecretBuf(shares[0].1.to_vec()));
+    }
+    let secret = interpolate(shares, SECRET_INDEX);
+    let digest_share = interpolate(shares, DIGEST_INDEX);
+    let (digest, random_part) = digest_share.split_at(DIGEST_LEN);
+    if digest != create_digest(random_part, &secret) {
+        return Err(Error::shamir_recovery_failed("invalid digest of the shared secret"));
+    }
+    Ok(secret)
+}
+
+fn entropy_len(dictionary: MnemonicDictionary, word_count: u8) -> usize {
+    match dictionary {
+        MnemonicDictionary::Ton => (word_count as usize * BITS_PER_WORD).div_ceil(8),
+        _ => word_count as usize * 4 / 3,
+    }
+}
+
+#[derive(ZeroizeOnDrop)]
+struct Share {
+    identifier: u16,
+    threshold: u8,
+    index: u8,
+    dictionary: MnemonicDictionary,
+    word_count: u8,
+    value: SecretBuf,
+}
+
+impl Share {
+    fn encode(&self) -> String {
+        let mut bytes = SecretBuf(Vec::with_capacity(HEADER_LEN + self.value.len() + CHECKSUM_LEN));
+        bytes.0.extend_from_slice(&self.identifier.to_be_bytes());
+        bytes.0.push(((self.threshold - 1) << 4) | self.index);
+        bytes.0.push(self.dictionary as u8);
+        bytes.0.push(self.word_count);
+        bytes.0.extend_from_slice(&self.value);
+        let checksum = Sha256::digest(&bytes.0);
+        bytes.0.extend_from_slice(&checksum[..CHECKSUM_LEN]);
+
+        let word_count = (bytes.len() * 8).div_ceil(BITS_PER_WORD);
+        let mut words = Vec::with_capacity(word_count);
+        for i in 0..word_count {
+            let mut word_i = 0;
+            for j in 0..BITS_PER_WORD {
+                let offset = i * BITS_PER_WORD + j;
+                let bit = bytes.get(offset / 8).map(|byte| (byte >> (7 - offset % 8)) & 1);
+                word_i = (word_i << 1) | bit.unwrap_or(0) as usize;
+            }
+            words.push(TVM_WORDS[word_i]);
+        }
+        words.join(" ")
+    }
+
+    fn decode(share: &str) -> ClientResult<Self> {
+        let words: Vec<&str> = share.split_whitespace().collect();
+        let mut bytes = SecretBuf(vec![0; words.len() * BITS_PER_WORD / 8 + 1]);
+        for (i, word) in words.iter().enumerate() {
+            let word_i = TVM_WORDS
+                .iter()
+                .position(|w| w.eq_ignore_ascii_case(word))
+                .ok_or_else(|| Error::invalid_shamir_share(&format!("unknown word {}", word)))?;
+            for j in 0..BITS_PER_WORD {
+                if word_i & (1 << (BITS_PER_WORD - 1 - j)) != 0 {
+                    let offset = i * BITS_PER_WORD + j;
+                    bytes.0[offset / 8] |= 1 << (7 - offset % 8);
+                }
+            }
+        }
+        if words.len() * BITS_PER_WORD < (HEADER_LEN + CHECKSUM_LEN) * 8 {
+            return Err(Error::invalid_shamir_share("share is too short"));
+        }
+        let dictionary = MnemonicDictionary::try_from(bytes[3])
+            .map_err(|_| Error::invalid_shamir_share("unknown dictionary"))?;
+        let word_count = bytes[4];
+        if entropy_len(dictionary, word_count) < MIN_ENTROPY_LEN {
+            return Err(Error::invalid_shamir_share("invalid word count"));
+        }
+        let len = HEADER_LEN + entropy_len(dictionary, word_count) + CHECKSUM_LEN;
+        if (len * 8).div_ceil(BITS_PER_WORD) != words.len()
+            || bytes[len..].iter().any(|byte| *byte != 0)
+        {
+            return Err(Error::invalid_shamir_share("invalid share length"));
+        }
+        let (data, checksum) = bytes[..len].split_at(len - CHECKSUM_LEN);
+        if checksum != &Sha256::digest(data)[..CHECKSUM_LEN] {
+            return Err(Error::invalid_shamir_share("invalid checksum"));
+        }
+        Ok(Share {
+            identifier: u16::from_be_bytes([data[0], data[1]]),
+            threshold: (data[2] >> 4) + 1,
+            index: data[2] & 0x0f,
+            dictionary,
+            word_count,
+            value: SecretBuf(data[HEADER_LEN..].to_vec()),
+        })
+    }
+}
+
+pub(crate) fn split_seed_phrase(
+    config: &CryptoConfig,
+    phrase: &str,
+    dictionary: Option<MnemonicDictionary>,
+    word_count: Option<u8>,
+    threshold: u8,
+    share_count: u8,
+) -> ClientResult<Vec<String>> {
+    if share_count == 0 || share_count > MAX_SHARE_COUNT {
+        return Err(Error::invalid_shamir_params(&format!(
+            "share count must be from 1 to {}",
+            MAX_SHARE_COUNT
+        )));
+    }
+    if threshold == 0 || threshold > share_count {
+        return Err(Error::invalid_shamir_params("threshold must be from 1 to the share count"));
+    }
+    let dictionary = dictionary.unwrap_or(config.mnemonic_dictionary);
+    let word_count = word_count.unwrap_or(config.mnemonic_word_count);
+    let entropy =
+        mnemonics(config, Some(dictionary), Some(word_count))?.entropy_from_phrase(phrase)?;
+    let identifier = rand::thread_rng().next_u32() as u16;
+    Ok(split_secret(threshold, share_count, &entropy)
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| {
+            Share { identifier, threshold, index: index as u8, dictionary, word_count, value }
+                .encode()
+        })
+        .collect())
+}
+
+pub(crate) fn combine_seed_phrase_shares(
+    config: &CryptoConfig,
+    shares: &[String],
+) -> ClientResult<(SecretString, MnemonicDictionary, u8)> {
+    let shares =
+        shares.iter().map(|share| Share::decode(share)).collect::<ClientResult<Vec<_>>>()?;
+    let first = shares.first().ok_or_else(|| Error::shamir_recovery_failed("no shares"))?;
+    for share in &shares {
+        if share.identifier != first.identifier
+            || share.threshold != first.threshold
+            || share.dictionary != first.dictionary
+            || share.word_count != first.word_count
+        {
+            return Err(Error::shamir_recovery_failed("shares belong to different secrets"));
+        }
+    }
+    let mut points: Vec<(u8, &[u8])> = Vec::new();
+    for share in &shares {
+        if !points.iter().any(|(index, _)| *index == share.index) {
+            points.push((share.index, &share.value));
+        }
+    }
+    if points.len() < first.threshold as usize {
+        return Err(Error::shamir_recovery_failed(&format!(
+            "{} distinct shares required, {} provided",
+            first.threshold,
+            points.len()
+        )));
+    }
+    let entropy = recover_secret(first.threshold, &points[..first.threshold as usize])?;
+    let phrase = mnemonics(config, Some(first.dictionary), Some(first.word_count))?
+        .phrase_from_entropy(&entropy)?;
+    Ok((SecretString(phrase), first.dictionary, first.word_count))
+}
+
+//--------------------------------------------------------------------------- mnemonic_split_shares
+
+#[derive(Serialize, Deserialize, ApiType, Default, ZeroizeOnDrop)]
+pub struct ParamsOfMnemonicSplitShares {
+    /// Phrase
+    pub phrase: String,
+    /// Dictionary identifier
+    pub dictionary: Option<MnemonicDictionary>,
+    /// Word count
+    pub word_count: Option<u8>,
+    /// Number of shares required to restore the phrase.
+    pub threshold: u8,
+    /// Total number of shares. Maximum is 16.
+    pub share_count: u8,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, ZeroizeOnDrop)]
+pub struct ResultOfMnemonicSplitShares {
+    /// Shares of the phrase. Each share is a string of words from the English
+    /// BIP-39 dictionary.
+    pub shares: Vec<String>,
+}
+
+/// Splits mnemonic phrase into shares
+///
+/// Splits the entropy of the phrase with Shamir's secret sharing, in the
+/// manner of SLIP-0039, so that any `threshold` of `share_count` shares
+/// restore the phrase with `mnemonic_combine_shares`. Every share carries
+/// the dictionary, word count and a checksum, and recovery verifies a digest
+/// of the original entropy, so mistyped or mixed up shares are detected.
+#[api_function]
+pub fn mnemonic_split_shares(
+    context: Arc<ClientContext>,
+    params: ParamsOfMnemonicSplitShares,
+) -> ClientResult<ResultOfMnemonicSplitShares> {
+    Ok(ResultOfMnemonicSplitShares {
+        shares: split_seed_phrase(
+            &context.config.crypto,
+            &params.phrase,
+            params.dictionary,
+            params.word_count,
+            params.threshold,
+            params.share_count,
+        )?,
+    })
+}
+
+//------------------------------------------------------------------------- mnemonic_combine_shares
+
+#[derive(Serialize, Deserialize, ApiType, Default, ZeroizeOnDrop)]
+pub struct ParamsOfMnemonicCombineShares {
+    /// Shares produced by `mnemonic_split_shares`. At least `threshold`
+    /// shares are required.
+    pub shares: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, ZeroizeOnDrop)]
+pub struct ResultOfMnemonicCombineShares {
+    /// Phrase
+    pub phrase: String,
+    /// Dictionary identifier
+    pub dictionary: MnemonicDictionary,
+    /// Word count
+    pub word_count: u8,
+}
+
+/// Restores mnemonic phrase from shares
+#[api_function]
+pub fn mnemonic_combine_shares(
+    context: Arc<ClientContext>,
+    params: ParamsOfMnemonicCombineShares,
+) -> ClientResult<ResultOfMnemonicCombineShares> {
+    let (phrase, dictionary, word_count) =
+        combine_seed_phrase_shares(&context.config.crypto, &params.shares)?;
+    Ok(ResultOfMnemonicCombineShares { phrase: phrase.0.clone(), dictionary, word_count })
+}
@@ -0,0 +1,245 @@
+// Copyright 2018-2021 TON Labs LTD.
+//
+// Licensed under the SOFTWARE EVALUATION License (the "License"); you may not
+// use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific TON DEV software governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use ed25519_dalek::Verifier;
+
+use crate::client::ClientContext;
+use crate::crypto::Error;
+use crate::crypto::KeyPair;
+use crate::crypto::internal::key256;
+use crate::encoding::base64_decode;
+use crate::encoding::hex_decode;
+use crate::error::ClientResult;
+
+/// Signature scheme used by a signing box.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, ApiType, PartialEq, Default)]
+pub enum SignatureScheme {
+    /// Ed25519. Public key is 32 bytes, signature is 64 bytes.
+    #[default]
+    Ed25519,
+    /// BLS12-381 in the min-pk mode of `tvm_types::bls`. Public key is 48
+    /// bytes, signature is 96 bytes. Not available in WASM.
+    Bls,
+    /// ECDSA over secp256k1 with SHA-256 digest of the data. Public key is 33
+    /// bytes compressed SEC1 point, signature is 64 bytes `r || s` with low
+    /// `s`.
+    Secp256k1,
+}
+
+impl From<SignatureScheme> for tvm_abi::SignatureScheme {
+    fn from(scheme: SignatureScheme) -> Self {
+        match scheme {
+            SignatureScheme::Ed25519 => tvm_abi::SignatureScheme::Ed25519,
+            SignatureScheme::Bls => tvm_abi::SignatureScheme::Bls,
+            SignatureScheme::Secp256k1 => tvm_abi::SignatureScheme::Secp256k1,
+        }
+    }
+}
+
+impl SignatureScheme {
+    pub(crate) fn public_key(&self, secret: &[u8]) -> ClientResult<Vec<u8>> {
+        match self {
+            SignatureScheme::Ed25519 => {
+                let key = ed25519_dalek::SigningKey::from_bytes(&key256(secret)?.0);
+                Ok(key.verifying_key().to_bytes().to_vec())
+            }
+            SignatureScheme::Bls => bls::public_key(secret),
+            SignatureScheme::Secp256k1 => {
+                let key = secp256k1_signing_key(secret)?;
+                Ok(key.verifying_key().to_encoded_point(true).as_bytes().to_vec())
+            }
+        }
+    }
+
+    pub(crate) fn sign(&self, secret: &[u8], unsigned: &[u8]) -> ClientResult<Vec<u8>> {
+        match self {
+            SignatureScheme::Ed25519 => {
+                let key = ed25519_dalek::SigningKey::from_bytes(&key256(secret)?.0);
+                crate::crypto::internal::sign_using_keys(unsigned, &key).map(|result| result.1)
+            }
+            SignatureScheme::Bls => bls::sign(secret, unsigned),
+            SignatureScheme::Secp256k1 => {
+                let signature: k256::ecdsa::Signature =
+                    k256::ecdsa::signature::Signer::sign(&secp256k1_signing_key(secret)?, unsigned);
+                Ok(signature.to_bytes().to_vec())
+            }
+        }
+    }
+
+    /// Returns `false` for a malformed signature and fails for a malformed
+    /// public key.
+    pub(crate) fn verify(
+        &self,
+        public: &[u8],
+        unsigned: &[u8],
+        signature: &[u8],
+    ) -> ClientResult<bool> {
+        match self {
+            SignatureScheme::Ed25519 => {
+                let public = ed25519_dalek::VerifyingKey::from_bytes(&key256(public)?.0)
+                    .map_err(|err| Error::invalid_signature_scheme_key(self, err))?;
+                let Ok(signature) = ed25519_dalek::Signature::from_slice(signature) else {
+                    return Ok(false);
+                };
+                Ok(public.verify(unsigned, &signature).is_ok())
+            }
+            SignatureScheme::Bls => bls::verify(public, unsigned, signature),
+            SignatureScheme::Secp256k1 => {
+                let public = k256::ecdsa::VerifyingKey::from_sec1_bytes(public)
+                    .map_err(|err| Error::invalid_signature_scheme_key(self, err))?;
+                let Ok(signature) = k256::ecdsa::Signature::from_slice(signature) else {
+                    return Ok(false);
+                };
+                Ok(k256::ecdsa::signature::Verifier::verify(&public, unsigned, &signature).is_ok())
+            }
+        }
+    }
+
+    fn generate_secret(&self) -> ClientResult<Vec<u8>> {
+        match self {
+            SignatureScheme::Ed25519 => Ok(rand::random::<[u8; 32]>().to_vec()),
+            SignatureScheme::Bls => bls::generate_secret(),
+            SignatureScheme::Secp256k1 => loop {
+                let secret: [u8; 32] = rand::random();
+                if k256::SecretKey::from_slice(&secret).is_ok() {
+                    return Ok(secret.to_vec());
+                }
+            },
+        }
+    }
+}
+
+fn secp256k1_signing_key(secret: &[u8]) -> ClientResult<k256::ecdsa::SigningKey> {
+    k256::ecdsa::SigningKey::from_slice(&key256(secret)?.0)
+        .map_err(|err| Error::invalid_signature_scheme_key(&SignatureScheme::Secp256k1, err))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod bls {
+    use tvm_types::bls::BLS_PUBLIC_KEY_LEN;
+    use tvm_types::bls::BLS_SIG_LEN;
+
+    use super::SignatureScheme;
+    use crate::crypto::Error;
+    use crate::crypto::internal::key_from_slice;
+    use crate::error::ClientResult;
+
+    pub(super) fn public_key(secret: &[u8]) -> ClientResult<Vec<u8>> {
+        tvm_types::bls::gen_public_key_based_on_secret_key(&key_from_slice(secret)?.0)
+            .map(|public| public.to_vec())
+            .map_err(|err| Error::invalid_signature_scheme_key(&SignatureScheme::Bls, err))
+    }
+
+    pub(super) fn sign(secret: &[u8], unsigned: &[u8]) -> ClientResult<Vec<u8>> {
+        tvm_types::bls::sign(&key_from_slice(secret)?.0, unsigned)
+            .map(|signature| signature.to_vec())
+            .map_err(|err| Error::invalid_signature_scheme_key(&SignatureScheme::Bls, err))
+    }
+
+    pub(super) fn verify(public: &[u8], unsigned: &[u8], signature: &[u8]) -> ClientResult<bool> {
+        let public: &[u8; BLS_PUBLIC_KEY_LEN] = public
+            .try_into()
+            .map_err(|_| Error::invalid_key_size(public.len(), &[BLS_PUBLIC_KEY_LEN]))?;
+        let Ok(signature) = <&[u8; BLS_SIG_LEN]>::try_from(signature) else {
+            return Ok(false);
+        };
+        Ok(tvm_types::bls::verify(signature, unsigned, public).unwrap_or(false))
+    }
+
+    pub(super) fn generate_secret() -> ClientResult<Vec<u8>> {
+        tvm_types::bls::gen_bls_key_pair()
+            .map(|(_, secret)| secret.to_vec())
+            .map_err(|err| Error::invalid_signature_scheme_key(&SignatureScheme::Bls, err))
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod bls {
+    use super::SignatureScheme;
+    use crate::crypto::Error;
+    use crate::error::ClientResult;
+
+    pub(super) fn public_key(_secret: &[u8]) -> ClientResult<Vec<u8>> {
+        Err(Error::unsupported_signature_scheme(&SignatureScheme::Bls))
+    }
+
+    pub(super) fn sign(_secret: &[u8], _unsigned: &[u8]) -> ClientResult<Vec<u8>> {
+        Err(Error::unsupported_signature_scheme(&SignatureScheme::Bls))
+    }
+
+    pub(super) fn verify(
+        _public: &[u8],
+        _unsigned: &[u8],
+        _signature: &[u8],
+    ) -> ClientResult<bool> {
+        Err(Error::unsupported_signature_scheme(&SignatureScheme::Bls))
+    }
+
+    pub(super) fn generate_secret() -> ClientResult<Vec<u8>> {
+        Err(Error::unsupported_signature_scheme(&SignatureScheme::Bls))
+    }
+}
+
+//---------------------------------------------------------------- generate_signature_scheme_keypair
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default)]
+pub struct ParamsOfGenerateSignatureSchemeKeypair {
+    /// Signature scheme of the key pair.
+    pub scheme: SignatureScheme,
+}
+
+/// Generates random key pair of the signature scheme. Keys are encoded with
+/// `hex`.
+#[api_function]
+pub fn generate_signature_scheme_keypair(
+    _context: Arc<ClientContext>,
+    params: ParamsOfGenerateSignatureSchemeKeypair,
+) -> ClientResult<KeyPair> {
+    let secret = params.scheme.generate_secret()?;
+    let public = params.scheme.public_key(&secret)?;
+    Ok(KeyPair::new(hex::encode(public), hex::encode(secret)))
+}
+
+//--------------------------------------------------------------------- verify_signature_with_scheme
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default)]
+pub struct ParamsOfVerifySignatureWithScheme {
+    /// Signature scheme.
+    pub scheme: SignatureScheme,
+    /// Signed data. Encoded with `base64`.
+    pub unsigned: String,
+    /// Signature. Encoded with `hex`.
+    pub signature: String,
+    /// Signer's public key. Encoded with `hex`.
+    pub public: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default)]
+pub struct ResultOfVerifySignatureWithScheme {
+    /// `true` if the signature is valid.
+    pub succeeded: bool,
+}
+
+/// Verifies a signature made with any supported signature scheme.
+#[api_function]
+pub fn verify_signature_with_scheme(
+    _context: Arc<ClientContext>,
+    params: ParamsOfVerifySignatureWithScheme,
+) -> ClientResult<ResultOfVerifySignatureWithScheme> {
+    let unsigned = base64_decode(&params.unsigned)?;
+    let signature = hex_decode(&params.signature)?;
+    let public = hex_decode(&params.public)?;
+    Ok(ResultOfVerifySignatureWithScheme {
+        succeeded: params.scheme.verify(&public, &unsigned, &signature)?,
+    })
+}
@@ -214,6 +214,121 @@ fn scrypt() {
     );
 }
 
+#[test]
+fn keystore() {
+    TestClient::init_log();
+    let client = TestClient::new();
+    let keys = client.generate_sign_keys();
+    let kdf_params = KeystoreScryptParams { log_n: Some(10), r: Some(8), p: Some(1) };
+
+    let result: ResultOfKeystoreAddKey = client
+        .request(
+            "crypto.keystore_add_key",
+            ParamsOfKeystoreAddKey {
+                keystore: None,
+                alias: "main".into(),
+                keys: keys.clone(),
+                password: base64_encode("Test Password"),
+                kdf_params: Some(kdf_params.clone()),
+                overwrite: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(result.keystore.version, KEYSTORE_VERSION);
+    assert_eq!(result.keystore.accounts.len(), 1);
+    assert_eq!(result.keystore.accounts[0].public, keys.public);
+    assert!(!result.keystore.accounts[0].ciphertext.contains(&keys.secret));
+
+    let error = client
+        .request::<_, ResultOfKeystoreAddKey>(
+            "crypto.keystore_add_key",
+            ParamsOfKeystoreAddKey {
+                keystore: Some(result.keystore.clone()),
+                alias: "main".into(),
+                keys: keys.clone(),
+                password: base64_encode("Test Password"),
+                kdf_params: Some(kdf_params.clone()),
+                overwrite: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(error.code(), ErrorCode::KeystoreAliasAlreadyExists as u32);
+
+    let other_keys = client.generate_sign_keys();
+    let keystore = client
+        .request::<_, ResultOfKeystoreAddKey>(
+            "crypto.keystore_add_key",
+            ParamsOfKeystoreAddKey {
+                keystore: Some(result.keystore.clone()),
+                alias: "other".into(),
+                keys: other_keys.clone(),
+                password: base64_encode("Other Password"),
+                kdf_params: Some(kdf_params),
+                overwrite: None,
+            },
+        )
+        .unwrap()
+        .keystore;
+
+    let result: ResultOfKeystoreListKeys = client
+        .request(
+            "crypto.keystore_list_keys",
+            ParamsOfKeystoreListKeys { keystore: keystore.clone() },
+        )
+        .unwrap();
+    assert_eq!(
+        result.keys,
+        vec![
+            KeystoreKeyInfo { alias: "main".into(), public: keys.public.clone() },
+            KeystoreKeyInfo { alias: "other".into(), public: other_keys.public.clone() },
+        ]
+    );
+
+    let result: KeyPair = client
+        .request(
+            "crypto.keystore_get_key",
+            ParamsOfKeystoreGetKey {
+                keystore: keystore.clone(),
+                alias: "other".into(),
+                password: base64_encode("Other Password"),
+            },
+        )
+        .unwrap();
+    assert_eq!(result, other_keys);
+
+    let error = client
+        .request::<_, KeyPair>(
+            "crypto.keystore_get_key",
+            ParamsOfKeystoreGetKey {
+                keystore: keystore.clone(),
+                alias: "main".into(),
+                password: base64_encode("Other Password"),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(error.code(), ErrorCode::KeystoreInvalidPassword as u32);
+
+    let result: ResultOfKeystoreRemoveKey = client
+        .request(
+            "crypto.keystore_remove_key",
+            ParamsOfKeystoreRemoveKey { keystore, alias: "main".into() },
+        )
+        .unwrap();
+    assert_eq!(result.keystore.accounts.len(), 1);
+
+    let error = client
+        .request::<_, KeyPair>(
+            "crypto.keystore_get_key",
+            ParamsOfKeystoreGetKey {
+                keystore: result.keystore,
+                alias: "main".into(),
+                password: base64_encode("Test Password"),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(error.code(), ErrorCode::KeystoreAliasNotFound as u32);
+}
+
 #[test]
 fn nacl() {
     TestClient::init_log();
@@ -377,6 +492,39 @@ fn nacl() {
     assert_eq!(text_from_base64(&d.decrypted), "Text with \' and \" and : {}");
 }
 
+#[test]
+fn nacl_sign_detached_verify_batch() {
+    let client = TestClient::new();
+    let mut signatures = Vec::new();
+    for i in 0..10 {
+        let keys = client.generate_sign_keys();
+        let unsigned = base64_encode(format!("Message {}", i));
+        let signature = client.sign_detached(&unsigned, &keys);
+        signatures.push(NaclDetachedSignature { unsigned, signature, public: keys.public.clone() });
+    }
+
+    let result: ResultOfNaclSignDetachedVerifyBatch = client
+        .request(
+            "crypto.nacl_sign_detached_verify_batch",
+            ParamsOfNaclSignDetachedVerifyBatch { signatures: signatures.clone() },
+        )
+        .unwrap();
+    assert!(result.succeeded);
+    assert!(result.invalid.is_empty());
+
+    signatures[3].unsigned = base64_encode("Another message");
+    signatures[7].signature = signatures[6].signature.clone();
+    signatures[8].public = "invalid".into();
+    let result: ResultOfNaclSignDetachedVerifyBatch = client
+        .request(
+            "crypto.nacl_sign_detached_verify_batch",
+            ParamsOfNaclSignDetachedVerifyBatch { signatures },
+        )
+        .unwrap();
+    assert!(!result.succeeded);
+    assert_eq!(result.invalid, vec![3, 7, 8]);
+}
+
 #[test]
 fn mnemonic() {
     TestClient::init_log();
@@ -563,6 +711,127 @@ fn mnemonic() {
     assert_eq!(result.tvm_public_key, "PuZdw_KyXIzo8IksTrERN3_WoAoYTyK7OvM-yaLk711sUIB3");
 }
 
+#[test]
+fn mnemonic_shares() {
+    let client = TestClient::new();
+    for (dictionary, word_count) in [
+        (MnemonicDictionary::English, 12),
+        (MnemonicDictionary::English, 24),
+        (MnemonicDictionary::Ton, 24),
+    ] {
+        let phrase = client
+            .request::<_, ResultOfMnemonicFromRandom>(
+                "crypto.mnemonic_from_random",
+                ParamsOfMnemonicFromRandom {
+                    dictionary: Some(dictionary),
+                    word_count: Some(word_count),
+                },
+            )
+            .unwrap()
+            .phrase
+            .clone();
+
+        let result: ResultOfMnemonicSplitShares = client
+            .request(
+                "crypto.mnemonic_split_shares",
+                ParamsOfMnemonicSplitShares {
+                    phrase: phrase.clone(),
+                    dictionary: Some(dictionary),
+                    word_count: Some(word_count),
+                    threshold: 3,
+                    share_count: 5,
+                },
+            )
+            .unwrap();
+        assert_eq!(result.shares.len(), 5);
+
+        for shares in [&result.shares[..3], &result.shares[2..], &result.shares[..]] {
+            let restored: ResultOfMnemonicCombineShares = client
+                .request(
+                    "crypto.mnemonic_combine_shares",
+                    ParamsOfMnemonicCombineShares { shares: shares.to_vec() },
+                )
+                .unwrap();
+            assert_eq!(restored.phrase, phrase);
+            assert_eq!(restored.dictionary, dictionary);
+            assert_eq!(restored.word_count, word_count);
+        }
+
+        let error = client
+            .request::<_, ResultOfMnemonicCombineShares>(
+                "crypto.mnemonic_combine_shares",
+                ParamsOfMnemonicCombineShares { shares: result.shares[..2].to_vec() },
+            )
+            .unwrap_err();
+        assert_eq!(error.code(), ErrorCode::ShamirRecoveryFailed as u32);
+
+        let mut words: Vec<&str> = result.shares[0].split(' ').collect();
+        words[7] = if words[7] == "abandon" { "ability" } else { "abandon" };
+        let error = client
+            .request::<_, ResultOfMnemonicCombineShares>(
+                "crypto.mnemonic_combine_shares",
+                ParamsOfMnemonicCombineShares {
+                    shares: vec![
+                        words.join(" "),
+                        result.shares[1].clone(),
+                        result.shares[2].clone(),
+                    ],
+                },
+            )
+            .unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidShamirShare as u32);
+    }
+
+    let error = client
+        .request::<_, ResultOfMnemonicSplitShares>(
+            "crypto.mnemonic_split_shares",
+            ParamsOfMnemonicSplitShares {
+                phrase: "abandon math mimic master filter design carbon crystal rookie group knife young"
+                    .into(),
+                dictionary: Some(MnemonicDictionary::English),
+                word_count: Some(12),
+                threshold: 4,
+                share_count: 3,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(error.code(), ErrorCode::InvalidShamirParams as u32);
+}
+
+#[test]
+fn mnemonic_shares_invalid_word_count() {
+    use sha2::Digest;
+
+    // Correctly checksummed shares of the English dictionary with zero words
+    // and empty entropy.
+    let encode = |index: u8| {
+        let mut bytes = vec![0x12, 0x34, 0x10 | index, MnemonicDictionary::English as u8, 0];
+        let checksum = sha2::Sha256::digest(&bytes);
+        bytes.extend_from_slice(&checksum[..4]);
+        let words: Vec<&str> = (0..(bytes.len() * 8).div_ceil(11))
+            .map(|i| {
+                let word_i = (0..11).fold(0, |word_i, j| {
+                    let offset = i * 11 + j;
+                    let bit =
+                        bytes.get(offset / 8).map_or(0, |byte| (byte >> (7 - offset % 8)) & 1);
+                    (word_i << 1) | bit as usize
+                });
+                mnemonic::TVM_WORDS[word_i]
+            })
+            .collect();
+        words.join(" ")
+    };
+
+    let client = TestClient::new();
+    let error = client
+        .request::<_, ResultOfMnemonicCombineShares>(
+            "crypto.mnemonic_combine_shares",
+            ParamsOfMnemonicCombineShares { shares: vec![encode(0), encode(1)] },
+        )
+        .unwrap_err();
+    assert_eq!(error.code(), ErrorCode::InvalidShamirShare as u32);
+}
+
 #[ignore]
 #[test]
 fn hdkey() {
@@ -664,6 +933,162 @@ fn hdkey() {
     assert_eq!(result.public, "302a832bad9e5c9906422a82c28b39ae465dcd60178480f7309e183ee34b5e83");
 }
 
+#[tokio::test]
+async fn test_multisig_signatures() {
+    let client = TestClient::new();
+    let unsigned = base64_encode("Transaction to confirm");
+    let custodians: Vec<KeyPair> = (0..3).map(|_| client.generate_sign_keys()).collect();
+    let outsider = client.generate_sign_keys();
+
+    let mut signatures = Vec::new();
+    for keys in [&custodians[0], &custodians[2], &custodians[0], &outsider] {
+        let signing_box: RegisteredSigningBox =
+            client.request_async("crypto.get_signing_box", keys.clone()).await.unwrap();
+        let result: ResultOfMultisigSign = client
+            .request_async(
+                "crypto.multisig_sign",
+                ParamsOfMultisigSign {
+                    unsigned: unsigned.clone(),
+                    signing_box: signing_box.handle,
+                    signatures,
+                },
+            )
+            .await
+            .unwrap();
+        signatures = result.signatures;
+    }
+    assert_eq!(signatures.len(), 3);
+
+    let custodian_keys: Vec<String> = custodians.iter().map(|keys| keys.public.clone()).collect();
+    let result: ResultOfMultisigVerify = client
+        .request(
+            "crypto.multisig_verify",
+            ParamsOfMultisigVerify {
+                unsigned: unsigned.clone(),
+                signatures: signatures.clone(),
+                custodians: custodian_keys.clone(),
+                required: 2,
+            },
+        )
+        .unwrap();
+    assert!(result.succeeded);
+    assert_eq!(result.confirmed, vec![custodians[2].public.clone(), custodians[0].public.clone()]);
+    assert_eq!(result.rejected.len(), 1);
+    assert_eq!(result.rejected[0].public, outsider.public);
+
+    let result: ResultOfMultisigVerify = client
+        .request(
+            "crypto.multisig_verify",
+            ParamsOfMultisigVerify {
+                unsigned: base64_encode("Another transaction"),
+                signatures,
+                custodians: custodian_keys,
+                required: 2,
+            },
+        )
+        .unwrap();
+    assert!(!result.succeeded);
+    assert!(result.confirmed.is_empty());
+    assert_eq!(result.rejected.len(), 3);
+}
+
+#[tokio::test]
+async fn test_signature_schemes() {
+    let client = TestClient::new();
+    let unsigned = base64_encode("Test Message");
+
+    for (scheme, public_len, signature_len) in [
+        (SignatureScheme::Ed25519, 32, 64),
+        (SignatureScheme::Bls, 48, 96),
+        (SignatureScheme::Secp256k1, 33, 64),
+    ] {
+        let keys: KeyPair = client
+            .request_async(
+                "crypto.generate_signature_scheme_keypair",
+                ParamsOfGenerateSignatureSchemeKeypair { scheme },
+            )
+            .await
+            .unwrap();
+        assert_eq!(keys.public.len(), public_len * 2);
+
+        let signing_box: RegisteredSigningBox = client
+            .request_async(
+                "crypto.get_signing_box_with_scheme",
+                ParamsOfGetSigningBoxWithScheme { scheme, keys: keys.clone() },
+            )
+            .await
+            .unwrap();
+        let result: ResultOfSigningBoxGetScheme = client
+            .request_async("crypto.signing_box_get_scheme", signing_box.clone())
+            .await
+            .unwrap();
+        assert_eq!(result.scheme, scheme);
+        let result: ResultOfSigningBoxGetPublicKey = client
+            .request_async("crypto.signing_box_get_public_key", signing_box.clone())
+            .await
+            .unwrap();
+        assert_eq!(result.pubkey, keys.public);
+
+        let signature = client
+            .request_async::<_, ResultOfSigningBoxSign>(
+                "crypto.signing_box_sign",
+                ParamsOfSigningBoxSign {
+                    signing_box: signing_box.handle.clone(),
+                    unsigned: unsigned.clone(),
+                },
+            )
+            .await
+            .unwrap()
+            .signature;
+        assert_eq!(signature.len(), signature_len * 2);
+
+        let verify = |unsigned: &str| {
+            client
+                .request::<_, ResultOfVerifySignatureWithScheme>(
+                    "crypto.verify_signature_with_scheme",
+                    ParamsOfVerifySignatureWithScheme {
+                        scheme,
+                        unsigned: unsigned.to_owned(),
+                        signature: signature.clone(),
+                        public: keys.public.clone(),
+                    },
+                )
+                .unwrap()
+                .succeeded
+        };
+        assert!(verify(&unsigned));
+        assert!(!verify(&base64_encode("Another Message")));
+
+        let _: () = client.request_async("crypto.remove_signing_box", signing_box).await.unwrap();
+    }
+
+    let secp256k1_keys: KeyPair = client
+        .request_async(
+            "crypto.generate_signature_scheme_keypair",
+            ParamsOfGenerateSignatureSchemeKeypair { scheme: SignatureScheme::Secp256k1 },
+        )
+        .await
+        .unwrap();
+    let bls_keys: KeyPair = client
+        .request_async(
+            "crypto.generate_signature_scheme_keypair",
+            ParamsOfGenerateSignatureSchemeKeypair { scheme: SignatureScheme::Bls },
+        )
+        .await
+        .unwrap();
+    let error = client
+        .request_async::<_, RegisteredSigningBox>(
+            "crypto.get_signing_box_with_scheme",
+            ParamsOfGetSigningBoxWithScheme {
+                scheme: SignatureScheme::Secp256k1,
+                keys: KeyPair::new(bls_keys.public.clone(), secp256k1_keys.secret.clone()),
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), ErrorCode::InvalidPublicKey as u32);
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_signing_box() {
     let client = std::sync::Arc::new(TestClient::new());
@@ -1207,6 +1632,62 @@ async fn test_crypto_boxes() -> tvm_types::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_crypto_box_seed_phrase_shares() -> tvm_types::Result<()> {
+    let client = Arc::new(TestClient::new());
+    let password_hash =
+        Arc::new("1234567890ABCDEF1234567890ABCDEF1234567890ABCDEF1234567890ABCDEF".to_string());
+    let salt = "123123123";
+
+    let RegisteredCryptoBox { handle } = client
+        .request_async_callback(
+            "crypto.create_crypto_box",
+            ParamsOfCreateCryptoBox {
+                secret_encryption_salt: salt.to_string(),
+                secret: CryptoBoxSecret::RandomSeedPhrase {
+                    dictionary: MnemonicDictionary::Ton,
+                    wordcount: 12,
+                },
+            },
+            password_provider(&client, &password_hash, || ()),
+        )
+        .await?;
+
+    let seed_phrase: ResultOfGetCryptoBoxSeedPhrase = client
+        .request_async("crypto.get_crypto_box_seed_phrase", RegisteredCryptoBox { handle })
+        .await?;
+
+    let result: ResultOfMnemonicSplitShares = client
+        .request_async(
+            "crypto.get_crypto_box_seed_phrase_shares",
+            ParamsOfGetCryptoBoxSeedPhraseShares { handle: handle.0, threshold: 2, share_count: 3 },
+        )
+        .await?;
+
+    let RegisteredCryptoBox { handle } = client
+        .request_async_callback(
+            "crypto.create_crypto_box",
+            ParamsOfCreateCryptoBox {
+                secret_encryption_salt: salt.to_string(),
+                secret: CryptoBoxSecret::SeedPhraseShares {
+                    shares: vec![result.shares[2].clone(), result.shares[0].clone()],
+                },
+            },
+            password_provider(&client, &password_hash, || ()),
+        )
+        .await?;
+
+    let restored: ResultOfGetCryptoBoxSeedPhrase = client
+        .request_async("crypto.get_crypto_box_seed_phrase", RegisteredCryptoBox { handle })
+        .await?;
+
+    assert_eq!(seed_phrase.phrase, restored.phrase);
+    assert_eq!(restored.dictionary, MnemonicDictionary::Ton);
+    assert_eq!(restored.wordcount, 12);
+
+    Ok(())
+}
+
 #[allow(warnings)]
 #[tokio::test]
 async fn test_crypto_box_signing_boxes() -> tvm_types::Result<()> {
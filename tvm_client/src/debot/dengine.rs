@@ -777,6 +777,7 @@ impl DEngine {
                 message_encode_params: call_params,
                 send_events: true,
                 dapp_id: String::new(),
+                dry_run: false,
             },
             callback,
         )
@@ -60,6 +60,10 @@ fn register_account(handlers: &mut RuntimeHandlers) {
     module.register_type::<crate::account::ParamsOfGetAccount>();
     module.register_type::<crate::account::ResultOfGetAccount>();
     module.register_async_fn(crate::account::get_account, crate::account::get_account_api);
+    module.register_async_fn(
+        crate::account::get_account_at,
+        crate::account::history::get_account_at_api,
+    );
     module.register();
 }
 
@@ -73,6 +77,7 @@ fn register_crypto(handlers: &mut RuntimeHandlers) {
 
     module.register_error_code::<crate::crypto::ErrorCode>();
     module.register_type::<crate::crypto::SigningBoxHandle>();
+    module.register_type::<crate::crypto::SignatureScheme>();
     module.register_type::<crate::crypto::EncryptionBoxHandle>();
     module.register_type::<crate::crypto::EncryptionBoxInfo>();
     module.register_type::<crate::crypto::EncryptionAlgorithm>();
@@ -89,6 +94,14 @@ fn register_crypto(handlers: &mut RuntimeHandlers) {
     module.register_type::<crate::crypto::NaclBoxParamsCB>();
     module.register_type::<crate::crypto::NaclSecretBoxParamsCB>();
     module.register_type::<crate::crypto::MnemonicDictionary>();
+    module.register_type::<crate::crypto::Keystore>();
+    module.register_type::<crate::crypto::KeystoreEntry>();
+    module.register_type::<crate::crypto::KeystoreKdf>();
+    module.register_type::<crate::crypto::KeystoreCipher>();
+    module.register_type::<crate::crypto::KeystoreScryptParams>();
+    module.register_type::<crate::crypto::KeystoreKeyInfo>();
+    module.register_type::<crate::crypto::NaclDetachedSignature>();
+    module.register_type::<crate::crypto::CustodianSignature>();
 
     // Math
 
@@ -142,6 +155,18 @@ fn register_crypto(handlers: &mut RuntimeHandlers) {
         crate::crypto::nacl_sign_detached_verify,
         crate::crypto::nacl::nacl_sign_detached_verify_api,
     );
+    module.register_sync_fn(
+        crate::crypto::nacl_sign_detached_verify_batch,
+        crate::crypto::nacl::nacl_sign_detached_verify_batch_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::generate_signature_scheme_keypair,
+        crate::crypto::signature_scheme::generate_signature_scheme_keypair_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::verify_signature_with_scheme,
+        crate::crypto::signature_scheme::verify_signature_with_scheme_api,
+    );
 
     module.register_sync_fn_without_args(
         crate::crypto::nacl_box_keypair,
@@ -182,6 +207,14 @@ fn register_crypto(handlers: &mut RuntimeHandlers) {
         crate::crypto::mnemonic_derive_sign_keys,
         crate::crypto::mnemonic::mnemonic_derive_sign_keys_api,
     );
+    module.register_sync_fn(
+        crate::crypto::mnemonic_split_shares,
+        crate::crypto::shamir::mnemonic_split_shares_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::mnemonic_combine_shares,
+        crate::crypto::shamir::mnemonic_combine_shares_api,
+    );
 
     // HDKey
 
@@ -210,6 +243,40 @@ fn register_crypto(handlers: &mut RuntimeHandlers) {
 
     module.register_sync_fn(crate::crypto::chacha20, crate::crypto::encryption::chacha20_api);
 
+    // Keystore
+
+    module.register_sync_fn(
+        crate::crypto::keystore_add_key,
+        crate::crypto::keystore::keystore_add_key_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::keystore_get_key,
+        crate::crypto::keystore::keystore_get_key_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::keystore_list_keys,
+        crate::crypto::keystore::keystore_list_keys_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::keystore_remove_key,
+        crate::crypto::keystore::keystore_remove_key_api,
+    );
+    module.register_async_fn(
+        crate::crypto::keystore_get_signing_box,
+        crate::crypto::keystore::keystore_get_signing_box_api,
+    );
+
+    // Multisig
+
+    module.register_async_fn(
+        crate::crypto::multisig_sign,
+        crate::crypto::multisig::multisig_sign_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::multisig_verify,
+        crate::crypto::multisig::multisig_verify_api,
+    );
+
     // Boxes
 
     // Crypto box
@@ -229,6 +296,10 @@ fn register_crypto(handlers: &mut RuntimeHandlers) {
         crate::crypto::get_crypto_box_seed_phrase,
         crate::crypto::boxes::crypto_box::get_crypto_box_seed_phrase_api,
     );
+    module.register_async_fn(
+        crate::crypto::get_crypto_box_seed_phrase_shares,
+        crate::crypto::boxes::crypto_box::get_crypto_box_seed_phrase_shares_api,
+    );
     module.register_async_fn(
         crate::crypto::get_signing_box_from_crypto_box,
         crate::crypto::boxes::crypto_box::get_signing_box_from_crypto_box_api,
@@ -251,10 +322,18 @@ fn register_crypto(handlers: &mut RuntimeHandlers) {
         crate::crypto::get_signing_box,
         crate::crypto::boxes::signing_box::get_signing_box_api,
     );
+    module.register_async_fn(
+        crate::crypto::get_signing_box_with_scheme,
+        crate::crypto::boxes::signing_box::get_signing_box_with_scheme_api,
+    );
     module.register_async_fn(
         crate::crypto::signing_box_get_public_key,
         crate::crypto::boxes::signing_box::signing_box_get_public_key_api,
     );
+    module.register_async_fn(
+        crate::crypto::signing_box_get_scheme,
+        crate::crypto::boxes::signing_box::signing_box_get_scheme_api,
+    );
     module.register_async_fn(
         crate::crypto::signing_box_sign,
         crate::crypto::boxes::signing_box::signing_box_sign_api,
@@ -315,6 +394,8 @@ fn register_abi(handlers: &mut RuntimeHandlers) {
     module.register_type::<crate::abi::AbiFunction>();
     module.register_type::<crate::abi::AbiContract>();
     module.register_type::<crate::abi::DataLayout>();
+    module.register_type::<crate::abi::AbiMap>();
+    module.register_type::<crate::abi::MapItem>();
 
     module.register_async_fn(
         crate::abi::encode_message_body,
@@ -374,6 +455,11 @@ fn register_abi(handlers: &mut RuntimeHandlers) {
         crate::abi::get_signature_data,
         crate::abi::decode_message::get_signature_data_api,
     );
+    module.register_sync_fn(crate::abi::map_get, crate::abi::decode_map::map_get_api);
+    module.register_sync_fn(crate::abi::map_get_items, crate::abi::decode_map::map_get_items_api);
+    module.register_sync_fn(crate::abi::map_get_min, crate::abi::decode_map::map_get_min_api);
+    module.register_sync_fn(crate::abi::map_get_max, crate::abi::decode_map::map_get_max_api);
+    module.register_sync_fn(crate::abi::map_count, crate::abi::decode_map::map_count_api);
     module.register();
 }
 
@@ -448,6 +534,7 @@ fn register_net(handlers: &mut RuntimeHandlers) {
     module.register_type::<crate::net::AggregationFn>();
     module.register_type::<crate::net::TransactionNode>();
     module.register_type::<crate::net::MessageNode>();
+    module.register_type::<crate::net::ContractEvent>();
 
     module.register_async_fn(crate::net::query, crate::net::queries::query_api);
     module.register_async_fn(crate::net::batch_query, crate::net::batch::batch_query_api);
@@ -501,6 +588,14 @@ fn register_net(handlers: &mut RuntimeHandlers) {
         crate::net::iterators::transaction_iterator::resume_transaction_iterator,
         crate::net::iterators::transaction_iterator::resume_transaction_iterator_api,
     );
+    module.register_async_fn(
+        crate::net::iterators::event_iterator::create_event_iterator,
+        crate::net::iterators::event_iterator::create_event_iterator_api,
+    );
+    module.register_async_fn(
+        crate::net::iterators::event_iterator::resume_event_iterator,
+        crate::net::iterators::event_iterator::resume_event_iterator_api,
+    );
     module.register_async_fn(
         crate::net::iterators::iterator_next,
         crate::net::iterators::iterator_next_api,
@@ -612,6 +707,10 @@ fn register_utils(handlers: &mut RuntimeHandlers) {
         crate::utils::calc_storage_fee,
         crate::utils::calc_storage_fee::calc_storage_fee_api,
     );
+    module.register_async_fn(
+        crate::utils::forecast_storage_fee,
+        crate::utils::forecast_storage_fee::forecast_storage_fee_api,
+    );
     #[cfg(feature = "include-zstd")]
     module.register_sync_fn(super::utils::compress_zstd, super::utils::compress_zstd_api);
     #[cfg(feature = "include-zstd")]
@@ -0,0 +1,411 @@
+// Copyright 2018-2021 TON Labs LTD.
+//
+// Licensed under the SOFTWARE EVALUATION License (the "License"); you may not
+// use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific TON DEV software governing permissions and
+// limitations under the License.
+//
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use serde::Deserialize;
+use serde_json::Value;
+use tvm_abi::token::Detokenizer;
+use tvm_block::Message;
+
+use crate::abi::Abi;
+use crate::boc::internal::deserialize_object_from_boc;
+use crate::client::ClientContext;
+use crate::encoding::account_decode;
+use crate::encoding::decode_abi_number;
+use crate::error::ClientResult;
+use crate::net::ChainIterator;
+use crate::net::OrderBy;
+use crate::net::ParamsOfQueryCollection;
+use crate::net::RegisteredIterator;
+use crate::net::SortDirection;
+use crate::net::iterators::ResultOfIteratorNext;
+use crate::net::iterators::register_iterator;
+use crate::net::query_collection;
+
+const MESSAGE_FIELDS: &str = r#"
+    id
+    boc
+    block_id
+    created_at
+    created_lt(format:DEC)
+    src
+    src_transaction { id }
+"#;
+
+const MSG_TYPE_EXT_OUT: u32 = 2;
+const QUERY_LIMIT: u32 = 50;
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone)]
+pub struct EventSource {
+    /// Contract address.
+    pub address: String,
+
+    /// Contract ABI used to decode emitted events.
+    pub abi: Abi,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone, Debug, PartialEq)]
+pub struct ContractEvent {
+    /// Address of the contract emitted the event.
+    pub address: String,
+
+    /// Event message id.
+    pub message_id: String,
+
+    /// Id of the transaction that produced the event message.
+    pub transaction_id: Option<String>,
+
+    /// Id of the block containing the event message.
+    pub block_id: Option<String>,
+
+    /// Logical time of the event message, decimal string.
+    pub created_lt: String,
+
+    /// Creation time of the event message.
+    pub created_at: u32,
+
+    /// Event id.
+    pub event_id: u32,
+
+    /// Event name.
+    pub name: String,
+
+    /// Event parameters decoded with the contract ABI.
+    pub value: Value,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ResumeState {
+    start_lt: Option<String>,
+    cursors: BTreeMap<String, CursorState>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CursorState {
+    last_lt: String,
+    seen: Vec<String>,
+}
+
+/// Iteration position of one contract: logical time of the last iterated
+/// message and ids of the iterated messages with this logical time.
+struct Cursor {
+    last_lt: u64,
+    seen: HashSet<String>,
+}
+
+/// External outbound message fetched from the network.
+struct EventMessage {
+    address: String,
+    lt: u64,
+    id: String,
+    event: Option<ContractEvent>,
+}
+
+pub(crate) struct EventIterator {
+    contracts: HashMap<String, tvm_abi::Contract>,
+    start_lt: Option<u64>,
+    cursors: HashMap<String, Cursor>,
+    next: VecDeque<EventMessage>,
+}
+
+impl EventIterator {
+    fn with_contracts(contracts: Vec<EventSource>) -> ClientResult<Self> {
+        let mut map = HashMap::new();
+        for source in contracts {
+            map.insert(account_decode(&source.address)?.to_string(), source.abi.abi()?);
+        }
+        Ok(Self { contracts: map, start_lt: None, cursors: HashMap::new(), next: VecDeque::new() })
+    }
+
+    pub fn new(params: ParamsOfCreateEventIterator) -> ClientResult<Self> {
+        let mut iterator = Self::with_contracts(params.contracts)?;
+        iterator.start_lt = params.start_lt.map(|lt| decode_abi_number(&lt)).transpose()?;
+        Ok(iterator)
+    }
+
+    pub fn resume(params: ParamsOfResumeEventIterator) -> ClientResult<Self> {
+        let resume = ResumeState::deserialize(&params.resume_state).map_err(|e| {
+            crate::client::Error::internal_error(format!("Invalid iterator resume state: {}", e))
+        })?;
+        let mut iterator = Self::with_contracts(params.contracts)?;
+        iterator.start_lt = resume.start_lt.map(|lt| decode_abi_number(&lt)).transpose()?;
+        for (address, cursor) in resume.cursors {
+            let address = account_decode(&address)?.to_string();
+            if iterator.contracts.contains_key(&address) {
+                iterator.cursors.insert(
+                    address,
+                    Cursor {
+                        last_lt: decode_abi_number(&cursor.last_lt)?,
+                        seen: cursor.seen.into_iter().collect(),
+                    },
+                );
+            }
+        }
+        Ok(iterator)
+    }
+
+    pub fn get_resume_state_value(&self) -> ClientResult<Value> {
+        let cursors = self
+            .cursors
+            .iter()
+            .map(|(address, cursor)| {
+                let mut seen = cursor.seen.iter().cloned().collect::<Vec<_>>();
+                seen.sort();
+                (address.clone(), CursorState { last_lt: cursor.last_lt.to_string(), seen })
+            })
+            .collect();
+        let state = ResumeState { start_lt: self.start_lt.map(|lt| lt.to_string()), cursors };
+        serde_json::to_value(state).map_err(|e| {
+            crate::client::Error::internal_error(format!(
+                "Can't serialize iterator resume state: {}",
+                e
+            ))
+        })
+    }
+
+    /// Filter of the messages after the position of every contract. Contracts
+    /// are joined with `OR` since their logical times are not related.
+    fn filter(&self) -> Value {
+        let mut addresses = self.contracts.keys().collect::<Vec<_>>();
+        addresses.sort();
+        let mut filter = None;
+        for address in addresses.into_iter().rev() {
+            let mut address_filter = json!({
+                "src": { "eq": address },
+                "msg_type": { "eq": MSG_TYPE_EXT_OUT },
+            });
+            match self.cursors.get(address) {
+                Some(cursor) => {
+                    address_filter["created_lt"] = json!({ "ge": cursor.last_lt.to_string() });
+                    if !cursor.seen.is_empty() {
+                        let mut seen = cursor.seen.iter().cloned().collect::<Vec<_>>();
+                        seen.sort();
+                        address_filter["id"] = json!({ "notIn": seen });
+                    }
+                }
+                None => {
+                    if let Some(start_lt) = self.start_lt {
+                        address_filter["created_lt"] = json!({ "ge": start_lt.to_string() });
+                    }
+                }
+            }
+            if let Some(next) = filter {
+                address_filter["OR"] = next;
+            }
+            filter = Some(address_filter);
+        }
+        filter.unwrap_or_else(|| json!({ "src": { "in": [] } }))
+    }
+
+    fn decode_event(
+        &self,
+        context: &ClientContext,
+        message: &Value,
+    ) -> ClientResult<Option<ContractEvent>> {
+        let address = message["src"].as_str().unwrap_or_default();
+        let contract = match self.contracts.get(address) {
+            Some(contract) => contract,
+            None => return Ok(None),
+        };
+        let boc = message["boc"].as_str().unwrap_or_default();
+        let body =
+            match deserialize_object_from_boc::<Message>(context, boc, "message")?.object.body() {
+                Some(body) => body,
+                None => return Ok(None),
+            };
+        let event =
+            match tvm_abi::Event::decode_id(body.clone()).and_then(|id| contract.event_by_id(id)) {
+                Ok(event) => event,
+                Err(_) => return Ok(None),
+            };
+        let value = match event
+            .decode_input(body, false)
+            .and_then(|tokens| Detokenizer::detokenize_to_json_value(&tokens))
+        {
+            Ok(value) => value,
+            Err(_) => return Ok(None),
+        };
+        Ok(Some(ContractEvent {
+            address: address.to_string(),
+            message_id: message["id"].as_str().unwrap_or_default().to_string(),
+            transaction_id: message["src_transaction"]["id"].as_str().map(|x| x.to_string()),
+            block_id: message["block_id"].as_str().map(|x| x.to_string()),
+            created_lt: message["created_lt"].as_str().unwrap_or_default().to_string(),
+            created_at: message["created_at"].as_u64().unwrap_or_default() as u32,
+            event_id: event.get_id(),
+            name: event.name.clone(),
+            value,
+        }))
+    }
+
+    async fn query_next(&mut self, context: &Arc<ClientContext>) -> ClientResult<()> {
+        let messages = query_collection(
+            context.clone(),
+            ParamsOfQueryCollection {
+                collection: "messages".to_string(),
+                filter: Some(self.filter()),
+                result: MESSAGE_FIELDS.to_string(),
+                order: Some(vec![OrderBy {
+                    path: "created_lt".to_string(),
+                    direction: SortDirection::ASC,
+                }]),
+                limit: Some(QUERY_LIMIT),
+            },
+        )
+        .await?
+        .result;
+        for message in messages {
+            let lt = message["created_lt"].as_str().unwrap_or_default();
+            let lt = decode_abi_number(lt).map_err(|_| {
+                crate::net::Error::invalid_server_response(format!(
+                    "Invalid message created_lt: {}",
+                    lt
+                ))
+            })?;
+            self.next.push_back(EventMessage {
+                address: message["src"].as_str().unwrap_or_default().to_string(),
+                lt,
+                id: message["id"].as_str().unwrap_or_default().to_string(),
+                event: self.decode_event(context, &message)?,
+            });
+        }
+        Ok(())
+    }
+
+    /// Moves the iteration position of the contract after the message.
+    fn advance(&mut self, address: String, lt: u64, id: String) {
+        let cursor = self
+            .cursors
+            .entry(address)
+            .or_insert_with(|| Cursor { last_lt: lt, seen: HashSet::new() });
+        if cursor.last_lt != lt {
+            cursor.last_lt = lt;
+            cursor.seen.clear();
+        }
+        cursor.seen.insert(id);
+    }
+}
+
+#[async_trait::async_trait]
+impl ChainIterator for EventIterator {
+    async fn next(
+        &mut self,
+        context: &Arc<ClientContext>,
+        limit: u32,
+        return_resume_state: bool,
+    ) -> ClientResult<ResultOfIteratorNext> {
+        let limit = limit.max(1) as usize;
+
+        if self.next.is_empty() {
+            self.query_next(context).await?;
+        }
+
+        let mut items = Vec::new();
+        while items.len() < limit {
+            let Some(message) = self.next.pop_front() else {
+                break;
+            };
+            self.advance(message.address, message.lt, message.id);
+            if let Some(event) = message.event {
+                items.push(json!(event));
+            }
+        }
+
+        let resume_state =
+            if return_resume_state { Some(self.get_resume_state_value()?) } else { None };
+
+        Ok(ResultOfIteratorNext { has_more: true, items, resume_state })
+    }
+
+    fn after_remove(&mut self, _context: &Arc<ClientContext>) {}
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone)]
+pub struct ParamsOfCreateEventIterator {
+    /// Contracts whose events are iterated.
+    pub contracts: Vec<EventSource>,
+
+    /// Logical time to iterate from.
+    ///
+    /// If the application specifies this parameter then the iteration
+    /// includes event messages with `created_lt` >= `start_lt`.
+    /// Otherwise the iteration starts from the first contract event.
+    ///
+    /// Must be specified as a decimal or `0x` prefixed hexadecimal string.
+    pub start_lt: Option<String>,
+}
+
+/// Creates contract event iterator.
+///
+/// Iterates external outbound messages emitted by the specified contracts
+/// ordered by logical time and decodes them into events with the contract
+/// ABI. Messages that can not be decoded as an event of the contract ABI are
+/// skipped.
+///
+/// Iterated item is a `ContractEvent` JSON object containing the decoded
+/// event along with the message, transaction and block metadata.
+///
+/// The iterator never finishes: when all existing events are iterated
+/// `iterator_next` returns empty items and the application can repeat the
+/// call later to receive new events. Backfilling is done with the `start_lt`
+/// parameter.
+///
+/// The resume state stores for every contract the logical time of its last
+/// iterated message and the ids of its iterated messages with this logical
+/// time, so the iteration resumed after a reconnect neither misses nor
+/// repeats events even if messages of different contracts arrive out of
+/// logical time order.
+///
+/// Application should call the `remove_iterator` when iterator is no longer
+/// required.
+#[api_function]
+pub async fn create_event_iterator(
+    context: Arc<ClientContext>,
+    params: ParamsOfCreateEventIterator,
+) -> ClientResult<RegisteredIterator> {
+    register_iterator(&context, Box::new(EventIterator::new(params)?)).await
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone)]
+pub struct ParamsOfResumeEventIterator {
+    /// Iterator state from which to resume.
+    ///
+    /// Same as value returned from `iterator_next`.
+    pub resume_state: Value,
+
+    /// Contracts whose events are iterated.
+    pub contracts: Vec<EventSource>,
+}
+
+/// Resumes contract event iterator.
+///
+/// The iterator stays exactly at the same position where the `resume_state`
+/// was caught. Note that `resume_state` doesn't store the contracts, so the
+/// application must pass them again in `contracts` parameter.
+///
+/// Application should call the `remove_iterator` when iterator is no longer
+/// required.
+#[api_function]
+pub async fn resume_event_iterator(
+    context: Arc<ClientContext>,
+    params: ParamsOfResumeEventIterator,
+) -> ClientResult<RegisteredIterator> {
+    register_iterator(&context, Box::new(EventIterator::resume(params)?)).await
+}
@@ -0,0 +1,106 @@
+use super::*;
+use crate::boc::ParamsOfParse;
+use crate::boc::ResultOfParse;
+use crate::tests::EVENTS_OLD;
+use crate::tests::TestClient;
+use crate::utils::conversion::abi_uint;
+
+const EVENT_MESSAGE: &str = "te6ccgEBAQEAVQAApeACvg5/pmQpY4m61HmJ0ne+zjHJu3MNG8rJxUDLbHKBu/AAAAAAAAAMJL6z6ro48sYvAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABA";
+
+fn event_message(client: &TestClient) -> Value {
+    let parsed: ResultOfParse =
+        client.request("boc.parse_message", ParamsOfParse { boc: EVENT_MESSAGE.into() }).unwrap();
+    json!({
+        "id": parsed.parsed["id"],
+        "boc": EVENT_MESSAGE,
+        "block_id": "block",
+        "created_at": 1599458364,
+        "created_lt": "100",
+        "src": parsed.parsed["src"],
+        "src_transaction": { "id": "transaction" },
+    })
+}
+
+#[test]
+fn test_decode_event() {
+    let client = TestClient::new();
+    let message = event_message(&client);
+    let address = message["src"].as_str().unwrap().to_string();
+    let abi = TestClient::abi(EVENTS_OLD, Some(2));
+    let iterator = EventIterator::new(ParamsOfCreateEventIterator {
+        contracts: vec![EventSource { address: address.clone(), abi }],
+        start_lt: None,
+    })
+    .unwrap();
+
+    let event = iterator.decode_event(&client.context(), &message).unwrap().unwrap();
+    assert_eq!(event.address, address);
+    assert_eq!(event.name, "EventThrown");
+    assert_eq!(event.transaction_id.as_deref(), Some("transaction"));
+    assert_eq!(event.block_id.as_deref(), Some("block"));
+    assert_eq!(event.created_lt, "100");
+    assert_eq!(event.value, json!({ "id": abi_uint(0, 256) }));
+
+    let mut other = message.clone();
+    other["src"] = json!(format!("0:{}", "1".repeat(64)));
+    assert_eq!(iterator.decode_event(&client.context(), &other).unwrap(), None);
+}
+
+#[test]
+fn test_resume_position() {
+    let abi = TestClient::abi(EVENTS_OLD, Some(2));
+    let first = format!("0:{}", "a".repeat(64));
+    let second = format!("0:{}", "b".repeat(64));
+    let contracts = vec![
+        EventSource { address: first.clone(), abi: abi.clone() },
+        EventSource { address: second.clone(), abi },
+    ];
+    let mut iterator = EventIterator::new(ParamsOfCreateEventIterator {
+        contracts: contracts.clone(),
+        start_lt: Some("0x10".into()),
+    })
+    .unwrap();
+    assert_eq!(
+        iterator.filter(),
+        json!({
+            "src": { "eq": first },
+            "msg_type": { "eq": 2 },
+            "created_lt": { "ge": "16" },
+            "OR": {
+                "src": { "eq": second },
+                "msg_type": { "eq": 2 },
+                "created_lt": { "ge": "16" },
+            },
+        })
+    );
+
+    iterator.advance(first.clone(), 20, "b".into());
+    iterator.advance(first.clone(), 20, "a".into());
+    let resume_state = iterator.get_resume_state_value().unwrap();
+    assert_eq!(
+        resume_state,
+        json!({
+            "start_lt": "16",
+            "cursors": { first.clone(): { "last_lt": "20", "seen": ["a", "b"] } },
+        })
+    );
+
+    // Position of one contract does not move the other one
+    let resumed =
+        EventIterator::resume(ParamsOfResumeEventIterator { resume_state, contracts }).unwrap();
+    let filter = resumed.filter();
+    assert_eq!(filter["created_lt"], json!({ "ge": "20" }));
+    assert_eq!(filter["id"], json!({ "notIn": ["a", "b"] }));
+    assert_eq!(filter["OR"]["created_lt"], json!({ "ge": "16" }));
+    assert_eq!(filter["OR"].get("id"), None);
+
+    iterator.advance(second.clone(), 18, "d".into());
+    iterator.advance(first.clone(), 21, "c".into());
+    assert_eq!(
+        iterator.get_resume_state_value().unwrap()["cursors"],
+        json!({
+            first: { "last_lt": "21", "seen": ["c"] },
+            second: { "last_lt": "18", "seen": ["d"] },
+        })
+    );
+}
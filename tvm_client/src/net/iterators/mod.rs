@@ -11,6 +11,7 @@
 
 pub(crate) mod block;
 pub(crate) mod block_iterator;
+pub(crate) mod event_iterator;
 pub(crate) mod transaction;
 pub(crate) mod transaction_iterator;
 
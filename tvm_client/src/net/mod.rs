@@ -23,6 +23,12 @@ pub use iterators::block_iterator::ParamsOfCreateBlockIterator;
 pub use iterators::block_iterator::ParamsOfResumeBlockIterator;
 pub use iterators::block_iterator::create_block_iterator;
 pub use iterators::block_iterator::resume_block_iterator;
+pub use iterators::event_iterator::ContractEvent;
+pub use iterators::event_iterator::EventSource;
+pub use iterators::event_iterator::ParamsOfCreateEventIterator;
+pub use iterators::event_iterator::ParamsOfResumeEventIterator;
+pub use iterators::event_iterator::create_event_iterator;
+pub use iterators::event_iterator::resume_event_iterator;
 pub use iterators::iterator_next;
 pub use iterators::remove_iterator;
 pub use iterators::transaction_iterator::ParamsOfCreateTransactionIterator;
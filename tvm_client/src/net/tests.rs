@@ -567,6 +567,7 @@ async fn subscribe_for_transactions_with_addresses() {
                 message_encode_params: deploy_params,
                 send_events: false,
                 dapp_id: String::new(),
+                dry_run: false,
             },
             TestClient::default_callback,
         )
@@ -602,6 +603,7 @@ async fn subscribe_for_transactions_with_addresses() {
                 },
                 send_events: false,
                 dapp_id: String::new(),
+                dry_run: false,
             },
             TestClient::default_callback,
         )
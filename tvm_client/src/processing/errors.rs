@@ -39,6 +39,7 @@ pub enum ErrorCode {
     NextRempStatusTimeout = 516,
     InvalidThread = 517,
     DappIdRequired = 518,
+    DryRunFailed = 519,
 }
 
 pub struct Error;
@@ -240,4 +241,23 @@ impl Error {
             "`dapp_id` is required when connected to a v>=1.0.0 server".into(),
         )
     }
+
+    pub fn dry_run_failed(message_id: &str, local_error: ClientError) -> ClientError {
+        let mut error = Self::processing_error(
+            ErrorCode::DryRunFailed,
+            format!(
+                "Message was not sent because local execution failed: {}",
+                local_error.message()
+            ),
+            message_id,
+            None,
+        );
+        for field in ["exit_code", "exit_arg", "contract_error", "phase"] {
+            if !local_error.data()[field].is_null() {
+                error.data_mut()[field] = local_error.data()[field].clone();
+            }
+        }
+        error.data_mut()["local_error"] = serde_json::to_value(&local_error).unwrap_or_default();
+        error
+    }
 }
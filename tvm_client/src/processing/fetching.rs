@@ -213,5 +213,11 @@ pub async fn fetch_transaction_result(
         None
     };
 
-    Ok(ResultOfProcessMessage { transaction, out_messages, decoded: abi_decoded, fees })
+    Ok(ResultOfProcessMessage {
+        transaction,
+        out_messages,
+        decoded: abi_decoded,
+        fees,
+        expected_transaction: None,
+    })
 }
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use serde_json::Value;
 use tvm_block::MsgAddressInt;
 
 use super::ErrorCode;
@@ -98,6 +99,39 @@ async fn get_local_error(
     .map(|_| "Local contract call emulation was successful".to_owned())
 }
 
+/// Runs the message on the current account state fetched from the network
+/// with the network blockchain config. Returns the parsed transaction or the
+/// local execution error with the decoded exit code.
+pub(crate) async fn dry_run_message(
+    context: Arc<ClientContext>,
+    address: &MsgAddressInt,
+    message: String,
+    abi: Abi,
+) -> ClientResult<Value> {
+    let account = fetch_account(context.clone(), address, "boc acc_type").await?;
+
+    if account["acc_type"].as_i64() == Some(ACCOUNT_NONEXIST as i64) {
+        return Err(crate::tvm::Error::account_missing(address));
+    }
+
+    let account: Account = serde_json::from_value(account).map_err(|err| {
+        Error::invalid_data(format!("Can not parse account for dry run: {}", err))
+    })?;
+
+    crate::tvm::run_executor_internal(
+        context,
+        ParamsOfRunExecutor {
+            abi: Some(abi),
+            account: AccountForExecutor::Account { boc: account.boc, unlimited_balance: None },
+            message,
+            ..Default::default()
+        },
+        true,
+    )
+    .await
+    .map(|result| result.transaction)
+}
+
 pub(crate) async fn resolve_error(
     context: Arc<ClientContext>,
     address: &MsgAddressInt,
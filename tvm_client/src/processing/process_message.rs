@@ -4,12 +4,14 @@ use crate::abi::ParamsOfEncodeMessage;
 use crate::client::ClientContext;
 use crate::error::AddNetworkUrl;
 use crate::error::ClientResult;
+use crate::processing::Error;
 use crate::processing::ErrorCode;
 use crate::processing::ParamsOfSendMessage;
 use crate::processing::ParamsOfWaitForTransaction;
 use crate::processing::ProcessingEvent;
 use crate::processing::ResultOfProcessMessage;
 use crate::processing::internal::can_retry_expired_message;
+use crate::processing::internal::dry_run_message;
 use crate::processing::send_message;
 use crate::processing::wait_for_transaction;
 use crate::tvm::StdContractError;
@@ -28,6 +30,16 @@ pub struct ParamsOfProcessMessage {
     /// Required for v>=1.0.0 servers; for v<1.0.0 may be empty.
     #[serde(default)]
     pub dapp_id: String,
+
+    /// Flag for running the message locally before sending.
+    /// The message is executed on the account state fetched from the network
+    /// with the network blockchain config. If compute or action phase fails,
+    /// the message is not sent and `DryRunFailed` error with the decoded exit
+    /// code is returned. Otherwise the expected transaction is returned in
+    /// `expected_transaction` of the result.
+    /// Default is `false`.
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 pub async fn process_message<F: futures::Future<Output = ()> + Send>(
@@ -44,6 +56,20 @@ pub async fn process_message<F: futures::Future<Output = ()> + Send>(
         encode_params.processing_try_index = Some(try_index);
         let message = crate::abi::encode_message(context.clone(), encode_params).await?;
 
+        // Dry run
+        let expected_transaction = if params.dry_run {
+            let address = crate::encoding::account_decode(&message.address)?;
+            let transaction =
+                dry_run_message(context.clone(), &address, message.message.clone(), abi.clone())
+                    .await
+                    .add_network_url_from_context(&context)
+                    .await
+                    .map_err(|err| Error::dry_run_failed(&message.message_id, err))?;
+            Some(transaction)
+        } else {
+            None
+        };
+
         // Send
         let send_result = send_message(
             context.clone(),
@@ -77,8 +103,9 @@ pub async fn process_message<F: futures::Future<Output = ()> + Send>(
         .await;
 
         match wait_for {
-            Ok(output) => {
+            Ok(mut output) => {
                 // Waiting is complete, return output
+                output.expected_transaction = expected_transaction;
                 return Ok(output);
             }
             Err(err) => {
@@ -231,6 +231,7 @@ async fn test_process_message() {
                 message_encode_params: encode_params,
                 send_events: true,
                 dapp_id: String::new(),
+                dry_run: false,
             },
             callback,
         )
@@ -271,6 +272,7 @@ async fn test_process_message() {
                 },
                 send_events: true,
                 dapp_id: String::new(),
+                dry_run: false,
             },
             callback,
         )
@@ -362,6 +364,7 @@ async fn test_error_resolving() {
                 message_encode_params: deploy_params.clone(),
                 send_events: false,
                 dapp_id: String::new(),
+                dry_run: false,
             },
             TestClient::default_callback,
         )
@@ -385,6 +388,7 @@ async fn test_error_resolving() {
                 message_encode_params: deploy_params.clone(),
                 send_events: false,
                 dapp_id: String::new(),
+                dry_run: false,
             },
             TestClient::default_callback,
         )
@@ -399,6 +403,24 @@ async fn test_error_resolving() {
         assert_eq!(result.data()["local_error"]["code"], TvmErrorCode::LowBalance as u32);
     }
 
+    // dry run aborts processing before sending
+    let result = client
+        .net_process_message(
+            ParamsOfProcessMessage {
+                message_encode_params: deploy_params.clone(),
+                send_events: false,
+                dapp_id: String::new(),
+                dry_run: true,
+            },
+            TestClient::default_callback,
+        )
+        .await
+        .unwrap_err();
+
+    log::debug!("{:#}", json!(result));
+    assert_eq!(result.code(), ErrorCode::DryRunFailed as u32);
+    assert_eq!(result.data()["local_error"]["code"], TvmErrorCode::LowBalance as u32);
+
     // ABI version 1 messages don't expire so previous deploy message can be
     // processed after increasing balance. Need to wait until message will be
     // rejected by all validators
@@ -415,6 +437,7 @@ async fn test_error_resolving() {
                 message_encode_params: run_params.clone(),
                 send_events: false,
                 dapp_id: String::new(),
+                dry_run: false,
             },
             TestClient::default_callback,
         )
@@ -436,6 +459,7 @@ async fn test_error_resolving() {
                 message_encode_params: deploy_params.clone(),
                 send_events: false,
                 dapp_id: String::new(),
+                dry_run: false,
             },
             TestClient::default_callback,
         )
@@ -450,6 +474,7 @@ async fn test_error_resolving() {
                 message_encode_params: run_params.clone(),
                 send_events: false,
                 dapp_id: String::new(),
+                dry_run: false,
             },
             TestClient::default_callback,
         )
@@ -524,6 +549,7 @@ async fn test_retries() {
                         },
                         send_events: false,
                         dapp_id: String::new(),
+                        dry_run: false,
                     },
                     TestClient::default_callback,
                 )
@@ -592,6 +618,7 @@ async fn test_fees() {
                 message_encode_params: params,
                 send_events: false,
                 dapp_id: String::new(),
+                dry_run: false,
             },
             TestClient::default_callback,
         )
@@ -663,6 +690,7 @@ async fn test_deploy_from_tvc_v1() {
                 message_encode_params: encode_params,
                 send_events: false,
                 dapp_id: String::new(),
+                dry_run: false,
             },
             |_: ProcessingEvent, _: ProcessingResponseType| async {},
         )
@@ -686,6 +714,7 @@ async fn test_deploy_from_tvc_v1() {
                 },
                 send_events: false,
                 dapp_id: String::new(),
+                dry_run: false,
             },
             move |_: ProcessingEvent, _: ProcessingResponseType| async {},
         )
@@ -751,6 +780,7 @@ fn test_process_message_sync() {
             message_encode_params: encode_params,
             send_events: true,
             dapp_id: String::new(),
+            dry_run: false,
         })
         .unwrap();
 
@@ -774,6 +804,7 @@ fn test_process_message_sync() {
             },
             send_events: true,
             dapp_id: String::new(),
+            dry_run: false,
         })
         .unwrap();
     assert_eq!(output.out_messages.len(), 2);
@@ -34,6 +34,11 @@ pub struct ResultOfProcessMessage {
 
     /// Transaction fees
     pub fees: TransactionFees,
+
+    /// Transaction expected by the local dry run. Parsed the same way as
+    /// `transaction`, so both can be compared. Returned only if `dry_run` is
+    /// requested.
+    pub expected_transaction: Option<Value>,
 }
 
 #[derive(Clone, num_derive::FromPrimitive, PartialEq, Debug)]
@@ -685,6 +685,7 @@ impl TestClient {
                 },
                 send_events: false,
                 dapp_id: String::new(),
+                dry_run: false,
             },
             Self::default_callback,
         )
@@ -796,6 +797,7 @@ impl TestClient {
                     message_encode_params: params,
                     send_events: false,
                     dapp_id: String::new(),
+                    dry_run: false,
                 },
                 Self::default_callback,
             )
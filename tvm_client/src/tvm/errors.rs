@@ -39,6 +39,7 @@ pub enum ErrorCode {
     InvalidMessageType = 413,
     ContractExecutionError = 414,
     AccountIsSuspended = 415,
+    AccountStateMismatch = 416,
 }
 pub struct Error;
 
@@ -330,6 +331,25 @@ impl Error {
         )
     }
 
+    pub fn account_state_mismatch(
+        transaction_id: &str,
+        hash_kind: &str,
+        expected: &str,
+        actual: &str,
+    ) -> ClientError {
+        let mut error = error(
+            ErrorCode::AccountStateMismatch,
+            format!(
+                "Replayed account state does not match {} state hash of transaction {}",
+                hash_kind, transaction_id
+            ),
+        );
+        error.data_mut()["transaction_id"] = transaction_id.into();
+        error.data_mut()["expected_hash"] = expected.into();
+        error.data_mut()["actual_hash"] = actual.into();
+        error
+    }
+
     pub fn internal_error<E: Display>(err: E) -> ClientError {
         error(ErrorCode::InternalError, format!("TVM internal error: {}", err))
     }
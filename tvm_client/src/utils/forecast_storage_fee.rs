@@ -0,0 +1,182 @@
+// Copyright 2018-2021 TON Labs LTD.
+//
+// Licensed under the SOFTWARE EVALUATION License (the "License"); you may not
+// use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific TON DEV software governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use tvm_block::Account;
+use tvm_block::AccountStatus;
+use tvm_executor::BlockchainConfig;
+use tvm_executor::STORAGE_FEE_COOLER_TIME;
+
+use crate::boc::internal::deserialize_object_from_boc;
+use crate::client::ClientContext;
+use crate::error::ClientResult;
+use crate::tvm::Error;
+use crate::tvm::types::blockchain_config_from_boc;
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug)]
+pub struct ParamsOfForecastStorageFee {
+    /// Account BOC or BOC cache reference
+    pub account: String,
+    /// Time the forecast starts from. Current time is used if omitted.
+    pub now: Option<u32>,
+    /// Time the account must survive until. Required for `top_up`
+    /// calculation.
+    pub target_time: Option<u32>,
+    /// Blockchain config BOC. Default network config is used if omitted.
+    pub blockchain_config: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, PartialEq)]
+pub struct ResultOfForecastStorageFee {
+    /// Storage fee and due payment accrued by the `now` time in nanotokens
+    pub accrued_fee: String,
+    /// Earliest time when a transaction will freeze the account.
+    /// Omitted if the account is not active or never gets frozen.
+    pub frozen_at: Option<u32>,
+    /// Earliest time when a transaction will delete the account.
+    /// Omitted if the account never gets deleted.
+    pub deleted_at: Option<u32>,
+    /// Amount in nanotokens that must be added to the account balance to pay
+    /// all storage fees until `target_time` without a due payment.
+    /// Omitted if `target_time` is not specified.
+    pub top_up: Option<String>,
+}
+
+/// Predicts when the account will be frozen and deleted for unpaid storage
+/// fees and how much it must be topped up to survive until the target time.
+///
+/// The forecast assumes that the account receives no funds and its storage
+/// size does not change. Storage prices are taken from `ConfigParam18` with the
+/// last known prices applied to the future. No storage fee is charged during
+/// `STORAGE_FEE_COOLER_TIME` after the last payment unless the account
+/// already has a due payment.
+#[api_function]
+pub async fn forecast_storage_fee(
+    context: Arc<ClientContext>,
+    params: ParamsOfForecastStorageFee,
+) -> ClientResult<ResultOfForecastStorageFee> {
+    let account =
+        deserialize_object_from_boc::<Account>(&context, &params.account, "account")?.object;
+    let config = match &params.blockchain_config {
+        Some(config) => Arc::new(blockchain_config_from_boc(&context, config)?),
+        None => crate::net::network_params::get_default_params(&context).await?.blockchain_config,
+    };
+    let now = params.now.unwrap_or_else(|| (context.env.now_ms() / 1000) as u32);
+    forecast(&config, account, now, params.target_time)
+}
+
+pub(crate) fn forecast(
+    config: &BlockchainConfig,
+    account: Account,
+    now: u32,
+    target_time: Option<u32>,
+) -> ClientResult<ResultOfForecastStorageFee> {
+    let addr = account.get_addr().ok_or(Error::invalid_account_boc("Account is None"))?.clone();
+    let last_paid = account.last_paid();
+    if last_paid == 0 {
+        return Err(Error::invalid_account_boc("Account `last_paid` field is not initialized"));
+    }
+    let is_special = config.is_special_account(&addr).map_err(|err| {
+        Error::invalid_account_boc(format!("can not check special account: {}", err))
+    })?;
+    let is_masterchain = addr.is_masterchain();
+    let gas_config = config.get_gas_config(is_masterchain);
+    let freeze_due_limit = gas_config.freeze_due_limit as u128;
+    let delete_due_limit = gas_config.delete_due_limit as u128;
+
+    let debt = |account: &Account, time: u32| -> ClientResult<u128> {
+        if is_special {
+            return Ok(0);
+        }
+        let storage =
+            account.storage_info().ok_or(Error::invalid_account_boc("Account is None"))?;
+        let fee = config.calc_storage_fee(storage, is_masterchain, time).map_err(|err| {
+            Error::invalid_account_boc(format!("can not calculate storage fee: {}", err))
+        })?;
+        let due = account.due_payment().map(|due| due.as_u128()).unwrap_or_default();
+        Ok(fee.as_u128() + due)
+    };
+    let balance = account.balance().map(|balance| balance.grams.as_u128()).unwrap_or_default();
+    let has_due = account.due_payment().is_some_and(|due| due.as_u128() != 0);
+    let first_charge =
+        if has_due { last_paid } else { last_paid.saturating_add(STORAGE_FEE_COOLER_TIME) };
+    let start = std::cmp::max(now, first_charge);
+
+    let (frozen_at, deleted_at) = match account.status() {
+        AccountStatus::AccStateActive => {
+            let frozen_at =
+                find_first(start, |time| Ok(debt(&account, time)? > balance + freeze_due_limit))?;
+            let deleted_at = match frozen_at {
+                Some(frozen_at) => {
+                    let frozen = freeze(&account, frozen_at, debt(&account, frozen_at)? - balance)?;
+                    find_first(frozen_at, |time| Ok(debt(&frozen, time)? > delete_due_limit))?
+                }
+                None => None,
+            };
+            (frozen_at, deleted_at)
+        }
+        AccountStatus::AccStateUninit | AccountStatus::AccStateFrozen => {
+            let deleted_at =
+                find_first(start, |time| Ok(debt(&account, time)? > balance + delete_due_limit))?;
+            (None, deleted_at)
+        }
+        AccountStatus::AccStateNonexist => {
+            return Err(Error::invalid_account_boc("Account is None"));
+        }
+    };
+
+    let top_up = match target_time {
+        Some(target_time) => Some(debt(&account, target_time)?.saturating_sub(balance).to_string()),
+        None => None,
+    };
+
+    Ok(ResultOfForecastStorageFee {
+        accrued_fee: debt(&account, now)?.to_string(),
+        frozen_at,
+        deleted_at,
+        top_up,
+    })
+}
+
+/// Returns the state of the account after it was frozen at the `time` with
+/// the `due` payment left.
+fn freeze(account: &Account, time: u32, due: u128) -> ClientResult<Account> {
+    let map_err = |err| Error::invalid_account_boc(format!("can not freeze account: {}", err));
+    let mut frozen = account.clone();
+    frozen.set_balance(Default::default());
+    frozen.set_due_payment(Some(tvm_block::Grams::new(due).map_err(map_err)?));
+    frozen.set_last_paid(time);
+    frozen.try_freeze().map_err(map_err)?;
+    frozen.update_storage_stat().map_err(map_err)?;
+    Ok(frozen)
+}
+
+/// Finds the earliest time not less than `from` satisfying the monotonic
+/// `predicate`.
+fn find_first(
+    from: u32,
+    predicate: impl Fn(u32) -> ClientResult<bool>,
+) -> ClientResult<Option<u32>> {
+    if !predicate(u32::MAX)? {
+        return Ok(None);
+    }
+    let (mut lo, mut hi) = (from, u32::MAX);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if predicate(mid)? {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    Ok(Some(lo))
+}
@@ -18,6 +18,7 @@ pub(crate) mod calc_storage_fee;
 pub(crate) mod compression;
 pub(crate) mod conversion;
 mod errors;
+pub(crate) mod forecast_storage_fee;
 pub(crate) mod json;
 
 pub use calc_storage_fee::ParamsOfCalcStorageFee;
@@ -36,5 +37,8 @@ pub use conversion::convert_address;
 pub use conversion::get_address_type;
 pub use errors::Error;
 pub use errors::ErrorCode;
+pub use forecast_storage_fee::ParamsOfForecastStorageFee;
+pub use forecast_storage_fee::ResultOfForecastStorageFee;
+pub use forecast_storage_fee::forecast_storage_fee;
 
 pub use crate::encoding::AccountAddressType;
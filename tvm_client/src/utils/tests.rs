@@ -293,3 +293,86 @@ fn utility_errors_preserve_code_and_context() {
     assert_eq!(decompression.code(), ErrorCode::CompressionError as u32);
     assert_eq!(decompression.message(), "Decompression error: bad frame");
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_forecast_storage_fee() {
+    use tvm_block::Account;
+    use tvm_block::Deserializable;
+    use tvm_block::Grams;
+    use tvm_block::Serializable;
+    use tvm_executor::BlockchainConfig;
+    use tvm_executor::STORAGE_FEE_COOLER_TIME;
+
+    let client = TestClient::new();
+    let config_boc = include_bytes!("../boc/test_data/block_config.boc");
+    let config = BlockchainConfig::with_config(
+        tvm_block::ConfigParams::construct_from_bytes(config_boc).unwrap(),
+    )
+    .unwrap();
+    let last_paid = 1_700_000_000;
+    let mut state_init = tvm_block::StateInit::default();
+    let mut code = tvm_types::Cell::default();
+    for i in 0..1000u32 {
+        let mut builder = tvm_types::BuilderData::with_raw(vec![i as u8; 127], 1016).unwrap();
+        builder.checked_append_reference(code).unwrap();
+        code = builder.into_cell().unwrap();
+    }
+    state_init.set_code(code);
+    let mut account = Account::active_by_init_code_hash(
+        tvm_block::MsgAddressInt::with_standart(None, 0, [0x11; 32].into()).unwrap(),
+        Default::default(),
+        last_paid,
+        state_init,
+        false,
+    )
+    .unwrap();
+    let is_masterchain = account.get_addr().unwrap().is_masterchain();
+    let limits = config.get_gas_config(is_masterchain).clone();
+    let fee_at = |account: &Account, time: u32| {
+        config.calc_storage_fee(account.storage_info().unwrap(), is_masterchain, time).unwrap()
+    };
+    account.set_balance(tvm_block::CurrencyCollection::with_grams(10_000_000));
+    account.set_due_payment(None);
+
+    let forecast = |account: &Account, target_time: Option<u32>| {
+        client.request_async::<_, ResultOfForecastStorageFee>(
+            "utils.forecast_storage_fee",
+            ParamsOfForecastStorageFee {
+                account: base64_encode(account.write_to_bytes().unwrap()),
+                now: Some(last_paid),
+                target_time,
+                blockchain_config: Some(base64_encode(config_boc)),
+            },
+        )
+    };
+
+    let result = forecast(&account, Some(last_paid + 1000)).await.unwrap();
+    assert_eq!(result.accrued_fee, "0");
+    assert_eq!(result.top_up, Some("0".to_string()));
+    let frozen_at = result.frozen_at.unwrap();
+    assert!(frozen_at >= last_paid + STORAGE_FEE_COOLER_TIME);
+    let threshold = 10_000_000 + limits.freeze_due_limit as u128;
+    assert!(fee_at(&account, frozen_at).as_u128() > threshold);
+    assert!(fee_at(&account, frozen_at - 1).as_u128() <= threshold);
+    // Frozen account storage is too small to reach the delete due limit
+    assert_eq!(result.deleted_at, None);
+
+    let result = forecast(&account, Some(frozen_at)).await.unwrap();
+    let top_up = fee_at(&account, frozen_at).as_u128() - 10_000_000;
+    assert_eq!(result.top_up, Some(top_up.to_string()));
+
+    // Due payment disables the cooler period, uninit account is deleted
+    // without being frozen
+    let due = limits.delete_due_limit as u128 - 1_000_000;
+    account.uninit_account();
+    account.set_due_payment(Some(Grams::new(due).unwrap()));
+    account.set_balance(Default::default());
+    let result = forecast(&account, None).await.unwrap();
+    assert_eq!(result.accrued_fee, due.to_string());
+    assert_eq!(result.frozen_at, None);
+    assert_eq!(result.top_up, None);
+    let deleted_at = result.deleted_at.unwrap();
+    assert!(deleted_at < last_paid + STORAGE_FEE_COOLER_TIME);
+    assert!(fee_at(&account, deleted_at).as_u128() + due > limits.delete_due_limit as u128);
+    assert!(fee_at(&account, deleted_at - 1).as_u128() + due <= limits.delete_due_limit as u128);
+}
@@ -6,6 +6,8 @@ mod helper;
 mod message;
 mod result;
 mod state;
+#[cfg(feature = "wasmtime")]
+mod wasm;
 
 use std::path::PathBuf;
 
@@ -61,6 +63,11 @@ enum Commands {
 
     /// Creates account state BOC
     AccountEncode(AccountEncodeArgs),
+
+    /// Inspects, hashes, whitelists and runs WASM components
+    #[cfg(feature = "wasmtime")]
+    #[command(subcommand)]
+    Wasm(wasm::WasmCommands),
 }
 
 // Read BOC string fron stdin and encode it as a set of provided parameters in
@@ -222,6 +229,8 @@ fn main() {
         Commands::StateEncode(args) => run_command(|| state::encode(args)),
         Commands::StateDecode(args) => run_command(|| state::decode(args)),
         Commands::AccountEncode(args) => run_command(|| account::encode(args)),
+        #[cfg(feature = "wasmtime")]
+        Commands::Wasm(command) => wasm::command(command),
     };
 
     match output {
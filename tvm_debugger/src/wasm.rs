@@ -0,0 +1,360 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use clap::Parser;
+use serde::Serialize;
+use tvm_types::sha256_digest;
+use tvm_vm::executor::Engine;
+use tvm_vm::executor::wasm::RUNWASM_GAS_PRICE;
+use tvm_vm::executor::wasm::WASM_200MS_FUEL;
+use tvm_vm::executor::wasm::call_wasm_component;
+use tvm_vm::executor::wasm::is_allowed_wasm_import;
+use tvm_vm::executor::wasm::validate_wasm_component;
+
+use crate::run_command;
+
+#[derive(Parser, Debug)]
+pub(crate) enum WasmCommands {
+    /// Lists exports and imports of a component and the imports `runwasm`
+    /// does not provide
+    Inspect(WasmFileArgs),
+    /// Prints the hash of a component as used in whitelists
+    Hash(WasmFileArgs),
+    /// Manages whitelist files and the binaries they refer to
+    #[command(subcommand)]
+    Whitelist(WasmWhitelistCommands),
+    /// Runs a component function locally and reports fuel and gas usage
+    Run(WasmRunArgs),
+}
+
+#[derive(Parser, Debug)]
+pub(crate) enum WasmWhitelistCommands {
+    /// Validates a component, copies it to the binaries directory and
+    /// whitelists its hash
+    Add(WhitelistAddArgs),
+    /// Removes a hash from the whitelist, the binary is kept
+    Remove(WhitelistRemoveArgs),
+    /// Lists whitelisted hashes and checks their binaries
+    List(WhitelistListArgs),
+}
+
+#[derive(Parser, Debug, Default)]
+pub(crate) struct WasmFileArgs {
+    /// WASM component file
+    file: PathBuf,
+}
+
+#[derive(Parser, Debug, Default)]
+pub(crate) struct WhitelistAddArgs {
+    /// WASM component file
+    file: PathBuf,
+
+    /// Whitelist file with one hex hash per line, created if missing
+    #[arg(short, long)]
+    whitelist: PathBuf,
+
+    /// Directory with component binaries named by their hashes
+    #[arg(short, long)]
+    binaries: PathBuf,
+}
+
+#[derive(Parser, Debug, Default)]
+pub(crate) struct WhitelistRemoveArgs {
+    /// Hex hash of the component
+    hash: String,
+
+    /// Whitelist file with one hex hash per line
+    #[arg(short, long)]
+    whitelist: PathBuf,
+}
+
+#[derive(Parser, Debug, Default)]
+pub(crate) struct WhitelistListArgs {
+    /// Whitelist file with one hex hash per line
+    #[arg(short, long)]
+    whitelist: PathBuf,
+
+    /// Directory with component binaries to check against the hashes
+    #[arg(short, long)]
+    binaries: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug, Default)]
+pub(crate) struct WasmRunArgs {
+    /// WASM component file
+    file: PathBuf,
+
+    /// Exported instance name, e.g. `docs:adder/add@0.1.0`
+    #[arg(short, long)]
+    instance: String,
+
+    /// Function name in the instance
+    #[arg(short, long)]
+    function: String,
+
+    /// Function argument bytes as hex
+    #[arg(short, long, default_value = "")]
+    args: String,
+
+    /// Fuel limit of the call
+    #[arg(long, default_value_t = WASM_200MS_FUEL)]
+    fuel: u64,
+
+    /// Block time seen by the component clocks
+    #[arg(long, default_value_t = 0)]
+    block_time: u64,
+
+    /// Global capabilities of the engine, e.g. `CapWasmDeterminism` seeds
+    /// the random source from c7 and ticks the monotonic clock in nanoseconds
+    #[arg(long, default_value_t = 0)]
+    capabilities: u64,
+}
+
+#[derive(Serialize, Debug)]
+pub(crate) struct ResultOfInspect {
+    hash: String,
+    exports: Vec<String>,
+    imports: Vec<String>,
+    forbidden_imports: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub(crate) struct ResultOfHash {
+    hash: String,
+}
+
+#[derive(Serialize, Debug)]
+pub(crate) struct ResultOfWhitelistChange {
+    hash: String,
+    changed: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub(crate) struct ResultOfWhitelistList {
+    hashes: Vec<WhitelistEntry>,
+}
+
+#[derive(Serialize, Debug)]
+pub(crate) struct WhitelistEntry {
+    hash: String,
+    /// Whether the binary is present and matches the hash, if checked
+    binary_ok: Option<bool>,
+}
+
+#[derive(Serialize, Debug)]
+pub(crate) struct ResultOfRun {
+    result: String,
+    fuel_used: u64,
+    gas_used: i64,
+}
+
+pub(crate) fn command(command: &WasmCommands) -> anyhow::Result<String> {
+    match command {
+        WasmCommands::Inspect(args) => run_command(|| inspect(args)),
+        WasmCommands::Hash(args) => run_command(|| hash(args)),
+        WasmCommands::Whitelist(WasmWhitelistCommands::Add(args)) => {
+            run_command(|| whitelist_add(args))
+        }
+        WasmCommands::Whitelist(WasmWhitelistCommands::Remove(args)) => {
+            run_command(|| whitelist_remove(args))
+        }
+        WasmCommands::Whitelist(WasmWhitelistCommands::List(args)) => {
+            run_command(|| whitelist_list(args))
+        }
+        WasmCommands::Run(args) => run_command(|| run(args)),
+    }
+}
+
+fn read_component(file: &Path) -> anyhow::Result<Vec<u8>> {
+    std::fs::read(file)
+        .map_err(|e| anyhow::format_err!("Failed to read WASM component {file:?}: {e}"))
+}
+
+fn wasm_engine(capabilities: u64) -> anyhow::Result<Engine> {
+    let mut engine = Engine::with_capabilities(capabilities);
+    engine.wasm_engine_init_cached()?;
+    Ok(engine)
+}
+
+pub fn inspect(args: &WasmFileArgs) -> anyhow::Result<ResultOfInspect> {
+    let binary = read_component(&args.file)?;
+    let engine = wasm_engine(0)?;
+    let component = engine.create_single_use_wasm_component(binary.clone())?;
+    let (exports, imports) = engine.wasm_component_exports_and_imports(&component)?;
+    let forbidden_imports =
+        imports.iter().filter(|import| !is_allowed_wasm_import(import)).cloned().collect();
+    Ok(ResultOfInspect {
+        hash: hex::encode(sha256_digest(&binary)),
+        exports,
+        imports,
+        forbidden_imports,
+    })
+}
+
+pub fn hash(args: &WasmFileArgs) -> anyhow::Result<ResultOfHash> {
+    Ok(ResultOfHash { hash: hex::encode(sha256_digest(read_component(&args.file)?)) })
+}
+
+fn read_whitelist(whitelist: &Path) -> anyhow::Result<Vec<String>> {
+    if !whitelist.exists() {
+        return Ok(Vec::new());
+    }
+    let text = std::fs::read_to_string(whitelist)
+        .map_err(|e| anyhow::format_err!("Failed to read whitelist {whitelist:?}: {e}"))?;
+    Ok(text
+        .lines()
+        .map(|line| line.trim().to_lowercase())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
+fn write_whitelist(whitelist: &Path, hashes: &[String]) -> anyhow::Result<()> {
+    std::fs::write(whitelist, hashes.iter().map(|hash| format!("{hash}\n")).collect::<String>())
+        .map_err(|e| anyhow::format_err!("Failed to write whitelist {whitelist:?}: {e}"))
+}
+
+pub fn whitelist_add(args: &WhitelistAddArgs) -> anyhow::Result<ResultOfWhitelistChange> {
+    let binary = read_component(&args.file)?;
+    validate_wasm_component(&Engine::extern_wasm_engine_init()?, &binary)?;
+    let hash = hex::encode(sha256_digest(&binary));
+    std::fs::create_dir_all(&args.binaries)?;
+    std::fs::write(args.binaries.join(&hash), &binary)?;
+    let mut hashes = read_whitelist(&args.whitelist)?;
+    if hashes.contains(&hash) {
+        return Ok(ResultOfWhitelistChange { hash, changed: false });
+    }
+    hashes.push(hash.clone());
+    write_whitelist(&args.whitelist, &hashes)?;
+    Ok(ResultOfWhitelistChange { hash, changed: true })
+}
+
+pub fn whitelist_remove(args: &WhitelistRemoveArgs) -> anyhow::Result<ResultOfWhitelistChange> {
+    let hash = args.hash.trim().to_lowercase();
+    let mut hashes = read_whitelist(&args.whitelist)?;
+    let len = hashes.len();
+    hashes.retain(|line| *line != hash);
+    let changed = hashes.len() != len;
+    if changed {
+        write_whitelist(&args.whitelist, &hashes)?;
+    }
+    Ok(ResultOfWhitelistChange { hash, changed })
+}
+
+pub fn whitelist_list(args: &WhitelistListArgs) -> anyhow::Result<ResultOfWhitelistList> {
+    let whitelist =
+        Engine::extern_load_wasm_hash_whitelist_from_path(args.whitelist.display().to_string())?;
+    let mut hashes = whitelist.iter().copied().collect::<Vec<_>>();
+    hashes.sort();
+    let hashes = hashes
+        .into_iter()
+        .map(|hash| WhitelistEntry {
+            hash: hex::encode(hash),
+            binary_ok: args.binaries.as_ref().map(|binaries| {
+                Engine::extern_get_wasm_binary_by_hash(
+                    binaries.display().to_string(),
+                    whitelist.clone(),
+                    hash.to_vec(),
+                )
+                .is_ok()
+            }),
+        })
+        .collect();
+    Ok(ResultOfWhitelistList { hashes })
+}
+
+pub fn run(args: &WasmRunArgs) -> anyhow::Result<ResultOfRun> {
+    let binary = read_component(&args.file)?;
+    let func_args = hex::decode(&args.args)
+        .map_err(|e| anyhow::format_err!("Failed to decode args as hex: {e}"))?;
+    let mut engine = wasm_engine(args.capabilities)?;
+    engine.set_wasm_block_time(args.block_time);
+    let component = engine.create_single_use_wasm_component(binary)?;
+    let call = call_wasm_component(
+        &engine,
+        &component,
+        &args.function,
+        &args.instance,
+        func_args,
+        args.fuel,
+        i64::MAX,
+    )?;
+    Ok(ResultOfRun {
+        result: hex::encode(call.result),
+        fuel_used: call.fuel_used,
+        gas_used: RUNWASM_GAS_PRICE as i64 + call.host_gas_used,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tvm_block::GlobalCapabilities;
+
+    use super::*;
+
+    const ADD_COMPONENT: &str = "../tvm_vm/src/tests/add.wasm";
+
+    #[test]
+    fn test_whitelist() {
+        let dir = std::env::temp_dir().join(format!("tvm_debugger_wasm_{}", std::process::id()));
+        let whitelist = dir.join("whitelist");
+        let binaries = dir.join("binaries");
+        let add = WhitelistAddArgs {
+            file: ADD_COMPONENT.into(),
+            whitelist: whitelist.clone(),
+            binaries: binaries.clone(),
+        };
+
+        let hash = hash(&WasmFileArgs { file: ADD_COMPONENT.into() }).unwrap().hash;
+        let result = whitelist_add(&add).unwrap();
+        assert_eq!(result.hash, hash);
+        assert!(result.changed);
+        assert!(!whitelist_add(&add).unwrap().changed);
+
+        let list = whitelist_list(&WhitelistListArgs {
+            whitelist: whitelist.clone(),
+            binaries: Some(binaries),
+        })
+        .unwrap();
+        assert_eq!(list.hashes.len(), 1);
+        assert_eq!(list.hashes[0].hash, hash);
+        assert_eq!(list.hashes[0].binary_ok, Some(true));
+
+        let remove = WhitelistRemoveArgs { hash: hash.clone(), whitelist: whitelist.clone() };
+        assert!(whitelist_remove(&remove).unwrap().changed);
+        assert!(!whitelist_remove(&remove).unwrap().changed);
+        assert!(read_whitelist(&whitelist).unwrap().is_empty());
+
+        // an entry without a trailing newline is kept apart from the new one
+        let other = "00".repeat(32);
+        std::fs::write(&whitelist, &other).unwrap();
+        assert!(whitelist_add(&add).unwrap().changed);
+        assert_eq!(read_whitelist(&whitelist).unwrap(), vec![other, hash]);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_inspect_and_run() {
+        let result = inspect(&WasmFileArgs { file: ADD_COMPONENT.into() }).unwrap();
+        assert!(result.exports.contains(&"docs:adder/add@0.1.0#add".to_string()));
+        assert!(result.forbidden_imports.is_empty());
+
+        let mut args = WasmRunArgs {
+            file: ADD_COMPONENT.into(),
+            instance: "docs:adder/add@0.1.0".to_string(),
+            function: "add".to_string(),
+            args: "0102".to_string(),
+            fuel: WASM_200MS_FUEL,
+            block_time: 0,
+            capabilities: 0,
+        };
+        let result = run(&args).unwrap();
+        assert!(result.fuel_used > 0);
+        assert_eq!(result.gas_used, RUNWASM_GAS_PRICE as i64);
+
+        args.capabilities = GlobalCapabilities::CapWasmDeterminism as u64;
+        let deterministic = run(&args).unwrap();
+        assert_eq!(deterministic.result, result.result);
+        assert_eq!(deterministic.gas_used, result.gas_used);
+    }
+}
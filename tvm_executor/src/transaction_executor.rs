@@ -112,7 +112,9 @@ const MAX_ACTIONS: usize = 255;
 const MAX_MSG_BITS: usize = 1 << 21;
 const MAX_MSG_CELLS: usize = 1 << 13;
 
-const STORAGE_FEE_COOLER_TIME: u32 = 2592000;
+/// Period after the last storage payment during which the storage fee is not
+/// charged unless the account has a due payment.
+pub const STORAGE_FEE_COOLER_TIME: u32 = 2592000;
 
 #[derive(Eq, PartialEq, Debug)]
 pub enum IncorrectCheckRewrite {
@@ -0,0 +1,47 @@
+mod rpc;
+mod server;
+#[cfg(test)]
+mod tests;
+
+use std::net::SocketAddr;
+
+use clap::Parser;
+use tokio::net::TcpListener;
+
+/// JSON-RPC 2.0 server exposing TVM SDK client functions over HTTP and
+/// WebSocket, so the SDK can be used as a sidecar from any language.
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Cli {
+    /// Address to listen on
+    #[arg(short, long, default_value = "127.0.0.1:8080")]
+    listen: SocketAddr,
+
+    /// Client config specified as a JSON string or file path
+    #[arg(short, long)]
+    config: Option<String>,
+}
+
+fn load_config(config: Option<&str>) -> anyhow::Result<String> {
+    match config {
+        None => Ok("{}".to_string()),
+        Some(config) if config.trim_start().starts_with('{') => Ok(config.to_string()),
+        Some(path) => Ok(std::fs::read_to_string(path)?),
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let config = load_config(cli.config.as_deref())?;
+    // Every connection gets its own context, check the config once up front.
+    tvm_client::destroy_context(rpc::create_context(config.clone())?);
+    let listener = TcpListener::bind(cli.listen).await?;
+    eprintln!("Listening on {}", listener.local_addr()?);
+    axum::serve(listener, server::router(config))
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    Ok(())
+}
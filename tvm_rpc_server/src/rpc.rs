@@ -0,0 +1,338 @@
+//! JSON-RPC 2.0 mapping of the client library json interface.
+//!
+//! Request `method` is the `module.function` name and `params` are the
+//! function parameters. Success and error responses of the library become the
+//! JSON-RPC response, other library responses are sent to the session as
+//! notifications:
+//! - `event` with `{ id, response_type, data }` for function events like
+//!   `processing.process_message` events and `net.subscribe_collection` data;
+//! - `app_request` with `{ id, data: { app_request_id, request_data } }` for
+//!   requests to application objects, resolved by the client with
+//!   `client.resolve_app_request`;
+//! - `app_notify` with `{ id, data }` for application object notifications.
+//!
+//! `id` is the id of the JSON-RPC request that started the function.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use anyhow::anyhow;
+use futures::future::join_all;
+use serde::Deserialize;
+use serde_json::Value;
+use serde_json::json;
+use tokio::sync::mpsc;
+use tvm_client::ContextHandle;
+use tvm_client::ResponseType;
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INTERNAL_ERROR: i64 = -32603;
+
+const SUBSCRIBE_FUNCTIONS: &[&str] = &["net.subscribe", "net.subscribe_collection"];
+const APP_OBJECTS_UNSUPPORTED: &str = "Application objects are supported over WebSocket only";
+const SUBSCRIPTIONS_UNSUPPORTED: &str = "Subscriptions are supported over WebSocket only";
+
+/// Response of the library to the dispatched function.
+#[derive(Debug)]
+pub struct LibraryResponse {
+    pub params_json: String,
+    pub response_type: u32,
+    pub finished: bool,
+}
+
+lazy_static::lazy_static! {
+    static ref REQUESTS: Mutex<HashMap<usize, mpsc::UnboundedSender<LibraryResponse>>> =
+        Mutex::new(HashMap::new());
+}
+
+static NEXT_REQUEST_ID: AtomicUsize = AtomicUsize::new(1);
+
+fn response_handler(
+    request_ptr: *const (),
+    params_json: String,
+    response_type: u32,
+    finished: bool,
+) {
+    // `request_ptr` is not dereferenced, it is the key of the request sender.
+    let request_id = request_ptr as usize;
+    let mut requests = REQUESTS.lock().unwrap();
+    let sender =
+        if finished { requests.remove(&request_id) } else { requests.get(&request_id).cloned() };
+    drop(requests);
+    if let Some(sender) = sender {
+        let _ = sender.send(LibraryResponse { params_json, response_type, finished });
+    }
+}
+
+/// Dispatches `function` to the library and returns the channel of its
+/// responses. The channel is closed after the finishing response.
+pub fn dispatch(
+    context: ContextHandle,
+    function: String,
+    params_json: String,
+) -> mpsc::UnboundedReceiver<LibraryResponse> {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let request_id = NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed);
+    REQUESTS.lock().unwrap().insert(request_id, sender);
+    tvm_client::request_ptr(
+        context,
+        function,
+        params_json,
+        request_id as *const (),
+        response_handler,
+    );
+    receiver
+}
+
+/// Creates client context with `config`.
+pub fn create_context(config: String) -> anyhow::Result<ContextHandle> {
+    let response: Value = serde_json::from_str(&tvm_client::create_context(config))?;
+    match response["result"].as_u64() {
+        Some(handle) => Ok(handle as ContextHandle),
+        None => Err(anyhow!("Failed to create client context: {}", response["error"])),
+    }
+}
+
+#[derive(Deserialize)]
+struct Request {
+    jsonrpc: String,
+    #[serde(default)]
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Option<Value>,
+}
+
+pub fn result_response(id: Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+pub fn error_response(id: Value, code: i64, message: impl Into<String>, data: Value) -> Value {
+    let mut error = json!({ "code": code, "message": message.into() });
+    if !data.is_null() {
+        error["data"] = data;
+    }
+    json!({ "jsonrpc": "2.0", "id": id, "error": error })
+}
+
+fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+/// Connection state of the transport. The session owns its client context, so
+/// signing boxes, crypto boxes, application objects and other handles created
+/// in one session can not be used from another one.
+#[derive(Debug)]
+pub struct Session {
+    context: ContextHandle,
+    notifications: Option<mpsc::UnboundedSender<Value>>,
+    subscriptions: Mutex<Vec<Value>>,
+}
+
+impl Session {
+    /// Session of a transport without notifications (HTTP). Function events are
+    /// dropped, subscriptions and application requests are rejected.
+    pub fn new(config: String) -> anyhow::Result<Self> {
+        Self::create(config, None)
+    }
+
+    /// Session of a transport that can deliver notifications (WebSocket).
+    pub fn with_notifications(
+        config: String,
+        notifications: mpsc::UnboundedSender<Value>,
+    ) -> anyhow::Result<Self> {
+        Self::create(config, Some(notifications))
+    }
+
+    fn create(
+        config: String,
+        notifications: Option<mpsc::UnboundedSender<Value>>,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            context: create_context(config)?,
+            notifications,
+            subscriptions: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Handles JSON-RPC message: single request or batch. Returns `None` if
+    /// there is nothing to reply, e.g. for notifications.
+    pub async fn handle_message(&self, message: &str) -> Option<Value> {
+        let message = match serde_json::from_str::<Value>(message) {
+            Ok(message) => message,
+            Err(err) => {
+                return Some(error_response(
+                    Value::Null,
+                    PARSE_ERROR,
+                    err.to_string(),
+                    Value::Null,
+                ));
+            }
+        };
+        match message {
+            Value::Array(batch) if batch.is_empty() => {
+                Some(error_response(Value::Null, INVALID_REQUEST, "Empty batch", Value::Null))
+            }
+            Value::Array(batch) => {
+                let replies =
+                    join_all(batch.into_iter().map(|request| self.handle_request(request)))
+                        .await
+                        .into_iter()
+                        .flatten()
+                        .collect::<Vec<_>>();
+                if replies.is_empty() { None } else { Some(Value::Array(replies)) }
+            }
+            request => self.handle_request(request).await,
+        }
+    }
+
+    async fn handle_request(&self, request: Value) -> Option<Value> {
+        let request = match serde_json::from_value::<Request>(request) {
+            Ok(request) if request.jsonrpc == "2.0" => request,
+            Ok(_) => {
+                return Some(error_response(
+                    Value::Null,
+                    INVALID_REQUEST,
+                    "Unsupported jsonrpc version",
+                    Value::Null,
+                ));
+            }
+            Err(err) => {
+                return Some(error_response(
+                    Value::Null,
+                    INVALID_REQUEST,
+                    err.to_string(),
+                    Value::Null,
+                ));
+            }
+        };
+        let params_json = match request.params {
+            None | Some(Value::Null) => String::new(),
+            Some(params) => params.to_string(),
+        };
+        let id = request.id;
+        let reply = self.call(request.method, params_json, id.clone().unwrap_or(Value::Null)).await;
+        id.map(|_| reply)
+    }
+
+    /// Calls library function and waits for its result. Responses that come
+    /// after the result are forwarded as notifications until the function is
+    /// finished.
+    async fn call(&self, function: String, params_json: String, id: Value) -> Value {
+        if self.notifications.is_none() && SUBSCRIBE_FUNCTIONS.contains(&function.as_str()) {
+            return error_response(id, INVALID_REQUEST, SUBSCRIPTIONS_UNSUPPORTED, Value::Null);
+        }
+        let mut responses = dispatch(self.context, function.clone(), params_json);
+        let mut target = NotificationTarget {
+            context: self.context,
+            notifications: self.notifications.clone(),
+            id: id.clone(),
+        };
+        while let Some(response) = responses.recv().await {
+            let data = response_data(&response);
+            let reply = match response.response_type {
+                t if t == ResponseType::Success as u32 => result_response(id, data),
+                t if t == ResponseType::Error as u32 => {
+                    let code = match data["code"].as_i64().unwrap_or_default() {
+                        code if code == tvm_client::client::ErrorCode::UnknownFunction as i64 => {
+                            METHOD_NOT_FOUND
+                        }
+                        code => code,
+                    };
+                    let message = data["message"].as_str().unwrap_or_default().to_string();
+                    error_response(id, code, message, data["data"].clone())
+                }
+                response_type => {
+                    target.send(response_type, data);
+                    continue;
+                }
+            };
+            if response.response_type == ResponseType::Success as u32 && !response.finished {
+                if SUBSCRIBE_FUNCTIONS.contains(&function.as_str()) {
+                    self.subscriptions.lock().unwrap().push(reply["result"]["handle"].clone());
+                }
+                // Application objects and subscriptions live after the result.
+                tokio::spawn(async move {
+                    while let Some(response) = responses.recv().await {
+                        let data = response_data(&response);
+                        target.send(response.response_type, data);
+                    }
+                });
+            }
+            return reply;
+        }
+        error_response(id, INTERNAL_ERROR, "Function finished without result", Value::Null)
+    }
+
+    /// Cancels subscriptions made in the session and destroys its context with
+    /// all handles and application objects. Called when the connection is
+    /// closed.
+    pub async fn close(&self) {
+        let subscriptions = std::mem::take(&mut *self.subscriptions.lock().unwrap());
+        for handle in subscriptions {
+            let mut responses = dispatch(
+                self.context,
+                "net.unsubscribe".to_string(),
+                json!({ "handle": handle }).to_string(),
+            );
+            while responses.recv().await.is_some() {}
+        }
+        tvm_client::destroy_context(self.context);
+    }
+}
+
+fn response_data(response: &LibraryResponse) -> Value {
+    serde_json::from_str(&response.params_json).unwrap_or(Value::Null)
+}
+
+/// Receiver of the library responses other than the function result.
+struct NotificationTarget {
+    context: ContextHandle,
+    notifications: Option<mpsc::UnboundedSender<Value>>,
+    id: Value,
+}
+
+impl NotificationTarget {
+    fn send(&mut self, response_type: u32, data: Value) {
+        if response_type == ResponseType::Nop as u32 {
+            return;
+        }
+        if let Some(notifications) = &self.notifications {
+            let notification = response_notification(&self.id, response_type, data.clone());
+            if notifications.send(notification).is_ok() {
+                return;
+            }
+            // The connection is closed, nobody can answer from now on.
+            self.notifications = None;
+        }
+        if response_type == ResponseType::AppRequest as u32 {
+            let params = json!({
+                "app_request_id": data["app_request_id"],
+                "result": { "type": "Error", "text": APP_OBJECTS_UNSUPPORTED },
+            });
+            drop(dispatch(
+                self.context,
+                "client.resolve_app_request".to_string(),
+                params.to_string(),
+            ));
+        }
+    }
+}
+
+fn response_notification(id: &Value, response_type: u32, data: Value) -> Value {
+    match response_type {
+        t if t == ResponseType::AppRequest as u32 => {
+            notification("app_request", json!({ "id": id, "data": data }))
+        }
+        t if t == ResponseType::AppNotify as u32 => {
+            notification("app_notify", json!({ "id": id, "data": data }))
+        }
+        response_type => {
+            notification("event", json!({ "id": id, "response_type": response_type, "data": data }))
+        }
+    }
+}
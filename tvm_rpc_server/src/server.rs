@@ -0,0 +1,98 @@
+//! HTTP and WebSocket transports of the JSON-RPC server.
+//!
+//! `POST /` handles JSON-RPC request or batch in the request body. The session
+//! lives for one request, so subscriptions are available over WebSocket only.
+//! `GET /` upgrades to WebSocket: every text message is a JSON-RPC request or
+//! batch, replies and notifications are sent back as text messages.
+//!
+//! Every session gets its own client context created from the server config.
+//! Handles registered over HTTP live until the reply is sent, handles
+//! registered over WebSocket live until the socket is closed.
+
+use std::sync::Arc;
+
+use axum::Router;
+use axum::extract::State;
+use axum::extract::WebSocketUpgrade;
+use axum::extract::ws::Message;
+use axum::extract::ws::WebSocket;
+use axum::http::StatusCode;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use axum::routing::get;
+use futures::SinkExt;
+use futures::StreamExt;
+use serde_json::Value;
+use tokio::sync::mpsc;
+
+use crate::rpc::INTERNAL_ERROR;
+use crate::rpc::Session;
+use crate::rpc::error_response;
+
+pub fn router(config: String) -> Router {
+    Router::new().route("/", get(websocket).post(http)).with_state(Arc::new(config))
+}
+
+async fn http(State(config): State<Arc<String>>, body: String) -> Response {
+    let session = match Session::new(config.to_string()) {
+        Ok(session) => session,
+        Err(err) => {
+            let reply = error_response(Value::Null, INTERNAL_ERROR, err.to_string(), Value::Null);
+            return json_response(reply);
+        }
+    };
+    let reply = session.handle_message(&body).await;
+    session.close().await;
+    match reply {
+        Some(reply) => json_response(reply),
+        None => StatusCode::NO_CONTENT.into_response(),
+    }
+}
+
+fn json_response(reply: Value) -> Response {
+    ([(header::CONTENT_TYPE, "application/json")], reply.to_string()).into_response()
+}
+
+async fn websocket(State(config): State<Arc<String>>, upgrade: WebSocketUpgrade) -> Response {
+    upgrade.on_upgrade(move |socket| serve_websocket(config.to_string(), socket))
+}
+
+async fn serve_websocket(config: String, socket: WebSocket) {
+    let (mut sink, mut stream) = socket.split();
+    let (sender, mut outgoing) = mpsc::unbounded_channel::<Value>();
+    let session = match Session::with_notifications(config, sender.clone()) {
+        Ok(session) => Arc::new(session),
+        Err(err) => {
+            let reply = error_response(Value::Null, INTERNAL_ERROR, err.to_string(), Value::Null);
+            let _ = sink.send(Message::Text(reply.to_string().into())).await;
+            return;
+        }
+    };
+
+    let writer = tokio::spawn(async move {
+        while let Some(message) = outgoing.recv().await {
+            if sink.send(Message::Text(message.to_string().into())).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    while let Some(Ok(message)) = stream.next().await {
+        let text = match message {
+            Message::Text(text) => text.to_string(),
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        let session = session.clone();
+        let sender = sender.clone();
+        tokio::spawn(async move {
+            if let Some(reply) = session.handle_message(&text).await {
+                let _ = sender.send(reply);
+            }
+        });
+    }
+
+    session.close().await;
+    writer.abort();
+}
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+
+use futures::SinkExt;
+use futures::StreamExt;
+use serde_json::Value;
+use serde_json::json;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+use tokio_tungstenite::MaybeTlsStream;
+use tokio_tungstenite::WebSocketStream;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::rpc::INVALID_REQUEST;
+use crate::rpc::METHOD_NOT_FOUND;
+use crate::rpc::PARSE_ERROR;
+
+const PUBLIC_KEY: &str = "134c67910aa0bd4410e0b62379d517af13df99ba04764bca06e0ba86c736b80a";
+
+async fn start_server() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, crate::server::router("{}".to_string())).await.unwrap();
+    });
+    address
+}
+
+async fn post(address: SocketAddr, body: impl ToString) -> (u16, Option<Value>) {
+    let response = reqwest::Client::new()
+        .post(format!("http://{}/", address))
+        .body(body.to_string())
+        .send()
+        .await
+        .unwrap();
+    let status = response.status().as_u16();
+    let text = response.text().await.unwrap();
+    (status, if text.is_empty() { None } else { Some(serde_json::from_str(&text).unwrap()) })
+}
+
+fn request(id: u32, method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_http() {
+    let address = start_server().await;
+
+    let (status, reply) = post(address, request(1, "client.version", Value::Null)).await;
+    assert_eq!(status, 200);
+    let reply = reply.unwrap();
+    assert_eq!(reply["id"], 1);
+    assert_eq!(reply["result"]["version"], env!("CARGO_PKG_VERSION"));
+
+    let (_, reply) =
+        post(address, request(2, "crypto.generate_random_bytes", json!({ "length": 8 }))).await;
+    assert_eq!(base64_len(&reply.unwrap()["result"]["bytes"]), 8);
+
+    let (_, reply) = post(address, request(3, "crypto.unknown", json!({}))).await;
+    let error = &reply.unwrap()["error"];
+    assert_eq!(error["code"], METHOD_NOT_FOUND);
+    assert!(error["message"].as_str().unwrap().contains("crypto.unknown"));
+
+    let (_, reply) = post(address, "{").await;
+    assert_eq!(reply.unwrap()["error"]["code"], PARSE_ERROR);
+
+    let (_, reply) = post(address, json!({ "jsonrpc": "2.0", "id": 4 })).await;
+    assert_eq!(reply.unwrap()["error"]["code"], INVALID_REQUEST);
+
+    let batch = json!([
+        request(5, "client.version", Value::Null),
+        { "jsonrpc": "2.0", "method": "client.version" },
+    ]);
+    let (_, reply) = post(address, batch).await;
+    let reply = reply.unwrap();
+    assert_eq!(reply.as_array().unwrap().len(), 1);
+    assert_eq!(reply[0]["id"], 5);
+
+    let (status, reply) =
+        post(address, json!({ "jsonrpc": "2.0", "method": "client.version" })).await;
+    assert_eq!(status, 204);
+    assert!(reply.is_none());
+}
+
+fn base64_len(value: &Value) -> usize {
+    let text = value.as_str().unwrap();
+    text.len() / 4 * 3 - text.chars().rev().take_while(|c| *c == '=').count()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_http_rejects_subscriptions() {
+    let address = start_server().await;
+    let params = json!({ "collection": "accounts", "filter": {}, "result": "id" });
+    let (_, reply) = post(address, request(1, "net.subscribe_collection", params)).await;
+    let error = &reply.unwrap()["error"];
+    assert_eq!(error["code"], INVALID_REQUEST);
+    assert!(error["message"].as_str().unwrap().contains("WebSocket"), "{}", error);
+}
+
+async fn signing_box_keys(address: SocketAddr) -> Value {
+    let (_, reply) =
+        post(address, request(1, "crypto.generate_random_sign_keys", Value::Null)).await;
+    reply.unwrap()["result"].clone()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_http_handles_are_per_request() {
+    let address = start_server().await;
+    let keys = signing_box_keys(address).await;
+    let (_, reply) = post(address, request(1, "crypto.get_signing_box", keys)).await;
+    let handle = reply.unwrap()["result"]["handle"].clone();
+
+    let (_, reply) =
+        post(address, request(2, "crypto.signing_box_get_public_key", json!({ "handle": handle })))
+            .await;
+    let error = &reply.unwrap()["error"];
+    assert!(error["message"].as_str().unwrap().contains("not registered"), "{}", error);
+}
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+async fn send(socket: &mut Socket, message: Value) {
+    socket.send(Message::text(message.to_string())).await.unwrap();
+}
+
+async fn receive(socket: &mut Socket) -> Value {
+    let message = socket.next().await.unwrap().unwrap();
+    serde_json::from_str(message.to_text().unwrap()).unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_websocket_app_object() {
+    let address = start_server().await;
+    let (mut socket, _) = connect_async(format!("ws://{}/", address)).await.unwrap();
+
+    send(&mut socket, request(1, "crypto.register_signing_box", Value::Null)).await;
+    let handle = receive(&mut socket).await["result"]["handle"].clone();
+
+    send(&mut socket, request(2, "crypto.signing_box_get_public_key", json!({ "handle": handle })))
+        .await;
+    let app_request = receive(&mut socket).await;
+    assert_eq!(app_request["method"], "app_request");
+    assert_eq!(app_request["params"]["id"], 1);
+    let data = &app_request["params"]["data"];
+    assert_eq!(data["request_data"]["type"], "GetPublicKey");
+
+    let resolve = request(
+        3,
+        "client.resolve_app_request",
+        json!({
+            "app_request_id": data["app_request_id"],
+            "result": { "type": "Ok", "result": { "type": "GetPublicKey", "public_key": PUBLIC_KEY } },
+        }),
+    );
+    send(&mut socket, resolve).await;
+    let replies = [receive(&mut socket).await, receive(&mut socket).await]
+        .into_iter()
+        .map(|reply| (reply["id"].as_u64().unwrap(), reply))
+        .collect::<HashMap<_, _>>();
+    assert_eq!(replies[&3]["result"], Value::Null);
+    assert_eq!(replies[&2]["result"]["pubkey"], PUBLIC_KEY);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_websocket_handles_are_per_connection() {
+    let address = start_server().await;
+    let keys = signing_box_keys(address).await;
+    let (mut owner, _) = connect_async(format!("ws://{}/", address)).await.unwrap();
+    let (mut other, _) = connect_async(format!("ws://{}/", address)).await.unwrap();
+
+    send(&mut owner, request(1, "crypto.get_signing_box", keys.clone())).await;
+    let handle = receive(&mut owner).await["result"]["handle"].clone();
+
+    let get_public_key =
+        request(2, "crypto.signing_box_get_public_key", json!({ "handle": handle }));
+    send(&mut other, get_public_key.clone()).await;
+    let error = &receive(&mut other).await["error"];
+    assert!(error["message"].as_str().unwrap().contains("not registered"), "{}", error);
+
+    send(&mut owner, get_public_key).await;
+    assert_eq!(receive(&mut owner).await["result"]["pubkey"], keys["public"]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_session_close_destroys_context() {
+    let (sender, _notifications) = tokio::sync::mpsc::unbounded_channel();
+    let session = crate::rpc::Session::with_notifications("{}".to_string(), sender).unwrap();
+    let reply = session
+        .handle_message(&request(1, "crypto.register_signing_box", Value::Null).to_string())
+        .await
+        .unwrap();
+    let handle = reply["result"]["handle"].clone();
+
+    session.close().await;
+    let reply = session
+        .handle_message(
+            &request(2, "crypto.signing_box_get_public_key", json!({ "handle": handle }))
+                .to_string(),
+        )
+        .await
+        .unwrap();
+    let error = &reply["error"];
+    assert!(error["message"].as_str().unwrap().contains("Invalid context handle"), "{}", error);
+}
@@ -15,6 +15,9 @@ use std::io::Seek;
 use chrono::prelude::Utc;
 use serde_json::Value;
 use tvm_abi::PublicKeyData;
+use tvm_abi::SchemeSignature;
+use tvm_abi::SignatureData;
+use tvm_abi::SignatureScheme;
 use tvm_abi::json_abi::DecodedMessage;
 use tvm_block::AccountIdPrefixFull;
 use tvm_block::CurrencyCollection;
@@ -395,14 +398,32 @@ impl Contract {
         dst_address: MsgAddressInt,
         src_address: MsgAddressExt,
         params: &FunctionCallSet,
+    ) -> Result<MessageToSign> {
+        Self::get_call_message_bytes_for_signing_with_scheme(
+            dst_address,
+            src_address,
+            params,
+            SignatureScheme::Ed25519,
+        )
+    }
+
+    // Same as `get_call_message_bytes_for_signing`, but the message is prepared
+    // for the signature of `scheme`. Sign should be then added with
+    // `add_sign_to_message_with_scheme` function.
+    pub fn get_call_message_bytes_for_signing_with_scheme(
+        dst_address: MsgAddressInt,
+        src_address: MsgAddressExt,
+        params: &FunctionCallSet,
+        scheme: SignatureScheme,
     ) -> Result<MessageToSign> {
         // pack params into bag of cells via ABI
-        let (msg_body, data_to_sign) = tvm_abi::prepare_function_call_for_sign(
+        let (msg_body, data_to_sign) = tvm_abi::prepare_function_call_for_sign_with_scheme(
             &params.abi,
             &params.func,
             params.header.as_deref(),
             &params.input,
             Some(&dst_address.to_string()),
+            scheme,
         )?;
         let msg = Self::create_ext_in_message(
             dst_address,
@@ -497,12 +518,32 @@ impl Contract {
         workchain_id: i32,
         src_address: MsgAddressExt,
     ) -> Result<MessageToSign> {
-        let (msg_body, data_to_sign) = tvm_abi::prepare_function_call_for_sign(
+        Self::get_deploy_message_bytes_for_signing_with_scheme(
+            params,
+            image,
+            workchain_id,
+            src_address,
+            SignatureScheme::Ed25519,
+        )
+    }
+
+    // Same as `get_deploy_message_bytes_for_signing`, but the message is
+    // prepared for the signature of `scheme`. Signature should be then added
+    // with `add_sign_to_message_with_scheme` function.
+    pub fn get_deploy_message_bytes_for_signing_with_scheme(
+        params: &FunctionCallSet,
+        image: ContractImage,
+        workchain_id: i32,
+        src_address: MsgAddressExt,
+        scheme: SignatureScheme,
+    ) -> Result<MessageToSign> {
+        let (msg_body, data_to_sign) = tvm_abi::prepare_function_call_for_sign_with_scheme(
             &params.abi,
             &params.func,
             params.header.as_deref(),
             &params.input,
             Some(&image.msg_address(workchain_id).to_string()),
+            scheme,
         )?;
 
         let cell = SliceData::load_cell(msg_body.into_cell()?)?;
@@ -554,6 +595,20 @@ impl Contract {
         signature: &[u8],
         public_key: Option<&[u8]>,
         message: &[u8],
+    ) -> Result<SdkMessage> {
+        let signature = SchemeSignature::new(SignatureScheme::Ed25519, signature.to_vec())?;
+        Self::add_sign_to_message_with_scheme(abi, signature, public_key, message)
+    }
+
+    // Add sign of any signature scheme to message, returned by
+    // `get_deploy_message_bytes_for_signing_with_scheme` or
+    // `get_call_message_bytes_for_signing_with_scheme` function.
+    // Returns serialized message and identifier.
+    pub fn add_sign_to_message_with_scheme(
+        abi: &str,
+        signature: SchemeSignature,
+        public_key: Option<&[u8]>,
+        message: &[u8],
     ) -> Result<SdkMessage> {
         let mut slice = Self::deserialize_tree_to_slice(message)?;
 
@@ -565,7 +620,7 @@ impl Contract {
 
         let signed_body = tvm_abi::add_sign_to_function_call(
             abi,
-            signature.try_into()?,
+            signature,
             public_key.map(|slice| slice.try_into()).transpose()?,
             body,
         )?;
@@ -600,7 +655,7 @@ impl Contract {
             .ok_or(error!(SdkError::InvalidData { msg: "No message body".to_owned() }))?;
 
         let signed_body = abi.add_sign_to_encoded_input(
-            signature.try_into()?,
+            <&SignatureData>::try_from(signature)?,
             public_key.map(|slice| slice.try_into()).transpose()?,
             body,
         )?;
@@ -583,6 +583,15 @@ impl SliceData {
         Ok(value)
     }
 
+    pub fn get_next_i64(&mut self) -> Result<i64> {
+        let mut value: i64 = 0;
+        for i in 0..8 {
+            value |= (self.get_byte(8 * i)? as i64) << (8 * (7 - i));
+        }
+        self.move_by(64)?;
+        Ok(value)
+    }
+
     pub fn get_next_u128(&mut self) -> Result<u128> {
         let mut value: u128 = 0;
         for i in 0..16 {
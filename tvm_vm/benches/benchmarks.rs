@@ -9,6 +9,7 @@
 // See the License for the specific TON DEV software governing permissions and
 // limitations under the License.
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 
 use base64ct::Encoding;
@@ -25,7 +26,9 @@ use tvm_abi::TokenValue;
 use tvm_abi::contract::ABI_VERSION_2_4;
 use tvm_block::Deserializable;
 use tvm_block::StateInit;
+use tvm_types::BuilderData;
 use tvm_types::SliceData;
+use tvm_vm::executor::DecodedCodeCache;
 use tvm_vm::executor::Engine;
 use tvm_vm::executor::zk_stuff::error::ZkCryptoError;
 use tvm_vm::executor::zk_stuff::utils::gen_address_seed;
@@ -100,23 +103,29 @@ fn bench_elector_algo_1000_vtors(c: &mut Criterion) {
     group.noise_threshold(0.03);
     group.sample_size(10);
     group.sampling_mode(SamplingMode::Flat);
-    group.bench_function("elector-algo-1000-vtors", |b| {
-        b.iter(|| {
-            let mut engine = Engine::with_capabilities(DEFAULT_CAPABILITIES).setup_with_libraries(
-                SliceData::load_cell_ref(&elector_code).unwrap(),
-                Some(ctrls.clone()),
-                Some(stack.clone()),
-                None,
-                vec![],
-            );
-            engine.execute().unwrap();
-            assert_eq!(engine.gas_used(), 82386791);
-            let output = engine.ctrl(4).unwrap().as_cell().unwrap();
-            assert_eq!(output, &elector_data_output);
-            let actions = engine.ctrl(5).unwrap().as_cell().unwrap();
-            assert_eq!(actions, &elector_actions);
-        })
-    });
+    let run = |cache: Option<&Arc<DecodedCodeCache>>| {
+        let mut engine = Engine::with_capabilities(DEFAULT_CAPABILITIES).setup_with_libraries(
+            SliceData::load_cell_ref(&elector_code).unwrap(),
+            Some(ctrls.clone()),
+            Some(stack.clone()),
+            None,
+            vec![],
+        );
+        if let Some(cache) = cache {
+            engine.set_decoded_code_cache(cache.clone());
+        }
+        engine.execute().unwrap();
+        assert_eq!(engine.gas_used(), 82386791);
+        let output = engine.ctrl(4).unwrap().as_cell().unwrap();
+        assert_eq!(output, &elector_data_output);
+        let actions = engine.ctrl(5).unwrap().as_cell().unwrap();
+        assert_eq!(actions, &elector_actions);
+    };
+    group.bench_function("elector-algo-1000-vtors", |b| b.iter(|| run(None)));
+    // cache stays warm between iterations, as in an executor running the same
+    // contract
+    let cache = Arc::new(DecodedCodeCache::new());
+    group.bench_function("elector-algo-1000-vtors-decoded", |b| b.iter(|| run(Some(&cache))));
     group.finish();
 }
 
@@ -153,20 +162,57 @@ fn bench_tiny_loop_200000_iters(c: &mut Criterion) {
     group.noise_threshold(0.03);
     group.sample_size(10);
     group.sampling_mode(SamplingMode::Flat);
-    group.bench_function("tiny-loop-200000-iters", |b| {
-        b.iter(|| {
-            let mut engine = Engine::with_capabilities(DEFAULT_CAPABILITIES).setup_with_libraries(
-                SliceData::load_cell_ref(&tiny_code).unwrap(),
-                Some(ctrls.clone()),
-                Some(stack.clone()),
-                None,
-                vec![],
-            );
-            engine.execute().unwrap();
-            assert_eq!(engine.gas_used(), 34000891);
-            // result of computation gets verified within the test itself
-        })
-    });
+    let run = |cache: Option<&Arc<DecodedCodeCache>>| {
+        let mut engine = Engine::with_capabilities(DEFAULT_CAPABILITIES).setup_with_libraries(
+            SliceData::load_cell_ref(&tiny_code).unwrap(),
+            Some(ctrls.clone()),
+            Some(stack.clone()),
+            None,
+            vec![],
+        );
+        if let Some(cache) = cache {
+            engine.set_decoded_code_cache(cache.clone());
+        }
+        engine.execute().unwrap();
+        assert_eq!(engine.gas_used(), 34000891);
+        // result of computation gets verified within the test itself
+    };
+    group.bench_function("tiny-loop-200000-iters", |b| b.iter(|| run(None)));
+    let cache = Arc::new(DecodedCodeCache::new());
+    group.bench_function("tiny-loop-200000-iters-decoded", |b| b.iter(|| run(Some(&cache))));
+}
+
+fn bench_decode_heavy_loop(c: &mut Criterion) {
+    // PUSHSLICE with 124 data bits; DROP; 51-bit PUSHINT; DROP; PUSHCONT { PUSHINT
+    // 1; PUSHINT 2 }; DROP
+    let body = [
+        0x8B, 0xF1, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0x01, 0x23, 0x45, 0x67, 0x89, 0xAB,
+        0xCD, 0x80, 0x30, 0x82, 0x21, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0x30, 0x92, 0x71, 0x72,
+        0x30, 0x80,
+    ];
+    let body = BuilderData::with_bitstring(body.to_vec()).unwrap().into_cell().unwrap();
+    // PUSHINT 20000; PUSHREFCONT; REPEAT
+    let mut code = BuilderData::with_bitstring(vec![0x81, 0x4E, 0x20, 0x8A, 0xE4, 0x80]).unwrap();
+    code.checked_append_reference(body).unwrap();
+    let code = code.into_cell().unwrap();
+
+    let run = |cache: Option<&Arc<DecodedCodeCache>>| {
+        let mut engine = Engine::with_capabilities(DEFAULT_CAPABILITIES).setup_with_libraries(
+            SliceData::load_cell_ref(&code).unwrap(),
+            None,
+            None,
+            None,
+            vec![],
+        );
+        if let Some(cache) = cache {
+            engine.set_decoded_code_cache(cache.clone());
+        }
+        engine.execute().unwrap();
+        assert_eq!(engine.steps(), 140004);
+    };
+    c.bench_function("decode-heavy-loop", |b| b.iter(|| run(None)));
+    let cache = Arc::new(DecodedCodeCache::new());
+    c.bench_function("decode-heavy-loop-decoded", |b| b.iter(|| run(Some(&cache))));
 }
 
 fn bench_num_bigint(c: &mut Criterion) {
@@ -988,6 +1034,7 @@ criterion_group!(
         bench_load_boc,
         bench_elector_algo_1000_vtors,
         bench_tiny_loop_200000_iters,
+        bench_decode_heavy_loop,
         bench_mergesort_tuple,
         bench_massive_cell_upload,
         bench_massive_cell_finalize,
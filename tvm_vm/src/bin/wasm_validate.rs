@@ -0,0 +1,58 @@
+// Copyright (C) 2019-2023 TON Labs. All Rights Reserved.
+//
+// Licensed under the SOFTWARE EVALUATION License (the "License"); you may not
+// use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific TON DEV software governing permissions and
+// limitations under the License.
+
+//! Checks WASM components before their hashes are added to a whitelist.
+//!
+//! Usage: `wasm-validate <component.wasm>...`
+//!
+//! Prints the hash and imports of every accepted component and exits with an
+//! error if any component fails to compile under the deterministic profile
+//! or imports interfaces that `runwasm` does not provide.
+
+use std::process::ExitCode;
+
+use tvm_types::Result;
+use tvm_types::sha256_digest;
+use tvm_vm::executor::Engine;
+use tvm_vm::executor::wasm::validate_wasm_component;
+
+fn main() -> ExitCode {
+    let paths: Vec<String> = std::env::args().skip(1).collect();
+    if paths.is_empty() {
+        eprintln!("usage: wasm-validate <component.wasm>...");
+        return ExitCode::from(2);
+    }
+    let wasm_engine = match Engine::extern_wasm_engine_init() {
+        Ok(wasm_engine) => wasm_engine,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let mut rejected = false;
+    for path in paths {
+        if let Err(e) = validate(&wasm_engine, &path) {
+            eprintln!("rejected {}: {}", path, e);
+            rejected = true;
+        }
+    }
+    if rejected { ExitCode::from(1) } else { ExitCode::from(0) }
+}
+
+fn validate(wasm_engine: &wasmtime::Engine, path: &str) -> Result<()> {
+    let binary = std::fs::read(path)?;
+    let imports = validate_wasm_component(wasm_engine, &binary)?;
+    println!("ok {} {}", hex::encode(sha256_digest(&binary)), path);
+    for import in imports {
+        println!("  import {}", import);
+    }
+    Ok(())
+}
@@ -0,0 +1,614 @@
+use blst::BLST_ERROR;
+use blst::blst_bendian_from_fp;
+use blst::blst_final_exp;
+use blst::blst_fp;
+use blst::blst_fp_from_bendian;
+use blst::blst_fp2;
+use blst::blst_fp12;
+use blst::blst_fp12_is_one;
+use blst::blst_fp12_mul;
+use blst::blst_fp12_one;
+use blst::blst_map_to_g1;
+use blst::blst_map_to_g2;
+use blst::blst_miller_loop;
+use blst::blst_p1;
+use blst::blst_p1_add_or_double;
+use blst::blst_p1_affine;
+use blst::blst_p1_affine_in_g1;
+use blst::blst_p1_cneg;
+use blst::blst_p1_compress;
+use blst::blst_p1_from_affine;
+use blst::blst_p1_is_inf;
+use blst::blst_p1_mult;
+use blst::blst_p1_to_affine;
+use blst::blst_p1_uncompress;
+use blst::blst_p2;
+use blst::blst_p2_add_or_double;
+use blst::blst_p2_affine;
+use blst::blst_p2_affine_in_g2;
+use blst::blst_p2_cneg;
+use blst::blst_p2_compress;
+use blst::blst_p2_from_affine;
+use blst::blst_p2_is_inf;
+use blst::blst_p2_mult;
+use blst::blst_p2_to_affine;
+use blst::blst_p2_uncompress;
+use blst::min_pk::AggregateSignature;
+use blst::min_pk::PublicKey;
+use blst::min_pk::Signature;
+use num::BigInt;
+use num::Integer;
+use num::bigint::Sign;
+use tvm_block::GlobalCapabilities;
+use tvm_types::ExceptionCode;
+use tvm_types::Result;
+use tvm_types::SliceData;
+use tvm_types::error;
+
+use crate::error::TvmError;
+use crate::executor::engine::Engine;
+use crate::executor::engine::storage::fetch_stack;
+use crate::executor::gas::gas_state::Gas;
+use crate::executor::types::Instruction;
+use crate::stack::StackItem;
+use crate::stack::integer::IntegerData;
+use crate::stack::integer::utils::process_value;
+use crate::types::Exception;
+use crate::types::Status;
+
+/// Order r of the BLS12-381 G1 and G2 groups, big-endian.
+const BLS_ORDER: [u8; 32] = [
+    0x73, 0xED, 0xA7, 0x53, 0x29, 0x9D, 0x7D, 0x48, 0x33, 0x39, 0xD8, 0x08, 0x09, 0xA1, 0xD8, 0x05,
+    0x53, 0xBD, 0xA4, 0x02, 0xFF, 0xFE, 0x5B, 0xFE, 0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x01,
+];
+const BLS_FP_BYTES: usize = 48;
+
+/// Point of the G1 or G2 group of BLS12-381 in projective coordinates.
+/// Points are passed through the stack as Slices with the compressed
+/// encoding: 48 bytes for G1 and 96 bytes for G2.
+trait BlsPoint: Copy + Default {
+    const BYTES: usize;
+    const ADD_GAS_PRICE: i64;
+    const NEG_GAS_PRICE: i64;
+    const MUL_GAS_PRICE: i64;
+    const MAP_GAS_PRICE: i64;
+    const IN_GROUP_GAS_PRICE: i64;
+
+    fn uncompress(bytes: &[u8]) -> Option<Self>;
+    fn compress(&self) -> Vec<u8>;
+    fn add(&self, other: &Self) -> Self;
+    fn neg(&self) -> Self;
+    /// Multiplies by a 255-bit little-endian scalar
+    fn mul(&self, scalar: &[u8]) -> Self;
+    fn in_group(&self) -> bool;
+    fn is_zero(&self) -> bool;
+    /// Maps a field element to the group without hashing
+    fn map_to(bytes: &[u8]) -> Option<Self>;
+}
+
+impl BlsPoint for blst_p1 {
+    const ADD_GAS_PRICE: i64 = 3900;
+    const BYTES: usize = 48;
+    const IN_GROUP_GAS_PRICE: i64 = 2950;
+    const MAP_GAS_PRICE: i64 = 2350;
+    const MUL_GAS_PRICE: i64 = 5200;
+    const NEG_GAS_PRICE: i64 = 750;
+
+    fn uncompress(bytes: &[u8]) -> Option<Self> {
+        let mut affine = blst_p1_affine::default();
+        if unsafe { blst_p1_uncompress(&mut affine, bytes.as_ptr()) } != BLST_ERROR::BLST_SUCCESS {
+            return None;
+        }
+        let mut point = blst_p1::default();
+        unsafe { blst_p1_from_affine(&mut point, &affine) };
+        Some(point)
+    }
+
+    fn compress(&self) -> Vec<u8> {
+        let mut bytes = vec![0; Self::BYTES];
+        unsafe { blst_p1_compress(bytes.as_mut_ptr(), self) };
+        bytes
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        let mut point = blst_p1::default();
+        unsafe { blst_p1_add_or_double(&mut point, self, other) };
+        point
+    }
+
+    fn neg(&self) -> Self {
+        let mut point = *self;
+        unsafe { blst_p1_cneg(&mut point, true) };
+        point
+    }
+
+    fn mul(&self, scalar: &[u8]) -> Self {
+        let mut point = blst_p1::default();
+        unsafe { blst_p1_mult(&mut point, self, scalar.as_ptr(), 255) };
+        point
+    }
+
+    fn in_group(&self) -> bool {
+        let mut affine = blst_p1_affine::default();
+        unsafe {
+            blst_p1_to_affine(&mut affine, self);
+            blst_p1_affine_in_g1(&affine)
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        unsafe { blst_p1_is_inf(self) }
+    }
+
+    fn map_to(bytes: &[u8]) -> Option<Self> {
+        let fp = fp_from_bytes(bytes)?;
+        let mut point = blst_p1::default();
+        unsafe { blst_map_to_g1(&mut point, &fp, std::ptr::null()) };
+        Some(point)
+    }
+}
+
+impl BlsPoint for blst_p2 {
+    const ADD_GAS_PRICE: i64 = 6100;
+    const BYTES: usize = 96;
+    const IN_GROUP_GAS_PRICE: i64 = 4250;
+    const MAP_GAS_PRICE: i64 = 7950;
+    const MUL_GAS_PRICE: i64 = 10550;
+    const NEG_GAS_PRICE: i64 = 1550;
+
+    fn uncompress(bytes: &[u8]) -> Option<Self> {
+        let mut affine = blst_p2_affine::default();
+        if unsafe { blst_p2_uncompress(&mut affine, bytes.as_ptr()) } != BLST_ERROR::BLST_SUCCESS {
+            return None;
+        }
+        let mut point = blst_p2::default();
+        unsafe { blst_p2_from_affine(&mut point, &affine) };
+        Some(point)
+    }
+
+    fn compress(&self) -> Vec<u8> {
+        let mut bytes = vec![0; Self::BYTES];
+        unsafe { blst_p2_compress(bytes.as_mut_ptr(), self) };
+        bytes
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        let mut point = blst_p2::default();
+        unsafe { blst_p2_add_or_double(&mut point, self, other) };
+        point
+    }
+
+    fn neg(&self) -> Self {
+        let mut point = *self;
+        unsafe { blst_p2_cneg(&mut point, true) };
+        point
+    }
+
+    fn mul(&self, scalar: &[u8]) -> Self {
+        let mut point = blst_p2::default();
+        unsafe { blst_p2_mult(&mut point, self, scalar.as_ptr(), 255) };
+        point
+    }
+
+    fn in_group(&self) -> bool {
+        let mut affine = blst_p2_affine::default();
+        unsafe {
+            blst_p2_to_affine(&mut affine, self);
+            blst_p2_affine_in_g2(&affine)
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        unsafe { blst_p2_is_inf(self) }
+    }
+
+    fn map_to(bytes: &[u8]) -> Option<Self> {
+        let fp2 = blst_fp2 {
+            fp: [fp_from_bytes(&bytes[..BLS_FP_BYTES])?, fp_from_bytes(&bytes[BLS_FP_BYTES..])?],
+        };
+        let mut point = blst_p2::default();
+        unsafe { blst_map_to_g2(&mut point, &fp2, std::ptr::null()) };
+        Some(point)
+    }
+}
+
+/// Converts a big-endian field element rejecting values not less than the
+/// field modulus.
+fn fp_from_bytes(bytes: &[u8]) -> Option<blst_fp> {
+    let mut fp = blst_fp::default();
+    let mut canonical = [0; BLS_FP_BYTES];
+    unsafe {
+        blst_fp_from_bendian(&mut fp, bytes.as_ptr());
+        blst_bendian_from_fp(canonical.as_mut_ptr(), &fp);
+    }
+    (canonical[..] == bytes[..BLS_FP_BYTES]).then_some(fp)
+}
+
+fn slice_bytes(item: &StackItem, bytes: usize) -> Result<Vec<u8>> {
+    let slice = item.as_slice()?;
+    if slice.remaining_bits() < bytes * 8 {
+        return err!(ExceptionCode::CellUnderflow);
+    }
+    let mut data = slice.get_bytestring(0);
+    data.truncate(bytes);
+    Ok(data)
+}
+
+fn message_bytes(item: &StackItem) -> Result<Vec<u8>> {
+    let slice = item.as_slice()?;
+    if slice.remaining_bits() % 8 != 0 {
+        return err!(ExceptionCode::CellUnderflow);
+    }
+    Ok(slice.get_bytestring(0))
+}
+
+fn point<G: BlsPoint>(item: &StackItem) -> Result<G> {
+    match G::uncompress(&slice_bytes(item, G::BYTES)?) {
+        Some(point) => Ok(point),
+        None => err!(ExceptionCode::RangeCheckError, "invalid BLS12-381 point"),
+    }
+}
+
+/// Reduces the integer modulo the group order and returns it little-endian
+fn scalar(item: &StackItem) -> Result<Vec<u8>> {
+    process_value(item.as_integer()?, |value| {
+        let order = BigInt::from_bytes_be(Sign::Plus, &BLS_ORDER);
+        let (_, mut bytes) = value.mod_floor(&order).to_bytes_le();
+        bytes.resize(BLS_ORDER.len(), 0);
+        Ok(bytes)
+    })
+}
+
+fn push_point<G: BlsPoint>(engine: &mut Engine, point: &G) {
+    let bytes = point.compress();
+    let bits = bytes.len() * 8;
+    engine.cc.stack.push(StackItem::Slice(SliceData::from_raw(bytes, bits)));
+}
+
+fn public_key(item: &StackItem) -> Result<Option<PublicKey>> {
+    let bytes = slice_bytes(item, <blst_p1 as BlsPoint>::BYTES)?;
+    Ok(PublicKey::key_validate(&bytes).ok())
+}
+
+fn signature(item: &StackItem) -> Result<Option<Signature>> {
+    let bytes = slice_bytes(item, <blst_p2 as BlsPoint>::BYTES)?;
+    Ok(Signature::sig_validate(&bytes, true).ok())
+}
+
+/// Fetches the count n from the stack checking that there are at least
+/// `n * items` more entries.
+fn fetch_count(engine: &mut Engine, items: usize) -> Result<usize> {
+    fetch_stack(engine, 1)?;
+    let max_count = engine.cc.stack.depth() / items;
+    engine.cmd.last_var()?.as_integer()?.into(0..=max_count)
+}
+
+fn bls_instruction(engine: &mut Engine, name: &'static str) -> Status {
+    engine.check_capability(GlobalCapabilities::CapBls)?;
+    engine.load_instruction(Instruction::new(name))
+}
+
+/// BLS_VERIFY (pk msg sig - ?)
+/// checks the BLS signature sig (96-byte slice) of the data portion of Slice
+/// msg using public key pk (48-byte slice).
+pub(super) fn execute_bls_verify(engine: &mut Engine) -> Status {
+    bls_instruction(engine, "BLS_VERIFY")?;
+    fetch_stack(engine, 3)?;
+    engine.try_use_gas(Gas::bls_verify_price())?;
+    let signature = signature(engine.cmd.var(0))?;
+    let message = message_bytes(engine.cmd.var(1))?;
+    let public_key = public_key(engine.cmd.var(2))?;
+    let result = match (public_key, signature) {
+        (Some(public_key), Some(signature)) => {
+            signature.verify(false, &message, &tvm_types::bls::DST, &[], &public_key, false)
+                == BLST_ERROR::BLST_SUCCESS
+        }
+        _ => false,
+    };
+    engine.cc.stack.push(boolean!(result));
+    Ok(())
+}
+
+/// BLS_AGGREGATE (sig_1 ... sig_n n - sig)
+/// aggregates n > 0 signatures into one. Throws a range check exception if
+/// some signature is invalid.
+pub(super) fn execute_bls_aggregate(engine: &mut Engine) -> Status {
+    bls_instruction(engine, "BLS_AGGREGATE")?;
+    let count = fetch_count(engine, 1)?;
+    if count == 0 {
+        return err!(ExceptionCode::RangeCheckError, "no signatures to aggregate");
+    }
+    engine.try_use_gas(Gas::bls_aggregate_price(count))?;
+    fetch_stack(engine, count)?;
+    let mut signatures = Vec::with_capacity(count);
+    for i in (1..=count).rev() {
+        match signature(engine.cmd.var(i))? {
+            Some(signature) => signatures.push(signature),
+            None => return err!(ExceptionCode::RangeCheckError, "invalid BLS signature"),
+        }
+    }
+    let signatures = signatures.iter().collect::<Vec<_>>();
+    let signature = match AggregateSignature::aggregate(&signatures, false) {
+        Ok(signature) => signature.to_signature().compress(),
+        Err(err) => return err!(ExceptionCode::RangeCheckError, "{:?}", err),
+    };
+    let bits = signature.len() * 8;
+    engine.cc.stack.push(StackItem::Slice(SliceData::from_raw(signature.to_vec(), bits)));
+    Ok(())
+}
+
+/// BLS_FASTAGGREGATEVERIFY (pk_1 ... pk_n n msg sig - ?)
+/// checks the aggregated signature sig of the same message msg signed by n
+/// public keys. Returns 0 if n is 0.
+pub(super) fn execute_bls_fast_aggregate_verify(engine: &mut Engine) -> Status {
+    bls_instruction(engine, "BLS_FASTAGGREGATEVERIFY")?;
+    fetch_stack(engine, 2)?;
+    let count = fetch_count(engine, 1)?;
+    engine.try_use_gas(Gas::bls_fast_aggregate_verify_price(count))?;
+    fetch_stack(engine, count)?;
+    let signature = signature(engine.cmd.var(0))?;
+    let message = message_bytes(engine.cmd.var(1))?;
+    let mut public_keys = Vec::with_capacity(count);
+    for i in (3..3 + count).rev() {
+        public_keys.push(public_key(engine.cmd.var(i))?);
+    }
+    let public_keys = public_keys.into_iter().collect::<Option<Vec<_>>>();
+    let result = match (public_keys, signature) {
+        (Some(public_keys), Some(signature)) if count != 0 => {
+            let public_keys = public_keys.iter().collect::<Vec<_>>();
+            signature.fast_aggregate_verify(false, &message, &tvm_types::bls::DST, &public_keys)
+                == BLST_ERROR::BLST_SUCCESS
+        }
+        _ => false,
+    };
+    engine.cc.stack.push(boolean!(result));
+    Ok(())
+}
+
+/// BLS_AGGREGATEVERIFY (pk_1 msg_1 ... pk_n msg_n n sig - ?)
+/// checks the aggregated signature sig of n messages msg_i signed by the
+/// public keys pk_i. Returns 0 if n is 0.
+pub(super) fn execute_bls_aggregate_verify(engine: &mut Engine) -> Status {
+    bls_instruction(engine, "BLS_AGGREGATEVERIFY")?;
+    fetch_stack(engine, 1)?;
+    let count = fetch_count(engine, 2)?;
+    engine.try_use_gas(Gas::bls_aggregate_verify_price(count))?;
+    fetch_stack(engine, 2 * count)?;
+    let signature = signature(engine.cmd.var(0))?;
+    let mut public_keys = Vec::with_capacity(count);
+    let mut messages = Vec::with_capacity(count);
+    for i in (0..count).rev() {
+        messages.push(message_bytes(engine.cmd.var(2 + 2 * i))?);
+        public_keys.push(public_key(engine.cmd.var(3 + 2 * i))?);
+    }
+    let public_keys = public_keys.into_iter().collect::<Option<Vec<_>>>();
+    let result = match (public_keys, signature) {
+        (Some(public_keys), Some(signature)) if count != 0 => {
+            let public_keys = public_keys.iter().collect::<Vec<_>>();
+            let messages = messages.iter().map(Vec::as_slice).collect::<Vec<_>>();
+            signature.aggregate_verify(false, &messages, &tvm_types::bls::DST, &public_keys, false)
+                == BLST_ERROR::BLST_SUCCESS
+        }
+        _ => false,
+    };
+    engine.cc.stack.push(boolean!(result));
+    Ok(())
+}
+
+fn bls_add<G: BlsPoint>(engine: &mut Engine, name: &'static str, sub: bool) -> Status {
+    bls_instruction(engine, name)?;
+    fetch_stack(engine, 2)?;
+    engine.try_use_gas(G::ADD_GAS_PRICE)?;
+    let y = point::<G>(engine.cmd.var(0))?;
+    let x = point::<G>(engine.cmd.var(1))?;
+    let y = if sub { y.neg() } else { y };
+    push_point(engine, &x.add(&y));
+    Ok(())
+}
+
+fn bls_neg<G: BlsPoint>(engine: &mut Engine, name: &'static str) -> Status {
+    bls_instruction(engine, name)?;
+    fetch_stack(engine, 1)?;
+    engine.try_use_gas(G::NEG_GAS_PRICE)?;
+    let x = point::<G>(engine.cmd.var(0))?;
+    push_point(engine, &x.neg());
+    Ok(())
+}
+
+fn bls_mul<G: BlsPoint>(engine: &mut Engine, name: &'static str) -> Status {
+    bls_instruction(engine, name)?;
+    fetch_stack(engine, 2)?;
+    engine.try_use_gas(G::MUL_GAS_PRICE)?;
+    let s = scalar(engine.cmd.var(0))?;
+    let x = point::<G>(engine.cmd.var(1))?;
+    push_point(engine, &x.mul(&s));
+    Ok(())
+}
+
+fn bls_multiexp<G: BlsPoint>(engine: &mut Engine, name: &'static str) -> Status {
+    bls_instruction(engine, name)?;
+    let count = fetch_count(engine, 2)?;
+    engine.try_use_gas(G::MUL_GAS_PRICE * count as i64)?;
+    fetch_stack(engine, 2 * count)?;
+    let mut result = G::default();
+    for i in 0..count {
+        let s = scalar(engine.cmd.var(1 + 2 * i))?;
+        let x = point::<G>(engine.cmd.var(2 + 2 * i))?;
+        result = result.add(&x.mul(&s));
+    }
+    push_point(engine, &result);
+    Ok(())
+}
+
+fn bls_zero<G: BlsPoint>(engine: &mut Engine, name: &'static str) -> Status {
+    bls_instruction(engine, name)?;
+    push_point(engine, &G::default());
+    Ok(())
+}
+
+fn bls_map_to<G: BlsPoint>(engine: &mut Engine, name: &'static str) -> Status {
+    bls_instruction(engine, name)?;
+    fetch_stack(engine, 1)?;
+    engine.try_use_gas(G::MAP_GAS_PRICE)?;
+    let bytes = slice_bytes(engine.cmd.var(0), G::BYTES)?;
+    match G::map_to(&bytes) {
+        Some(point) => push_point(engine, &point),
+        None => return err!(ExceptionCode::RangeCheckError, "invalid BLS12-381 field element"),
+    }
+    Ok(())
+}
+
+fn bls_in_group<G: BlsPoint>(engine: &mut Engine, name: &'static str) -> Status {
+    bls_instruction(engine, name)?;
+    fetch_stack(engine, 1)?;
+    engine.try_use_gas(G::IN_GROUP_GAS_PRICE)?;
+    let bytes = slice_bytes(engine.cmd.var(0), G::BYTES)?;
+    let result = G::uncompress(&bytes).is_some_and(|point| point.in_group());
+    engine.cc.stack.push(boolean!(result));
+    Ok(())
+}
+
+fn bls_is_zero<G: BlsPoint>(engine: &mut Engine, name: &'static str) -> Status {
+    bls_instruction(engine, name)?;
+    fetch_stack(engine, 1)?;
+    let x = point::<G>(engine.cmd.var(0))?;
+    engine.cc.stack.push(boolean!(x.is_zero()));
+    Ok(())
+}
+
+// BLS_G1_ADD (x y - x+y)
+pub(super) fn execute_bls_g1_add(engine: &mut Engine) -> Status {
+    bls_add::<blst_p1>(engine, "BLS_G1_ADD", false)
+}
+
+// BLS_G1_SUB (x y - x-y)
+pub(super) fn execute_bls_g1_sub(engine: &mut Engine) -> Status {
+    bls_add::<blst_p1>(engine, "BLS_G1_SUB", true)
+}
+
+// BLS_G1_NEG (x - -x)
+pub(super) fn execute_bls_g1_neg(engine: &mut Engine) -> Status {
+    bls_neg::<blst_p1>(engine, "BLS_G1_NEG")
+}
+
+// BLS_G1_MUL (x s - x*s)
+pub(super) fn execute_bls_g1_mul(engine: &mut Engine) -> Status {
+    bls_mul::<blst_p1>(engine, "BLS_G1_MUL")
+}
+
+// BLS_G1_MULTIEXP (x_1 s_1 ... x_n s_n n - x_1*s_1+...+x_n*s_n)
+pub(super) fn execute_bls_g1_multiexp(engine: &mut Engine) -> Status {
+    bls_multiexp::<blst_p1>(engine, "BLS_G1_MULTIEXP")
+}
+
+// BLS_G1_ZERO ( - zero)
+pub(super) fn execute_bls_g1_zero(engine: &mut Engine) -> Status {
+    bls_zero::<blst_p1>(engine, "BLS_G1_ZERO")
+}
+
+// BLS_MAP_TO_G1 (f - x)
+pub(super) fn execute_bls_map_to_g1(engine: &mut Engine) -> Status {
+    bls_map_to::<blst_p1>(engine, "BLS_MAP_TO_G1")
+}
+
+// BLS_G1_INGROUP (x - ?)
+pub(super) fn execute_bls_g1_in_group(engine: &mut Engine) -> Status {
+    bls_in_group::<blst_p1>(engine, "BLS_G1_INGROUP")
+}
+
+// BLS_G1_ISZERO (x - ?)
+pub(super) fn execute_bls_g1_is_zero(engine: &mut Engine) -> Status {
+    bls_is_zero::<blst_p1>(engine, "BLS_G1_ISZERO")
+}
+
+// BLS_G2_ADD (x y - x+y)
+pub(super) fn execute_bls_g2_add(engine: &mut Engine) -> Status {
+    bls_add::<blst_p2>(engine, "BLS_G2_ADD", false)
+}
+
+// BLS_G2_SUB (x y - x-y)
+pub(super) fn execute_bls_g2_sub(engine: &mut Engine) -> Status {
+    bls_add::<blst_p2>(engine, "BLS_G2_SUB", true)
+}
+
+// BLS_G2_NEG (x - -x)
+pub(super) fn execute_bls_g2_neg(engine: &mut Engine) -> Status {
+    bls_neg::<blst_p2>(engine, "BLS_G2_NEG")
+}
+
+// BLS_G2_MUL (x s - x*s)
+pub(super) fn execute_bls_g2_mul(engine: &mut Engine) -> Status {
+    bls_mul::<blst_p2>(engine, "BLS_G2_MUL")
+}
+
+// BLS_G2_MULTIEXP (x_1 s_1 ... x_n s_n n - x_1*s_1+...+x_n*s_n)
+pub(super) fn execute_bls_g2_multiexp(engine: &mut Engine) -> Status {
+    bls_multiexp::<blst_p2>(engine, "BLS_G2_MULTIEXP")
+}
+
+// BLS_G2_ZERO ( - zero)
+pub(super) fn execute_bls_g2_zero(engine: &mut Engine) -> Status {
+    bls_zero::<blst_p2>(engine, "BLS_G2_ZERO")
+}
+
+// BLS_MAP_TO_G2 (f - x)
+pub(super) fn execute_bls_map_to_g2(engine: &mut Engine) -> Status {
+    bls_map_to::<blst_p2>(engine, "BLS_MAP_TO_G2")
+}
+
+// BLS_G2_INGROUP (x - ?)
+pub(super) fn execute_bls_g2_in_group(engine: &mut Engine) -> Status {
+    bls_in_group::<blst_p2>(engine, "BLS_G2_INGROUP")
+}
+
+// BLS_G2_ISZERO (x - ?)
+pub(super) fn execute_bls_g2_is_zero(engine: &mut Engine) -> Status {
+    bls_is_zero::<blst_p2>(engine, "BLS_G2_ISZERO")
+}
+
+/// BLS_PAIRING (x_1 y_1 ... x_n y_n n - ?)
+/// checks that the product of pairings e(x_i, y_i) of G1 points x_i and G2
+/// points y_i is one. Throws a range check exception if some point is not in
+/// its group.
+pub(super) fn execute_bls_pairing(engine: &mut Engine) -> Status {
+    bls_instruction(engine, "BLS_PAIRING")?;
+    let count = fetch_count(engine, 2)?;
+    engine.try_use_gas(Gas::bls_pairing_price(count))?;
+    fetch_stack(engine, 2 * count)?;
+    let mut product = unsafe { *blst_fp12_one() };
+    let product_ptr: *mut blst_fp12 = &mut product;
+    for i in 0..count {
+        let y = point::<blst_p2>(engine.cmd.var(1 + 2 * i))?;
+        let x = point::<blst_p1>(engine.cmd.var(2 + 2 * i))?;
+        if !x.in_group() || !y.in_group() {
+            return err!(ExceptionCode::RangeCheckError, "BLS12-381 point is not in group");
+        }
+        if x.is_zero() || y.is_zero() {
+            continue;
+        }
+        let mut x_affine = blst_p1_affine::default();
+        let mut y_affine = blst_p2_affine::default();
+        let mut pairing = blst_fp12::default();
+        unsafe {
+            blst_p1_to_affine(&mut x_affine, &x);
+            blst_p2_to_affine(&mut y_affine, &y);
+            blst_miller_loop(&mut pairing, &y_affine, &x_affine);
+            blst_fp12_mul(product_ptr, product_ptr, &pairing);
+        }
+    }
+    let mut result = blst_fp12::default();
+    let result = unsafe {
+        blst_final_exp(&mut result, product_ptr);
+        blst_fp12_is_one(&result)
+    };
+    engine.cc.stack.push(boolean!(result));
+    Ok(())
+}
+
+// BLS_PUSHR ( - r)
+// pushes the order of the G1 and G2 groups
+pub(super) fn execute_bls_push_r(engine: &mut Engine) -> Status {
+    bls_instruction(engine, "BLS_PUSHR")?;
+    engine.cc.stack.push(StackItem::integer(IntegerData::from_unsigned_bytes_be(BLS_ORDER)));
+    Ok(())
+}
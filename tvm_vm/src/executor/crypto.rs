@@ -14,6 +14,7 @@ use std::borrow::Cow;
 use ed25519::Signature;
 use ed25519_dalek::Verifier;
 use ed25519_dalek::VerifyingKey;
+use sha2::Digest;
 use tvm_block::GlobalCapabilities;
 use tvm_types::BuilderData;
 use tvm_types::ExceptionCode;
@@ -24,7 +25,9 @@ use tvm_types::error;
 use crate::error::TvmError;
 use crate::executor::engine::Engine;
 use crate::executor::engine::storage::fetch_stack;
+use crate::executor::gas::gas_state::Gas;
 use crate::executor::types::Instruction;
+use crate::executor::types::InstructionOptions;
 use crate::stack::StackItem;
 use crate::stack::integer::IntegerData;
 use crate::stack::integer::serialization::UnsignedIntegerBigEndianEncoding;
@@ -35,6 +38,15 @@ const PUBLIC_KEY_BITS: usize = PUBLIC_KEY_BYTES * 8;
 const SIGNATURE_BITS: usize = SIGNATURE_BYTES * 8;
 const PUBLIC_KEY_BYTES: usize = ed25519_dalek::PUBLIC_KEY_LENGTH;
 const SIGNATURE_BYTES: usize = ed25519_dalek::SIGNATURE_LENGTH;
+const ECDSA_PUBLIC_KEY_BITS: usize = 33 * 8;
+const ECDSA_SIGNATURE_BITS: usize = 64 * 8;
+
+const HASHEXT_SHA256: u8 = 0;
+const HASHEXT_SHA512: u8 = 1;
+const HASHEXT_BLAKE2B: u8 = 2;
+const HASHEXT_KECCAK256: u8 = 3;
+const HASHEXT_KECCAK512: u8 = 4;
+const HASHEXT_FROM_STACK: u8 = 255;
 
 fn hash_to_uint(bits: impl AsRef<[u8]>) -> IntegerData {
     IntegerData::from_unsigned_bytes_be(bits)
@@ -191,3 +203,296 @@ pub(super) fn execute_chksigns(engine: &mut Engine) -> Status {
 pub(super) fn execute_chksignu(engine: &mut Engine) -> Status {
     check_signature(engine, "CHKSIGNU", true)
 }
+
+#[derive(Clone, Copy)]
+enum EcdsaCurve {
+    Secp256k1,
+    P256,
+}
+
+/// Checks the ECDSA signature of a 256-bit hash or of the data portion of a
+/// Slice. The public key is a Slice with a 33-byte compressed SEC1 point and
+/// the signature is a Slice with 64 bytes of r and s. Data is hashed with
+/// sha256 before verification.
+fn check_ecdsa_signature(
+    engine: &mut Engine,
+    name: &'static str,
+    curve: EcdsaCurve,
+    hash: bool,
+) -> Status {
+    engine.check_capability(GlobalCapabilities::CapEcdsa)?;
+    engine.load_instruction(Instruction::new(name))?;
+    fetch_stack(engine, 3)?;
+    let pub_key = engine.cmd.var(0).as_slice()?;
+    if pub_key.remaining_bits() < ECDSA_PUBLIC_KEY_BITS {
+        return err!(ExceptionCode::CellUnderflow);
+    }
+    let pub_key = pub_key.get_bytestring(0);
+    let signature = engine.cmd.var(1).as_slice()?;
+    if signature.remaining_bits() < ECDSA_SIGNATURE_BITS {
+        return err!(ExceptionCode::CellUnderflow);
+    }
+    let signature = signature.get_bytestring(0);
+    let data = if hash {
+        engine
+            .cmd
+            .var(2)
+            .as_integer()?
+            .as_builder::<UnsignedIntegerBigEndianEncoding>(256)?
+            .data()
+            .to_vec()
+    } else {
+        let slice = engine.cmd.var(2).as_slice()?;
+        if slice.remaining_bits() % 8 != 0 {
+            return err!(ExceptionCode::CellUnderflow);
+        }
+        slice.get_bytestring(0)
+    };
+    let pub_key = &pub_key[..ECDSA_PUBLIC_KEY_BITS / 8];
+    let signature = &signature[..ECDSA_SIGNATURE_BITS / 8];
+    let result = match curve {
+        EcdsaCurve::Secp256k1 => {
+            engine.try_use_gas(Gas::secp256k1_chksign_price())?;
+            verify_secp256k1(pub_key, signature, &data, hash)
+        }
+        EcdsaCurve::P256 => {
+            engine.try_use_gas(Gas::p256_chksign_price())?;
+            verify_p256(pub_key, signature, &data, hash)
+        }
+    };
+    #[cfg(feature = "signature_no_check")]
+    let result = engine.modifiers.chksig_always_succeed || result;
+
+    engine.cc.stack.push(boolean!(result));
+    Ok(())
+}
+
+fn verify_secp256k1(pub_key: &[u8], signature: &[u8], data: &[u8], prehashed: bool) -> bool {
+    use k256::ecdsa::signature::Verifier;
+    use k256::ecdsa::signature::hazmat::PrehashVerifier;
+    let Ok(pub_key) = k256::ecdsa::VerifyingKey::from_sec1_bytes(pub_key) else {
+        return false;
+    };
+    let Ok(signature) = k256::ecdsa::Signature::from_slice(signature) else {
+        return false;
+    };
+    if prehashed {
+        pub_key.verify_prehash(data, &signature).is_ok()
+    } else {
+        pub_key.verify(data, &signature).is_ok()
+    }
+}
+
+fn verify_p256(pub_key: &[u8], signature: &[u8], data: &[u8], prehashed: bool) -> bool {
+    use p256::ecdsa::signature::Verifier;
+    use p256::ecdsa::signature::hazmat::PrehashVerifier;
+    let Ok(pub_key) = p256::ecdsa::VerifyingKey::from_sec1_bytes(pub_key) else {
+        return false;
+    };
+    let Ok(signature) = p256::ecdsa::Signature::from_slice(signature) else {
+        return false;
+    };
+    if prehashed {
+        pub_key.verify_prehash(data, &signature).is_ok()
+    } else {
+        pub_key.verify(data, &signature).is_ok()
+    }
+}
+
+/// ECRECOVER (h v r s - 0 or h x1 x2 -1)
+/// recovers the secp256k1 public key from the signature (v, r, s) of a hash h,
+/// where v is the 8-bit recovery id and r, s are 256-bit unsigned integers.
+/// On success returns the uncompressed 65-byte key split into the 8-bit prefix
+/// h and two 256-bit coordinates x1 and x2 followed by -1, otherwise returns 0.
+pub(super) fn execute_ecrecover(engine: &mut Engine) -> Status {
+    engine.check_capability(GlobalCapabilities::CapEcdsa)?;
+    engine.load_instruction(Instruction::new("ECRECOVER"))?;
+    fetch_stack(engine, 4)?;
+    let s = engine.cmd.var(0).as_integer()?.as_builder::<UnsignedIntegerBigEndianEncoding>(256)?;
+    let r = engine.cmd.var(1).as_integer()?.as_builder::<UnsignedIntegerBigEndianEncoding>(256)?;
+    let v = engine.cmd.var(2).as_integer()?.into(0..=255u8)?;
+    let hash =
+        engine.cmd.var(3).as_integer()?.as_builder::<UnsignedIntegerBigEndianEncoding>(256)?;
+    engine.try_use_gas(Gas::ecrecover_price())?;
+
+    let pub_key = k256::ecdsa::RecoveryId::from_byte(v).and_then(|recovery_id| {
+        let signature = k256::ecdsa::Signature::from_scalars(
+            *k256::FieldBytes::from_slice(r.data()),
+            *k256::FieldBytes::from_slice(s.data()),
+        )
+        .ok()?;
+        k256::ecdsa::VerifyingKey::recover_from_prehash(hash.data(), &signature, recovery_id).ok()
+    });
+    match pub_key {
+        Some(pub_key) => {
+            let point = pub_key.to_encoded_point(false);
+            let bytes = point.as_bytes();
+            engine.cc.stack.push(int!(bytes[0]));
+            engine.cc.stack.push(StackItem::integer(hash_to_uint(&bytes[1..33])));
+            engine.cc.stack.push(StackItem::integer(hash_to_uint(&bytes[33..65])));
+            engine.cc.stack.push(boolean!(true));
+        }
+        None => {
+            engine.cc.stack.push(boolean!(false));
+        }
+    }
+    Ok(())
+}
+
+/// P256_CHKSIGNU (h sig k - ?)
+/// checks the secp256r1 signature sig (64-byte slice) of a hash h (256-bit
+/// unsigned integer) using public key k (33-byte compressed slice).
+pub(super) fn execute_p256_chksignu(engine: &mut Engine) -> Status {
+    check_ecdsa_signature(engine, "P256_CHKSIGNU", EcdsaCurve::P256, true)
+}
+
+/// P256_CHKSIGNS (d sig k - ?)
+/// checks the secp256r1 signature sig of the data portion of Slice d hashed
+/// with sha256. If the bit length of d is not divisible by eight, throws a
+/// cell underflow exception.
+pub(super) fn execute_p256_chksigns(engine: &mut Engine) -> Status {
+    check_ecdsa_signature(engine, "P256_CHKSIGNS", EcdsaCurve::P256, false)
+}
+
+/// SECP256K1_CHKSIGNU (h sig k - ?)
+/// checks the secp256k1 signature sig (64-byte slice) of a hash h (256-bit
+/// unsigned integer) using public key k (33-byte compressed slice).
+pub(super) fn execute_secp256k1_chksignu(engine: &mut Engine) -> Status {
+    check_ecdsa_signature(engine, "SECP256K1_CHKSIGNU", EcdsaCurve::Secp256k1, true)
+}
+
+/// SECP256K1_CHKSIGNS (d sig k - ?)
+/// checks the secp256k1 signature sig of the data portion of Slice d hashed
+/// with sha256. If the bit length of d is not divisible by eight, throws a
+/// cell underflow exception.
+pub(super) fn execute_secp256k1_chksigns(engine: &mut Engine) -> Status {
+    check_ecdsa_signature(engine, "SECP256K1_CHKSIGNS", EcdsaCurve::Secp256k1, false)
+}
+
+/// Hashes the data bits of Slices and Builders s_1 ... s_n concatenated.
+/// `hash_id` is taken from the instruction or from the stack if it is 255.
+/// In reverse mode the arguments are concatenated starting from s_n. In
+/// append mode the hash is stored into Builder b instead of being returned
+/// as a 256-bit unsigned integer or as a tuple of them for longer hashes.
+/// If the total bit length is not divisible by eight, throws a cell underflow
+/// exception.
+fn hash_ext(engine: &mut Engine, name: &'static str, append: bool, reverse: bool) -> Status {
+    engine.check_capability(GlobalCapabilities::CapHashExt)?;
+    engine
+        .load_instruction(Instruction::new(name).set_opts(InstructionOptions::Integer(0..256)))?;
+    let mut hash_id = engine.cmd.integer() as u8;
+    if hash_id == HASHEXT_FROM_STACK {
+        fetch_stack(engine, 1)?;
+        hash_id = engine.cmd.last_var()?.as_integer()?.into(0..=254u8)?;
+    }
+    let bytes_per_gas = match hash_id {
+        HASHEXT_SHA256 => 33,
+        HASHEXT_SHA512 => 16,
+        HASHEXT_BLAKE2B => 19,
+        HASHEXT_KECCAK256 => 11,
+        HASHEXT_KECCAK512 => 6,
+        _ => return err!(ExceptionCode::RangeCheckError, "unknown hash id {}", hash_id),
+    };
+    fetch_stack(engine, 1)?;
+    let max_count = engine.cc.stack.depth().saturating_sub(append as usize);
+    let count = engine.cmd.last_var()?.as_integer()?.into(0..=max_count)?;
+    let first = engine.cmd.var_count();
+    fetch_stack(engine, count)?;
+    if append {
+        fetch_stack(engine, 1)?;
+        engine.cmd.last_var()?.as_builder()?;
+    }
+
+    // arguments are fetched starting from the top of the stack, that is from s_n
+    let mut data = HashData::default();
+    for i in 0..count {
+        let index = if reverse { first + i } else { first + count - 1 - i };
+        match engine.cmd.var(index) {
+            StackItem::Slice(slice) => {
+                data.append(&slice.get_bytestring(0), slice.remaining_bits())
+            }
+            StackItem::Builder(builder) => data.append(builder.data(), builder.length_in_bits()),
+            _ => return err!(ExceptionCode::TypeCheckError, "item is not a slice or builder"),
+        }
+    }
+    engine.try_use_gas(Gas::hashext_price(count, data.bits / 8, bytes_per_gas))?;
+    if data.bits % 8 != 0 {
+        return err!(ExceptionCode::CellUnderflow);
+    }
+
+    let hash = match hash_id {
+        HASHEXT_SHA256 => sha2::Sha256::digest(&data.bytes).to_vec(),
+        HASHEXT_SHA512 => sha2::Sha512::digest(&data.bytes).to_vec(),
+        HASHEXT_BLAKE2B => blake2::Blake2b512::digest(&data.bytes).to_vec(),
+        HASHEXT_KECCAK256 => sha3::Keccak256::digest(&data.bytes).to_vec(),
+        _ => sha3::Keccak512::digest(&data.bytes).to_vec(),
+    };
+
+    if append {
+        let last = engine.cmd.var_count() - 1;
+        let mut builder = engine.cmd.var_mut(last).as_builder_mut()?;
+        if builder.bits_free() < hash.len() * 8 {
+            return err!(ExceptionCode::CellOverflow);
+        }
+        builder.append_raw(&hash, hash.len() * 8)?;
+        engine.cc.stack.push_builder(builder);
+    } else if hash.len() == 32 {
+        engine.cc.stack.push(StackItem::integer(hash_to_uint(hash)));
+    } else {
+        let items = hash
+            .chunks(32)
+            .map(|chunk| StackItem::integer(hash_to_uint(chunk)))
+            .collect::<Vec<_>>();
+        engine.use_gas(Gas::tuple_gas_price(items.len()));
+        engine.cc.stack.push(StackItem::tuple(items));
+    }
+    Ok(())
+}
+
+/// Bit string accumulated from the arguments of HASHEXT instructions.
+#[derive(Default)]
+struct HashData {
+    bytes: Vec<u8>,
+    bits: usize,
+}
+
+impl HashData {
+    fn append(&mut self, data: &[u8], bits: usize) {
+        let shift = self.bits % 8;
+        if shift == 0 {
+            self.bytes.extend_from_slice(&data[..bits.div_ceil(8)]);
+        } else {
+            for byte in &data[..bits.div_ceil(8)] {
+                let last = self.bytes.len() - 1;
+                self.bytes[last] |= byte >> shift;
+                self.bytes.push(byte << (8 - shift));
+            }
+        }
+        self.bits += bits;
+        self.bytes.truncate(self.bits.div_ceil(8));
+        if self.bits % 8 != 0 {
+            let last = self.bytes.len() - 1;
+            self.bytes[last] &= 0xFF << (8 - self.bits % 8);
+        }
+    }
+}
+
+// HASHEXT hash_id (s_1 ... s_n n - h)
+pub(super) fn execute_hashext(engine: &mut Engine) -> Status {
+    hash_ext(engine, "HASHEXT", false, false)
+}
+
+// HASHEXTR hash_id (s_1 ... s_n n - h)
+pub(super) fn execute_hashextr(engine: &mut Engine) -> Status {
+    hash_ext(engine, "HASHEXTR", false, true)
+}
+
+// HASHEXTA hash_id (b s_1 ... s_n n - b')
+pub(super) fn execute_hashexta(engine: &mut Engine) -> Status {
+    hash_ext(engine, "HASHEXTA", true, false)
+}
+
+// HASHEXTAR hash_id (b s_1 ... s_n n - b')
+pub(super) fn execute_hashextar(engine: &mut Engine) -> Status {
+    hash_ext(engine, "HASHEXTAR", true, true)
+}
@@ -41,7 +41,10 @@ use crate::error::tvm_exception_full;
 use crate::error::update_error_description;
 use crate::executor::continuation::switch;
 use crate::executor::continuation::switch_to_c0;
+use crate::executor::engine::decoded::DecodedCell;
+use crate::executor::engine::decoded::DecodedCodeCache;
 use crate::executor::engine::handlers::Handlers;
+use crate::executor::extension::CodePageExtension;
 use crate::executor::gas::gas_state::Gas;
 use crate::executor::math::DivMode;
 use crate::executor::microcode::CTRL;
@@ -116,22 +119,27 @@ pub struct Engine {
     pub(in crate::executor) modifiers: BehaviorModifiers,
     // SliceData::load_cell() is faster than trying to cache SliceData for each
     // visited cell with HashMap<UInt256, SliceData>
-    visited_cells: HashSet<UInt256>,
-    visited_exotic_cells: HashMap<UInt256, SliceData>,
-    cstate: CommittedState,
-    time: u64,
-    gas: Gas,
-    code_page: isize,
-    debug_on: isize, // status of debug can be recursively incremented
-    step: u32,       // number of executable command
+    pub(in crate::executor) visited_cells: HashSet<UInt256>,
+    pub(super) visited_exotic_cells: HashMap<UInt256, SliceData>,
+    pub(super) cstate: CommittedState,
+    pub(super) time: u64,
+    pub(super) gas: Gas,
+    pub(super) code_page: isize,
+    pub(in crate::executor) extensions: HashMap<isize, Arc<CodePageExtension>>,
+    pub(super) decoded_code_cache: Option<Arc<DecodedCodeCache>>,
+    pub(super) decoded_cells: Vec<(Cell, Arc<DecodedCell>)>, // recently executed, latest first
+    pub(super) decoded_cmd: Option<usize>,                   /* offset of cmd dispatched via
+                                                              * decoded cache */
+    debug_on: isize,      // status of debug can be recursively incremented
+    pub(super) step: u32, // number of executable command
     debug_buffer: String,
     cmd_code: SliceProto, // start of current cmd
-    last_cmd: u8,
+    pub(super) last_cmd: u8,
     trace: u8,
     trace_callback: Option<Arc<TraceCallback>>,
     log_string: Option<&'static str>,
     flags: u64,
-    capabilities: u64,
+    pub(super) capabilities: u64,
     block_version: u32,
     #[cfg(feature = "signature_with_id")]
     signature_id: i32,
@@ -199,9 +207,9 @@ impl EngineTraceInfo<'_> {
 
 #[derive(Debug)]
 pub struct CommittedState {
-    c4: StackItem,
-    c5: StackItem,
-    committed: bool,
+    pub(super) c4: StackItem,
+    pub(super) c5: StackItem,
+    pub(super) committed: bool,
 }
 
 impl CommittedState {
@@ -250,7 +258,7 @@ impl GasConsumer for Engine {
 }
 
 lazy_static::lazy_static! {
-    static ref HANDLERS_CP0: Handlers = Handlers::new_code_page_0();
+    pub(super) static ref HANDLERS_CP0: Handlers = Handlers::new_code_page_0();
 }
 
 impl Engine {
@@ -302,6 +310,10 @@ impl Engine {
             time: 0,
             gas: Gas::empty(),
             code_page: 0,
+            extensions: HashMap::new(),
+            decoded_code_cache: None,
+            decoded_cells: Vec::new(),
+            decoded_cmd: None,
             debug_on: 1,
             step: 0,
             debug_buffer: String::new(),
@@ -455,6 +467,10 @@ impl Engine {
         &self.cc.stack
     }
 
+    pub fn stack_mut(&mut self) -> &mut Stack {
+        &mut self.cc.stack
+    }
+
     pub fn try_use_gas(&mut self, gas: i64) -> Result<()> {
         self.gas.try_use_gas(gas)?;
         Ok(())
@@ -522,14 +538,7 @@ impl Engine {
     pub fn extern_wasm_engine_init() -> Result<wasmtime::Engine> {
         log::debug!("Extern Initialising Wasm Engine");
         // load or access WASM engine
-        let mut wasm_config = wasmtime::Config::new();
-        wasm_config.wasm_component_model(true);
-        wasm_config.consume_fuel(true);
-        // configs to assure determinism
-        wasm_config.cranelift_nan_canonicalization(true);
-        wasm_config.cranelift_pcc(true);
-        wasm_config.wasm_relaxed_simd(true);
-        wasm_config.relaxed_simd_deterministic(true);
+        let wasm_config = crate::executor::wasm::deterministic_wasm_config();
         let wasm_engine = match wasmtime::Engine::new(&wasm_config) {
             Ok(module) => module,
             Err(e) => {
@@ -543,14 +552,7 @@ impl Engine {
     pub fn wasm_engine_init_cached(&mut self) -> Result<()> {
         log::debug!("Internal Initialising Wasm Engine");
         // load or access WASM engine
-        let mut wasm_config = wasmtime::Config::new();
-        wasm_config.wasm_component_model(true);
-        wasm_config.consume_fuel(true);
-        // configs to assure determinism
-        wasm_config.cranelift_nan_canonicalization(true);
-        wasm_config.cranelift_pcc(true);
-        wasm_config.wasm_relaxed_simd(true);
-        wasm_config.relaxed_simd_deterministic(true);
+        let wasm_config = crate::executor::wasm::deterministic_wasm_config();
         let wasm_engine = match wasmtime::Engine::new(&wasm_config) {
             Ok(module) => module,
             Err(e) => {
@@ -740,30 +742,33 @@ impl Engine {
         &self,
         component: &wasmtime::component::Component,
     ) -> Result<()> {
-        let component_type = component.component_type();
-        let engine = self.get_wasm_engine()?;
-        let mut exports = component_type.exports(engine);
-        let arg = exports.next();
-        log::debug!("List of exports from WASM: {:?}", arg);
-        if let Some(arg) = arg {
-            log::debug!("{:?}", arg);
+        let (exports, imports) = self.wasm_component_exports_and_imports(component)?;
+        log::debug!("List of exports from WASM: {:?}", exports);
+        log::debug!("List of imports from WASM: {:?}", imports);
+        Ok(())
+    }
 
-            for arg in exports {
-                log::debug!(" {:?}", arg);
-            }
-        }
-        let binding = component.component_type();
-        let mut imports = binding.imports(engine);
-        let arg = imports.next();
-        log::debug!("List of imports from WASM: {:?}", arg);
-        if let Some(arg) = arg {
-            log::debug!("{:?}", arg);
+    /// Names of the exports and imports of a WASM component. Functions of
+    /// exported instances are listed as `instance#function`.
+    #[cfg(feature = "wasmtime")]
+    pub fn wasm_component_exports_and_imports(
+        &self,
+        component: &wasmtime::component::Component,
+    ) -> Result<(Vec<String>, Vec<String>)> {
+        use wasmtime::component::types::ComponentItem;
 
-            for arg in imports {
-                log::debug!(" {:?}", arg);
+        let component_type = component.component_type();
+        let engine = self.get_wasm_engine()?;
+        let mut exports = Vec::new();
+        for (name, item) in component_type.exports(engine) {
+            match item {
+                ComponentItem::ComponentInstance(instance) => exports
+                    .extend(instance.exports(engine).map(|(func, _)| format!("{}#{}", name, func))),
+                _ => exports.push(name.to_string()),
             }
         }
-        Ok(())
+        let imports = component_type.imports(engine).map(|(name, _)| name.to_string()).collect();
+        Ok((exports, imports))
     }
 
     #[cfg(feature = "wasmtime")]
@@ -1092,6 +1097,20 @@ impl Engine {
     }
 
     pub fn execute(&mut self) -> Result<i32> {
+        let result = self.run(None)?;
+        Ok(result.expect("execution without a step limit runs to completion"))
+    }
+
+    /// Executes until the program terminates or at least `steps` more steps
+    /// are made. Returns `None` if execution was suspended at the following
+    /// instruction boundary:
+    /// it can be continued by calling `execute` or `execute_steps` again, or
+    /// saved with [`Engine::save_state`] and resumed by another engine.
+    pub fn execute_steps(&mut self, steps: u32) -> Result<Option<i32>> {
+        self.run(Some(self.step.saturating_add(steps)))
+    }
+
+    fn run(&mut self, suspend_at: Option<u32>) -> Result<Option<i32>> {
         let deadline =
             match (self.termination_deadline, self.execution_timeout.map(|x| Instant::now() + x)) {
                 (Some(a), Some(b)) => Some(a.min(b)),
@@ -1099,6 +1118,9 @@ impl Engine {
             };
         self.trace_info(EngineTraceInfoType::Start, 0, None);
         let result = loop {
+            if suspend_at.is_some_and(|step| self.step >= step) {
+                return Ok(None);
+            }
             if let Some(result) = self.seek_next_cmd()? {
                 break result;
             }
@@ -1110,12 +1132,19 @@ impl Engine {
                 }
                 Some(exception!(ExceptionCode::ExecutionTimeout, "execution_timeout"))
             } else {
-                match HANDLERS_CP0.get_handler(self) {
+                let status = match self.execute_extension() {
+                    Some(status) => Ok(status),
+                    None => match self.execute_decoded() {
+                        Some(status) => status,
+                        None => HANDLERS_CP0.get_handler(self).map(|handler| handler(self)),
+                    },
+                };
+                match status {
                     Err(err) => {
                         self.basic_use_gas(8);
                         Some(err)
                     }
-                    Ok(handler) => match handler(self) {
+                    Ok(status) => match status {
                         Err(e) => Some(update_error_description(e, |e| {
                             format!(
                                 "CMD: {}{} err: {}",
@@ -1144,7 +1173,7 @@ impl Engine {
             Some("NORMAL TERMINATION".to_string()),
         );
         self.commit();
-        Ok(result)
+        Ok(Some(result))
     }
 
     fn step_next_ref(&mut self, reference: Cell) -> Result<Option<i32>> {
@@ -1358,6 +1387,11 @@ impl Engine {
         self.check_capability(GlobalCapabilities::CapSetLibCode)?;
         let mut hash = SliceData::load_cell(cell)?;
         hash.move_by(8)?;
+        self.find_library(hash)
+    }
+
+    /// Looks up a public library cell by its 256-bit representation hash
+    pub(in crate::executor) fn find_library(&mut self, hash: SliceData) -> Result<Cell> {
         for library in self.libraries.clone() {
             if let Some(lib_bucket) = library.get_with_gas(hash.clone(), self)? {
                 let lib = lib_bucket.reference(0)?;
@@ -1380,14 +1414,9 @@ impl Engine {
         let slice = loop {
             let hash = cell.repr_hash();
             if !resolve_special || cell.cell_type() == CellType::Ordinary {
-                if self.visited_cells.contains(&hash) {
-                    self.try_use_gas(Gas::load_cell_price(false))?;
-                    break SliceData::load_cell(cell)?;
-                } else {
-                    self.try_use_gas(Gas::load_cell_price(true))?;
-                    self.visited_cells.insert(hash);
-                    break SliceData::load_cell(cell)?;
-                }
+                let gas = ordinary_cell_load_price(&mut self.visited_cells, hash);
+                self.try_use_gas(gas)?;
+                break SliceData::load_cell(cell)?;
             }
             if let Some(slice) = self.visited_exotic_cells.get(&hash).cloned() {
                 self.try_use_gas(Gas::load_cell_price(false))?;
@@ -1633,7 +1662,10 @@ impl Engine {
         self.cmd.params.clear();
         self.cmd.vars.clear();
         self.step += 1;
-        self.extract_instruction()
+        match self.decoded_cmd.take() {
+            Some(offset) => self.extract_decoded(offset),
+            None => self.extract_instruction(),
+        }
     }
 
     pub(in crate::executor) fn switch_debug(&mut self, on_off: bool) {
@@ -1729,7 +1761,7 @@ impl Engine {
         self.use_gas(Gas::basic_gas_price(bits, 0))
     }
 
-    fn extract_instruction(&mut self) -> Status {
+    pub(super) fn extract_instruction(&mut self) -> Status {
         match self.cmd.proto.opts {
             Some(InstructionOptions::ArgumentConstraints) => {
                 let param = self.next_cmd()?;
@@ -2144,6 +2176,11 @@ impl Engine {
         Ok(code)
     }
 
+    /// Current code page used to interpret bytecode
+    pub(in crate::executor) fn code_page(&self) -> isize {
+        self.code_page
+    }
+
     /// Set code page for interpret bytecode. now only code page 0 is supported
     pub(in crate::executor) fn code_page_mut(&mut self) -> &mut isize {
         &mut self.code_page
@@ -2215,6 +2252,15 @@ impl Engine {
     }
 }
 
+/// Load price of an ordinary cell, which is cheaper if the cell has been
+/// visited already; marks the cell as visited
+pub(in crate::executor) fn ordinary_cell_load_price(
+    visited_cells: &mut HashSet<UInt256>,
+    hash: UInt256,
+) -> i64 {
+    Gas::load_cell_price(visited_cells.insert(hash))
+}
+
 #[inline(always)]
 fn is_deadline_reached(deadline: Option<Instant>) -> bool {
     deadline.map(|deadline| Instant::now() > deadline).unwrap_or(false)
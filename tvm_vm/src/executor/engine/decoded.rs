@@ -0,0 +1,363 @@
+//! Decoded-instruction cache shared between engines.
+//!
+//! Decoding an instruction depends only on the code bits, so the handler
+//! found in the code page table and the operands extracted by
+//! `load_instruction` can be remembered per position of a code cell and
+//! replayed the next time the same cell is executed, by this or any other
+//! [`Engine`] holding the same [`DecodedCodeCache`]. Cells are keyed by their
+//! representation hash. Replayed slices always refer to the cell the engine
+//! is running, and the recorded gas is charged as one sum, so results, gas
+//! and steps match a fresh decode.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+
+use tvm_types::Cell;
+use tvm_types::Result;
+use tvm_types::SliceData;
+use tvm_types::UInt256;
+
+use super::core::ExecuteHandler;
+use super::core::HANDLERS_CP0;
+use crate::executor::Engine;
+use crate::executor::types::InstructionParameter;
+use crate::types::Status;
+
+/// Number of code cells kept by [`DecodedCodeCache::new`]
+pub const DECODED_CODE_CACHE_CAPACITY: usize = 4096;
+
+/// Number of code cells an engine resolves without touching the shared cache
+const RECENT_CODE_CELLS: usize = 8;
+
+/// Bounded FIFO map from code cell hash to its decoded instructions
+pub struct DecodedCodeCache {
+    cells: Mutex<DecodedCells>,
+}
+
+struct DecodedCells {
+    entries: HashMap<UInt256, Arc<DecodedCell>>,
+    insertion_order: VecDeque<UInt256>,
+    capacity: usize,
+}
+
+impl DecodedCodeCache {
+    pub fn new() -> Self {
+        Self::with_capacity(DECODED_CODE_CACHE_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            cells: Mutex::new(DecodedCells {
+                entries: HashMap::with_capacity(capacity),
+                insertion_order: VecDeque::with_capacity(capacity),
+                capacity,
+            }),
+        }
+    }
+
+    /// Number of code cells with decoded instructions
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        let mut cells = self.lock();
+        cells.entries.clear();
+        cells.insertion_order.clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, DecodedCells> {
+        self.cells.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn cell(&self, hash: &UInt256, cell: &Cell) -> Arc<DecodedCell> {
+        let mut cells = self.lock();
+        if let Some(decoded) = cells.entries.get(hash) {
+            return decoded.clone();
+        }
+        let decoded = Arc::new(DecodedCell::new(cell));
+        if cells.capacity == 0 {
+            return decoded;
+        }
+        if cells.insertion_order.len() == cells.capacity {
+            if let Some(evicted) = cells.insertion_order.pop_front() {
+                cells.entries.remove(&evicted);
+            }
+        }
+        cells.insertion_order.push_back(hash.clone());
+        cells.entries.insert(hash.clone(), decoded.clone());
+        decoded
+    }
+}
+
+impl Default for DecodedCodeCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for DecodedCodeCache {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DecodedCodeCache").field("cells", &self.len()).finish()
+    }
+}
+
+/// Instructions of one code cell indexed by their byte offset
+pub(super) struct DecodedCell {
+    slots: Vec<OnceLock<Box<DecodedInstruction>>>,
+}
+
+impl DecodedCell {
+    fn new(cell: &Cell) -> Self {
+        let slots = (0..cell.bit_length() / 8).map(|_| OnceLock::new()).collect();
+        Self { slots }
+    }
+
+    fn instruction(&self, index: usize) -> Option<&DecodedInstruction> {
+        self.slots.get(index)?.get().map(Box::as_ref)
+    }
+}
+
+/// Handler lookup result for one position, valid for the code window it was
+/// recorded with
+struct DecodedInstruction {
+    data_end: usize,
+    references: Range<usize>,
+    handler: ExecuteHandler,
+    cmd_bits: usize,
+    last_cmd: u8,
+    operands: OnceLock<DecodedOperands>,
+}
+
+impl DecodedInstruction {
+    fn starts(&self, code: &SliceData) -> bool {
+        code.pos() + code.remaining_bits() == self.data_end
+            && code.get_references() == self.references
+    }
+
+    fn dispatched(&self, offset: usize, code: &SliceData, last_cmd: u8) -> bool {
+        code.pos() == offset + self.cmd_bits && last_cmd == self.last_cmd && self.starts(code)
+    }
+}
+
+/// Result of `extract_instruction` for the instruction `proto`
+struct DecodedOperands {
+    proto: &'static str,
+    name: &'static str,
+    params: Vec<DecodedParameter>,
+    data_start: usize,
+    references_start: usize,
+    last_cmd: u8,
+    gas: i64,
+}
+
+enum DecodedParameter {
+    Value(InstructionParameter),
+    /// Slice of the code cell with absolute windows
+    Slice {
+        data: Range<usize>,
+        references: Range<usize>,
+    },
+}
+
+impl DecodedParameter {
+    fn record(param: &InstructionParameter, code: &Cell) -> Option<Self> {
+        let InstructionParameter::Slice(slice) = param else {
+            return Some(Self::Value(param.clone()));
+        };
+        let cell = slice.cell_opt()?;
+        if same_cell(cell, code) {
+            Some(Self::Slice {
+                data: slice.pos()..slice.pos() + slice.remaining_bits(),
+                references: slice.get_references(),
+            })
+        } else if cell.bit_length() == 0 && cell.references_count() == 0 {
+            Some(Self::Value(InstructionParameter::Slice(SliceData::default())))
+        } else {
+            None
+        }
+    }
+
+    fn restore(&self, cell: &Cell) -> Result<InstructionParameter> {
+        match self {
+            Self::Value(param) => Ok(param.clone()),
+            Self::Slice { data, references } => {
+                let mut slice = SliceData::load_cell_ref(cell)?;
+                slice.shrink_data(data.clone());
+                slice.shrink_references(references.clone());
+                Ok(InstructionParameter::Slice(slice))
+            }
+        }
+    }
+}
+
+impl Engine {
+    /// Shares `cache` of decoded instructions with this engine. Engines
+    /// running the same code reuse each other's decoding.
+    pub fn set_decoded_code_cache(&mut self, cache: Arc<DecodedCodeCache>) {
+        self.decoded_cells.clear();
+        self.decoded_code_cache = Some(cache);
+    }
+
+    /// Dispatches the instruction at the current position through the
+    /// decoded cache, if one is set and the code is a byte-aligned cell slice
+    pub(super) fn execute_decoded(&mut self) -> Option<Result<Status>> {
+        let cache = self.decoded_code_cache.as_ref()?;
+        let code = self.cc.code();
+        let offset = code.pos();
+        if offset % 8 != 0 {
+            return None;
+        }
+        let cell = code.cell_opt()?;
+        if let Cell::Boc3(_) = cell {
+            return None;
+        }
+        let mut cells = std::mem::take(&mut self.decoded_cells);
+        match cells.iter().position(|(recent, _)| same_cell(recent, cell)) {
+            Some(index) => cells[..=index].rotate_right(1),
+            None => {
+                let decoded = cache.cell(&cell.repr_hash(), cell);
+                cells.truncate(RECENT_CODE_CELLS - 1);
+                cells.insert(0, (cell.clone(), decoded));
+            }
+        }
+        let decoded = &cells[0].1;
+        let index = offset / 8;
+        let found = decoded
+            .instruction(index)
+            .filter(|instruction| instruction.starts(code))
+            .map(|instruction| (instruction.handler, instruction.cmd_bits, instruction.last_cmd));
+        let handler = match found {
+            Some((handler, cmd_bits, last_cmd)) => {
+                if let Err(err) = self.cc.code_mut().move_by(cmd_bits) {
+                    self.decoded_cells = cells;
+                    return Some(Err(err));
+                }
+                self.last_cmd = last_cmd;
+                handler
+            }
+            None => {
+                let (data_end, references) =
+                    (code.pos() + code.remaining_bits(), code.get_references());
+                let handler = match HANDLERS_CP0.get_handler(self) {
+                    Ok(handler) => handler,
+                    Err(err) => {
+                        self.decoded_cells = cells;
+                        return Some(Err(err));
+                    }
+                };
+                if let Some(slot) = decoded.slots.get(index) {
+                    let _ = slot.set(Box::new(DecodedInstruction {
+                        data_end,
+                        references,
+                        handler,
+                        cmd_bits: self.cc.code().pos() - offset,
+                        last_cmd: self.last_cmd,
+                        operands: OnceLock::new(),
+                    }));
+                }
+                handler
+            }
+        };
+        self.decoded_cells = cells;
+        self.decoded_cmd = Some(offset);
+        let status = handler(self);
+        self.decoded_cmd = None;
+        Some(Ok(status))
+    }
+
+    /// Replays or records operands of the instruction dispatched from
+    /// `offset`
+    pub(super) fn extract_decoded(&mut self, offset: usize) -> Status {
+        let cells = std::mem::take(&mut self.decoded_cells);
+        let result = match cells.first() {
+            Some((cell, decoded)) => self.extract_decoded_in(cell, decoded, offset),
+            None => self.extract_instruction(),
+        };
+        self.decoded_cells = cells;
+        result
+    }
+
+    fn extract_decoded_in(&mut self, cell: &Cell, decoded: &DecodedCell, offset: usize) -> Status {
+        let code = self.cc.code();
+        let instruction = match (decoded.instruction(offset / 8), code.cell_opt()) {
+            (Some(instruction), Some(code_cell))
+                if instruction.dispatched(offset, code, self.last_cmd)
+                    && same_cell(cell, code_cell) =>
+            {
+                instruction
+            }
+            _ => return self.extract_instruction(),
+        };
+        let proto = self.cmd.proto.name;
+        if let Some(operands) = instruction.operands.get() {
+            if operands.proto == proto {
+                return self.restore_operands(cell, operands);
+            }
+            return self.extract_instruction();
+        }
+        let gas = self.get_gas().get_gas_remaining();
+        self.extract_instruction()?;
+        let code = self.cc.code();
+        if code.pos() + code.remaining_bits() != instruction.data_end
+            || code.get_references().end != instruction.references.end
+        {
+            return Ok(());
+        }
+        let params = self
+            .cmd
+            .params
+            .iter()
+            .map(|param| DecodedParameter::record(param, cell))
+            .collect::<Option<Vec<_>>>();
+        if let Some(params) = params {
+            let _ = instruction.operands.set(DecodedOperands {
+                proto,
+                name: self.cmd.proto.name,
+                params,
+                data_start: code.pos(),
+                references_start: code.get_references().start,
+                last_cmd: self.last_cmd,
+                gas: gas - self.get_gas().get_gas_remaining(),
+            });
+        }
+        Ok(())
+    }
+
+    fn restore_operands(&mut self, cell: &Cell, operands: &DecodedOperands) -> Status {
+        self.cmd.proto.name = operands.name;
+        for param in &operands.params {
+            let param = param.restore(cell)?;
+            self.cmd.params.push(param);
+        }
+        let code = self.cc.code_mut();
+        code.move_by(operands.data_start - code.pos())?;
+        let references = operands.references_start - code.get_references().start;
+        if references != 0 {
+            code.shrink_references(references..);
+        }
+        self.last_cmd = operands.last_cmd;
+        self.use_gas(operands.gas);
+        Ok(())
+    }
+}
+
+/// Identity check for cells an engine keeps alive in its recent list, much
+/// cheaper than comparing representation hashes on every step
+fn same_cell(a: &Cell, b: &Cell) -> bool {
+    match (a, b) {
+        (Cell::Data(a), Cell::Data(b)) => Arc::ptr_eq(a, b),
+        (Cell::Usage(a), Cell::Usage(b)) => Arc::ptr_eq(a, b),
+        (Cell::Virtual(a), Cell::Virtual(b)) => Arc::ptr_eq(a, b),
+        _ => false,
+    }
+}
@@ -18,6 +18,7 @@ use tvm_types::types::ExceptionCode;
 
 use crate::error::TvmError;
 use crate::executor::blockchain::*;
+use crate::executor::bls::*;
 #[cfg(feature = "gosh")]
 use crate::executor::chk_hist_proof::execute_chk_hist_proof;
 use crate::executor::config::*;
@@ -48,6 +49,8 @@ use crate::executor::types::InstructionOptions;
 #[cfg(feature = "gosh")]
 use crate::executor::zk::*;
 #[cfg(feature = "gosh")]
+use crate::executor::zk_groth16_with_vk::execute_vergrth16_with_vk;
+#[cfg(feature = "gosh")]
 use crate::executor::zk_halo2::*;
 #[cfg(feature = "gosh")]
 use crate::executor::zk_halo2_with_vk::execute_zkhalo2_verify_with_vk;
@@ -421,6 +424,7 @@ impl Handlers {
                 // tvm_vm/src/executor/zk_halo2_with_vk.rs and
                 // docs/zkhalo2verifywithvk_design.md.
                 .set(0x4A, execute_zkhalo2_verify_with_vk)
+                .set(0x4B, execute_vergrth16_with_vk)
                 .set(0x50, execute_chk_hist_proof);
             // Pre-build VK + KZG params in background so the first
             // ZKHALO2VERIFY call doesn't block for seconds.
@@ -975,8 +979,45 @@ impl Handlers {
                 .set(0x00, execute_hashcu)
                 .set(0x01, execute_hashsu)
                 .set(0x02, execute_sha256u)
+                .set(0x04, execute_hashext)
+                .set(0x05, execute_hashextr)
+                .set(0x06, execute_hashexta)
+                .set(0x07, execute_hashextar)
                 .set(0x10, execute_chksignu)
                 .set(0x11, execute_chksigns)
+                .set(0x12, execute_ecrecover)
+                .set(0x14, execute_p256_chksignu)
+                .set(0x15, execute_p256_chksigns)
+                .set(0x16, execute_secp256k1_chksignu)
+                .set(0x17, execute_secp256k1_chksigns)
+                .add_subset(
+                    0x30,
+                    Handlers::new()
+                        .set(0x00, execute_bls_verify)
+                        .set(0x01, execute_bls_aggregate)
+                        .set(0x02, execute_bls_fast_aggregate_verify)
+                        .set(0x03, execute_bls_aggregate_verify)
+                        .set(0x10, execute_bls_g1_add)
+                        .set(0x11, execute_bls_g1_sub)
+                        .set(0x12, execute_bls_g1_neg)
+                        .set(0x13, execute_bls_g1_mul)
+                        .set(0x14, execute_bls_g1_multiexp)
+                        .set(0x15, execute_bls_g1_zero)
+                        .set(0x16, execute_bls_map_to_g1)
+                        .set(0x17, execute_bls_g1_in_group)
+                        .set(0x18, execute_bls_g1_is_zero)
+                        .set(0x20, execute_bls_g2_add)
+                        .set(0x21, execute_bls_g2_sub)
+                        .set(0x22, execute_bls_g2_neg)
+                        .set(0x23, execute_bls_g2_mul)
+                        .set(0x24, execute_bls_g2_multiexp)
+                        .set(0x25, execute_bls_g2_zero)
+                        .set(0x26, execute_bls_map_to_g2)
+                        .set(0x27, execute_bls_g2_in_group)
+                        .set(0x28, execute_bls_g2_is_zero)
+                        .set(0x30, execute_bls_pairing)
+                        .set(0x31, execute_bls_push_r),
+                )
                 .set(0x40, execute_cdatasizeq)
                 .set(0x41, execute_cdatasize)
                 .set(0x42, execute_sdatasizeq)
@@ -11,11 +11,15 @@
 
 mod core;
 pub(in crate::executor) mod data;
+mod decoded;
 mod handlers;
+mod state;
 #[macro_use]
 pub(in crate::executor) mod storage;
 
 pub use self::core::*;
+pub use self::decoded::DECODED_CODE_CACHE_CAPACITY;
+pub use self::decoded::DecodedCodeCache;
 
 #[cfg(test)]
 #[path = "../../tests/test_microfunctions.rs"]
@@ -0,0 +1,178 @@
+//! Saved engine states.
+//!
+//! An execution suspended by [`Engine::execute_steps`] can be stored with
+//! [`Engine::save_state`] and continued later, possibly in another process,
+//! by an engine that [`Engine::restore_state`] loads it into. The state keeps
+//! the current continuation with its stack, control registers, gas counters,
+//! committed state, libraries and the sets of already loaded cells which
+//! decide cell load prices, so a resumed run ends with the same results, gas
+//! and steps as an uninterrupted one. It is a cell tree and can be written
+//! as a BOC. Tracing, deadlines, extensions and other host setup are not
+//! saved and must be configured on the restoring engine again.
+//!
+//! ```text
+//! vm_state#766d7374 version:uint8 capabilities:uint64 step:uint32
+//!   code_page:int16 time:uint64 committed:Bool
+//!   gas_limit_max:int64 gas_limit:int64 gas_credit:int64
+//!   gas_remaining:int64 gas_price:int64 gas_base:int64
+//!   cc:^Continuation ctrls:^Continuation committed_state:^StackItem
+//!   context:^[ libraries:^StackItem visited:(HashmapE 256 Unit)
+//!     visited_exotic:(HashmapE 256 Slice) ]
+//! ```
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use tvm_types::BuilderData;
+use tvm_types::Cell;
+use tvm_types::HashmapE;
+use tvm_types::HashmapType;
+use tvm_types::IBitstring;
+use tvm_types::Result;
+use tvm_types::SliceData;
+use tvm_types::UInt256;
+use tvm_types::fail;
+
+use super::core::CommittedState;
+use crate::executor::Engine;
+use crate::executor::gas::gas_state::Gas;
+use crate::stack::StackItem;
+use crate::stack::continuation::ContinuationData;
+use crate::stack::slice_deserialize;
+use crate::stack::slice_serialize;
+
+/// Tag of a saved engine state, "vmst"
+const STATE_TAG: u32 = 0x766d7374;
+const STATE_VERSION: u8 = 1;
+
+impl Engine {
+    /// Saves the state of a suspended execution. Saving does not consume
+    /// gas.
+    pub fn save_state(&self) -> Result<Cell> {
+        let mut builder = BuilderData::new();
+        builder
+            .append_u32(STATE_TAG)?
+            .append_u8(STATE_VERSION)?
+            .append_u64(self.capabilities)?
+            .append_u32(self.step)?
+            .append_i16(self.code_page as i16)?
+            .append_u64(self.time)?
+            .append_bit_bool(self.cstate.committed)?;
+        self.gas.serialize(&mut builder)?;
+        builder.checked_append_reference(self.cc.serialize(&mut 0u64)?.into_cell()?)?;
+        let mut ctrls = ContinuationData::new_empty();
+        ctrls.savelist = self.ctrls.clone();
+        builder.checked_append_reference(ctrls.serialize(&mut 0u64)?.into_cell()?)?;
+        let cstate = StackItem::tuple(vec![self.cstate.c4.clone(), self.cstate.c5.clone()]);
+        builder.checked_append_reference(cstate.serialize(&mut 0u64)?.into_cell()?)?;
+        builder.checked_append_reference(self.save_context()?)?;
+        builder.into_cell()
+    }
+
+    fn save_context(&self) -> Result<Cell> {
+        if self.libraries.len() > u8::MAX as usize {
+            fail!("cannot save {} library dictionaries", self.libraries.len())
+        }
+        let libraries = self
+            .libraries
+            .iter()
+            .map(|library| library.data().cloned().map_or(StackItem::None, StackItem::cell))
+            .collect();
+        let mut visited = HashmapE::with_bit_len(256);
+        for hash in &self.visited_cells {
+            visited.set_builder(hash_key(hash), &BuilderData::new())?;
+        }
+        let mut visited_exotic = HashmapE::with_bit_len(256);
+        for (hash, slice) in &self.visited_exotic_cells {
+            visited_exotic.set_builder(hash_key(hash), &slice_serialize(slice)?)?;
+        }
+        let mut builder = BuilderData::new();
+        builder.checked_append_reference(
+            StackItem::tuple(libraries).serialize(&mut 0u64)?.into_cell()?,
+        )?;
+        visited.write_hashmap_data(&mut builder)?;
+        visited_exotic.write_hashmap_data(&mut builder)?;
+        builder.into_cell()
+    }
+
+    /// Loads a state saved by [`Engine::save_state`]. The engine must have
+    /// the same capabilities as the one which saved it; execution continues
+    /// with `execute` or `execute_steps`. On error the engine is unchanged.
+    pub fn restore_state(&mut self, state: Cell) -> Result<()> {
+        let mut slice = SliceData::load_cell(state)?;
+        if slice.get_next_u32()? != STATE_TAG {
+            fail!("cell is not a saved engine state")
+        }
+        let version = slice.get_next_byte()?;
+        if version != STATE_VERSION {
+            fail!("unsupported engine state version {}", version)
+        }
+        let capabilities = slice.get_next_u64()?;
+        if capabilities != self.capabilities {
+            fail!(
+                "engine state was saved with capabilities {:#x}, engine has {:#x}",
+                capabilities,
+                self.capabilities
+            )
+        }
+        let step = slice.get_next_u32()?;
+        let code_page = slice.get_next_i16()? as isize;
+        let time = slice.get_next_u64()?;
+        let committed = slice.get_next_bit()?;
+        let gas = Gas::deserialize(&mut slice)?;
+        let cc = load_continuation(slice.checked_drain_reference()?)?;
+        let ctrls = load_continuation(slice.checked_drain_reference()?)?.savelist;
+        let cstate = load_item(slice.checked_drain_reference()?)?;
+        let (c4, c5) = match cstate.as_tuple()? {
+            [c4, c5] => (c4.clone(), c5.clone()),
+            _ => fail!("wrong committed state in saved engine state"),
+        };
+        let mut context = SliceData::load_cell(slice.checked_drain_reference()?)?;
+        let libraries = load_item(context.checked_drain_reference()?)?
+            .as_tuple()?
+            .iter()
+            .map(|library| match library {
+                StackItem::None => Ok(HashmapE::with_bit_len(256)),
+                library => Ok(HashmapE::with_hashmap(256, Some(library.as_cell()?.clone()))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut visited = HashmapE::with_bit_len(256);
+        visited.read_hashmap_data(&mut context)?;
+        let mut visited_cells = HashSet::new();
+        visited.iterate_slices(|key, _| {
+            visited_cells.insert(UInt256::try_from(key)?);
+            Ok(true)
+        })?;
+        let mut visited_exotic = HashmapE::with_bit_len(256);
+        visited_exotic.read_hashmap_data(&mut context)?;
+        let mut visited_exotic_cells = HashMap::new();
+        visited_exotic.iterate_slices(|key, mut value| {
+            visited_exotic_cells.insert(UInt256::try_from(key)?, slice_deserialize(&mut value)?);
+            Ok(true)
+        })?;
+
+        self.cc = cc;
+        self.ctrls = ctrls;
+        self.cstate = CommittedState { c4, c5, committed };
+        self.gas = gas;
+        self.step = step;
+        self.code_page = code_page;
+        self.time = time;
+        self.libraries = libraries;
+        self.visited_cells = visited_cells;
+        self.visited_exotic_cells = visited_exotic_cells;
+        Ok(())
+    }
+}
+
+fn hash_key(hash: &UInt256) -> SliceData {
+    SliceData::from_raw(hash.as_slice().to_vec(), 256)
+}
+
+fn load_continuation(cell: Cell) -> Result<ContinuationData> {
+    ContinuationData::deserialize(&mut SliceData::load_cell(cell)?, &mut 0u64)
+}
+
+fn load_item(cell: Cell) -> Result<StackItem> {
+    StackItem::deserialize(SliceData::load_cell(cell)?, &mut 0u64)
+}
@@ -0,0 +1,139 @@
+//! Host-defined instructions living in alternative code pages.
+//!
+//! An embedder builds a [`CodePageExtension`], registers its instructions and
+//! attaches it to an [`Engine`] under a code page number. Once a contract
+//! switches to that page with `SETCP`/`SETCPX`, every instruction is first
+//! matched against the extension prefixes; anything else is decoded by the
+//! standard code page 0 table, so `SETCP 0` and the rest of the instruction
+//! set keep working.
+
+use std::fmt;
+use std::sync::Arc;
+
+use tvm_types::Result;
+use tvm_types::SliceData;
+use tvm_types::fail;
+
+use crate::executor::Engine;
+use crate::executor::types::Instruction;
+use crate::types::Status;
+
+/// Gas charged for an extension instruction on top of the basic price.
+pub type ExtensionGas = Arc<dyn Send + Sync + Fn(&Engine) -> i64>;
+/// Body of an extension instruction.
+pub type ExtensionHandler = Arc<dyn Send + Sync + Fn(&mut Engine) -> Status>;
+
+#[derive(Clone)]
+struct ExtensionOpcode {
+    prefix: Vec<u8>,
+    name: &'static str,
+    gas: ExtensionGas,
+    handler: ExtensionHandler,
+}
+
+impl ExtensionOpcode {
+    fn matches(&self, code: &SliceData) -> bool {
+        code.remaining_bits() >= self.prefix.len() * 8
+            && code.get_bytestring(0).starts_with(&self.prefix)
+    }
+
+    fn execute(&self, engine: &mut Engine) -> Status {
+        engine.cc.code_mut().move_by(self.prefix.len() * 8)?;
+        engine.load_instruction(Instruction::new(self.name))?;
+        let gas = (self.gas)(engine);
+        engine.try_use_gas(gas)?;
+        (self.handler)(engine)
+    }
+}
+
+/// Set of instructions selectable through `SETCP`
+#[derive(Clone, Default)]
+pub struct CodePageExtension {
+    opcodes: Vec<ExtensionOpcode>,
+}
+
+impl CodePageExtension {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers instruction `name` encoded by the byte `prefix`. The basic
+    /// instruction price is charged for the prefix length, then `gas` is
+    /// evaluated and charged before `handler` runs. Prefixes must be
+    /// prefix-free within the page.
+    pub fn register(
+        &mut self,
+        prefix: &[u8],
+        name: &'static str,
+        gas: impl Send + Sync + Fn(&Engine) -> i64 + 'static,
+        handler: impl Send + Sync + Fn(&mut Engine) -> Status + 'static,
+    ) -> Result<&mut Self> {
+        if prefix.is_empty() {
+            fail!("extension instruction {} has an empty prefix", name)
+        }
+        if let Some(other) = self
+            .opcodes
+            .iter()
+            .find(|other| other.prefix.starts_with(prefix) || prefix.starts_with(&other.prefix))
+        {
+            fail!(
+                "prefix {} of extension instruction {} conflicts with {}",
+                hex::encode(prefix),
+                name,
+                other.name
+            )
+        }
+        self.opcodes.push(ExtensionOpcode {
+            prefix: prefix.to_vec(),
+            name,
+            gas: Arc::new(gas),
+            handler: Arc::new(handler),
+        });
+        Ok(self)
+    }
+
+    /// Iterates over registered `(prefix, name)` pairs
+    pub fn instructions(&self) -> impl Iterator<Item = (&[u8], &'static str)> {
+        self.opcodes.iter().map(|opcode| (opcode.prefix.as_slice(), opcode.name))
+    }
+
+    fn find(&self, code: &SliceData) -> Option<&ExtensionOpcode> {
+        self.opcodes.iter().find(|opcode| opcode.matches(code))
+    }
+}
+
+impl fmt::Debug for CodePageExtension {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map()
+            .entries(self.opcodes.iter().map(|opcode| (hex::encode(&opcode.prefix), opcode.name)))
+            .finish()
+    }
+}
+
+impl Engine {
+    /// Makes instructions of `extension` available while `code_page` is
+    /// selected. Code page 0 is the standard instruction set and cannot be
+    /// extended.
+    pub fn set_code_page_extension(
+        &mut self,
+        code_page: isize,
+        extension: Arc<CodePageExtension>,
+    ) -> Result<()> {
+        if code_page == 0 {
+            fail!("code page 0 cannot be extended")
+        }
+        self.extensions.insert(code_page, extension);
+        Ok(())
+    }
+
+    /// Runs the extension instruction at the current position, if the
+    /// selected code page has one
+    pub(in crate::executor) fn execute_extension(&mut self) -> Option<Status> {
+        if self.extensions.is_empty() {
+            return None;
+        }
+        let extension = self.extensions.get(&self.code_page())?.clone();
+        let opcode = extension.find(self.cc.code())?;
+        Some(opcode.execute(self))
+    }
+}
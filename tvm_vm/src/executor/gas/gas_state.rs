@@ -12,7 +12,10 @@
 use std::cmp::max;
 use std::cmp::min;
 
+use tvm_types::BuilderData;
+use tvm_types::IBitstring;
 use tvm_types::Result;
+use tvm_types::SliceData;
 use tvm_types::error;
 use tvm_types::types::ExceptionCode;
 
@@ -24,6 +27,10 @@ use crate::executor::zk::POSEIDON_ZK_LOGIN_GAS_PRICE;
 #[cfg(feature = "gosh")]
 use crate::executor::zk::VERGRTH16_GAS_PRICE;
 #[cfg(feature = "gosh")]
+use crate::executor::zk_groth16_with_vk::Groth16Curve;
+#[cfg(feature = "gosh")]
+use crate::executor::zk_groth16_with_vk::VERGRTH16VK_KEY_BYTE_GAS_PRICE;
+#[cfg(feature = "gosh")]
 use crate::executor::zk_halo2_with_vk::ZKHALO2_VERIFY_WITH_VK_GAS_PRICE;
 use crate::types::Exception;
 
@@ -47,6 +54,18 @@ const IMPLICIT_JMPREF_GAS_PRICE: i64 = 10;
 const IMPLICIT_RET_GAS_PRICE: i64 = 5;
 const FREE_STACK_DEPTH: usize = 32;
 const STACK_ENTRY_GAS_PRICE: i64 = 1;
+const HASHEXT_ENTRY_GAS_PRICE: usize = 1;
+const ECRECOVER_GAS_PRICE: i64 = 1500;
+const SECP256K1_CHKSIGN_GAS_PRICE: i64 = 1500;
+const P256_CHKSIGN_GAS_PRICE: i64 = 3500;
+const BLS_VERIFY_GAS_PRICE: i64 = 61000;
+const BLS_AGGREGATE_ENTRY_GAS_PRICE: i64 = 4350;
+const BLS_FAST_AGGREGATE_VERIFY_GAS_PRICE: i64 = 58000;
+const BLS_FAST_AGGREGATE_VERIFY_ENTRY_GAS_PRICE: i64 = 3000;
+const BLS_AGGREGATE_VERIFY_GAS_PRICE: i64 = 38500;
+const BLS_AGGREGATE_VERIFY_ENTRY_GAS_PRICE: i64 = 22500;
+const BLS_PAIRING_GAS_PRICE: i64 = 20000;
+const BLS_PAIRING_ENTRY_GAS_PRICE: i64 = 11800;
 #[cfg(feature = "gosh")]
 const DIFF_DURATION_FOR_LINE: i64 = 60;
 #[cfg(feature = "gosh")]
@@ -115,6 +134,32 @@ impl Gas {
         }
     }
 
+    /// Stores all counters, used by saved engine states
+    pub(crate) fn serialize(&self, builder: &mut BuilderData) -> Result<()> {
+        for value in [
+            self.gas_limit_max,
+            self.gas_limit,
+            self.gas_credit,
+            self.gas_remaining,
+            self.gas_price,
+            self.gas_base,
+        ] {
+            builder.append_i64(value)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn deserialize(slice: &mut SliceData) -> Result<Gas> {
+        Ok(Gas {
+            gas_limit_max: slice.get_next_i64()?,
+            gas_limit: slice.get_next_i64()?,
+            gas_credit: slice.get_next_i64()?,
+            gas_remaining: slice.get_next_i64()?,
+            gas_price: slice.get_next_i64()?,
+            gas_base: slice.get_next_i64()?,
+        })
+    }
+
     /// Compute instruction cost
     pub const fn basic_gas_price(
         instruction_length: usize,
@@ -201,6 +246,52 @@ impl Gas {
         self.use_gas(TUPLE_ENTRY_GAS_PRICE * tuple_length as i64)
     }
 
+    /// Compute HASHEXT usage cost for `count` arguments with `bytes` of data
+    pub const fn hashext_price(count: usize, bytes: usize, bytes_per_gas: usize) -> i64 {
+        (HASHEXT_ENTRY_GAS_PRICE * count + bytes / bytes_per_gas) as i64
+    }
+
+    /// Compute ECRECOVER usage cost
+    pub const fn ecrecover_price() -> i64 {
+        ECRECOVER_GAS_PRICE
+    }
+
+    /// Compute SECP256K1_CHKSIGNU and SECP256K1_CHKSIGNS usage cost
+    pub const fn secp256k1_chksign_price() -> i64 {
+        SECP256K1_CHKSIGN_GAS_PRICE
+    }
+
+    /// Compute P256_CHKSIGNU and P256_CHKSIGNS usage cost
+    pub const fn p256_chksign_price() -> i64 {
+        P256_CHKSIGN_GAS_PRICE
+    }
+
+    /// Compute BLS_VERIFY usage cost
+    pub const fn bls_verify_price() -> i64 {
+        BLS_VERIFY_GAS_PRICE
+    }
+
+    /// Compute BLS_AGGREGATE usage cost for `count` signatures
+    pub const fn bls_aggregate_price(count: usize) -> i64 {
+        BLS_AGGREGATE_ENTRY_GAS_PRICE * count as i64
+    }
+
+    /// Compute BLS_FASTAGGREGATEVERIFY usage cost for `count` public keys
+    pub const fn bls_fast_aggregate_verify_price(count: usize) -> i64 {
+        BLS_FAST_AGGREGATE_VERIFY_GAS_PRICE
+            + BLS_FAST_AGGREGATE_VERIFY_ENTRY_GAS_PRICE * count as i64
+    }
+
+    /// Compute BLS_AGGREGATEVERIFY usage cost for `count` signed messages
+    pub const fn bls_aggregate_verify_price(count: usize) -> i64 {
+        BLS_AGGREGATE_VERIFY_GAS_PRICE + BLS_AGGREGATE_VERIFY_ENTRY_GAS_PRICE * count as i64
+    }
+
+    /// Compute BLS_PAIRING usage cost for `count` pairs of points
+    pub const fn bls_pairing_price(count: usize) -> i64 {
+        BLS_PAIRING_GAS_PRICE + BLS_PAIRING_ENTRY_GAS_PRICE * count as i64
+    }
+
     #[cfg(feature = "gosh")]
     /// Compute POSEIDON ZK LOGIN usage cost
     pub const fn poseidon_zk_login_price() -> i64 {
@@ -223,6 +314,18 @@ impl Gas {
         self.use_gas(VERGRTH16_GAS_PRICE)
     }
 
+    /// Compute VERGRTH16VK usage cost for `inputs` public inputs
+    #[cfg(feature = "gosh")]
+    pub const fn vergrth16_vk_price(curve: Groth16Curve, inputs: usize) -> i64 {
+        curve.gas_price(inputs)
+    }
+
+    /// Compute VERGRTH16VK usage cost for a verifying key of `bytes` length
+    #[cfg(feature = "gosh")]
+    pub const fn vergrth16_vk_key_price(bytes: usize) -> i64 {
+        VERGRTH16VK_KEY_BYTE_GAS_PRICE * bytes as i64
+    }
+
     /// Compute CHKHISTPROOF usage cost
     #[cfg(feature = "gosh")]
     pub const fn chkhistproof_price() -> i64 {
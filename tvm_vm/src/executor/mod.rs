@@ -14,6 +14,7 @@ mod microcode;
 #[macro_use]
 mod engine;
 mod blockchain;
+mod bls;
 mod config;
 mod continuation;
 mod crypto;
@@ -24,6 +25,7 @@ mod dictionary;
 mod diff;
 mod dump;
 mod exceptions;
+pub mod extension;
 pub mod gas;
 mod globals;
 mod math;
@@ -37,6 +39,10 @@ mod tuple;
 mod types;
 #[cfg(feature = "wasmtime")]
 pub mod wasm;
+#[cfg(feature = "wasmtime")]
+pub mod wasm_tvm;
+#[cfg(feature = "gosh")]
+pub mod zk_groth16_with_vk;
 #[cfg(feature = "gosh")]
 pub mod zk_halo2_with_vk;
 
@@ -54,6 +60,9 @@ use tvm_types::Result;
 #[cfg(all(test, feature = "wasmtime"))]
 #[path = "../tests/test_multifactor_tls_wasm_execution.rs"]
 mod test_multifactor_tls_wasm_execution;
+#[cfg(all(test, feature = "wasmtime"))]
+#[path = "../tests/test_wasm_tvm.rs"]
+mod test_wasm_tvm;
 
 #[cfg(all(test, feature = "gosh"))]
 #[path = "../tests/test_halo2.rs"]
@@ -67,6 +76,10 @@ mod test_halo2_with_vk;
 #[path = "../tests/test_vergrth_poseidon_execution.rs"]
 mod test_vergrth_poseidon_execution;
 
+#[cfg(all(test, feature = "gosh"))]
+#[path = "../tests/test_vergrth16_with_vk.rs"]
+mod test_vergrth16_with_vk;
+
 #[cfg(all(test, feature = "gosh"))]
 #[path = "../tests/test_vergrth_bad_args.rs"]
 mod test_vergrth_bad_args;
@@ -79,6 +92,30 @@ mod test_poseidon_bad_args;
 #[path = "../tests/test_chk_hist_proof.rs"]
 mod test_chk_hist_proof;
 
+#[cfg(test)]
+#[path = "../tests/test_hashext.rs"]
+mod test_hashext;
+
+#[cfg(test)]
+#[path = "../tests/test_ecdsa.rs"]
+mod test_ecdsa;
+
+#[cfg(test)]
+#[path = "../tests/test_bls.rs"]
+mod test_bls;
+
+#[cfg(test)]
+#[path = "../tests/test_extension.rs"]
+mod test_extension;
+
+#[cfg(test)]
+#[path = "../tests/test_decoded_cache.rs"]
+mod test_decoded_cache;
+
+#[cfg(test)]
+#[path = "../tests/test_engine_state.rs"]
+mod test_engine_state;
+
 #[cfg(test)]
 #[path = "../tests/test_executor.rs"]
 mod tests;
@@ -86,7 +123,7 @@ mod tests;
 #[path = "../tests/test_data.rs"]
 mod test_data;
 
-#[cfg(feature = "gosh")]
+#[cfg(test)]
 #[path = "../tests/test_helper.rs"]
 mod test_helper;
 
@@ -90,7 +90,7 @@ pub(super) enum WhereToGetParams {
     GetFromNextByteMinusOneMinusTwo,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub(super) enum InstructionParameter {
     BigInteger(IntegerData),
     ControlRegister(usize),
@@ -107,20 +107,20 @@ pub(super) enum InstructionParameter {
     StackRegisterTrio(RegisterTrio),
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub(super) struct RegisterPair {
     pub(super) ra: usize,
     pub(super) rb: usize,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub(super) struct RegisterTrio {
     pub(super) ra: usize,
     pub(super) rb: usize,
     pub(super) rc: usize,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub(super) struct LengthAndIndex {
     pub(super) length: usize,
     pub(super) index: usize,
@@ -1,9 +1,13 @@
+use std::collections::HashSet;
+
 use rand_chacha::rand_core::RngCore;
 use rand_chacha::rand_core::SeedableRng;
 use tvm_abi::TokenValue;
 use tvm_abi::contract::ABI_VERSION_2_4;
+use tvm_block::GlobalCapabilities;
 use tvm_types::ExceptionCode;
 use tvm_types::SliceData;
+use tvm_types::UInt256;
 use tvm_types::error;
 use wasmtime::component::ResourceTable;
 use wasmtime_wasi::WasiCtx;
@@ -13,7 +17,9 @@ use wasmtime_wasi::WasiView;
 
 use crate::error::TvmError;
 use crate::executor::engine::Engine;
+use crate::executor::wasm_tvm::TvmHost;
 use crate::stack::StackItem;
+use crate::stack::integer::serialization::UnsignedIntegerBigEndianEncoding;
 use crate::types::Exception;
 use crate::types::Status;
 
@@ -21,6 +27,104 @@ pub const WASM_FUEL_MULTIPLIER: u64 = 2220000u64;
 pub const WASM_200MS_FUEL: u64 = 2220000000u64;
 pub const RUNWASM_GAS_PRICE: u64 = WASM_200MS_FUEL / WASM_FUEL_MULTIPLIER;
 
+/// Interfaces a WASM component may import, without versions. These are the
+/// sync WASI interfaces linked by `add_to_linker_gosh`, answered from block
+/// data instead of the host, and the `tvm:vm` host API.
+pub const ALLOWED_WASM_IMPORTS: &[&str] = &[
+    "wasi:cli/environment",
+    "wasi:cli/exit",
+    "wasi:cli/stderr",
+    "wasi:cli/stdin",
+    "wasi:cli/stdout",
+    "wasi:cli/terminal-input",
+    "wasi:cli/terminal-output",
+    "wasi:cli/terminal-stderr",
+    "wasi:cli/terminal-stdin",
+    "wasi:cli/terminal-stdout",
+    "wasi:clocks/monotonic-clock",
+    "wasi:clocks/wall-clock",
+    "wasi:filesystem/preopens",
+    "wasi:filesystem/types",
+    "wasi:io/error",
+    "wasi:io/poll",
+    "wasi:io/streams",
+    "wasi:random/insecure",
+    "wasi:random/insecure-seed",
+    "wasi:random/random",
+    "tvm:vm/cells",
+    "tvm:vm/registers",
+];
+
+/// Engine config giving the same results on every node: fuel metering,
+/// canonical NaNs, no relaxed SIMD, whose results are implementation-defined,
+/// and no threads, whose results depend on scheduling.
+pub fn deterministic_wasm_config() -> wasmtime::Config {
+    let mut wasm_config = wasmtime::Config::new();
+    wasm_config.wasm_component_model(true);
+    wasm_config.consume_fuel(true);
+    // configs to assure determinism
+    wasm_config.cranelift_nan_canonicalization(true);
+    wasm_config.cranelift_pcc(true);
+    wasm_config.wasm_relaxed_simd(false);
+    wasm_config.wasm_threads(false);
+    wasm_config
+}
+
+/// Checks a WASM component before it is whitelisted: it must compile under
+/// [`deterministic_wasm_config`] and import only [`ALLOWED_WASM_IMPORTS`].
+/// Returns the names of its imports.
+pub fn validate_wasm_component(
+    wasm_engine: &wasmtime::Engine,
+    wasm_executable: &[u8],
+) -> tvm_types::Result<Vec<String>> {
+    let component = match wasmtime::component::Component::new(wasm_engine, wasm_executable) {
+        Ok(component) => component,
+        Err(e) => err!(ExceptionCode::WasmLoadFail, "Failed to compile WASM component {:?}", e)?,
+    };
+    let mut imports = Vec::new();
+    for (name, _) in component.component_type().imports(wasm_engine) {
+        if !is_allowed_wasm_import(name) {
+            err!(ExceptionCode::WasmForbiddenBinary, "WASM component imports forbidden {}", name)?
+        }
+        imports.push(name.to_string());
+    }
+    Ok(imports)
+}
+
+/// Whether an import name, with or without version, is one of
+/// [`ALLOWED_WASM_IMPORTS`]
+pub fn is_allowed_wasm_import(name: &str) -> bool {
+    let interface = name.split_once('@').map_or(name, |(interface, _version)| interface);
+    ALLOWED_WASM_IMPORTS.contains(&interface)
+}
+
+/// Random source of a WASM call, seeded from the block time or, with
+/// `CapWasmDeterminism`, from `RANDSEED` of c7 when c7 has one
+pub(crate) fn wasm_random_source(engine: &Engine) -> rand_chacha::ChaCha20Rng {
+    if engine.check_capabilities(GlobalCapabilities::CapWasmDeterminism as u64) {
+        let seed = engine
+            .rand()
+            .and_then(|seed| seed.as_builder::<UnsignedIntegerBigEndianEncoding>(256))
+            .ok()
+            .and_then(|seed| <[u8; 32]>::try_from(seed.data()).ok());
+        if let Some(seed) = seed {
+            return rand_chacha::ChaCha20Rng::from_seed(seed);
+        }
+    }
+    rand_chacha::ChaCha20Rng::seed_from_u64(engine.get_wasm_block_time())
+}
+
+/// Monotonic clock of a WASM call, which does not advance during the call:
+/// the block time in seconds or, with `CapWasmDeterminism`, in nanoseconds
+pub(crate) fn wasm_monotonic_time(engine: &Engine) -> u64 {
+    let time = engine.get_wasm_block_time();
+    if engine.check_capabilities(GlobalCapabilities::CapWasmDeterminism as u64) {
+        time.saturating_mul(1000000000u64)
+    } else {
+        time
+    }
+}
+
 // wasmtime::component::bindgen!({
 //     inline: r#"
 //         package wasi:io@0.2.3;
@@ -1218,6 +1322,8 @@ pub(crate) struct MyState {
     limiter: wasmtime::StoreLimits,
     random_source: rand_chacha::ChaCha20Rng,
     time: u64,
+    monotonic_time: u64,
+    tvm: TvmHost,
 }
 // impl IoView for MyState {
 //     fn table(&mut self) -> &mut ResourceTable {
@@ -1264,7 +1370,7 @@ impl wasi::clocks::monotonic_clock::Host for MyState {
     #[doc = " The clock is monotonic, therefore calling this function repeatedly will"]
     #[doc = " produce a sequence of non-decreasing values."]
     fn now(&mut self) -> wasi::clocks::monotonic_clock::Instant {
-        self.time
+        self.monotonic_time
     }
 
     #[doc = " Query the resolution of the clock. Returns the duration of time"]
@@ -2120,6 +2226,7 @@ impl HasData for MyLibrary {
 // If more deps are needed, add them in there!
 fn add_to_linker_gosh<T: WasiView + 'static>(
     wasm_linker: &mut wasmtime::component::Linker<T>,
+    tvm_host: fn(&mut T) -> &mut TvmHost,
 ) -> Result<(), wasmtime::Error> {
     use wasmtime_wasi::p2::bindings::cli;
     // use wasmtime_wasi::p2::bindings::clocks;
@@ -2147,6 +2254,7 @@ fn add_to_linker_gosh<T: WasiView + 'static>(
     cli::terminal_stdin::add_to_linker::<T, HasWasi<T>>(l, T::ctx)?;
     cli::terminal_stdout::add_to_linker::<T, HasWasi<T>>(l, T::ctx)?;
     cli::terminal_stderr::add_to_linker::<T, HasWasi<T>>(l, T::ctx)?;
+    crate::executor::wasm_tvm::add_to_linker(l, tvm_host)?;
     Ok(())
 }
 
@@ -2207,15 +2315,32 @@ pub(crate) fn check_and_get_wasm_by_hash(
     }
 }
 
-// Shared functionality for all wasm instructions
-pub(crate) fn run_wasm_core(
-    engine: &mut Engine,
-    wasm_executable: Vec<u8>,
+/// Outcome of a WASM function call
+#[derive(Debug)]
+pub struct WasmCallResult {
+    /// Bytes returned by the function
+    pub result: Vec<u8>,
+    /// Fuel spent by the call
+    pub fuel_used: u64,
+    /// Gas spent in `tvm:vm` host calls, charged on top of
+    /// [`RUNWASM_GAS_PRICE`]
+    pub host_gas_used: i64,
+    /// Cells loaded by the engine before the call and by `tvm:vm` host calls
+    pub loaded_cells: HashSet<UInt256>,
+}
+
+/// Calls `wasm_func_name` of `wasm_instance_name` exported by a component
+/// with the same linker and store setup as the WASM instructions, without
+/// charging gas to the engine. Host calls may spend up to `gas_limit`.
+pub fn call_wasm_component(
+    engine: &Engine,
+    wasm_component: &wasmtime::component::Component,
     wasm_func_name: &str,
     wasm_instance_name: &str,
     wasm_func_args: Vec<u8>,
-    wasm_hash: Option<[u8; 32]>,
-) -> Status {
+    wasm_fuel: u64,
+    gas_limit: i64,
+) -> tvm_types::Result<WasmCallResult> {
     let mut builder = WasiCtxBuilder::new();
     let mut wasm_store: wasmtime::Store<MyState> = engine.create_wasm_store(MyState {
         ctx: builder.build(),
@@ -2228,45 +2353,17 @@ pub(crate) fn run_wasm_core(
             .table_elements(1000000)
             .trap_on_grow_failure(true)
             .build(),
-        random_source: rand_chacha::ChaCha20Rng::seed_from_u64(engine.get_wasm_block_time()),
+        random_source: wasm_random_source(engine),
         time: engine.get_wasm_block_time(),
+        monotonic_time: wasm_monotonic_time(engine),
+        tvm: TvmHost::new(engine, gas_limit),
     })?;
     wasm_store.limiter(|state| &mut state.limiter);
-    // set WASM fuel limit based on available gas
-    // TODO: Consider adding a constant offset to account for cell pack/unpack and
-    // other actions to be run after WASM instruction
-    // TODO: Add a catch for out-of-fuel and remove matching consumed gas from
-    // instruction (or set to 0?)
-    log::debug!("Starting gas: {:?}", engine.gas_remaining());
-    let wasm_fuel: u64 = WASM_200MS_FUEL;
-    let gas_used: i64 = RUNWASM_GAS_PRICE.try_into()?;
-    match engine.gas_remaining() > gas_used {
-        true => {}
-        false => err!(ExceptionCode::OutOfGas, "Engine out of gas.")?,
-    }
-
-    // TODO: If switching to dunamic fuel limit, use this code:
-    // let wasm_fuel: u64 = match engine.gas_remaining() > 0 {
-    //     true => match
-    // u64::try_from(engine.gas_remaining())?.checked_mul(WASM_FUEL_MULTIPLIER) {
-    //         Some(k) => k,
-    //         None => err!(ExceptionCode::IntegerOverflow, "Overflow when
-    // calculating WASM fuel")?,     },
-    //     false => err!(ExceptionCode::OutOfGas, "Engine out of gas.")?,
-    // };
     match wasm_store.set_fuel(wasm_fuel) {
         Ok(module) => module,
         Err(e) => err!(ExceptionCode::WasmFuelError, "Failed to set WASm fuel {:?}", e)?,
     };
 
-    let wasm_component = match wasm_hash {
-        Some(h) => match engine.get_precompiled_wasm_component(h) {
-            Some(c) => c,
-            None => &engine.create_single_use_wasm_component(wasm_executable)?,
-        },
-        None => &engine.create_single_use_wasm_component(wasm_executable)?,
-    };
-
     engine.print_wasm_component_exports_and_imports(wasm_component)?;
 
     // Add wasi-cli libs to linker
@@ -2287,7 +2384,7 @@ pub(crate) fn run_wasm_core(
 
     // This is a custom linker method, adding only sync, non-io wasi dependencies.
     // If more deps are needed, add them in there!
-    match add_to_linker_gosh::<MyState>(wasm_linker) {
+    match add_to_linker_gosh::<MyState>(wasm_linker, |state| &mut state.tvm) {
         Ok(_) => {}
         Err(e) => err!(
             ExceptionCode::WasmLinkerFail,
@@ -2355,6 +2452,10 @@ pub(crate) fn run_wasm_core(
 
     let result = match wasm_function.call(&mut wasm_store, (wasm_func_args,)) {
         Ok(result) => result,
+        Err(e) if wasm_store.data().tvm.is_out_of_gas() => {
+            log::debug!("WASM function ran out of gas {:?}", e);
+            err!(ExceptionCode::OutOfGas, "WASM function ran out of gas in TVM host calls")?
+        }
         Err(e) => {
             log::debug!("Failed to execute WASM function {:?}", e);
             err!(ExceptionCode::WasmExecFail, "Failed to execute WASM function {:?}", e)?
@@ -2362,25 +2463,85 @@ pub(crate) fn run_wasm_core(
     };
     log::debug!("WASM Execution result: {:?}", result);
 
-    // TODO: If we switch to dynamic gas usage, reenable this code
-    // let gas_used: i64 = match wasm_store.get_fuel() {
-    //     Ok(new_fuel) => i64::try_from((wasm_fuel -
-    // new_fuel).div_ceil(WASM_FUEL_MULTIPLIER))?,     Err(e) => err!(
-    //         ExceptionCode::WasmLoadFail,
-    //         "Failed to get WASM engine fuel after execution {:?}",
-    //         e
-    //     )?,
+    let fuel_used = match wasm_store.get_fuel() {
+        Ok(fuel) => wasm_fuel - fuel,
+        Err(e) => err!(ExceptionCode::WasmFuelError, "Failed to get WASM fuel {:?}", e)?,
+    };
+    let tvm = wasm_store.into_data().tvm;
+    Ok(WasmCallResult {
+        result: result.0,
+        fuel_used,
+        host_gas_used: tvm.gas_used(),
+        loaded_cells: tvm.into_loaded_cells(),
+    })
+}
+
+// Shared functionality for all wasm instructions
+pub(crate) fn run_wasm_core(
+    engine: &mut Engine,
+    wasm_executable: Vec<u8>,
+    wasm_func_name: &str,
+    wasm_instance_name: &str,
+    wasm_func_args: Vec<u8>,
+    wasm_hash: Option<[u8; 32]>,
+) -> Status {
+    log::debug!("Starting gas: {:?}", engine.gas_remaining());
+    let gas_used: i64 = RUNWASM_GAS_PRICE.try_into()?;
+    match engine.gas_remaining() > gas_used {
+        true => {}
+        false => err!(ExceptionCode::OutOfGas, "Engine out of gas.")?,
+    }
+
+    // set WASM fuel limit based on available gas
+    // TODO: Consider adding a constant offset to account for cell pack/unpack and
+    // other actions to be run after WASM instruction
+    // TODO: Add a catch for out-of-fuel and remove matching consumed gas from
+    // instruction (or set to 0?)
+    let wasm_fuel: u64 = WASM_200MS_FUEL;
+
+    // TODO: If switching to dunamic fuel limit, use this code:
+    // let wasm_fuel: u64 = match engine.gas_remaining() > 0 {
+    //     true => match
+    // u64::try_from(engine.gas_remaining())?.checked_mul(WASM_FUEL_MULTIPLIER) {
+    //         Some(k) => k,
+    //         None => err!(ExceptionCode::IntegerOverflow, "Overflow when
+    // calculating WASM fuel")?,     },
+    //     false => err!(ExceptionCode::OutOfGas, "Engine out of gas.")?,
     // };
+
+    let wasm_component = match wasm_hash {
+        Some(h) => match engine.get_precompiled_wasm_component(h) {
+            Some(c) => c,
+            None => &engine.create_single_use_wasm_component(wasm_executable)?,
+        },
+        None => &engine.create_single_use_wasm_component(wasm_executable)?,
+    };
+
+    let call = call_wasm_component(
+        engine,
+        wasm_component,
+        wasm_func_name,
+        wasm_instance_name,
+        wasm_func_args,
+        wasm_fuel,
+        engine.gas_remaining() - gas_used,
+    )?;
+
+    // TODO: If we switch to dynamic gas usage, reenable this code
+    // let gas_used: i64 =
+    // i64::try_from(call.fuel_used.div_ceil(WASM_FUEL_MULTIPLIER))?;
     // engine.use_gas(gas_used);
+    let gas_used = gas_used + call.host_gas_used;
     match engine.try_use_gas(gas_used) {
         Ok(_) => {}
         Err(e) => err!(ExceptionCode::OutOfGas, "Engine out of gas {:?}.", e)?,
     }
+    engine.visited_cells = call.loaded_cells;
     log::debug!("Remaining gas: {:?}", engine.gas_remaining());
 
     // return result
-    log::debug!("EXEC Wasm execution result: {:?}", result);
-    let res_vec = result.0;
+    log::debug!("EXEC Wasm execution result: {:?}", call.result);
+    let res_vec = call.result;
 
     let cell = TokenValue::write_bytes(res_vec.as_slice(), &ABI_VERSION_2_4)?.into_cell()?;
     log::debug!("Pushing cell");
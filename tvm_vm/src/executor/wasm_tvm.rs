@@ -0,0 +1,610 @@
+//! `tvm:vm` host API for WASM components.
+//!
+//! Components called by the WASM instructions can import the `cells` and
+//! `registers` interfaces to work on contract data directly instead of
+//! parsing bytes: cells, slices and builders are host resources, integers
+//! are passed as big-endian two's complement bytes, and c4 and c7 of the
+//! calling contract are available read-only as they were when the
+//! instruction started. Every call, including dropping a handle, costs
+//! [`TVM_HOST_CALL_GAS_PRICE`]; calls reading a cell additionally cost the
+//! cell load price, reduced for cells already loaded by the contract or the
+//! component as with CTOS, and finishing a builder costs the cell creation
+//! price. Exotic cells can not be read. The gas is charged to the engine
+//! after the component returns. A call that
+//! spends the gas remaining for the instruction, or that would keep more
+//! than [`MAX_TVM_HOST_HANDLES`] handles alive, traps the component.
+
+use std::collections::HashSet;
+
+use tvm_types::BuilderData;
+use tvm_types::Cell;
+use tvm_types::CellType;
+use tvm_types::ExceptionCode;
+use tvm_types::IBitstring;
+use tvm_types::SliceData;
+use tvm_types::UInt256;
+use wasmtime::component::HasData;
+use wasmtime::component::Resource;
+use wasmtime::component::ResourceTable;
+
+use crate::error::tvm_exception_code;
+use crate::executor::engine::Engine;
+use crate::executor::engine::ordinary_cell_load_price;
+use crate::executor::gas::gas_state::Gas;
+use crate::stack::StackItem;
+use crate::stack::integer::IntegerData;
+use crate::stack::integer::serialization::Encoding;
+use crate::stack::integer::serialization::SignedIntegerBigEndianEncoding;
+use crate::stack::integer::serialization::UnsignedIntegerBigEndianEncoding;
+use crate::stack::serialization::Deserializer;
+
+wasmtime::component::bindgen!({
+    inline: r#"
+        package tvm:vm@0.1.0;
+
+        /// Cells of the calling contract and cells built by the component
+        interface cells {
+            /// Signed big-endian two's complement integer of at most 257 bits
+            type int257 = list<u8>;
+
+            enum error {
+                /// Not enough bits or references left
+                cell-underflow,
+                /// No room for more bits or references
+                cell-overflow,
+                /// Value does not fit into the given number of bits
+                range-check,
+                /// Item is not of the expected type, or the cell is exotic
+                type-check,
+            }
+
+            /// Every method but `drop` charges the cell load price
+            resource cell {
+                /// Representation hash
+                hash: func() -> list<u8>;
+                bit-length: func() -> u32;
+                references-count: func() -> u32;
+                reference: func(index: u32) -> result<cell, error>;
+                begin-parse: func() -> result<slice, error>;
+            }
+
+            resource slice {
+                remaining-bits: func() -> u32;
+                remaining-references: func() -> u32;
+                load-uint: func(bits: u32) -> result<u64, error>;
+                load-int: func(bits: u32) -> result<s64, error>;
+                load-integer: func(bits: u32, signed: bool) -> result<int257, error>;
+                /// Bits packed from the most significant bit of the first byte
+                load-bits: func(bits: u32) -> result<list<u8>, error>;
+                load-reference: func() -> result<cell, error>;
+                skip: func(bits: u32, references: u32) -> result<_, error>;
+            }
+
+            resource builder {
+                constructor();
+                bit-length: func() -> u32;
+                references-count: func() -> u32;
+                store-uint: func(value: u64, bits: u32) -> result<_, error>;
+                store-int: func(value: s64, bits: u32) -> result<_, error>;
+                store-integer: func(value: int257, bits: u32, signed: bool) -> result<_, error>;
+                store-bits: func(data: list<u8>, bits: u32) -> result<_, error>;
+                store-reference: func(cell: borrow<cell>) -> result<_, error>;
+                store-slice: func(slice: borrow<slice>) -> result<_, error>;
+                /// Finishes the cell, charging the cell creation price
+                end-cell: func() -> result<cell, error>;
+            }
+        }
+
+        /// Read-only registers of the calling contract
+        interface registers {
+            use cells.{cell, slice, builder, int257, error};
+
+            variant stack-value {
+                null,
+                nan,
+                integer(int257),
+                cell(cell),
+                slice(slice),
+                builder(builder),
+                /// Tuple with this number of items, read them with a longer path
+                tuple-length(u32),
+                continuation,
+            }
+
+            /// Persistent data (c4)
+            c4: func() -> cell;
+            /// Item of c7 at `path`, each index selects an item of a nested tuple
+            c7: func(path: list<u32>) -> result<stack-value, error>;
+        }
+
+        world contract-host {
+            import cells;
+            import registers;
+        }
+    "#,
+    imports: { default: trappable },
+    trappable_error_type: {
+        "tvm:vm/cells.error" => HostError,
+    },
+    with: {
+        "tvm:vm/cells.cell": CellHandle,
+        "tvm:vm/cells.slice": SliceHandle,
+        "tvm:vm/cells.builder": BuilderHandle,
+    },
+});
+
+use self::tvm::vm::cells;
+use self::tvm::vm::cells::Error;
+use self::tvm::vm::registers;
+use self::tvm::vm::registers::StackValue;
+
+/// Gas for every call of a `tvm:vm` host function
+pub const TVM_HOST_CALL_GAS_PRICE: i64 = 10;
+/// Cells, slices and builders a component may hold at the same time
+pub const MAX_TVM_HOST_HANDLES: usize = 1024;
+
+pub struct CellHandle(Cell);
+pub struct SliceHandle(SliceData);
+pub struct BuilderHandle(BuilderData);
+
+/// Failure of a `tvm:vm` host call. Cell errors are returned to the
+/// component, the others trap it.
+#[derive(Debug)]
+pub enum HostError {
+    Cell(Error),
+    OutOfGas,
+    TooManyHandles,
+}
+
+impl From<Error> for HostError {
+    fn from(err: Error) -> Self {
+        Self::Cell(err)
+    }
+}
+
+impl std::fmt::Display for HostError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cell(err) => write!(f, "{err:?}"),
+            Self::OutOfGas => write!(f, "out of gas in TVM host call"),
+            Self::TooManyHandles => {
+                write!(f, "more than {MAX_TVM_HOST_HANDLES} TVM host handles alive")
+            }
+        }
+    }
+}
+
+impl std::error::Error for HostError {}
+
+/// State of the `tvm:vm` interfaces for one WASM instruction
+pub struct TvmHost {
+    table: ResourceTable,
+    c4: Cell,
+    c7: StackItem,
+    loaded_cells: HashSet<UInt256>,
+    handles: usize,
+    gas_limit: i64,
+    gas_used: i64,
+}
+
+impl TvmHost {
+    /// Snapshot of the engine registers and visited cells; host calls may
+    /// spend up to `gas_limit`
+    pub(crate) fn new(engine: &Engine, gas_limit: i64) -> Self {
+        let c4 = engine.ctrls.get(4).and_then(|c4| c4.as_cell().ok()).cloned().unwrap_or_default();
+        let c7 = engine.ctrls.get(7).cloned().unwrap_or_default();
+        Self {
+            table: ResourceTable::new(),
+            c4,
+            c7,
+            loaded_cells: engine.visited_cells.clone(),
+            handles: 0,
+            gas_limit,
+            gas_used: 0,
+        }
+    }
+
+    /// Gas spent by host calls, to be charged to the engine
+    pub(crate) fn gas_used(&self) -> i64 {
+        self.gas_used
+    }
+
+    /// Cells loaded by the engine and by host calls, to be marked as visited
+    pub(crate) fn into_loaded_cells(self) -> HashSet<UInt256> {
+        self.loaded_cells
+    }
+
+    pub(crate) fn is_out_of_gas(&self) -> bool {
+        self.gas_used > self.gas_limit
+    }
+
+    fn use_gas(&mut self, gas: i64) -> Result<(), HostError> {
+        self.gas_used += gas;
+        if self.is_out_of_gas() { Err(HostError::OutOfGas) } else { Ok(()) }
+    }
+
+    fn call(&mut self) -> Result<(), HostError> {
+        self.use_gas(TVM_HOST_CALL_GAS_PRICE)
+    }
+
+    fn push<T: Send + 'static>(&mut self, value: T) -> Result<Resource<T>, HostError> {
+        if self.handles >= MAX_TVM_HOST_HANDLES {
+            return Err(HostError::TooManyHandles);
+        }
+        let handle = self.table.push(value).map_err(|_| HostError::TooManyHandles)?;
+        self.handles += 1;
+        Ok(handle)
+    }
+
+    fn delete<T: 'static>(&mut self, handle: Resource<T>) -> wasmtime::Result<()> {
+        self.call()?;
+        self.table.delete(handle)?;
+        self.handles -= 1;
+        Ok(())
+    }
+
+    fn cell(&self, handle: &Resource<CellHandle>) -> Result<&Cell, HostError> {
+        self.table.get(handle).map(|cell| &cell.0).map_err(|_| Error::TypeCheck.into())
+    }
+
+    /// Ordinary cell behind `handle`, charging the cell load price
+    fn load(&mut self, handle: &Resource<CellHandle>) -> Result<Cell, HostError> {
+        let cell = self.cell(handle)?.clone();
+        if cell.cell_type() != CellType::Ordinary {
+            return Err(Error::TypeCheck.into());
+        }
+        let gas = ordinary_cell_load_price(&mut self.loaded_cells, cell.repr_hash());
+        self.use_gas(gas)?;
+        Ok(cell)
+    }
+
+    fn slice(&mut self, handle: &Resource<SliceHandle>) -> Result<&mut SliceData, HostError> {
+        self.table.get_mut(handle).map(|slice| &mut slice.0).map_err(|_| Error::TypeCheck.into())
+    }
+
+    fn builder(&mut self, handle: &Resource<BuilderHandle>) -> Result<&mut BuilderData, HostError> {
+        self.table
+            .get_mut(handle)
+            .map(|builder| &mut builder.0)
+            .map_err(|_| Error::TypeCheck.into())
+    }
+
+    fn append_integer<T: Encoding>(
+        &mut self,
+        handle: Resource<BuilderHandle>,
+        value: IntegerData,
+        bits: u32,
+    ) -> Result<(), HostError> {
+        self.call()?;
+        let data = value.as_builder::<T>(bits as usize).map_err(cell_error)?;
+        let builder = self.builder(&handle)?;
+        if !builder.check_enough_space(data.length_in_bits()) {
+            return Err(Error::CellOverflow.into());
+        }
+        builder.append_builder(&data).map_err(cell_error)?;
+        Ok(())
+    }
+}
+
+/// Links `tvm:vm` interfaces backed by the [`TvmHost`] returned by `host`
+pub(crate) fn add_to_linker<T: 'static>(
+    linker: &mut wasmtime::component::Linker<T>,
+    host: fn(&mut T) -> &mut TvmHost,
+) -> wasmtime::Result<()> {
+    ContractHost::add_to_linker::<T, TvmLibrary>(linker, host)
+}
+
+struct TvmLibrary;
+
+impl HasData for TvmLibrary {
+    type Data<'a> = &'a mut TvmHost;
+}
+
+fn cell_error(err: tvm_types::Error) -> Error {
+    match tvm_exception_code(&err) {
+        Some(ExceptionCode::CellUnderflow) => Error::CellUnderflow,
+        Some(ExceptionCode::CellOverflow) => Error::CellOverflow,
+        Some(ExceptionCode::TypeCheckError) => Error::TypeCheck,
+        _ => Error::RangeCheck,
+    }
+}
+
+fn integer_from_bytes(value: &[u8]) -> Result<IntegerData, Error> {
+    IntegerData::from(num::BigInt::from_signed_bytes_be(value)).map_err(|_| Error::RangeCheck)
+}
+
+fn integer_to_bytes(value: &IntegerData) -> Result<Vec<u8>, Error> {
+    value.take_value_of(|value| Some(value.to_signed_bytes_be())).map_err(|_| Error::RangeCheck)
+}
+
+impl cells::Host for TvmHost {
+    fn convert_error(&mut self, err: HostError) -> wasmtime::Result<Error> {
+        match err {
+            HostError::Cell(err) => Ok(err),
+            err => Err(err.into()),
+        }
+    }
+}
+
+impl cells::HostCell for TvmHost {
+    fn hash(&mut self, self_: Resource<CellHandle>) -> wasmtime::Result<Vec<u8>> {
+        self.call()?;
+        Ok(self.load(&self_)?.repr_hash().as_slice().to_vec())
+    }
+
+    fn bit_length(&mut self, self_: Resource<CellHandle>) -> wasmtime::Result<u32> {
+        self.call()?;
+        Ok(self.load(&self_)?.bit_length() as u32)
+    }
+
+    fn references_count(&mut self, self_: Resource<CellHandle>) -> wasmtime::Result<u32> {
+        self.call()?;
+        Ok(self.load(&self_)?.references_count() as u32)
+    }
+
+    fn reference(
+        &mut self,
+        self_: Resource<CellHandle>,
+        index: u32,
+    ) -> Result<Resource<CellHandle>, HostError> {
+        self.call()?;
+        let cell = self.load(&self_)?;
+        let reference = cell.reference(index as usize).map_err(|_| Error::CellUnderflow)?;
+        self.push(CellHandle(reference))
+    }
+
+    fn begin_parse(
+        &mut self,
+        self_: Resource<CellHandle>,
+    ) -> Result<Resource<SliceHandle>, HostError> {
+        self.call()?;
+        let cell = self.load(&self_)?;
+        let slice = SliceData::load_cell(cell).map_err(cell_error)?;
+        self.push(SliceHandle(slice))
+    }
+
+    fn drop(&mut self, rep: Resource<CellHandle>) -> wasmtime::Result<()> {
+        self.delete(rep)
+    }
+}
+
+impl cells::HostSlice for TvmHost {
+    fn remaining_bits(&mut self, self_: Resource<SliceHandle>) -> wasmtime::Result<u32> {
+        self.call()?;
+        Ok(self.slice(&self_)?.remaining_bits() as u32)
+    }
+
+    fn remaining_references(&mut self, self_: Resource<SliceHandle>) -> wasmtime::Result<u32> {
+        self.call()?;
+        Ok(self.slice(&self_)?.remaining_references() as u32)
+    }
+
+    fn load_uint(&mut self, self_: Resource<SliceHandle>, bits: u32) -> Result<u64, HostError> {
+        self.call()?;
+        if bits > 64 {
+            return Err(Error::RangeCheck.into());
+        }
+        Ok(self.slice(&self_)?.get_next_int(bits as usize).map_err(cell_error)?)
+    }
+
+    fn load_int(&mut self, self_: Resource<SliceHandle>, bits: u32) -> Result<i64, HostError> {
+        self.call()?;
+        if bits > 64 {
+            return Err(Error::RangeCheck.into());
+        }
+        let value = self.slice(&self_)?.get_next_int(bits as usize).map_err(cell_error)?;
+        match bits {
+            0 => Ok(0),
+            bits => Ok(((value << (64 - bits)) as i64) >> (64 - bits)),
+        }
+    }
+
+    fn load_integer(
+        &mut self,
+        self_: Resource<SliceHandle>,
+        bits: u32,
+        signed: bool,
+    ) -> Result<Vec<u8>, HostError> {
+        self.call()?;
+        let bits = bits as usize;
+        if bits > if signed { 257 } else { 256 } {
+            return Err(Error::RangeCheck.into());
+        }
+        let data = self.slice(&self_)?.get_next_bits(bits).map_err(cell_error)?;
+        let value = match signed {
+            true => SignedIntegerBigEndianEncoding::new(bits).deserialize(&data),
+            false => UnsignedIntegerBigEndianEncoding::new(bits).deserialize(&data),
+        };
+        Ok(integer_to_bytes(&value)?)
+    }
+
+    fn load_bits(&mut self, self_: Resource<SliceHandle>, bits: u32) -> Result<Vec<u8>, HostError> {
+        self.call()?;
+        Ok(self.slice(&self_)?.get_next_bits(bits as usize).map_err(cell_error)?)
+    }
+
+    fn load_reference(
+        &mut self,
+        self_: Resource<SliceHandle>,
+    ) -> Result<Resource<CellHandle>, HostError> {
+        self.call()?;
+        let cell = self.slice(&self_)?.checked_drain_reference().map_err(cell_error)?;
+        self.push(CellHandle(cell))
+    }
+
+    fn skip(
+        &mut self,
+        self_: Resource<SliceHandle>,
+        bits: u32,
+        references: u32,
+    ) -> Result<(), HostError> {
+        self.call()?;
+        let slice = self.slice(&self_)?;
+        let (bits, references) = (bits as usize, references as usize);
+        if bits > slice.remaining_bits() || references > slice.remaining_references() {
+            return Err(Error::CellUnderflow.into());
+        }
+        slice.move_by(bits).map_err(cell_error)?;
+        if references != 0 {
+            slice.shrink_references(references..);
+        }
+        Ok(())
+    }
+
+    fn drop(&mut self, rep: Resource<SliceHandle>) -> wasmtime::Result<()> {
+        self.delete(rep)
+    }
+}
+
+impl cells::HostBuilder for TvmHost {
+    fn new(&mut self) -> wasmtime::Result<Resource<BuilderHandle>> {
+        self.call()?;
+        Ok(self.push(BuilderHandle(BuilderData::new()))?)
+    }
+
+    fn bit_length(&mut self, self_: Resource<BuilderHandle>) -> wasmtime::Result<u32> {
+        self.call()?;
+        Ok(self.builder(&self_)?.length_in_bits() as u32)
+    }
+
+    fn references_count(&mut self, self_: Resource<BuilderHandle>) -> wasmtime::Result<u32> {
+        self.call()?;
+        Ok(self.builder(&self_)?.references_used() as u32)
+    }
+
+    fn store_uint(
+        &mut self,
+        self_: Resource<BuilderHandle>,
+        value: u64,
+        bits: u32,
+    ) -> Result<(), HostError> {
+        self.append_integer::<UnsignedIntegerBigEndianEncoding>(
+            self_,
+            IntegerData::from_u64(value),
+            bits,
+        )
+    }
+
+    fn store_int(
+        &mut self,
+        self_: Resource<BuilderHandle>,
+        value: i64,
+        bits: u32,
+    ) -> Result<(), HostError> {
+        self.append_integer::<SignedIntegerBigEndianEncoding>(
+            self_,
+            IntegerData::from_i64(value),
+            bits,
+        )
+    }
+
+    fn store_integer(
+        &mut self,
+        self_: Resource<BuilderHandle>,
+        value: Vec<u8>,
+        bits: u32,
+        signed: bool,
+    ) -> Result<(), HostError> {
+        let value = integer_from_bytes(&value)?;
+        match signed {
+            true => self.append_integer::<SignedIntegerBigEndianEncoding>(self_, value, bits),
+            false => self.append_integer::<UnsignedIntegerBigEndianEncoding>(self_, value, bits),
+        }
+    }
+
+    fn store_bits(
+        &mut self,
+        self_: Resource<BuilderHandle>,
+        data: Vec<u8>,
+        bits: u32,
+    ) -> Result<(), HostError> {
+        self.call()?;
+        let bits = bits as usize;
+        if bits > data.len() * 8 {
+            return Err(Error::RangeCheck.into());
+        }
+        let builder = self.builder(&self_)?;
+        if !builder.check_enough_space(bits) {
+            return Err(Error::CellOverflow.into());
+        }
+        builder.append_raw(&data, bits).map_err(cell_error)?;
+        Ok(())
+    }
+
+    fn store_reference(
+        &mut self,
+        self_: Resource<BuilderHandle>,
+        cell: Resource<CellHandle>,
+    ) -> Result<(), HostError> {
+        self.call()?;
+        let cell = self.cell(&cell)?.clone();
+        let builder = self.builder(&self_)?;
+        if !builder.check_enough_refs(1) {
+            return Err(Error::CellOverflow.into());
+        }
+        builder.checked_append_reference(cell).map_err(cell_error)?;
+        Ok(())
+    }
+
+    fn store_slice(
+        &mut self,
+        self_: Resource<BuilderHandle>,
+        slice: Resource<SliceHandle>,
+    ) -> Result<(), HostError> {
+        self.call()?;
+        let slice = self.slice(&slice)?.clone();
+        let builder = self.builder(&self_)?;
+        if !builder.check_enough_space(slice.remaining_bits())
+            || !builder.check_enough_refs(slice.remaining_references())
+        {
+            return Err(Error::CellOverflow.into());
+        }
+        builder.checked_append_references_and_data(&slice).map_err(cell_error)?;
+        Ok(())
+    }
+
+    fn end_cell(
+        &mut self,
+        self_: Resource<BuilderHandle>,
+    ) -> Result<Resource<CellHandle>, HostError> {
+        self.call()?;
+        self.use_gas(Gas::finalize_price())?;
+        let builder = self.builder(&self_)?.clone();
+        let cell = builder.into_cell().map_err(cell_error)?;
+        self.push(CellHandle(cell))
+    }
+
+    fn drop(&mut self, rep: Resource<BuilderHandle>) -> wasmtime::Result<()> {
+        self.delete(rep)
+    }
+}
+
+impl registers::Host for TvmHost {
+    fn c4(&mut self) -> wasmtime::Result<Resource<CellHandle>> {
+        self.call()?;
+        Ok(self.push(CellHandle(self.c4.clone()))?)
+    }
+
+    fn c7(&mut self, path: Vec<u32>) -> Result<StackValue, HostError> {
+        self.call()?;
+        let mut item = &self.c7;
+        for index in path {
+            let tuple = item.as_tuple().map_err(|_| Error::TypeCheck)?;
+            item = tuple.get(index as usize).ok_or(Error::RangeCheck)?;
+        }
+        let item = item.clone();
+        let value = match &item {
+            StackItem::None => StackValue::Null,
+            StackItem::Integer(value) if value.is_nan() => StackValue::Nan,
+            StackItem::Integer(value) => StackValue::Integer(integer_to_bytes(value)?),
+            StackItem::Cell(cell) => StackValue::Cell(self.push(CellHandle(cell.clone()))?),
+            StackItem::Slice(slice) => StackValue::Slice(self.push(SliceHandle(slice.clone()))?),
+            StackItem::Builder(builder) => {
+                StackValue::Builder(self.push(BuilderHandle(builder.as_ref().clone()))?)
+            }
+            StackItem::Tuple(tuple) => StackValue::TupleLength(tuple.len() as u32),
+            StackItem::Continuation(_) => StackValue::Continuation,
+        };
+        Ok(value)
+    }
+}
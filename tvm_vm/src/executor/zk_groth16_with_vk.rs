@@ -0,0 +1,326 @@
+// `VERGRTH16VK` opcode handler — Groth16 verification with a caller-supplied
+// verifying key, over either BN254 or BLS12-381.
+//
+// `VERGRTH16` is wired to the zkLogin verifying key baked into
+// `super::zk::global_pvk()`. This sibling lets a dApp verify proofs of its
+// own circuit: the verifying key travels as a cell (or as the hash of a cell
+// published as a public library), the curve is selected by an integer id and
+// the number of public inputs is whatever the key declares.
+//
+// ## Stack ABI
+//
+// ```text
+//   top      curve_id             0 = BN254, 1 = BLS12-381
+//   ↑        vk                   Cell with the key, or its 256-bit
+//                                  representation hash (resolved through
+//                                  the public libraries)
+//   ↑        public_inputs_cell   Fr × N, strict 32-byte LE, no header
+//   bottom   proof_cell           ark-serialize compressed `Proof<E>`
+// ```
+//
+// The verifying key payload is an ark-serialize compressed
+// `VerifyingKey<E>`. Payloads are read with `unpack_data_from_cell`, so
+// anything larger than one cell is chained through single references.
+//
+// Enabled by the `CapGroth16WithVk` global capability. Besides the per-curve
+// base price and the price of every public input, every byte of the
+// verifying key is charged before the key is decoded.
+//
+// Pushes `true` when the proof verifies and `false` on cryptographic
+// rejection. Structural errors (undecodable key / proof / inputs, input
+// count that disagrees with the key) throw `FatalError`, mirroring
+// `VERGRTH16` and `ZKHALO2VERIFYWITHVK`.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+
+use ark_bls12_381::Bls12_381;
+use ark_bn254::Bn254;
+use ark_ec::pairing::Pairing;
+use ark_groth16::Groth16;
+use ark_groth16::PreparedVerifyingKey;
+use ark_groth16::Proof;
+use ark_groth16::VerifyingKey;
+use ark_groth16::prepare_verifying_key;
+use ark_serialize::CanonicalDeserialize;
+use tvm_block::GlobalCapabilities;
+use tvm_types::ExceptionCode;
+use tvm_types::Result;
+use tvm_types::SliceData;
+use tvm_types::UInt256;
+use tvm_types::error;
+
+use crate::error::TvmError;
+use crate::executor::Engine;
+use crate::executor::engine::storage::fetch_stack;
+use crate::executor::gas::gas_state::Gas;
+use crate::stack::StackItem;
+use crate::stack::integer::IntegerData;
+use crate::stack::integer::serialization::UnsignedIntegerBigEndianEncoding;
+use crate::types::Exception;
+use crate::types::Status;
+use crate::utils::unpack_data_from_cell;
+
+/// Base price of a BN254 verification, modelled on `VERGRTH16_GAS_PRICE`
+/// (four pairings plus proof decoding).
+pub const VERGRTH16VK_BN254_GAS_PRICE: i64 = 2_500;
+/// Base price of a BLS12-381 verification; pairings are about twice as
+/// expensive as on BN254.
+pub const VERGRTH16VK_BLS12_381_GAS_PRICE: i64 = 5_000;
+/// Price of every public input on BN254: one G1 scalar multiplication while
+/// preparing the inputs plus one key point to decode and subgroup-check.
+pub const VERGRTH16VK_BN254_INPUT_GAS_PRICE: i64 = 100;
+/// Price of every public input on BLS12-381.
+pub const VERGRTH16VK_BLS12_381_INPUT_GAS_PRICE: i64 = 200;
+/// Price of every byte of the verifying key, which is decoded and
+/// subgroup-checked point by point on a cache miss.
+pub const VERGRTH16VK_KEY_BYTE_GAS_PRICE: i64 = 2;
+
+/// Maximum number of prepared verifying keys kept in memory. Eviction is
+/// FIFO, same as the `ZKHALO2VERIFYWITHVK` cache.
+const VK_CACHE_CAPACITY: usize = 16;
+
+/// Size of a serialized public input for both supported scalar fields.
+const PUBLIC_INPUT_BYTES: usize = 32;
+
+/// Pairing-friendly curve a `VERGRTH16VK` proof is checked on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Groth16Curve {
+    Bn254 = 0,
+    Bls12_381 = 1,
+}
+
+impl Groth16Curve {
+    /// Gas charged for verifying a proof with `inputs` public inputs.
+    pub const fn gas_price(self, inputs: usize) -> i64 {
+        match self {
+            Groth16Curve::Bn254 => {
+                VERGRTH16VK_BN254_GAS_PRICE + VERGRTH16VK_BN254_INPUT_GAS_PRICE * inputs as i64
+            }
+            Groth16Curve::Bls12_381 => {
+                VERGRTH16VK_BLS12_381_GAS_PRICE
+                    + VERGRTH16VK_BLS12_381_INPUT_GAS_PRICE * inputs as i64
+            }
+        }
+    }
+}
+
+/// Prepared key for one of the supported curves.
+enum CachedVk {
+    Bn254(Box<PreparedVerifyingKey<Bn254>>),
+    Bls12_381(Box<PreparedVerifyingKey<Bls12_381>>),
+}
+
+/// Ties an arkworks pairing engine to its curve id and cache slot.
+trait Groth16Pairing: Pairing {
+    const CURVE: Groth16Curve;
+
+    fn wrap(pvk: PreparedVerifyingKey<Self>) -> CachedVk;
+    fn unwrap(cached: &CachedVk) -> Option<&PreparedVerifyingKey<Self>>;
+}
+
+impl Groth16Pairing for Bn254 {
+    const CURVE: Groth16Curve = Groth16Curve::Bn254;
+
+    fn wrap(pvk: PreparedVerifyingKey<Self>) -> CachedVk {
+        CachedVk::Bn254(Box::new(pvk))
+    }
+
+    fn unwrap(cached: &CachedVk) -> Option<&PreparedVerifyingKey<Self>> {
+        match cached {
+            CachedVk::Bn254(pvk) => Some(pvk.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl Groth16Pairing for Bls12_381 {
+    const CURVE: Groth16Curve = Groth16Curve::Bls12_381;
+
+    fn wrap(pvk: PreparedVerifyingKey<Self>) -> CachedVk {
+        CachedVk::Bls12_381(Box::new(pvk))
+    }
+
+    fn unwrap(cached: &CachedVk) -> Option<&PreparedVerifyingKey<Self>> {
+        match cached {
+            CachedVk::Bls12_381(pvk) => Some(pvk.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+type VkCacheKey = (Groth16Curve, UInt256);
+
+/// Bounded FIFO map keyed by the curve and the representation hash of the
+/// verifying key cell. A cell hash fixes the key bytes, so a hit always
+/// yields the same key a fresh deserialisation would.
+struct VkCache {
+    entries: HashMap<VkCacheKey, Arc<CachedVk>>,
+    insertion_order: VecDeque<VkCacheKey>,
+    capacity: usize,
+}
+
+impl VkCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::with_capacity(capacity),
+            insertion_order: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    fn get(&self, key: &VkCacheKey) -> Option<Arc<CachedVk>> {
+        self.entries.get(key).cloned()
+    }
+
+    fn insert(&mut self, key: VkCacheKey, value: Arc<CachedVk>) {
+        if self.entries.contains_key(&key) {
+            return;
+        }
+        if self.insertion_order.len() == self.capacity {
+            if let Some(evicted) = self.insertion_order.pop_front() {
+                self.entries.remove(&evicted);
+            }
+        }
+        self.insertion_order.push_back(key.clone());
+        self.entries.insert(key, value);
+    }
+}
+
+static VK_CACHE: OnceLock<Mutex<VkCache>> = OnceLock::new();
+
+fn vk_cache() -> &'static Mutex<VkCache> {
+    VK_CACHE.get_or_init(|| Mutex::new(VkCache::new(VK_CACHE_CAPACITY)))
+}
+
+/// Deserialises a compressed (and subgroup-checked) arkworks value that
+/// must span the whole buffer.
+fn deserialize_exact<T: CanonicalDeserialize>(bytes: &[u8], what: &str) -> Result<T> {
+    let mut reader = bytes;
+    let value = match T::deserialize_compressed(&mut reader) {
+        Ok(value) => value,
+        Err(err) => {
+            return err!(ExceptionCode::FatalError, "VERGRTH16VK: incorrect {}: {}", what, err);
+        }
+    };
+    if !reader.is_empty() {
+        return err!(
+            ExceptionCode::FatalError,
+            "VERGRTH16VK: {} has {} trailing bytes",
+            what,
+            reader.len()
+        );
+    }
+    Ok(value)
+}
+
+/// Decodes `N × 32` little-endian scalars, rejecting values `≥ modulus`.
+fn decode_public_inputs<E: Pairing>(bytes: &[u8]) -> Result<Vec<E::ScalarField>> {
+    bytes
+        .chunks_exact(PUBLIC_INPUT_BYTES)
+        .map(|chunk| deserialize_exact(chunk, "public input"))
+        .collect()
+}
+
+/// Returns the prepared key cached under `key`, deserialising and caching
+/// `vk_bytes` on a miss. The lock is released before verification runs.
+fn get_or_insert_vk<E: Groth16Pairing>(key: UInt256, vk_bytes: &[u8]) -> Result<Arc<CachedVk>> {
+    let key = (E::CURVE, key);
+    if let Some(hit) = vk_cache().lock().expect("VK cache mutex poisoned").get(&key) {
+        return Ok(hit);
+    }
+    let vk = deserialize_exact::<VerifyingKey<E>>(vk_bytes, "verifying key")?;
+    let entry = Arc::new(E::wrap(prepare_verifying_key(&vk)));
+    vk_cache().lock().expect("VK cache mutex poisoned").insert(key, entry.clone());
+    Ok(entry)
+}
+
+fn verify<E: Groth16Pairing>(
+    key: UInt256,
+    vk_bytes: &[u8],
+    public_inputs_bytes: &[u8],
+    proof_bytes: &[u8],
+) -> Result<bool> {
+    let public_inputs = decode_public_inputs::<E>(public_inputs_bytes)?;
+    let proof = deserialize_exact::<Proof<E>>(proof_bytes, "proof")?;
+    let cached = get_or_insert_vk::<E>(key, vk_bytes)?;
+    let Some(pvk) = E::unwrap(&cached) else {
+        return err!(ExceptionCode::FatalError, "VERGRTH16VK: cached key is for another curve");
+    };
+    if public_inputs.len() + 1 != pvk.vk.gamma_abc_g1.len() {
+        return err!(
+            ExceptionCode::FatalError,
+            "VERGRTH16VK: verifying key expects {} public inputs, got {}",
+            pvk.vk.gamma_abc_g1.len().saturating_sub(1),
+            public_inputs.len()
+        );
+    }
+    Ok(Groth16::<E>::verify_proof(pvk, &proof, &public_inputs).unwrap_or(false))
+}
+
+/// VERGRTH16VK (proof public_inputs vk curve_id - ?)
+///
+/// `vk` is either the verifying key cell itself or the 256-bit hash of such
+/// a cell, looked up in the public libraries exactly like a library
+/// reference would be. Resolution and cell loading are identical on cache
+/// hits and misses, so the gas charged does not depend on node state; the
+/// cache only saves re-deserialising and preparing the key.
+pub(crate) fn execute_vergrth16_with_vk(engine: &mut Engine) -> Status {
+    engine.check_capability(GlobalCapabilities::CapGroth16WithVk)?;
+    engine.load_instruction(crate::executor::types::Instruction::new("VERGRTH16VK"))?;
+    fetch_stack(engine, 4)?;
+
+    let curve = match engine.cmd.var(0).as_integer()?.into(0..=1)? {
+        0 => Groth16Curve::Bn254,
+        _ => Groth16Curve::Bls12_381,
+    };
+
+    let vk_hash = match engine.cmd.var(1) {
+        StackItem::Cell(_) => None,
+        StackItem::Integer(hash) => Some(hash.as_builder::<UnsignedIntegerBigEndianEncoding>(256)?),
+        _ => {
+            return err!(
+                ExceptionCode::TypeCheckError,
+                "VERGRTH16VK: verifying key must be a cell or its hash"
+            );
+        }
+    };
+    let vk_cell = match vk_hash {
+        Some(hash) => engine.find_library(SliceData::load_builder(hash)?)?,
+        None => engine.cmd.var(1).as_cell()?.clone(),
+    };
+
+    let public_inputs_slice = SliceData::load_cell_ref(engine.cmd.var(2).as_cell()?)?;
+    let public_inputs_bytes = unpack_data_from_cell(public_inputs_slice, engine)?;
+    if public_inputs_bytes.len() % PUBLIC_INPUT_BYTES != 0 {
+        return err!(
+            ExceptionCode::FatalError,
+            "VERGRTH16VK: public inputs length {} is not a multiple of {}",
+            public_inputs_bytes.len(),
+            PUBLIC_INPUT_BYTES
+        );
+    }
+    engine.try_use_gas(Gas::vergrth16_vk_price(
+        curve,
+        public_inputs_bytes.len() / PUBLIC_INPUT_BYTES,
+    ))?;
+
+    let proof_slice = SliceData::load_cell_ref(engine.cmd.var(3).as_cell()?)?;
+    let proof_bytes = unpack_data_from_cell(proof_slice, engine)?;
+
+    let key = vk_cell.repr_hash();
+    let vk_bytes = unpack_data_from_cell(SliceData::load_cell(vk_cell)?, engine)?;
+    engine.try_use_gas(Gas::vergrth16_vk_key_price(vk_bytes.len()))?;
+
+    let result = match curve {
+        Groth16Curve::Bn254 => verify::<Bn254>(key, &vk_bytes, &public_inputs_bytes, &proof_bytes),
+        Groth16Curve::Bls12_381 => {
+            verify::<Bls12_381>(key, &vk_bytes, &public_inputs_bytes, &proof_bytes)
+        }
+    }?;
+    engine.cc.stack.push(boolean!(result));
+    Ok(())
+}
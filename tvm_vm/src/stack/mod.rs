@@ -657,7 +657,6 @@ impl StackItem {
         }
     }
 
-    #[allow(dead_code)] // reserved for future use
     pub(crate) fn serialize(&self, gas_consumer: &mut dyn GasConsumer) -> Result<BuilderData> {
         let items = vec![SerializeItem::Item(self)];
         items_serialize(items, gas_consumer)
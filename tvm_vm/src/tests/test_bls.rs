@@ -0,0 +1,242 @@
+use blst::min_pk::AggregateSignature;
+use blst::min_pk::Signature;
+use tvm_block::GlobalCapabilities;
+use tvm_types::ExceptionCode;
+use tvm_types::SliceData;
+
+use crate::executor::test_helper::*;
+use crate::stack::StackItem;
+use crate::stack::integer::IntegerData;
+
+const CAPABILITIES: u64 = GlobalCapabilities::CapBls as u64;
+
+const BLS_ORDER: &str = "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001";
+
+fn bytes(item: &StackItem) -> Vec<u8> {
+    item.as_slice().unwrap().get_bytestring(0)
+}
+
+fn execute_point(opcode: u8, items: Vec<StackItem>) -> Vec<u8> {
+    let result = execute_ok(CAPABILITIES, &[0xF9, 0x30, opcode], items);
+    assert_eq!(result.len(), 1);
+    bytes(&result[0])
+}
+
+fn key_pair(seed: u8) -> ([u8; 48], [u8; 32]) {
+    tvm_types::bls::gen_bls_key_pair_based_on_key_material(&[seed; 32]).unwrap()
+}
+
+fn g1() -> Vec<u8> {
+    let mut bytes = vec![0; 48];
+    unsafe { blst::blst_p1_compress(bytes.as_mut_ptr(), blst::blst_p1_generator()) };
+    bytes
+}
+
+fn g2() -> Vec<u8> {
+    let mut bytes = vec![0; 96];
+    unsafe { blst::blst_p2_compress(bytes.as_mut_ptr(), blst::blst_p2_generator()) };
+    bytes
+}
+
+#[test]
+fn test_bls_verify() {
+    let (public_key, secret_key) = key_pair(1);
+    let signature = tvm_types::bls::sign(&secret_key, b"block").unwrap();
+    let result = execute_ok(
+        CAPABILITIES,
+        &[0xF9, 0x30, 0x00],
+        vec![slice(&public_key), slice(b"block"), slice(&signature)],
+    );
+    assert_eq!(result, vec![int!(-1)]);
+
+    let result = execute_ok(
+        CAPABILITIES,
+        &[0xF9, 0x30, 0x00],
+        vec![slice(&public_key), slice(b"other"), slice(&signature)],
+    );
+    assert_eq!(result, vec![int!(0)]);
+
+    let result = execute_ok(
+        CAPABILITIES,
+        &[0xF9, 0x30, 0x00],
+        vec![slice(&[0xFF; 48]), slice(b"block"), slice(&signature)],
+    );
+    assert_eq!(result, vec![int!(0)]);
+}
+
+#[test]
+fn test_bls_aggregate() {
+    let keys = [key_pair(1), key_pair(2), key_pair(3)];
+    let signatures = keys
+        .iter()
+        .map(|(_, secret_key)| tvm_types::bls::sign(secret_key, b"block").unwrap())
+        .collect::<Vec<_>>();
+
+    let mut items = signatures.iter().map(|signature| slice(signature)).collect::<Vec<_>>();
+    items.push(int!(3));
+    let aggregated = execute_point(0x01, items);
+    let expected = signatures
+        .iter()
+        .map(|signature| Signature::from_bytes(signature).unwrap())
+        .collect::<Vec<_>>();
+    let expected = AggregateSignature::aggregate(&expected.iter().collect::<Vec<_>>(), true)
+        .unwrap()
+        .to_signature()
+        .compress();
+    assert_eq!(aggregated, expected);
+
+    let mut items = keys.iter().map(|(public_key, _)| slice(public_key)).collect::<Vec<_>>();
+    items.extend([int!(3), slice(b"block"), slice(&aggregated)]);
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x30, 0x02], items), vec![int!(-1)]);
+
+    let mut items = keys[..2].iter().map(|(public_key, _)| slice(public_key)).collect::<Vec<_>>();
+    items.extend([int!(2), slice(b"block"), slice(&aggregated)]);
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x30, 0x02], items), vec![int!(0)]);
+
+    let items = vec![int!(0), slice(b"block"), slice(&aggregated)];
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x30, 0x02], items), vec![int!(0)]);
+
+    assert_eq!(
+        execute_err(CAPABILITIES, &[0xF9, 0x30, 0x01], vec![int!(0)]),
+        Some(ExceptionCode::RangeCheckError)
+    );
+}
+
+#[test]
+fn test_bls_aggregate_verify() {
+    let (public_key1, secret_key1) = key_pair(1);
+    let (public_key2, secret_key2) = key_pair(2);
+    let signature1 = tvm_types::bls::sign(&secret_key1, b"first").unwrap();
+    let signature2 = tvm_types::bls::sign(&secret_key2, b"second").unwrap();
+    let aggregated = execute_point(0x01, vec![slice(&signature1), slice(&signature2), int!(2)]);
+
+    let items = vec![
+        slice(&public_key1),
+        slice(b"first"),
+        slice(&public_key2),
+        slice(b"second"),
+        int!(2),
+        slice(&aggregated),
+    ];
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x30, 0x03], items), vec![int!(-1)]);
+
+    let items = vec![
+        slice(&public_key2),
+        slice(b"first"),
+        slice(&public_key1),
+        slice(b"second"),
+        int!(2),
+        slice(&aggregated),
+    ];
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x30, 0x03], items), vec![int!(0)]);
+}
+
+#[test]
+fn test_bls_g1_arithmetic() {
+    let g = g1();
+    let double = execute_point(0x10, vec![slice(&g), slice(&g)]);
+    assert_eq!(execute_point(0x13, vec![slice(&g), int!(2)]), double);
+    assert_eq!(execute_point(0x11, vec![slice(&double), slice(&g)]), g);
+
+    let zero = execute_point(0x15, vec![]);
+    assert_eq!(zero[0], 0xC0);
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x30, 0x18], vec![slice(&zero)]), vec![int!(-1)]);
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x30, 0x18], vec![slice(&g)]), vec![int!(0)]);
+    assert_eq!(execute_point(0x11, vec![slice(&g), slice(&g)]), zero);
+
+    let neg = execute_point(0x12, vec![slice(&g)]);
+    assert_eq!(execute_point(0x13, vec![slice(&g), int!(-1)]), neg);
+    let order = IntegerData::from_unsigned_bytes_be(hex::decode(BLS_ORDER).unwrap());
+    assert_eq!(execute_point(0x13, vec![slice(&g), StackItem::integer(order)]), zero);
+
+    let five = execute_point(0x13, vec![slice(&g), int!(5)]);
+    let items = vec![slice(&g), int!(2), slice(&g), int!(3), int!(2)];
+    assert_eq!(execute_point(0x14, items), five);
+    assert_eq!(execute_point(0x14, vec![int!(0)]), zero);
+
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x30, 0x17], vec![slice(&g)]), vec![int!(-1)]);
+    assert_eq!(
+        execute_ok(CAPABILITIES, &[0xF9, 0x30, 0x17], vec![slice(&[0xFF; 48])]),
+        vec![int!(0)]
+    );
+
+    let mut one = [0; 48];
+    one[47] = 1;
+    let mapped = execute_point(0x16, vec![slice(&one)]);
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x30, 0x17], vec![slice(&mapped)]), vec![int!(-1)]);
+}
+
+#[test]
+fn test_bls_g2_arithmetic() {
+    let g = g2();
+    let double = execute_point(0x20, vec![slice(&g), slice(&g)]);
+    assert_eq!(execute_point(0x23, vec![slice(&g), int!(2)]), double);
+    assert_eq!(execute_point(0x21, vec![slice(&double), slice(&g)]), g);
+
+    let zero = execute_point(0x25, vec![]);
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x30, 0x28], vec![slice(&zero)]), vec![int!(-1)]);
+    let neg = execute_point(0x22, vec![slice(&g)]);
+    assert_eq!(execute_point(0x20, vec![slice(&g), slice(&neg)]), zero);
+
+    let items = vec![slice(&g), int!(4), slice(&double), int!(-2), int!(2)];
+    assert_eq!(execute_point(0x24, items), zero);
+
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x30, 0x27], vec![slice(&g)]), vec![int!(-1)]);
+    let mut element = [0; 96];
+    element[47] = 1;
+    let mapped = execute_point(0x26, vec![slice(&element)]);
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x30, 0x27], vec![slice(&mapped)]), vec![int!(-1)]);
+}
+
+#[test]
+fn test_bls_pairing() {
+    let g1 = g1();
+    let g2 = g2();
+    let g1_a = execute_point(0x13, vec![slice(&g1), int!(7)]);
+    let g2_a = execute_point(0x23, vec![slice(&g2), int!(7)]);
+    let g1_neg = execute_point(0x12, vec![slice(&g1)]);
+
+    let items = vec![slice(&g1_a), slice(&g2), slice(&g1_neg), slice(&g2_a), int!(2)];
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x30, 0x30], items), vec![int!(-1)]);
+    let items = vec![slice(&g1_a), slice(&g2), slice(&g1), slice(&g2_a), int!(2)];
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x30, 0x30], items), vec![int!(0)]);
+    assert_eq!(
+        execute_ok(CAPABILITIES, &[0xF9, 0x30, 0x30], vec![slice(&g1), slice(&g2), int!(1)]),
+        vec![int!(0)]
+    );
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x30, 0x30], vec![int!(0)]), vec![int!(-1)]);
+}
+
+#[test]
+fn test_bls_errors() {
+    assert_eq!(execute_err(0, &[0xF9, 0x30, 0x31], vec![]), Some(ExceptionCode::InvalidOpcode));
+    assert_eq!(execute_err(0, &[0xF9, 0x30, 0x15], vec![]), Some(ExceptionCode::InvalidOpcode));
+
+    let order = IntegerData::from_unsigned_bytes_be(hex::decode(BLS_ORDER).unwrap());
+    assert_eq!(
+        execute_ok(CAPABILITIES, &[0xF9, 0x30, 0x31], vec![]),
+        vec![StackItem::integer(order)]
+    );
+
+    assert_eq!(
+        execute_err(CAPABILITIES, &[0xF9, 0x30, 0x10], vec![slice(&g1()), slice(&[0xFF; 48])]),
+        Some(ExceptionCode::RangeCheckError)
+    );
+    assert_eq!(
+        execute_err(CAPABILITIES, &[0xF9, 0x30, 0x12], vec![slice(&[0xC0; 47])]),
+        Some(ExceptionCode::CellUnderflow)
+    );
+    assert_eq!(
+        execute_err(CAPABILITIES, &[0xF9, 0x30, 0x16], vec![slice(&[0xFF; 48])]),
+        Some(ExceptionCode::RangeCheckError)
+    );
+    let message = StackItem::Slice(SliceData::new(vec![0x68]));
+    assert_eq!(
+        execute_err(
+            CAPABILITIES,
+            &[0xF9, 0x30, 0x00],
+            vec![slice(&[0; 48]), message, slice(&[0; 96])]
+        ),
+        Some(ExceptionCode::CellUnderflow)
+    );
+}
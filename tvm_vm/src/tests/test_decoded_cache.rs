@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use tvm_types::Cell;
+use tvm_types::ExceptionCode;
+use tvm_types::SliceData;
+
+use crate::error::tvm_exception_code;
+use crate::executor::engine::DecodedCodeCache;
+use crate::executor::engine::Engine;
+use crate::executor::test_helper::cell;
+use crate::stack::StackItem;
+use crate::stack::integer::IntegerData;
+
+// PUSHINT 1; PUSHINT 3; PUSHCONT { DUP; ADD; PUSHINT 256; ADD; PUSHINT 2; DIV
+// }; REPEAT; PUSHSLICE x{ABC}; PUSHREFSLICE
+const LOOP: [u8; 17] = [
+    0x71, 0x73, 0x99, 0x20, 0xA0, 0x81, 0x01, 0x00, 0xA0, 0x72, 0xA9, 0x04, 0xE4, 0x8B, 0x1A, 0xBC,
+    0x89,
+];
+
+fn loop_code() -> Cell {
+    cell(&LOOP, vec![cell(&[0x12, 0x34], vec![])])
+}
+
+#[derive(Debug, PartialEq)]
+struct Outcome {
+    result: Result<i32, Option<ExceptionCode>>,
+    stack: Vec<StackItem>,
+    gas_used: i64,
+    steps: u32,
+}
+
+fn run(code: &Cell, cache: Option<&Arc<DecodedCodeCache>>) -> Outcome {
+    let mut engine = Engine::with_capabilities(0).setup_with_libraries(
+        SliceData::load_cell_ref(code).unwrap(),
+        None,
+        None,
+        None,
+        vec![],
+    );
+    if let Some(cache) = cache {
+        engine.set_decoded_code_cache(cache.clone());
+    }
+    let result = engine.execute().map_err(|err| tvm_exception_code(&err));
+    Outcome {
+        result,
+        stack: engine.stack().storage.clone(),
+        gas_used: engine.gas_used(),
+        steps: engine.steps(),
+    }
+}
+
+#[test]
+fn test_decoded_cache_matches_fresh_decoding() {
+    let code = loop_code();
+    let expected = run(&code, None);
+    assert_eq!(expected.result, Ok(0));
+    assert_eq!(expected.stack[0], int!(385));
+
+    let cache = Arc::new(DecodedCodeCache::new());
+    assert_eq!(run(&code, Some(&cache)), expected);
+    assert_eq!(cache.len(), 1);
+    // second run replays decoded handlers and operands only
+    assert_eq!(run(&code, Some(&cache)), expected);
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn test_decoded_cache_shared_between_threads() {
+    let code = loop_code();
+    let expected = run(&code, None);
+    let cache = Arc::new(DecodedCodeCache::new());
+    let threads = (0..4)
+        .map(|_| {
+            let code = code.clone();
+            let cache = cache.clone();
+            std::thread::spawn(move || run(&code, Some(&cache)))
+        })
+        .collect::<Vec<_>>();
+    for thread in threads {
+        assert_eq!(thread.join().unwrap(), expected);
+    }
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn test_decoded_cache_eviction() {
+    // PUSHREFCONT; EXECUTE; INC with the continuation `PUSHINT 7` in a reference
+    let code = cell(&[0x8A, 0xD8, 0xA4], vec![cell(&[0x77], vec![])]);
+    let expected = run(&code, None);
+    assert_eq!(expected.stack, vec![int!(8)]);
+
+    let cache = Arc::new(DecodedCodeCache::with_capacity(1));
+    assert_eq!(run(&code, Some(&cache)), expected);
+    assert_eq!(run(&code, Some(&cache)), expected);
+    assert_eq!(cache.len(), 1);
+    cache.clear();
+    assert!(cache.is_empty());
+
+    let cache = Arc::new(DecodedCodeCache::with_capacity(0));
+    assert_eq!(run(&code, Some(&cache)), expected);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_decoded_cache_failed_decoding() {
+    // PUSHINT 1; PUSHINT with a truncated 16-bit immediate
+    let code = cell(&[0x71, 0x81, 0x01], vec![]);
+    let expected = run(&code, None);
+    assert_eq!(expected.result, Err(Some(ExceptionCode::InvalidOpcode)));
+
+    let cache = Arc::new(DecodedCodeCache::new());
+    assert_eq!(run(&code, Some(&cache)), expected);
+    assert_eq!(run(&code, Some(&cache)), expected);
+}
@@ -0,0 +1,116 @@
+use k256::ecdsa::signature::Signer;
+use k256::ecdsa::signature::hazmat::PrehashSigner;
+use tvm_block::GlobalCapabilities;
+use tvm_types::ExceptionCode;
+
+use crate::executor::test_helper::*;
+use crate::stack::StackItem;
+use crate::stack::integer::IntegerData;
+
+const CAPABILITIES: u64 = GlobalCapabilities::CapEcdsa as u64;
+
+const HASH: [u8; 32] = [0x5A; 32];
+
+fn uint(data: &[u8]) -> StackItem {
+    StackItem::integer(IntegerData::from_unsigned_bytes_be(data))
+}
+
+fn secp256k1_key() -> k256::ecdsa::SigningKey {
+    k256::ecdsa::SigningKey::from_slice(&[0x11; 32]).unwrap()
+}
+
+fn p256_key() -> p256::ecdsa::SigningKey {
+    p256::ecdsa::SigningKey::from_slice(&[0x22; 32]).unwrap()
+}
+
+#[test]
+fn test_secp256k1_chksign() {
+    let key = secp256k1_key();
+    let pub_key = key.verifying_key().to_encoded_point(true);
+    let signature: k256::ecdsa::Signature = key.sign_prehash(&HASH).unwrap();
+    let items = vec![uint(&HASH), slice(&signature.to_bytes()), slice(pub_key.as_bytes())];
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x16], items), vec![int!(-1)]);
+
+    let items = vec![uint(&[0x5B; 32]), slice(&signature.to_bytes()), slice(pub_key.as_bytes())];
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x16], items), vec![int!(0)]);
+
+    let signature: k256::ecdsa::Signature = key.sign(b"hello");
+    let items = vec![slice(b"hello"), slice(&signature.to_bytes()), slice(pub_key.as_bytes())];
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x17], items), vec![int!(-1)]);
+
+    // invalid point encoding is not an error
+    let items = vec![slice(b"hello"), slice(&signature.to_bytes()), slice(&[0x05; 33])];
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x17], items), vec![int!(0)]);
+}
+
+#[test]
+fn test_p256_chksign() {
+    use p256::ecdsa::signature::Signer;
+    use p256::ecdsa::signature::hazmat::PrehashSigner;
+
+    let key = p256_key();
+    let pub_key = key.verifying_key().to_encoded_point(true);
+    let signature: p256::ecdsa::Signature = key.sign_prehash(&HASH).unwrap();
+    let items = vec![uint(&HASH), slice(&signature.to_bytes()), slice(pub_key.as_bytes())];
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x14], items), vec![int!(-1)]);
+
+    let signature: p256::ecdsa::Signature = key.sign(b"passkey");
+    let items = vec![slice(b"passkey"), slice(&signature.to_bytes()), slice(pub_key.as_bytes())];
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x15], items), vec![int!(-1)]);
+
+    // secp256k1 key does not verify a P-256 signature
+    let other = secp256k1_key().verifying_key().to_encoded_point(true);
+    let items = vec![slice(b"passkey"), slice(&signature.to_bytes()), slice(other.as_bytes())];
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x15], items), vec![int!(0)]);
+}
+
+#[test]
+fn test_ecrecover() {
+    let key = secp256k1_key();
+    let (signature, recovery_id) = key.sign_prehash_recoverable(&HASH).unwrap();
+    let (r, s) = signature.split_bytes();
+    let items = vec![uint(&HASH), int!(recovery_id.to_byte()), uint(&r), uint(&s)];
+    let pub_key = key.verifying_key().to_encoded_point(false);
+    let pub_key = pub_key.as_bytes();
+    assert_eq!(
+        execute_ok(CAPABILITIES, &[0xF9, 0x12], items),
+        vec![int!(4), uint(&pub_key[1..33]), uint(&pub_key[33..65]), int!(-1)]
+    );
+
+    let items = vec![uint(&HASH), int!(4), uint(&r), uint(&s)];
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x12], items), vec![int!(0)]);
+    let items = vec![uint(&HASH), int!(recovery_id.to_byte()), int!(0), uint(&s)];
+    assert_eq!(execute_ok(CAPABILITIES, &[0xF9, 0x12], items), vec![int!(0)]);
+}
+
+#[test]
+fn test_ecdsa_errors() {
+    let items = || vec![uint(&HASH), slice(&[0; 64]), slice(&[0x02; 33])];
+    assert_eq!(execute_err(0, &[0xF9, 0x14], items()), Some(ExceptionCode::InvalidOpcode));
+    assert_eq!(execute_err(0, &[0xF9, 0x16], items()), Some(ExceptionCode::InvalidOpcode));
+    assert_eq!(
+        execute_err(0, &[0xF9, 0x12], vec![uint(&HASH), int!(0), int!(1), int!(1)]),
+        Some(ExceptionCode::InvalidOpcode)
+    );
+
+    assert_eq!(
+        execute_err(
+            CAPABILITIES,
+            &[0xF9, 0x14],
+            vec![uint(&HASH), slice(&[0; 63]), slice(&[0x02; 33])]
+        ),
+        Some(ExceptionCode::CellUnderflow)
+    );
+    assert_eq!(
+        execute_err(
+            CAPABILITIES,
+            &[0xF9, 0x16],
+            vec![uint(&HASH), slice(&[0; 64]), slice(&[0x02; 32])]
+        ),
+        Some(ExceptionCode::CellUnderflow)
+    );
+    assert_eq!(
+        execute_err(CAPABILITIES, &[0xF9, 0x12], vec![uint(&HASH), int!(256), int!(1), int!(1)]),
+        Some(ExceptionCode::RangeCheckError)
+    );
+}
@@ -0,0 +1,147 @@
+use tvm_block::GlobalCapabilities;
+use tvm_types::BuilderData;
+use tvm_types::Cell;
+use tvm_types::ExceptionCode;
+use tvm_types::HashmapE;
+use tvm_types::SliceData;
+use tvm_types::read_single_root_boc;
+use tvm_types::write_boc;
+
+use crate::error::tvm_exception_code;
+use crate::executor::engine::Engine;
+use crate::executor::gas::gas_state::Gas;
+use crate::executor::test_helper::cell;
+use crate::stack::StackItem;
+use crate::stack::integer::IntegerData;
+
+// PUSHINT 0; PUSHREFCONT { PUSHINT 5; PUSHREFCONT { INC; DUP; NEWC; STU 32;
+// ENDC; DUP; POP c4; CTOS; LDU 32; ENDS; DROP; PUSH c4; CTOS; DROP; COMMIT };
+// REPEAT; THROW 42 }; PUSHCONT { INC }; TRY
+fn code() -> Cell {
+    let body = cell(
+        &[
+            0xA4, 0x20, 0xC8, 0xCB, 0x1F, 0xC9, 0x20, 0xED, 0x54, 0xD0, 0xD3, 0x1F, 0xD1, 0x30,
+            0xED, 0x44, 0xD0, 0x30, 0xF8, 0x0F,
+        ],
+        vec![],
+    );
+    let try_body = cell(&[0x75, 0x8A, 0xE4, 0xF2, 0x2A], vec![body]);
+    cell(&[0x70, 0x8A, 0x91, 0xA4, 0xF2, 0xFF], vec![try_body])
+}
+
+fn library() -> HashmapE {
+    let mut library = HashmapE::with_bit_len(256);
+    let lib = cell(&[0x71], vec![]);
+    let key = SliceData::from_raw(lib.repr_hash().as_slice().to_vec(), 256);
+    library
+        .set_builder(key, &BuilderData::with_raw_and_refs(vec![], 0, vec![lib]).unwrap())
+        .unwrap();
+    library
+}
+
+fn engine(capabilities: u64) -> Engine {
+    Engine::with_capabilities(capabilities).setup_with_libraries(
+        SliceData::load_cell(code()).unwrap(),
+        None,
+        None,
+        None,
+        vec![library()],
+    )
+}
+
+#[derive(Debug, PartialEq)]
+struct Outcome {
+    result: Result<i32, Option<ExceptionCode>>,
+    stack: Vec<StackItem>,
+    gas_used: i64,
+    steps: u32,
+    root: StackItem,
+    actions: StackItem,
+    committed: bool,
+}
+
+fn outcome(engine: &Engine, result: tvm_types::Result<i32>) -> Outcome {
+    let cstate = engine.get_committed_state();
+    Outcome {
+        result: result.map_err(|err| tvm_exception_code(&err)),
+        stack: engine.stack().storage.clone(),
+        gas_used: engine.gas_used(),
+        steps: engine.steps(),
+        root: cstate.get_root().clone(),
+        actions: cstate.get_actions().clone(),
+        committed: cstate.is_committed(),
+    }
+}
+
+/// Saves the state as a BOC and loads it into a new engine
+fn resume(engine: &Engine, capabilities: u64) -> Engine {
+    let boc = write_boc(&engine.save_state().unwrap()).unwrap();
+    let mut resumed = Engine::with_capabilities(capabilities);
+    resumed.restore_state(read_single_root_boc(boc).unwrap()).unwrap();
+    assert_eq!(resumed.libraries, engine.libraries);
+    resumed
+}
+
+#[test]
+fn test_engine_state_resume_at_every_step() {
+    for capabilities in [0, GlobalCapabilities::CapsTvmBugfixes2022 as u64] {
+        let mut expected = engine(capabilities);
+        let result = expected.execute();
+        let expected = outcome(&expected, result);
+        assert_eq!(expected.result, Ok(0));
+        assert_eq!(expected.stack, vec![int!(0), int!(43)]);
+
+        for steps in 0..=expected.steps {
+            let mut engine = engine(capabilities);
+            if let Some(result) = engine.execute_steps(steps).unwrap() {
+                assert_eq!(outcome(&engine, Ok(result)), expected);
+                continue;
+            }
+            assert!(engine.steps() >= steps);
+            let mut resumed = resume(&engine, capabilities);
+            let result = resumed.execute();
+            assert_eq!(outcome(&resumed, result), expected, "suspended at step {}", steps);
+        }
+    }
+}
+
+#[test]
+fn test_engine_state_checkpoints() {
+    let mut expected = engine(0);
+    let result = expected.execute();
+    let expected = outcome(&expected, result);
+
+    let mut engine = engine(0);
+    let result = loop {
+        if let Some(result) = engine.execute_steps(3).unwrap() {
+            break result;
+        }
+        engine = resume(&engine, 0);
+    };
+    assert_eq!(outcome(&engine, Ok(result)), expected);
+}
+
+#[test]
+fn test_engine_state_rejects_wrong_state() {
+    let mut engine = engine(0);
+    engine.execute_steps(10).unwrap();
+    let state = engine.save_state().unwrap();
+
+    let mut other = Engine::with_capabilities(GlobalCapabilities::CapsTvmBugfixes2022 as u64);
+    assert!(other.restore_state(state.clone()).is_err());
+    assert!(other.restore_state(code()).is_err());
+    assert_eq!(other.steps(), 0);
+
+    let mut resumed = Engine::with_capabilities(0);
+    resumed.restore_state(state).unwrap();
+    assert_eq!(resumed.steps(), 10);
+}
+
+#[test]
+fn test_engine_state_keeps_negative_gas() {
+    let gas = Gas::new(-5, -7, 1000, 10);
+    let mut builder = BuilderData::new();
+    gas.serialize(&mut builder).unwrap();
+    let mut slice = SliceData::load_builder(builder).unwrap();
+    assert_eq!(Gas::deserialize(&mut slice).unwrap(), gas);
+}
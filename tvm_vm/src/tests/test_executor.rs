@@ -18,6 +18,8 @@ use rand::RngCore;
 use rand::thread_rng;
 use tvm_abi::TokenValue;
 use tvm_abi::contract::ABI_VERSION_2_4;
+#[cfg(feature = "wasmtime")]
+use tvm_block::GlobalCapabilities;
 use tvm_types::BuilderData;
 use tvm_types::Cell;
 use tvm_types::ExceptionCode;
@@ -25,6 +27,8 @@ use tvm_types::IBitstring;
 use tvm_types::SliceData;
 
 use crate::error::TvmError;
+#[cfg(feature = "wasmtime")]
+use crate::error::tvm_exception_code;
 use crate::executor::deserialization::execute_schkrefs;
 use crate::executor::engine::Engine;
 use crate::executor::math::DivMode;
@@ -36,6 +40,12 @@ use crate::executor::token::execute_run_wasm;
 use crate::executor::token::execute_run_wasm_concat_multiarg;
 use crate::executor::types::Instruction;
 use crate::executor::types::InstructionOptions;
+#[cfg(feature = "wasmtime")]
+use crate::executor::wasm::validate_wasm_component;
+#[cfg(feature = "wasmtime")]
+use crate::executor::wasm::wasm_monotonic_time;
+#[cfg(feature = "wasmtime")]
+use crate::executor::wasm::wasm_random_source;
 use crate::stack::Stack;
 use crate::stack::StackItem;
 use crate::stack::integer::IntegerData;
@@ -1461,6 +1471,76 @@ fn test_run_wasm_fuel_error_from_hash() {
     assert_eq!(format!("{}", res_error), "VM Exception: 0 1");
 }
 
+#[cfg(feature = "wasmtime")]
+#[test]
+fn test_validate_wasm_component() {
+    let wasm_engine = Engine::extern_wasm_engine_init().unwrap();
+    let binary = std::fs::read("./src/tests/determinism-clocks.wasm").unwrap();
+    let imports = validate_wasm_component(&wasm_engine, &binary).unwrap();
+    assert!(imports.iter().any(|import| import == "wasi:clocks/wall-clock@0.2.3"));
+
+    let sockets = r#"(component (import "wasi:sockets/tcp@0.2.3" (instance)))"#;
+    let err = validate_wasm_component(&wasm_engine, sockets.as_bytes()).unwrap_err();
+    assert_eq!(tvm_exception_code(&err), Some(ExceptionCode::WasmForbiddenBinary));
+
+    let threads = "(component (core module (memory 1 1 shared)))";
+    let err = validate_wasm_component(&wasm_engine, threads.as_bytes()).unwrap_err();
+    assert_eq!(tvm_exception_code(&err), Some(ExceptionCode::WasmLoadFail));
+
+    let relaxed_simd = "(component (core module (func (param v128) (result v128) \
+                        local.get 0 i32x4.relaxed_trunc_f32x4_s)))";
+    let err = validate_wasm_component(&wasm_engine, relaxed_simd.as_bytes()).unwrap_err();
+    assert_eq!(tvm_exception_code(&err), Some(ExceptionCode::WasmLoadFail));
+}
+
+#[cfg(feature = "wasmtime")]
+fn wasm_env_engine(capabilities: u64, rand_seed: Option<i64>) -> Engine {
+    let mut ctrls = SaveList::default();
+    if let Some(rand_seed) = rand_seed {
+        let mut params = vec![StackItem::int(0); 6];
+        params.push(StackItem::int(rand_seed));
+        ctrls.put(7, &mut StackItem::tuple(vec![StackItem::tuple(params)])).unwrap();
+    }
+    let mut engine = Engine::with_capabilities(capabilities).setup_with_libraries(
+        SliceData::new(vec![0x80]),
+        Some(ctrls),
+        None,
+        None,
+        vec![],
+    );
+    engine.set_wasm_block_time(1633458077);
+    engine
+}
+
+#[cfg(feature = "wasmtime")]
+fn wasm_env_random(engine: &Engine) -> [u64; 2] {
+    use rand_chacha::rand_core::RngCore;
+    let mut random_source = wasm_random_source(engine);
+    [random_source.next_u64(), random_source.next_u64()]
+}
+
+#[cfg(feature = "wasmtime")]
+#[test]
+fn test_wasm_env_without_determinism_capability() {
+    // seeded from the block time whatever RANDSEED is
+    let engine = wasm_env_engine(0, Some(0x12345678));
+    assert_eq!(wasm_env_random(&engine), [0x1612ea005fbec312, 0x3b9b0bcb07441ec0]);
+    assert_eq!(wasm_monotonic_time(&engine), 1633458077);
+}
+
+#[cfg(feature = "wasmtime")]
+#[test]
+fn test_wasm_env_with_determinism_capability() {
+    let capabilities = GlobalCapabilities::CapWasmDeterminism as u64;
+    let engine = wasm_env_engine(capabilities, Some(0x12345678));
+    assert_eq!(wasm_env_random(&engine), [0x9f655e78618778b2, 0x055233f37315b309]);
+    assert_eq!(wasm_monotonic_time(&engine), 1633458077000000000);
+
+    // without RANDSEED in c7 the block time is the seed
+    let engine = wasm_env_engine(capabilities, None);
+    assert_eq!(wasm_env_random(&engine), [0x1612ea005fbec312, 0x3b9b0bcb07441ec0]);
+}
+
 #[test]
 fn test_bocdepth() {
     // let mut cell = BuilderData::new();
@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use tvm_types::ExceptionCode;
+use tvm_types::SliceData;
+
+use crate::error::tvm_exception_code;
+use crate::executor::engine::Engine;
+use crate::executor::extension::CodePageExtension;
+use crate::stack::Stack;
+use crate::stack::StackItem;
+use crate::stack::integer::IntegerData;
+use crate::stack::integer::behavior::Signaling;
+use crate::types::Status;
+
+const TRIPLE: [u8; 2] = [0xF9, 0xFF];
+const SETCP1: [u8; 2] = [0xFF, 0x01];
+const SETCP0: [u8; 2] = [0xFF, 0x00];
+
+fn triple(engine: &mut Engine) -> Status {
+    let x = engine.stack_mut().drop(0)?;
+    let x = x.as_integer()?.mul::<Signaling>(&IntegerData::from_i32(3))?;
+    engine.stack_mut().push(StackItem::int(x));
+    Ok(())
+}
+
+fn extension(gas: i64) -> Arc<CodePageExtension> {
+    let mut extension = CodePageExtension::new();
+    extension.register(&TRIPLE, "TRIPLE", move |_| gas, triple).unwrap();
+    extension
+        .register(
+            &[0xF9, 0xFE, 0x00],
+            "PUSHC4",
+            |_| 0,
+            |engine| {
+                let root = engine.ctrl(4)?.clone();
+                engine.stack_mut().push(root);
+                Ok(())
+            },
+        )
+        .unwrap();
+    Arc::new(extension)
+}
+
+fn execute(code: &[&[u8]], gas: i64) -> tvm_types::Result<Engine> {
+    let mut code = code.concat();
+    code.push(0x80);
+    let mut stack = Stack::new();
+    stack.push(int!(7));
+    let mut engine = Engine::with_capabilities(0).setup_with_libraries(
+        SliceData::new(code),
+        None,
+        Some(stack),
+        None,
+        vec![],
+    );
+    engine.set_code_page_extension(1, extension(gas))?;
+    engine.execute()?;
+    Ok(engine)
+}
+
+fn execute_err(code: &[&[u8]]) -> Option<ExceptionCode> {
+    match execute(code, 0) {
+        Ok(_) => None,
+        Err(err) => tvm_exception_code(&err),
+    }
+}
+
+#[test]
+fn test_extension_code_page() {
+    let engine = execute(&[&SETCP1, &TRIPLE], 0).unwrap();
+    assert_eq!(engine.stack().storage, vec![int!(21)]);
+
+    // code page 0 instructions keep working in the extension page
+    let engine = execute(&[&SETCP1, &[0x72], &TRIPLE, &[0xA0], &SETCP0], 0).unwrap();
+    assert_eq!(engine.stack().storage, vec![int!(13)]);
+
+    let engine = execute(&[&SETCP1, &[0xF9, 0xFE, 0x00]], 0).unwrap();
+    assert_eq!(engine.stack().storage.len(), 2);
+    assert!(engine.stack().storage[1].as_cell().is_ok());
+}
+
+#[test]
+fn test_extension_not_selected() {
+    assert_eq!(execute_err(&[&TRIPLE]), Some(ExceptionCode::InvalidOpcode));
+    assert_eq!(execute_err(&[&SETCP1, &SETCP0, &TRIPLE]), Some(ExceptionCode::InvalidOpcode));
+}
+
+#[test]
+fn test_extension_gas() {
+    let cheap = execute(&[&SETCP1, &TRIPLE], 0).unwrap().gas_used();
+    let expensive = execute(&[&SETCP1, &TRIPLE], 100).unwrap().gas_used();
+    assert_eq!(expensive - cheap, 100);
+}
+
+#[test]
+fn test_extension_register_errors() {
+    let mut extension = CodePageExtension::new();
+    extension.register(&TRIPLE, "TRIPLE", |_| 0, triple).unwrap();
+    assert!(extension.register(&[0xF9], "SHORT", |_| 0, triple).is_err());
+    assert!(extension.register(&[0xF9, 0xFF, 0x01], "LONG", |_| 0, triple).is_err());
+    assert!(extension.register(&[], "EMPTY", |_| 0, triple).is_err());
+    assert_eq!(extension.instructions().collect::<Vec<_>>(), vec![(&TRIPLE[..], "TRIPLE")]);
+
+    let mut engine = Engine::with_capabilities(0);
+    assert!(engine.set_code_page_extension(0, Arc::new(extension)).is_err());
+}
@@ -0,0 +1,161 @@
+use sha3::Digest;
+use tvm_block::GlobalCapabilities;
+use tvm_types::BuilderData;
+use tvm_types::ExceptionCode;
+
+use crate::error::tvm_exception_code;
+use crate::executor::test_helper::*;
+use crate::stack::StackItem;
+use crate::stack::integer::IntegerData;
+
+const SHA256_ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+const SHA512_ABC: &str = "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+                          2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f";
+const BLAKE2B_ABC: &str = "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1\
+                           7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923";
+const CAPABILITIES: u64 = GlobalCapabilities::CapHashExt as u64;
+
+const KECCAK256_EMPTY: &str = "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470";
+
+fn builder(data: &[u8], bits: usize) -> StackItem {
+    StackItem::builder(BuilderData::with_raw(data.to_vec(), bits).unwrap())
+}
+
+fn uint(hex: &str) -> StackItem {
+    StackItem::integer(IntegerData::from_unsigned_bytes_be(hex::decode(hex).unwrap()))
+}
+
+#[test]
+fn test_hashext_concatenates_slices_and_builders() {
+    let result = execute_ok(
+        CAPABILITIES,
+        &[0xF9, 0x04, 0],
+        vec![slice(b"a"), builder(b"b", 8), slice(b"c"), int!(3)],
+    );
+    assert_eq!(result, vec![uint(SHA256_ABC)]);
+
+    // reverse order starts from the top of the stack
+    let result = execute_ok(
+        CAPABILITIES,
+        &[0xF9, 0x05, 0],
+        vec![slice(b"c"), slice(b"b"), slice(b"a"), int!(3)],
+    );
+    assert_eq!(result, vec![uint(SHA256_ABC)]);
+
+    // only the total bit length has to be divisible by eight
+    let result = execute_ok(
+        CAPABILITIES,
+        &[0xF9, 0x04, 0],
+        vec![builder(&[0x60], 4), builder(&[0x10], 4), int!(2)],
+    );
+    assert_eq!(result, execute_ok(CAPABILITIES, &[0xF9, 0x04, 0], vec![slice(b"a"), int!(1)]));
+    let result = execute_ok(
+        CAPABILITIES,
+        &[0xF9, 0x04, 0],
+        vec![builder(&[0x60], 3), builder(&[0x0B, 0x10], 13), slice(b"c"), int!(3)],
+    );
+    assert_eq!(result, vec![uint(SHA256_ABC)]);
+}
+
+#[test]
+fn test_hashext_algorithms() {
+    let result = execute_ok(CAPABILITIES, &[0xF9, 0x04, 1], vec![slice(b"abc"), int!(1)]);
+    assert_eq!(
+        result,
+        vec![StackItem::tuple(vec![uint(&SHA512_ABC[..64]), uint(&SHA512_ABC[64..])])]
+    );
+
+    let result =
+        execute_ok(CAPABILITIES, &[0xF9, 0x04, 2], vec![slice(b"ab"), slice(b"c"), int!(2)]);
+    assert_eq!(
+        result,
+        vec![StackItem::tuple(vec![uint(&BLAKE2B_ABC[..64]), uint(&BLAKE2B_ABC[64..])])]
+    );
+
+    let result = execute_ok(CAPABILITIES, &[0xF9, 0x04, 3], vec![int!(0)]);
+    assert_eq!(result, vec![uint(KECCAK256_EMPTY)]);
+
+    // hash id is taken from the stack
+    let keccak512 = sha3::Keccak512::digest(b"abc");
+    let result =
+        execute_ok(CAPABILITIES, &[0xF9, 0x04, 255], vec![slice(b"abc"), int!(1), int!(4)]);
+    assert_eq!(
+        result,
+        vec![StackItem::tuple(vec![
+            uint(&hex::encode(&keccak512[..32])),
+            uint(&hex::encode(&keccak512[32..])),
+        ])]
+    );
+}
+
+#[test]
+fn test_hashext_append_to_builder() {
+    let result = execute_ok(
+        CAPABILITIES,
+        &[0xF9, 0x06, 0],
+        vec![builder(&[0xFF], 8), slice(b"ab"), slice(b"c"), int!(2)],
+    );
+    let mut expected = vec![0xFF];
+    expected.extend(hex::decode(SHA256_ABC).unwrap());
+    assert_eq!(result, vec![builder(&expected, expected.len() * 8)]);
+
+    let result = execute_ok(
+        CAPABILITIES,
+        &[0xF9, 0x07, 0],
+        vec![builder(&[0xFF], 8), slice(b"c"), slice(b"ab"), int!(2)],
+    );
+    assert_eq!(result, vec![builder(&expected, expected.len() * 8)]);
+
+    let full = builder(&[0; 128], 1000);
+    assert_eq!(
+        execute_err(CAPABILITIES, &[0xF9, 0x06, 0], vec![full, slice(b"abc"), int!(1)]),
+        Some(ExceptionCode::CellOverflow)
+    );
+}
+
+#[test]
+fn test_hashext_errors() {
+    let err = execute(0, &[0xF9, 0x04, 0], vec![slice(b"abc"), int!(1)]).err();
+    assert_eq!(err.as_ref().and_then(tvm_exception_code), Some(ExceptionCode::InvalidOpcode));
+
+    assert_eq!(
+        execute_err(CAPABILITIES, &[0xF9, 0x04, 0], vec![builder(&[0x60], 3), int!(1)]),
+        Some(ExceptionCode::CellUnderflow)
+    );
+    assert_eq!(
+        execute_err(CAPABILITIES, &[0xF9, 0x04, 5], vec![int!(0)]),
+        Some(ExceptionCode::RangeCheckError)
+    );
+    assert_eq!(
+        execute_err(CAPABILITIES, &[0xF9, 0x04, 255], vec![int!(0), int!(255)]),
+        Some(ExceptionCode::RangeCheckError)
+    );
+    assert_eq!(
+        execute_err(CAPABILITIES, &[0xF9, 0x04, 0], vec![slice(b"abc"), int!(2)]),
+        Some(ExceptionCode::RangeCheckError)
+    );
+    assert_eq!(
+        execute_err(CAPABILITIES, &[0xF9, 0x04, 0], vec![int!(7), int!(1)]),
+        Some(ExceptionCode::TypeCheckError)
+    );
+    assert_eq!(
+        execute_err(CAPABILITIES, &[0xF9, 0x06, 0], vec![slice(b"a"), slice(b"abc"), int!(1)]),
+        Some(ExceptionCode::TypeCheckError)
+    );
+}
+
+#[test]
+fn test_hashext_gas_depends_on_input_length() {
+    let data = vec![0x55; 120];
+    let short =
+        execute(CAPABILITIES, &[0xF9, 0x04, 3], vec![slice(&data), int!(1)]).unwrap().gas_used();
+    let long = execute(
+        CAPABILITIES,
+        &[0xF9, 0x04, 3],
+        vec![slice(&data), slice(&data), slice(&data), int!(3)],
+    )
+    .unwrap()
+    .gas_used();
+    // 1 + 120 / 11 for one argument against 3 + 360 / 11 for three ones
+    assert_eq!(long - short, 24);
+}
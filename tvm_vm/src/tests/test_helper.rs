@@ -3,21 +3,82 @@
 use std::collections::HashMap;
 use std::iter::repeat;
 
+#[cfg(feature = "gosh")]
 use base64ct::Encoding as bEncoding;
 use num_bigint::BigUint;
 use serde::Deserialize;
 use serde_derive::Serialize;
+use tvm_types::BuilderData;
 use tvm_types::Cell;
+use tvm_types::ExceptionCode;
+use tvm_types::SliceData;
 
+use crate::error::tvm_exception_code;
+use crate::executor::engine::Engine;
+#[cfg(feature = "gosh")]
 use crate::executor::zk_stuff::error::ZkCryptoError;
+#[cfg(feature = "gosh")]
 use crate::executor::zk_stuff::zk_login::CanonicalSerialize;
+#[cfg(feature = "gosh")]
 use crate::executor::zk_stuff::zk_login::JWK;
+#[cfg(feature = "gosh")]
 use crate::executor::zk_stuff::zk_login::JwkId;
+#[cfg(feature = "gosh")]
 use crate::executor::zk_stuff::zk_login::ZkLoginInputs;
+use crate::stack::Stack;
+use crate::stack::StackItem;
+#[cfg(feature = "gosh")]
 use crate::utils::pack_data_to_cell;
 
 pub static DEFAULT_CAPABILITIES: u64 = 0x572e;
 
+/// Cell with the given bytes and references.
+pub fn cell(data: &[u8], references: Vec<Cell>) -> Cell {
+    let mut data = data.to_vec();
+    data.push(0x80);
+    let mut builder = BuilderData::with_bitstring(data).unwrap();
+    for reference in references {
+        builder.checked_append_reference(reference).unwrap();
+    }
+    builder.into_cell().unwrap()
+}
+
+/// Slice with the given bytes.
+pub fn slice(data: &[u8]) -> StackItem {
+    StackItem::Slice(SliceData::new(data.iter().copied().chain([0x80]).collect()))
+}
+
+/// Runs `code` over a stack made of `items` with the given capabilities.
+pub fn execute(capabilities: u64, code: &[u8], items: Vec<StackItem>) -> tvm_types::Result<Engine> {
+    let mut stack = Stack::new();
+    for item in items {
+        stack.push(item);
+    }
+    let mut engine = Engine::with_capabilities(capabilities).setup_with_libraries(
+        SliceData::new(code.iter().copied().chain([0x80]).collect()),
+        None,
+        Some(stack),
+        None,
+        vec![],
+    );
+    engine.execute()?;
+    Ok(engine)
+}
+
+/// Runs `code` and returns the resulting stack.
+pub fn execute_ok(capabilities: u64, code: &[u8], items: Vec<StackItem>) -> Vec<StackItem> {
+    let engine = execute(capabilities, code, items).unwrap();
+    engine.stack().storage.clone()
+}
+
+/// Runs `code` and returns the code of the thrown exception.
+pub fn execute_err(capabilities: u64, code: &[u8], items: Vec<StackItem>) -> Option<ExceptionCode> {
+    match execute(capabilities, code, items) {
+        Ok(_) => None,
+        Err(err) => tvm_exception_code(&err),
+    }
+}
+
 pub fn read_boc(filename: &str) -> Vec<u8> {
     let mut bytes = Vec::new();
     let mut file = std::fs::File::open(filename).unwrap();
@@ -290,6 +351,7 @@ pub fn prepare_hex_representation(init_x: &str, y: BigUint) -> String {
     hex_string
 }
 
+#[cfg(feature = "gosh")]
 pub fn prepare_proof_and_public_key_cells_for_stack(
     eph_pubkey: &[u8],
     zk_login_inputs: &ZkLoginInputs,
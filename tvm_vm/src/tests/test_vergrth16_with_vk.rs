@@ -0,0 +1,228 @@
+use ark_bls12_381::Bls12_381;
+use ark_bn254::Bn254;
+use ark_ec::pairing::Pairing;
+use ark_ff::Field;
+use ark_groth16::Groth16;
+use ark_relations::lc;
+use ark_relations::r1cs::ConstraintSynthesizer;
+use ark_relations::r1cs::ConstraintSystemRef;
+use ark_relations::r1cs::SynthesisError;
+use ark_relations::r1cs::Variable;
+use ark_serialize::CanonicalSerialize;
+use ark_snark::SNARK;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use tvm_block::GlobalCapabilities;
+use tvm_types::BuilderData;
+use tvm_types::ExceptionCode;
+use tvm_types::HashmapE;
+use tvm_types::SliceData;
+
+use crate::error::tvm_exception_code;
+use crate::executor::engine::Engine;
+use crate::executor::gas::gas_state::Gas;
+use crate::executor::test_helper::DEFAULT_CAPABILITIES;
+use crate::stack::Stack;
+use crate::stack::StackItem;
+use crate::stack::integer::IntegerData;
+use crate::utils::pack_data_to_cell;
+
+/// Proves knowledge of `x` and `y` such that `x * y = product` and
+/// `x + y = sum`, with `product` and `sum` public.
+#[derive(Clone)]
+struct ProductSumCircuit<F: Field> {
+    x: F,
+    y: F,
+}
+
+impl<F: Field> ConstraintSynthesizer<F> for ProductSumCircuit<F> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> Result<(), SynthesisError> {
+        let product = cs.new_input_variable(|| Ok(self.x * self.y))?;
+        let sum = cs.new_input_variable(|| Ok(self.x + self.y))?;
+        let x = cs.new_witness_variable(|| Ok(self.x))?;
+        let y = cs.new_witness_variable(|| Ok(self.y))?;
+        cs.enforce_constraint(lc!() + x, lc!() + y, lc!() + product)?;
+        cs.enforce_constraint(lc!() + x + y, lc!() + Variable::One, lc!() + sum)?;
+        Ok(())
+    }
+}
+
+struct Fixture {
+    vk: Vec<u8>,
+    proof: Vec<u8>,
+    public_inputs: Vec<u8>,
+}
+
+fn serialize<T: CanonicalSerialize>(value: &T) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    value.serialize_compressed(&mut bytes).unwrap();
+    bytes
+}
+
+fn fixture<E: Pairing>() -> Fixture {
+    let mut rng = StdRng::seed_from_u64(0);
+    let circuit =
+        ProductSumCircuit { x: E::ScalarField::from(3u64), y: E::ScalarField::from(5u64) };
+    let (pk, vk) = Groth16::<E>::circuit_specific_setup(circuit.clone(), &mut rng).unwrap();
+    let proof = Groth16::<E>::prove(&pk, circuit, &mut rng).unwrap();
+    let mut public_inputs = serialize(&E::ScalarField::from(15u64));
+    public_inputs.extend(serialize(&E::ScalarField::from(8u64)));
+    Fixture { vk: serialize(&vk), proof: serialize(&proof), public_inputs }
+}
+
+fn cell(data: &[u8]) -> StackItem {
+    StackItem::Cell(pack_data_to_cell(data, &mut Engine::with_capabilities(0)).unwrap())
+}
+
+const CAPABILITIES: u64 = DEFAULT_CAPABILITIES | GlobalCapabilities::CapGroth16WithVk as u64;
+
+fn engine(items: Vec<StackItem>, libraries: Vec<HashmapE>, capabilities: u64) -> Engine {
+    let mut stack = Stack::new();
+    for item in items {
+        stack.push(item);
+    }
+    Engine::with_capabilities(capabilities).setup_with_libraries(
+        SliceData::new(vec![0xC7, 0x4B, 0x80]),
+        None,
+        Some(stack),
+        None,
+        libraries,
+    )
+}
+
+fn execute_with_libraries(
+    items: Vec<StackItem>,
+    libraries: Vec<HashmapE>,
+) -> tvm_types::Result<Vec<StackItem>> {
+    let mut engine = engine(items, libraries, CAPABILITIES);
+    engine.execute()?;
+    Ok(engine.stack().storage.clone())
+}
+
+fn execute(fixture: &Fixture, curve: i32) -> tvm_types::Result<Vec<StackItem>> {
+    let items =
+        vec![cell(&fixture.proof), cell(&fixture.public_inputs), cell(&fixture.vk), int!(curve)];
+    execute_with_libraries(items, vec![])
+}
+
+fn execute_err(fixture: &Fixture, curve: i32) -> Option<ExceptionCode> {
+    match execute(fixture, curve) {
+        Ok(_) => None,
+        Err(err) => tvm_exception_code(&err),
+    }
+}
+
+#[test]
+fn test_vergrth16_with_vk_bn254() {
+    let mut fixture = fixture::<Bn254>();
+    assert_eq!(execute(&fixture, 0).unwrap(), vec![int!(-1)]);
+    // cached key gives the same answer
+    assert_eq!(execute(&fixture, 0).unwrap(), vec![int!(-1)]);
+
+    fixture.public_inputs[0] ^= 1;
+    assert_eq!(execute(&fixture, 0).unwrap(), vec![int!(0)]);
+}
+
+#[test]
+fn test_vergrth16_with_vk_bls12_381() {
+    let mut fixture = fixture::<Bls12_381>();
+    assert_eq!(execute(&fixture, 1).unwrap(), vec![int!(-1)]);
+
+    fixture.public_inputs.swap(0, 32);
+    assert_eq!(execute(&fixture, 1).unwrap(), vec![int!(0)]);
+}
+
+#[test]
+fn test_vergrth16_with_vk_library_hash() {
+    let fixture = fixture::<Bn254>();
+    let vk = pack_data_to_cell(&fixture.vk, &mut Engine::with_capabilities(0)).unwrap();
+    let hash = vk.repr_hash();
+    let mut libraries = HashmapE::with_bit_len(256);
+    let mut bucket = BuilderData::new();
+    bucket.checked_append_reference(vk).unwrap();
+    libraries.set_builder(SliceData::from_raw(hash.as_slice().to_vec(), 256), &bucket).unwrap();
+
+    let items = || {
+        vec![
+            cell(&fixture.proof),
+            cell(&fixture.public_inputs),
+            StackItem::integer(IntegerData::from_unsigned_bytes_be(hash.as_slice())),
+            int!(0),
+        ]
+    };
+    assert_eq!(execute_with_libraries(items(), vec![libraries]).unwrap(), vec![int!(-1)]);
+
+    let code = match execute_with_libraries(items(), vec![]) {
+        Ok(_) => None,
+        Err(err) => tvm_exception_code(&err),
+    };
+    assert_eq!(code, Some(ExceptionCode::CellUnderflow));
+}
+
+#[test]
+fn test_vergrth16_with_vk_bad_args() {
+    let fixture = fixture::<Bn254>();
+    // BN254 key and proof do not decode as BLS12-381 points
+    assert_eq!(execute_err(&fixture, 1), Some(ExceptionCode::FatalError));
+    assert_eq!(execute_err(&fixture, 2), Some(ExceptionCode::RangeCheckError));
+
+    let short_inputs = Fixture {
+        public_inputs: fixture.public_inputs[..32].to_vec(),
+        vk: fixture.vk.clone(),
+        proof: fixture.proof.clone(),
+    };
+    assert_eq!(execute_err(&short_inputs, 0), Some(ExceptionCode::FatalError));
+
+    let ragged_inputs = Fixture {
+        public_inputs: fixture.public_inputs[..40].to_vec(),
+        vk: fixture.vk.clone(),
+        proof: fixture.proof.clone(),
+    };
+    assert_eq!(execute_err(&ragged_inputs, 0), Some(ExceptionCode::FatalError));
+
+    let out_of_field = Fixture {
+        public_inputs: vec![0xFF; 64],
+        vk: fixture.vk.clone(),
+        proof: fixture.proof.clone(),
+    };
+    assert_eq!(execute_err(&out_of_field, 0), Some(ExceptionCode::FatalError));
+
+    let mut trailing = fixture.proof.clone();
+    trailing.push(0);
+    let trailing = Fixture { proof: trailing, ..fixture };
+    assert_eq!(execute_err(&trailing, 0), Some(ExceptionCode::FatalError));
+}
+
+#[test]
+fn test_vergrth16_with_vk_requires_capability() {
+    let fixture = fixture::<Bn254>();
+    let items =
+        vec![cell(&fixture.proof), cell(&fixture.public_inputs), cell(&fixture.vk), int!(0)];
+    let err = engine(items, vec![], DEFAULT_CAPABILITIES).execute().unwrap_err();
+    assert_eq!(tvm_exception_code(&err), Some(ExceptionCode::InvalidOpcode));
+}
+
+#[test]
+fn test_vergrth16_with_vk_charges_key_bytes() {
+    let fixture = fixture::<Bn254>();
+    // trailing bytes fail decoding after the key is charged; both keys fill
+    // the same number of cells, so only the key bytes differ in price
+    let cell_bytes = 127;
+    let short_pad = 1;
+    let long_pad =
+        (fixture.vk.len() + short_pad).div_ceil(cell_bytes) * cell_bytes - fixture.vk.len();
+    assert!(long_pad > short_pad);
+    let gas_used = |pad: usize| {
+        let mut vk = fixture.vk.clone();
+        vk.resize(vk.len() + pad, 0);
+        let items = vec![cell(&fixture.proof), cell(&fixture.public_inputs), cell(&vk), int!(0)];
+        let mut engine = engine(items, vec![], CAPABILITIES);
+        let err = engine.execute().unwrap_err();
+        assert_eq!(tvm_exception_code(&err), Some(ExceptionCode::FatalError));
+        engine.gas_used()
+    };
+    assert_eq!(
+        gas_used(long_pad) - gas_used(short_pad),
+        Gas::vergrth16_vk_key_price(long_pad - short_pad)
+    );
+}
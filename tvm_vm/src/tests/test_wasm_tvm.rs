@@ -0,0 +1,286 @@
+use tvm_types::BuilderData;
+use tvm_types::Cell;
+use tvm_types::CellType;
+use tvm_types::ExceptionCode;
+use tvm_types::SliceData;
+
+use crate::error::tvm_exception_code;
+use crate::executor::engine::Engine;
+use crate::executor::gas::gas_state::Gas;
+use crate::executor::wasm::call_wasm_component;
+use crate::executor::wasm_tvm::MAX_TVM_HOST_HANDLES;
+use crate::executor::wasm_tvm::TVM_HOST_CALL_GAS_PRICE;
+use crate::stack::StackItem;
+use crate::stack::savelist::SaveList;
+
+const WASM_FUEL: u64 = 1_000_000_000;
+
+/// `test:host/calls` exports `run`, which reads the first byte of c4,
+/// builds a cell with a 16-bit number and a reference to c4, and reads
+/// c7[0][3]. It returns the byte, the bit length and references count of
+/// the built cell, and the bytes of the c7 integer. `handles` keeps taking
+/// c4 until the host refuses another handle.
+const TVM_HOST_COMPONENT: &str = r#"
+(component $C
+  (import "tvm:vm/cells@0.1.0" (instance $cells
+    (type $e (enum "cell-underflow" "cell-overflow" "range-check" "type-check"))
+    (export "error" (type $error (eq $e)))
+    (export "cell" (type $cell (sub resource)))
+    (export "slice" (type $slice (sub resource)))
+    (export "builder" (type $builder (sub resource)))
+    (export "[method]cell.bit-length" (func (param "self" (borrow $cell)) (result u32)))
+    (export "[method]cell.references-count" (func (param "self" (borrow $cell)) (result u32)))
+    (export "[method]cell.begin-parse"
+      (func (param "self" (borrow $cell)) (result (result (own $slice) (error $error)))))
+    (export "[method]slice.load-uint"
+      (func (param "self" (borrow $slice)) (param "bits" u32) (result (result u64 (error $error)))))
+    (export "[constructor]builder" (func (result (own $builder))))
+    (export "[method]builder.store-uint"
+      (func (param "self" (borrow $builder)) (param "value" u64) (param "bits" u32)
+        (result (result (error $error)))))
+    (export "[method]builder.store-reference"
+      (func (param "self" (borrow $builder)) (param "cell" (borrow $cell))
+        (result (result (error $error)))))
+    (export "[method]builder.end-cell"
+      (func (param "self" (borrow $builder)) (result (result (own $cell) (error $error)))))
+  ))
+  (alias export $cells "error" (type $error))
+  (alias export $cells "cell" (type $cell))
+  (alias export $cells "slice" (type $slice))
+  (alias export $cells "builder" (type $builder))
+  (import "tvm:vm/registers@0.1.0" (instance $registers
+    (alias outer $C $error (type $e))
+    (export "error" (type $error (eq $e)))
+    (alias outer $C $cell (type $c))
+    (export "cell" (type $cell (eq $c)))
+    (alias outer $C $slice (type $s))
+    (export "slice" (type $slice (eq $s)))
+    (alias outer $C $builder (type $b))
+    (export "builder" (type $builder (eq $b)))
+    (type $v (variant
+      (case "null")
+      (case "nan")
+      (case "integer" (list u8))
+      (case "cell" (own $cell))
+      (case "slice" (own $slice))
+      (case "builder" (own $builder))
+      (case "tuple-length" u32)
+      (case "continuation")))
+    (export "stack-value" (type $stack-value (eq $v)))
+    (export "c4" (func (result (own $cell))))
+    (export "c7" (func (param "path" (list u32)) (result (result $stack-value (error $error)))))
+  ))
+
+  (core module $Libc
+    (memory (export "memory") 1)
+    (global $heap (mut i32) (i32.const 1024))
+    (func (export "realloc") (param i32 i32 i32 i32) (result i32)
+      (local $ptr i32)
+      (local.set $ptr
+        (i32.and
+          (i32.add (global.get $heap) (i32.sub (local.get 2) (i32.const 1)))
+          (i32.sub (i32.const 0) (local.get 2))))
+      (global.set $heap (i32.add (local.get $ptr) (local.get 3)))
+      (local.get $ptr))
+  )
+  (core instance $libc (instantiate $Libc))
+  (alias core export $libc "memory" (core memory $memory))
+  (alias core export $libc "realloc" (core func $realloc))
+
+  (core func $c4 (canon lower (func $registers "c4")))
+  (core func $c7 (canon lower (func $registers "c7") (memory $memory) (realloc $realloc)))
+  (core func $cell-bit-length (canon lower (func $cells "[method]cell.bit-length")))
+  (core func $cell-references-count (canon lower (func $cells "[method]cell.references-count")))
+  (core func $begin-parse (canon lower (func $cells "[method]cell.begin-parse") (memory $memory)))
+  (core func $load-uint (canon lower (func $cells "[method]slice.load-uint") (memory $memory)))
+  (core func $slice-drop (canon resource.drop $slice))
+  (core func $builder-new (canon lower (func $cells "[constructor]builder")))
+  (core func $store-uint (canon lower (func $cells "[method]builder.store-uint") (memory $memory)))
+  (core func $store-reference
+    (canon lower (func $cells "[method]builder.store-reference") (memory $memory)))
+  (core func $end-cell (canon lower (func $cells "[method]builder.end-cell") (memory $memory)))
+
+  (core module $Main
+    (import "libc" "memory" (memory 1))
+    (import "tvm" "c4" (func $c4 (result i32)))
+    (import "tvm" "c7" (func $c7 (param i32 i32 i32)))
+    (import "tvm" "cell-bit-length" (func $cell-bit-length (param i32) (result i32)))
+    (import "tvm" "cell-references-count" (func $cell-references-count (param i32) (result i32)))
+    (import "tvm" "begin-parse" (func $begin-parse (param i32 i32)))
+    (import "tvm" "load-uint" (func $load-uint (param i32 i32 i32)))
+    (import "tvm" "slice-drop" (func $slice-drop (param i32)))
+    (import "tvm" "builder-new" (func $builder-new (result i32)))
+    (import "tvm" "store-uint" (func $store-uint (param i32 i64 i32 i32)))
+    (import "tvm" "store-reference" (func $store-reference (param i32 i32 i32)))
+    (import "tvm" "end-cell" (func $end-cell (param i32 i32)))
+
+    ;; results of host calls are written at 64, the output is built at 272
+    (func $check
+      (if (i32.load8_u (i32.const 64)) (then unreachable)))
+
+    (func (export "run") (param i32 i32) (result i32)
+      (local $c4 i32) (local $slice i32) (local $builder i32) (local $cell i32)
+      (local.set $c4 (call $c4))
+      (call $begin-parse (local.get $c4) (i32.const 64))
+      (call $check)
+      (local.set $slice (i32.load (i32.const 68)))
+      (call $load-uint (local.get $slice) (i32.const 8) (i32.const 64))
+      (call $check)
+      (i64.store8 (i32.const 272) (i64.load (i32.const 72)))
+      (call $slice-drop (local.get $slice))
+      (local.set $builder (call $builder-new))
+      (call $store-uint (local.get $builder) (i64.const 0xabcd) (i32.const 16) (i32.const 64))
+      (call $check)
+      (call $store-reference (local.get $builder) (local.get $c4) (i32.const 64))
+      (call $check)
+      (call $end-cell (local.get $builder) (i32.const 64))
+      (call $check)
+      (local.set $cell (i32.load (i32.const 68)))
+      (i32.store8 (i32.const 273) (call $cell-bit-length (local.get $cell)))
+      (i32.store8 (i32.const 274) (call $cell-references-count (local.get $cell)))
+      (i32.store (i32.const 128) (i32.const 0))
+      (i32.store (i32.const 132) (i32.const 3))
+      (call $c7 (i32.const 128) (i32.const 2) (i32.const 64))
+      (call $check)
+      ;; the stack value must be an integer
+      (if (i32.ne (i32.load8_u (i32.const 68)) (i32.const 2)) (then unreachable))
+      (memory.copy (i32.const 275) (i32.load (i32.const 72)) (i32.load (i32.const 76)))
+      (i32.store (i32.const 256) (i32.const 272))
+      (i32.store (i32.const 260) (i32.add (i32.const 3) (i32.load (i32.const 76))))
+      (i32.const 256))
+
+    (func (export "handles") (param i32 i32) (result i32)
+      (loop $take
+        (drop (call $c4))
+        (br $take))
+      unreachable)
+  )
+  (core instance $main (instantiate $Main
+    (with "libc" (instance $libc))
+    (with "tvm" (instance
+      (export "c4" (func $c4))
+      (export "c7" (func $c7))
+      (export "cell-bit-length" (func $cell-bit-length))
+      (export "cell-references-count" (func $cell-references-count))
+      (export "begin-parse" (func $begin-parse))
+      (export "load-uint" (func $load-uint))
+      (export "slice-drop" (func $slice-drop))
+      (export "builder-new" (func $builder-new))
+      (export "store-uint" (func $store-uint))
+      (export "store-reference" (func $store-reference))
+      (export "end-cell" (func $end-cell))))))
+
+  (func $run (param "args" (list u8)) (result (list u8))
+    (canon lift (core func $main "run") (memory $memory) (realloc $realloc)))
+  (func $handles (param "args" (list u8)) (result (list u8))
+    (canon lift (core func $main "handles") (memory $memory) (realloc $realloc)))
+  (instance $calls
+    (export "run" (func $run))
+    (export "handles" (func $handles)))
+  (export "test:host/calls" (instance $calls))
+)
+"#;
+
+/// Host calls made by `run`: c4, begin-parse, load-uint, slice drop, the
+/// builder constructor, store-uint, store-reference, end-cell, bit-length,
+/// references-count and c7
+const RUN_HOST_CALLS: i64 = 11;
+
+/// c4 and the built cell are loaded for the first time by begin-parse and
+/// bit-length, references-count reloads the built cell
+fn run_gas() -> i64 {
+    RUN_HOST_CALLS * TVM_HOST_CALL_GAS_PRICE
+        + 2 * Gas::load_cell_price(true)
+        + Gas::load_cell_price(false)
+        + Gas::finalize_price()
+}
+
+fn c4() -> Cell {
+    SliceData::new(vec![0x2a, 0x80]).into_cell()
+}
+
+fn engine() -> Engine {
+    engine_with_c4(c4())
+}
+
+fn engine_with_c4(c4: Cell) -> Engine {
+    let mut ctrls = SaveList::default();
+    ctrls.put(4, &mut StackItem::Cell(c4)).unwrap();
+    let params = vec![
+        StackItem::int(0x76ef1ea),
+        StackItem::int(0),
+        StackItem::int(0),
+        StackItem::int(1633458077),
+    ];
+    ctrls.put(7, &mut StackItem::tuple(vec![StackItem::tuple(params)])).unwrap();
+    let mut engine = Engine::with_capabilities(0).setup_with_libraries(
+        SliceData::new(vec![0x80]),
+        Some(ctrls),
+        None,
+        None,
+        vec![],
+    );
+    engine.wasm_engine_init_cached().unwrap();
+    engine
+}
+
+fn call(engine: &Engine, func: &str, gas_limit: i64) -> tvm_types::Result<(Vec<u8>, i64)> {
+    let component =
+        wasmtime::component::Component::new(engine.get_wasm_engine()?, TVM_HOST_COMPONENT)?;
+    let call = call_wasm_component(
+        engine,
+        &component,
+        func,
+        "test:host/calls",
+        vec![],
+        WASM_FUEL,
+        gas_limit,
+    )?;
+    Ok((call.result, call.host_gas_used))
+}
+
+#[test]
+fn test_tvm_host_calls_charge_gas() {
+    let engine = engine();
+    let (result, gas_used) = call(&engine, "run", run_gas()).unwrap();
+    // 0x2a from c4, 16 bits and 1 reference, then 1633458077 from c7
+    assert_eq!(result, vec![0x2a, 16, 1, 0x61, 0x5c, 0x97, 0x9d]);
+    assert_eq!(gas_used, run_gas());
+}
+
+#[test]
+fn test_tvm_host_reloads_cells_visited_by_engine() {
+    let mut engine = engine();
+    engine.load_hashed_cell(c4(), true).unwrap();
+    let (result, gas_used) = call(&engine, "run", run_gas()).unwrap();
+    assert_eq!(result, vec![0x2a, 16, 1, 0x61, 0x5c, 0x97, 0x9d]);
+    assert_eq!(run_gas() - gas_used, Gas::load_cell_price(true) - Gas::load_cell_price(false));
+}
+
+#[test]
+fn test_tvm_host_rejects_exotic_cells() {
+    let mut library = BuilderData::with_raw(vec![2], 8).unwrap();
+    library.append_raw(c4().repr_hash().as_slice(), 256).unwrap();
+    library.set_type(CellType::LibraryReference);
+    let engine = engine_with_c4(library.into_cell().unwrap());
+    // begin-parse returns type-check, which `run` turns into a trap
+    let err = call(&engine, "run", run_gas()).unwrap_err();
+    assert_eq!(tvm_exception_code(&err), Some(ExceptionCode::WasmExecFail));
+}
+
+#[test]
+fn test_tvm_host_out_of_gas_traps() {
+    let engine = engine();
+    let err = call(&engine, "run", run_gas() - 1).unwrap_err();
+    assert_eq!(tvm_exception_code(&err), Some(ExceptionCode::OutOfGas));
+    let err = call(&engine, "run", TVM_HOST_CALL_GAS_PRICE).unwrap_err();
+    assert_eq!(tvm_exception_code(&err), Some(ExceptionCode::OutOfGas));
+}
+
+#[test]
+fn test_tvm_host_handles_are_capped() {
+    let engine = engine();
+    let gas_limit = (MAX_TVM_HOST_HANDLES as i64 + 1) * TVM_HOST_CALL_GAS_PRICE;
+    let err = call(&engine, "handles", gas_limit).unwrap_err();
+    assert_eq!(tvm_exception_code(&err), Some(ExceptionCode::WasmExecFail));
+}